@@ -22,6 +22,22 @@ use std::path::PathBuf;
 use audio_engine::AudioEngine;
 use websocket::config as ws_config;
 
+/// Create the preset storage backend
+/// Uses S3 when built with the `s3` feature and `S3_BUCKET` is set, local files otherwise
+fn create_storage() -> Arc<dyn StorageBackend> {
+    #[cfg(feature = "s3")]
+    if std::env::var("S3_BUCKET").is_ok() {
+        let s3 = storage::StorageFactory::create_s3_storage()
+            .expect("Invalid S3 storage configuration");
+        log::info!("Storing presets in S3");
+        return Arc::new(s3);
+    }
+
+    let data_dir = std::env::var("DATA_DIR")
+        .unwrap_or_else(|_| "./data".to_string());
+    Arc::new(LocalStorage::new(PathBuf::from(data_dir)))
+}
+
 /// Health check endpoint
 async fn health() -> impl Responder {
     web::Json(serde_json::json!({
@@ -92,9 +108,7 @@ async fn main() -> std::io::Result<()> {
     let jwt = JwtService::new(None);
 
     // Storage backend for presets
-    let data_dir = std::env::var("DATA_DIR")
-        .unwrap_or_else(|_| "./data".to_string());
-    let storage = Arc::new(LocalStorage::new(PathBuf::from(data_dir))) as Arc<dyn StorageBackend>;
+    let storage = create_storage();
 
     // Preset service
     let preset_service = Arc::new(PresetService::new(&pool, storage.clone()));
@@ -2,10 +2,16 @@
 // WAVELET Backend - Preset file storage abstraction
 
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 use std::path::PathBuf;
 use std::fmt;
 
+#[cfg(feature = "s3")]
+mod s3;
+#[cfg(feature = "s3")]
+pub use s3::S3Storage;
+
 /// Storage error types
 #[derive(Debug)]
 pub enum StorageError {
@@ -17,6 +23,8 @@ pub enum StorageError {
     IoError(String),
     /// Invalid path
     InvalidPath,
+    /// Stored checksum doesn't match the file contents
+    IntegrityError,
     /// Other error
     Other(String),
 }
@@ -28,6 +36,7 @@ impl fmt::Display for StorageError {
             StorageError::PermissionDenied => write!(f, "Permission denied"),
             StorageError::IoError(msg) => write!(f, "IO error: {}", msg),
             StorageError::InvalidPath => write!(f, "Invalid file path"),
+            StorageError::IntegrityError => write!(f, "File checksum mismatch"),
             StorageError::Other(msg) => write!(f, "Storage error: {}", msg),
         }
     }
@@ -75,14 +84,22 @@ pub trait StorageBackend: Send + Sync {
     
     /// Get storage path for a preset (for reference)
     async fn get_preset_path(&self, preset_id: Uuid) -> Result<String, StorageError>;
+    
+    /// List all stored presets
+    /// 
+    /// # Returns
+    /// Preset identifiers, sorted
+    async fn list_presets(&self) -> Result<Vec<Uuid>, StorageError>;
 }
 
 /// Local filesystem storage implementation
-/// Stores preset files on the local filesystem
+/// Stores preset files on the local filesystem, each with a `.sha256` checksum sidecar
 #[derive(Clone, Debug)]
 pub struct LocalStorage {
     /// Base directory for preset storage
     base_path: PathBuf,
+    /// Check downloads against their checksum sidecar
+    verify_on_download: bool,
 }
 
 impl LocalStorage {
@@ -97,13 +114,43 @@ impl LocalStorage {
                 .expect("Failed to create preset storage directory");
         }
         
-        Self { base_path }
+        Self {
+            base_path,
+            verify_on_download: true,
+        }
+    }
+    
+    /// Enable or disable checksum verification on download (enabled by default)
+    pub fn with_verify_on_download(mut self, verify: bool) -> Self {
+        self.verify_on_download = verify;
+        self
     }
     
     /// Get the full path for a preset file
     fn get_file_path(&self, preset_id: Uuid) -> PathBuf {
         self.base_path.join(format!("{}.json", preset_id))
     }
+    
+    /// Get the checksum sidecar path for a preset file
+    fn get_checksum_path(&self, preset_id: Uuid) -> PathBuf {
+        self.base_path.join(format!("{}.json.sha256", preset_id))
+    }
+    
+    /// Hex-encoded SHA-256 of the data
+    fn checksum(data: &[u8]) -> String {
+        Sha256::digest(data)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+    
+    /// Write a file atomically using a temp file
+    async fn write_atomic(path: &PathBuf, data: &[u8]) -> Result<(), StorageError> {
+        let temp_path = path.with_extension("tmp");
+        tokio::fs::write(&temp_path, data).await?;
+        tokio::fs::rename(&temp_path, path).await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -118,10 +165,11 @@ impl StorageBackend for LocalStorage {
             }
         }
         
-        // Write file atomically using temp file
-        let temp_path = path.with_extension("tmp");
-        tokio::fs::write(&temp_path, data).await?;
-        tokio::fs::rename(&temp_path, &path).await?;
+        Self::write_atomic(&path, data).await?;
+        
+        // Checksum sidecar, written after the data it describes
+        let checksum = Self::checksum(data);
+        Self::write_atomic(&self.get_checksum_path(preset_id), checksum.as_bytes()).await?;
         
         // Return the file path as string
         Ok(path.to_string_lossy().to_string())
@@ -135,6 +183,20 @@ impl StorageBackend for LocalStorage {
         }
         
         let data = tokio::fs::read(&path).await?;
+        
+        if self.verify_on_download {
+            let checksum_path = self.get_checksum_path(preset_id);
+            if checksum_path.exists() {
+                let expected = tokio::fs::read_to_string(&checksum_path).await?;
+                if expected.trim() != Self::checksum(&data) {
+                    return Err(StorageError::IntegrityError);
+                }
+            } else {
+                // Presets stored before checksums were introduced
+                log::warn!("No checksum for preset {}, skipping verification", preset_id);
+            }
+        }
+        
         Ok(data)
     }
     
@@ -145,6 +207,11 @@ impl StorageBackend for LocalStorage {
             tokio::fs::remove_file(&path).await?;
         }
         
+        let checksum_path = self.get_checksum_path(preset_id);
+        if checksum_path.exists() {
+            tokio::fs::remove_file(&checksum_path).await?;
+        }
+        
         Ok(())
     }
     
@@ -152,6 +219,29 @@ impl StorageBackend for LocalStorage {
         let path = self.get_file_path(preset_id);
         Ok(path.to_string_lossy().to_string())
     }
+    
+    async fn list_presets(&self) -> Result<Vec<Uuid>, StorageError> {
+        if !self.base_path.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let mut ids = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.base_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            // Only `{uuid}.json`; skips `.tmp` files and `.json.sha256` sidecars
+            let name = entry.file_name();
+            let id = name
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|stem| Uuid::parse_str(stem).ok());
+            if let Some(id) = id {
+                ids.push(id);
+            }
+        }
+        
+        ids.sort();
+        Ok(ids)
+    }
 }
 
 /// In-memory storage for testing purposes
@@ -195,6 +285,13 @@ impl StorageBackend for InMemoryStorage {
     async fn get_preset_path(&self, preset_id: Uuid) -> Result<String, StorageError> {
         Ok(format!("memory://preset/{}", preset_id))
     }
+    
+    async fn list_presets(&self) -> Result<Vec<Uuid>, StorageError> {
+        let storage = self.data.lock().unwrap();
+        let mut ids: Vec<Uuid> = storage.keys().copied().collect();
+        ids.sort();
+        Ok(ids)
+    }
 }
 
 /// Storage factory for creating storage instances
@@ -214,4 +311,86 @@ impl StorageFactory {
     pub fn create_memory_storage() -> InMemoryStorage {
         InMemoryStorage::new()
     }
+
+    /// Create S3 storage configured from environment variables
+    #[cfg(feature = "s3")]
+    pub fn create_s3_storage() -> Result<S3Storage, StorageError> {
+        S3Storage::from_env()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage() -> LocalStorage {
+        let dir = std::env::temp_dir().join(format!("wavelet_storage_{}", Uuid::new_v4()));
+        LocalStorage::new(dir)
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip_with_checksum() {
+        let storage = temp_storage();
+        let id = Uuid::new_v4();
+
+        storage.upload_preset(id, b"{\"name\":\"Pad\"}").await.unwrap();
+        assert!(storage.get_checksum_path(id).exists());
+        assert_eq!(storage.download_preset(id).await.unwrap(), b"{\"name\":\"Pad\"}");
+
+        storage.delete_preset(id).await.unwrap();
+        assert!(!storage.get_checksum_path(id).exists());
+        assert!(matches!(storage.download_preset(id).await, Err(StorageError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_detects_corruption() {
+        let storage = temp_storage();
+        let id = Uuid::new_v4();
+        storage.upload_preset(id, b"{\"name\":\"Pad\"}").await.unwrap();
+
+        // Flip a byte on disk
+        std::fs::write(storage.get_file_path(id), b"{\"name\":\"Pat\"}").unwrap();
+        assert!(matches!(storage.download_preset(id).await, Err(StorageError::IntegrityError)));
+
+        // Verification can be turned off
+        let unchecked = storage.clone().with_verify_on_download(false);
+        assert!(unchecked.download_preset(id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_lists_presets() {
+        let storage = temp_storage();
+        let mut ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            storage.upload_preset(*id, b"{}").await.unwrap();
+        }
+        // Not presets
+        std::fs::write(storage.base_path.join("notes.json"), b"{}").unwrap();
+        std::fs::write(storage.base_path.join(format!("{}.tmp", Uuid::new_v4())), b"{}").unwrap();
+
+        ids.sort();
+        assert_eq!(storage.list_presets().await.unwrap(), ids);
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_lists_presets() {
+        let storage = InMemoryStorage::new();
+        let mut ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            storage.upload_preset(*id, b"{}").await.unwrap();
+        }
+
+        ids.sort();
+        assert_eq!(storage.list_presets().await.unwrap(), ids);
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_tolerates_missing_checksum() {
+        let storage = temp_storage();
+        let id = Uuid::new_v4();
+
+        // A legacy preset without a sidecar
+        std::fs::write(storage.get_file_path(id), b"{}").unwrap();
+        assert_eq!(storage.download_preset(id).await.unwrap(), b"{}");
+    }
 }
@@ -0,0 +1,235 @@
+// S3 / object-store storage backend
+// WAVELET Backend - Preset files in an S3-compatible bucket (AWS S3, MinIO, ...)
+
+use async_trait::async_trait;
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::error::{DisplayErrorContext, SdkError};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use std::env;
+use uuid::Uuid;
+
+use super::{StorageBackend, StorageError};
+
+/// S3 connection settings
+#[derive(Clone, Debug)]
+pub struct S3Config {
+    /// Bucket holding the preset objects
+    pub bucket: String,
+    /// Region name (MinIO accepts any, usually "us-east-1")
+    pub region: String,
+    /// Custom endpoint URL (None = AWS S3)
+    pub endpoint: Option<String>,
+    /// Access key ID
+    pub access_key_id: String,
+    /// Secret access key
+    pub secret_access_key: String,
+}
+
+impl S3Config {
+    /// Read settings from the environment
+    ///
+    /// `S3_BUCKET`, `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` are
+    /// required; `S3_REGION` (or `AWS_REGION`) defaults to "us-east-1" and
+    /// `S3_ENDPOINT` is optional.
+    pub fn from_env() -> Result<Self, StorageError> {
+        let required = |name: &str| {
+            env::var(name).map_err(|_| StorageError::Other(format!("{} is not set", name)))
+        };
+
+        Ok(Self {
+            bucket: required("S3_BUCKET")?,
+            region: env::var("S3_REGION")
+                .or_else(|_| env::var("AWS_REGION"))
+                .unwrap_or_else(|_| "us-east-1".to_string()),
+            endpoint: env::var("S3_ENDPOINT").ok().filter(|e| !e.is_empty()),
+            access_key_id: required("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: required("AWS_SECRET_ACCESS_KEY")?,
+        })
+    }
+}
+
+/// S3 storage implementation
+/// Stores each preset as `presets/{uuid}.json` in one bucket
+#[derive(Clone, Debug)]
+pub struct S3Storage {
+    /// S3 client
+    client: Client,
+    /// Bucket holding the preset objects
+    bucket: String,
+}
+
+impl S3Storage {
+    /// Create new S3 storage instance
+    ///
+    /// # Arguments
+    /// * `config` - Bucket, region, endpoint and credentials
+    pub fn new(config: S3Config) -> Self {
+        let credentials = Credentials::new(
+            config.access_key_id,
+            config.secret_access_key,
+            None,
+            None,
+            "wavelet-env",
+        );
+
+        let mut builder = aws_sdk_s3::config::Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(config.region))
+            .credentials_provider(credentials);
+
+        // Custom endpoints (MinIO etc.) generally don't support virtual-hosted buckets
+        if let Some(endpoint) = config.endpoint {
+            builder = builder.endpoint_url(endpoint).force_path_style(true);
+        }
+
+        Self {
+            client: Client::from_conf(builder.build()),
+            bucket: config.bucket,
+        }
+    }
+
+    /// Create S3 storage from environment variables (see [`S3Config::from_env`])
+    pub fn from_env() -> Result<Self, StorageError> {
+        Ok(Self::new(S3Config::from_env()?))
+    }
+
+    /// Get the object key for a preset file
+    fn object_key(preset_id: Uuid) -> String {
+        format!("presets/{}.json", preset_id)
+    }
+
+    /// Get the s3:// URL for a preset file
+    fn object_url(&self, preset_id: Uuid) -> String {
+        format!("s3://{}/{}", self.bucket, Self::object_key(preset_id))
+    }
+}
+
+/// Map an S3 SDK error to a storage error
+/// 404 responses become `NotFound`, transport failures become `IoError`
+fn map_sdk_error<E>(err: SdkError<E, HttpResponse>) -> StorageError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let message = DisplayErrorContext(&err).to_string();
+    match &err {
+        SdkError::ServiceError(service) => match service.raw().status().as_u16() {
+            404 => StorageError::NotFound,
+            401 | 403 => StorageError::PermissionDenied,
+            _ => StorageError::Other(message),
+        },
+        SdkError::DispatchFailure(_) | SdkError::TimeoutError(_) | SdkError::ResponseError(_) => {
+            StorageError::IoError(message)
+        }
+        _ => StorageError::Other(message),
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    async fn upload_preset(&self, preset_id: Uuid, data: &[u8]) -> Result<String, StorageError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(preset_id))
+            .content_type("application/json")
+            .body(ByteStream::from(data.to_vec()))
+            .send()
+            .await
+            .map_err(map_sdk_error)?;
+
+        Ok(self.object_url(preset_id))
+    }
+
+    async fn download_preset(&self, preset_id: Uuid) -> Result<Vec<u8>, StorageError> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(preset_id))
+            .send()
+            .await
+            .map_err(map_sdk_error)?;
+
+        // The body streams over the network, so read failures are IO errors
+        let data = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| StorageError::IoError(e.to_string()))?;
+        Ok(data.into_bytes().to_vec())
+    }
+
+    async fn delete_preset(&self, preset_id: Uuid) -> Result<(), StorageError> {
+        // S3 deletes are idempotent: a missing object is not an error
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(preset_id))
+            .send()
+            .await
+            .map_err(map_sdk_error)?;
+
+        Ok(())
+    }
+
+    async fn get_preset_path(&self, preset_id: Uuid) -> Result<String, StorageError> {
+        Ok(self.object_url(preset_id))
+    }
+
+    async fn list_presets(&self) -> Result<Vec<Uuid>, StorageError> {
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix("presets/")
+            .into_paginator()
+            .send();
+
+        let mut ids = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(map_sdk_error)?;
+            for object in page.contents() {
+                let id = object
+                    .key()
+                    .and_then(|key| key.strip_prefix("presets/"))
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .and_then(|stem| Uuid::parse_str(stem).ok());
+                if let Some(id) = id {
+                    ids.push(id);
+                }
+            }
+        }
+
+        ids.sort();
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> S3Config {
+        S3Config {
+            bucket: "wavelet".to_string(),
+            region: "us-east-1".to_string(),
+            endpoint: Some("http://localhost:9000".to_string()),
+            access_key_id: "minio".to_string(),
+            secret_access_key: "minio123".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_object_key_and_url() {
+        let storage = S3Storage::new(test_config());
+        let id = Uuid::new_v4();
+
+        assert_eq!(S3Storage::object_key(id), format!("presets/{}.json", id));
+        assert_eq!(
+            storage.object_url(id),
+            format!("s3://wavelet/presets/{}.json", id)
+        );
+    }
+}
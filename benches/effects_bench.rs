@@ -0,0 +1,116 @@
+//! Effect benchmarks for WAVELET audio engine
+//!
+//! Compares feedback effects on a live signal with the same effects ringing
+//! out into silence. Without denormal flushing the decaying tails fall into
+//! denormal floats and run many times slower than the live signal.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wavelet::effects::{Chorus, Delay, Effect, Reverb};
+use wavelet::filter::ZdfFilter;
+
+use std::f32::consts::PI;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+/// Seconds of silence fed in before measuring a tail
+const TAIL_SECONDS: usize = 30;
+
+fn generate_test_input(samples: usize) -> Vec<f32> {
+    (0..samples)
+        .map(|i| (2.0 * PI * 440.0 * i as f32 / SAMPLE_RATE).sin() * 0.5)
+        .collect()
+}
+
+/// Rings an effect out: one loud block, then `TAIL_SECONDS` of silence.
+fn ring_out(effect: &mut dyn Effect) {
+    let mut block = generate_test_input(1000);
+    effect.process_buffer(&mut block);
+
+    let mut silence = vec![0.0; SAMPLE_RATE as usize];
+    for _ in 0..TAIL_SECONDS {
+        silence.fill(0.0);
+        effect.process_buffer(&mut silence);
+    }
+}
+
+fn bench_effect(c: &mut Criterion, name: &str, make: fn() -> Box<dyn Effect>) {
+    let input = generate_test_input(1000);
+    let mut output = input.clone();
+
+    let mut live = make();
+    c.bench_function(&format!("{name}_live"), |b| {
+        b.iter(|| {
+            output.copy_from_slice(black_box(&input));
+            live.process_buffer(&mut output);
+            black_box(&output);
+        })
+    });
+
+    let mut tail = make();
+    ring_out(tail.as_mut());
+    c.bench_function(&format!("{name}_decaying_tail"), |b| {
+        b.iter(|| {
+            output.fill(0.0);
+            tail.process_buffer(&mut output);
+            black_box(&output);
+        })
+    });
+}
+
+fn bench_reverb_tail(c: &mut Criterion) {
+    bench_effect(c, "reverb", || {
+        let mut reverb = Reverb::new(SAMPLE_RATE);
+        reverb.set_decay(0.8);
+        reverb.set_mix(1.0);
+        Box::new(reverb)
+    });
+}
+
+fn bench_delay_tail(c: &mut Criterion) {
+    bench_effect(c, "delay", || {
+        let mut delay = Delay::new(SAMPLE_RATE);
+        delay.set_delay_time(50.0);
+        delay.set_feedback(0.5);
+        Box::new(delay)
+    });
+}
+
+fn bench_chorus_tail(c: &mut Criterion) {
+    bench_effect(c, "chorus", || {
+        let mut chorus = Chorus::new(SAMPLE_RATE);
+        chorus.set_feedback(0.9);
+        Box::new(chorus)
+    });
+}
+
+fn bench_zdf_tail(c: &mut Criterion) {
+    let input = generate_test_input(1000);
+    let mut output = input.clone();
+
+    let mut filter = ZdfFilter::new();
+    filter.set_cutoff(200.0);
+    filter.process_buffer(&mut output);
+    let mut silence = vec![0.0; SAMPLE_RATE as usize];
+    for _ in 0..TAIL_SECONDS {
+        silence.fill(0.0);
+        filter.process_buffer(&mut silence);
+    }
+
+    c.bench_function("zdf_decaying_tail", |b| {
+        b.iter(|| {
+            output.fill(0.0);
+            filter.process_buffer(&mut output);
+            black_box(&output);
+        })
+    });
+}
+
+criterion_group!(
+    effects_benches,
+    bench_reverb_tail,
+    bench_delay_tail,
+    bench_chorus_tail,
+    bench_zdf_tail,
+);
+
+criterion_main!(effects_benches);
@@ -0,0 +1,76 @@
+//! Real-time render benchmarks for WAVELET audio engine
+//!
+//! Measures `Synth::render_block` and checks that the audio path performs
+//! no heap allocations, using a counting global allocator.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wavelet::synth::{Synth, MAX_VOICES};
+
+const SAMPLE_RATE: f32 = 48000.0;
+const BLOCK_SIZE: usize = 256;
+
+/// System allocator that counts every allocation.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Plays chords through `render_block` and returns the allocation count.
+fn count_render_allocations(synth: &mut Synth, out: &mut [f32]) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    // Note on/off churn beyond the voice limit exercises voice stealing
+    for round in 0..32u8 {
+        for i in 0..8u8 {
+            synth.queue_note_on(36 + (round * 8 + i) % 80, 100);
+        }
+        synth.render_block(out);
+        for i in 0..8u8 {
+            synth.queue_note_off(36 + (round * 8 + i) % 80);
+        }
+        synth.render_block(out);
+        synth.render_block(&mut []);
+    }
+
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_render_block_allocations(c: &mut Criterion) {
+    let mut synth = Synth::new(SAMPLE_RATE);
+    synth.set_max_voices(MAX_VOICES);
+    let mut out = vec![0.0f32; BLOCK_SIZE];
+
+    let allocations = count_render_allocations(&mut synth, &mut out);
+    assert_eq!(allocations, 0, "render_block allocated {allocations} times");
+
+    c.bench_function("synth_render_block_256", |b| {
+        b.iter(|| {
+            synth.queue_note_on(60, 100);
+            synth.render_block(black_box(&mut out));
+            synth.queue_note_off(60);
+        })
+    });
+}
+
+criterion_group!(benches, bench_render_block_allocations);
+criterion_main!(benches);
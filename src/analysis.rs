@@ -0,0 +1,305 @@
+//! Frequency Analysis Module
+//!
+//! FFT-based spectrum measurement for testing filters and anti-aliasing and
+//! for driving spectrum displays. Time-domain measurements (RMS, peak,
+//! correlation) live in `audio_analysis`.
+//!
+//! Signals of any length are accepted: they are zero-padded to the next
+//! power of two before the transform.
+
+use std::f32::consts::PI;
+use std::ops::Mul;
+
+/// Window applied to a signal before the FFT to reduce spectral leakage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowFn {
+    /// No window: best frequency resolution, most leakage
+    Rectangular,
+
+    /// Hann window: good general-purpose leakage suppression
+    #[default]
+    Hann,
+
+    /// Blackman window: lowest leakage, widest peaks
+    Blackman,
+}
+
+impl WindowFn {
+    /// Gets the window value at sample `i` of `len`.
+    fn value(self, i: usize, len: usize) -> f32 {
+        let x = 2.0 * PI * i as f32 / len as f32;
+        match self {
+            WindowFn::Rectangular => 1.0,
+            WindowFn::Hann => 0.5 - 0.5 * x.cos(),
+            WindowFn::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+        }
+    }
+}
+
+/// Complex number for the FFT.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Complex {
+    /// Real part
+    pub re: f32,
+    /// Imaginary part
+    pub im: f32,
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+/// Computes the twiddle factors for an FFT of `fft_size` points.
+///
+/// # Returns
+///
+/// `e^(-2πik/N)` for `k` in `0..N/2`, as expected by [`fft`]
+pub fn fft_twiddles(fft_size: usize) -> Vec<Complex> {
+    (0..fft_size / 2)
+        .map(|k| {
+            let angle = -2.0 * PI * k as f32 / fft_size as f32;
+            Complex {
+                re: angle.cos(),
+                im: angle.sin(),
+            }
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT.
+///
+/// Shared by the spectrum analysis, convolution reverb and spectral freeze.
+/// It doesn't allocate, so it can run on the audio thread.
+///
+/// # Arguments
+///
+/// * `buffer` - Data to transform; its length must be a power of two
+/// * `twiddles` - Twiddle factors from [`fft_twiddles`] for the buffer length
+/// * `inverse` - Computes the inverse transform (including the 1/N scaling)
+pub fn fft(buffer: &mut [Complex], twiddles: &[Complex], inverse: bool) {
+    let n = buffer.len();
+    debug_assert!(n.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), n / 2);
+    if n < 2 {
+        return;
+    }
+
+    // Bit-reversal permutation
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+
+    // Butterflies
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let mut w = twiddles[k * stride];
+                if inverse {
+                    w.im = -w.im;
+                }
+                let a = buffer[start + k];
+                let b = buffer[start + k + half] * w;
+                buffer[start + k] = Complex {
+                    re: a.re + b.re,
+                    im: a.im + b.im,
+                };
+                buffer[start + k + half] = Complex {
+                    re: a.re - b.re,
+                    im: a.im - b.im,
+                };
+            }
+        }
+        len *= 2;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f32;
+        for value in buffer.iter_mut() {
+            value.re *= scale;
+            value.im *= scale;
+        }
+    }
+}
+
+/// Computes the magnitude spectrum of a signal.
+///
+/// The window spans the signal itself; zero padding up to the next power
+/// of two only interpolates the spectrum. Magnitudes are normalized by the
+/// window's coherent gain, so a full-scale sine centred on a bin reads 1.0.
+///
+/// # Arguments
+///
+/// * `signal` - Input samples (any length)
+/// * `window` - Window applied before the FFT
+///
+/// # Returns
+///
+/// `fft_size / 2 + 1` magnitudes from DC to Nyquist, where `fft_size` is
+/// the signal length rounded up to a power of two (empty for an empty
+/// signal)
+pub fn magnitude_spectrum(signal: &[f32], window: WindowFn) -> Vec<f32> {
+    if signal.is_empty() {
+        return Vec::new();
+    }
+
+    let fft_size = signal.len().next_power_of_two();
+    let mut spectrum = vec![Complex::default(); fft_size];
+    let mut window_sum = 0.0;
+    for (i, (bin, &sample)) in spectrum.iter_mut().zip(signal).enumerate() {
+        let w = window.value(i, signal.len());
+        bin.re = sample * w;
+        window_sum += w;
+    }
+    fft(&mut spectrum, &fft_twiddles(fft_size), false);
+
+    // One-sided spectrum: each bin but DC and Nyquist carries half the energy
+    let nyquist = fft_size / 2;
+    (0..=nyquist)
+        .map(|k| {
+            let scale = if k == 0 || k == nyquist { 1.0 } else { 2.0 } / window_sum;
+            let bin = spectrum[k];
+            (bin.re * bin.re + bin.im * bin.im).sqrt() * scale
+        })
+        .collect()
+}
+
+/// Estimates the frequency of the strongest spectral peak.
+///
+/// The peak bin is refined by parabolic interpolation of the log
+/// magnitudes, which resolves a pure tone to well under one bin. DC is
+/// ignored.
+///
+/// # Arguments
+///
+/// * `signal` - Input samples (any length)
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Returns
+///
+/// Frequency in Hz, or 0.0 for a silent or too-short signal
+pub fn dominant_frequency(signal: &[f32], sample_rate: f32) -> f32 {
+    let spectrum = magnitude_spectrum(signal, WindowFn::Hann);
+    let Some((peak, &magnitude)) = spectrum
+        .iter()
+        .enumerate()
+        .skip(1)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+    else {
+        return 0.0;
+    };
+    if magnitude <= 0.0 {
+        return 0.0;
+    }
+
+    let fft_size = (spectrum.len() - 1) * 2;
+    let offset = match (spectrum.get(peak - 1), spectrum.get(peak + 1)) {
+        (Some(&before), Some(&after)) if before > 0.0 && after > 0.0 => {
+            let (a, b, c) = (before.ln(), magnitude.ln(), after.ln());
+            let denominator = a - 2.0 * b + c;
+            if denominator.abs() > f32::EPSILON {
+                0.5 * (a - c) / denominator
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    };
+
+    (peak as f32 + offset) * sample_rate / fft_size as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, sample_rate: f32, samples: usize) -> Vec<f32> {
+        (0..samples)
+            .map(|i| (2.0 * PI * freq * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_round_trip() {
+        let original: Vec<Complex> = (0..512)
+            .map(|i| Complex {
+                re: (i as f32 * 0.37).sin(),
+                im: 0.0,
+            })
+            .collect();
+
+        let twiddles = fft_twiddles(512);
+        let mut buffer = original.clone();
+        fft(&mut buffer, &twiddles, false);
+        fft(&mut buffer, &twiddles, true);
+
+        for (a, b) in original.iter().zip(&buffer) {
+            assert!((a.re - b.re).abs() < 1e-4 && b.im.abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_dominant_frequency_of_sine() {
+        for &(freq, samples) in &[(440.0, 4096), (440.0, 3000), (1234.5, 8192)] {
+            let detected = dominant_frequency(&sine(freq, 44100.0, samples), 44100.0);
+            assert!(
+                (detected - freq).abs() < 2.0,
+                "{freq} Hz over {samples} samples read as {detected} Hz"
+            );
+        }
+
+        assert_eq!(dominant_frequency(&[0.0; 512], 44100.0), 0.0);
+        assert_eq!(dominant_frequency(&[], 44100.0), 0.0);
+    }
+
+    #[test]
+    fn test_magnitude_spectrum_pads_and_normalizes() {
+        // 1000 samples pad to a 1024-point FFT
+        assert_eq!(magnitude_spectrum(&[0.0; 1000], WindowFn::Hann).len(), 513);
+        assert!(magnitude_spectrum(&[], WindowFn::Hann).is_empty());
+
+        // Bin 64 of a 1024-point FFT is centred exactly
+        let spectrum = magnitude_spectrum(&sine(64.0, 1024.0, 1024), WindowFn::Hann);
+        assert!((spectrum[64] - 1.0).abs() < 1e-3, "peak {}", spectrum[64]);
+
+        // DC and Nyquist are not doubled
+        let spectrum = magnitude_spectrum(&[0.5; 1024], WindowFn::Hann);
+        assert!((spectrum[0] - 0.5).abs() < 1e-3, "DC {}", spectrum[0]);
+        let alternating: Vec<f32> = (0..1024)
+            .map(|i| if i % 2 == 0 { 0.5 } else { -0.5 })
+            .collect();
+        let spectrum = magnitude_spectrum(&alternating, WindowFn::Rectangular);
+        assert!(
+            (spectrum[512] - 0.5).abs() < 1e-3,
+            "Nyquist {}",
+            spectrum[512]
+        );
+    }
+
+    #[test]
+    fn test_window_reduces_leakage() {
+        // Halfway between bins, the worst case for leakage
+        let signal = sine(64.5, 1024.0, 1024);
+        let rectangular = magnitude_spectrum(&signal, WindowFn::Rectangular);
+        let hann = magnitude_spectrum(&signal, WindowFn::Hann);
+        let blackman = magnitude_spectrum(&signal, WindowFn::Blackman);
+
+        // Leakage far from the tone
+        assert!(hann[200] < rectangular[200] * 0.01);
+        assert!(blackman[200] < hann[200]);
+    }
+}
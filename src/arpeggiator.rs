@@ -8,7 +8,7 @@
 //! - 8 arpeggio patterns: Up, Down, Up-Down, Down-Up, Random, Order, Chord, Pattern
 //! - Configurable note order and pattern length
 //! - Syncable to MIDI clock
-//! - Automatic chord holding when keys are released
+//! - Latch mode: the arp keeps running after the keys are released
 
 #![allow(dead_code)] // Reserve fields for future MIDI sync features
 
@@ -28,7 +28,7 @@ pub enum ArpMode {
     DownUp,
     /// Notes played in random order
     Random,
-    /// Notes played in the order they were pressed
+    /// Notes played in the order they were pressed (as played)
     Order,
     /// All notes played simultaneously
     Chord,
@@ -139,6 +139,18 @@ impl fmt::Display for ArpSpeed {
 }
 
 impl ArpSpeed {
+    /// Converts a SPEED value (0-5) to a note value; larger values clamp to 1/32.
+    pub fn from_u8(val: u8) -> Self {
+        match val {
+            0 => ArpSpeed::Whole,
+            1 => ArpSpeed::Half,
+            2 => ArpSpeed::Quarter,
+            3 => ArpSpeed::Eighth,
+            4 => ArpSpeed::Sixteenth,
+            _ => ArpSpeed::ThirtySecond,
+        }
+    }
+
     /// Converts speed value to duration in seconds at the given BPM.
     pub fn to_duration(&self, bpm: f32) -> f32 {
         let beat_duration = 60.0 / bpm;
@@ -153,13 +165,25 @@ impl ArpSpeed {
     }
 }
 
+/// Note events produced by one sample of arpeggiator processing.
+///
+/// Release `note_off` before triggering `note_on`: with a gate of 1.0 both
+/// land on the same sample, so consecutive notes join without a gap (legato).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArpEvents {
+    /// Note whose gate just ended
+    pub note_off: Option<u8>,
+    /// Note to trigger, with velocity
+    pub note_on: Option<(u8, u8)>,
+}
+
 /// Internal representation of an arpeggio note.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct ArpNote {
     /// MIDI note number (0-127)
     note: u8,
 
-    /// Which voice index this note came from (for Order pattern)
+    /// Press order of the note (for Order pattern)
     order_index: usize,
 }
 
@@ -184,6 +208,9 @@ pub struct Arpeggiator {
     /// Notes that were held when arp started
     initial_notes: Vec<ArpNote>,
 
+    /// Initial notes in the order they were pressed (for Order mode)
+    played_notes: Vec<ArpNote>,
+
     /// Current position in the arpeggio pattern
     position: usize,
 
@@ -201,6 +228,42 @@ pub struct Arpeggiator {
 
     /// BPM for timing calculations
     bpm: f32,
+
+    /// Note currently sounding, waiting for its gate to end
+    gated_note: Option<u8>,
+
+    /// Samples until the sounding note is released
+    samples_until_off: f32,
+
+    /// Press counter, so Order survives releases
+    next_order_index: usize,
+
+    /// Last note picked, so Random doesn't repeat it
+    last_note: Option<u8>,
+
+    /// Random generator state for Random mode
+    rng_state: u32,
+
+    /// Latch: released notes stay in the arp set
+    latch: bool,
+
+    /// Keys physically held down
+    keys_down: Vec<u8>,
+
+    /// Triggers per ratcheted step (1 = no ratchets)
+    ratchet_max: u8,
+
+    /// Chance (0.0-1.0) that a step is ratcheted
+    ratchet_probability: f32,
+
+    /// Retriggers left in the current step
+    ratchets_left: u8,
+
+    /// Samples between retriggers in the current step
+    ratchet_interval: f32,
+
+    /// Samples until the next retrigger
+    samples_until_ratchet: f32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -217,12 +280,25 @@ impl Default for Arpeggiator {
             state: ArpState::Idle,
             held_notes: Vec::new(),
             initial_notes: Vec::new(),
+            played_notes: Vec::new(),
             position: 0,
             direction_up: true,
             sample_rate: 44100.0,
             samples_until_next: 0.0,
             samples_per_step: 0.0,
             bpm: 120.0,
+            gated_note: None,
+            samples_until_off: 0.0,
+            next_order_index: 0,
+            last_note: None,
+            rng_state: 0x9E37_79B9,
+            latch: false,
+            keys_down: Vec::new(),
+            ratchet_max: 1,
+            ratchet_probability: 0.0,
+            ratchets_left: 0,
+            ratchet_interval: 0.0,
+            samples_until_ratchet: 0.0,
         }
     }
 }
@@ -253,17 +329,29 @@ impl Arpeggiator {
     }
 
     /// Updates timing calculations when BPM or speed changes.
+    ///
+    /// The step in progress and the gate of the sounding note are rescaled,
+    /// so a tempo change takes effect immediately instead of on the next step.
     fn update_timing(&mut self) {
-        let beat_duration = 60.0 / self.bpm;
-        let step_duration = match self.config.speed {
-            0 => beat_duration * 4.0,
-            1 => beat_duration * 2.0,
-            2 => beat_duration,
-            3 => beat_duration / 2.0,
-            4 => beat_duration / 4.0,
-            _ => beat_duration / 8.0,
-        };
-        self.samples_per_step = step_duration * self.sample_rate;
+        let samples_per_step = self.step_duration() * self.sample_rate;
+        if self.samples_per_step > 0.0 {
+            let ratio = samples_per_step / self.samples_per_step;
+            self.samples_until_next *= ratio;
+            self.samples_until_off *= ratio;
+            self.ratchet_interval *= ratio;
+            self.samples_until_ratchet *= ratio;
+        }
+        self.samples_per_step = samples_per_step;
+    }
+
+    /// Gets the SPEED as a note value.
+    pub fn speed(&self) -> ArpSpeed {
+        ArpSpeed::from_u8(self.config.speed)
+    }
+
+    /// Gets the duration of one arpeggio step in seconds at the current BPM.
+    pub fn step_duration(&self) -> f32 {
+        self.speed().to_duration(self.bpm)
     }
 
     /// Sets the arpeggiator configuration.
@@ -319,6 +407,18 @@ impl Arpeggiator {
         self.config.note_length = length.min(100);
     }
 
+    /// Sets the gate length as a fraction of the step (0.1 = staccato, 1.0 = legato).
+    ///
+    /// This is N.LEN expressed as a fraction.
+    pub fn set_gate_length(&mut self, gate: f32) {
+        self.config.note_length = (gate.clamp(0.1, 1.0) * 100.0).round() as u8;
+    }
+
+    /// Gets the gate length as a fraction of the step (0.1-1.0).
+    pub fn gate_length(&self) -> f32 {
+        (self.config.note_length as f32 / 100.0).clamp(0.1, 1.0)
+    }
+
     /// Sets the OFFSET.
     pub fn set_offset(&mut self, offset: u8) {
         self.config.offset = offset.min(15);
@@ -329,23 +429,81 @@ impl Arpeggiator {
         self.config.arp_length = length.min(16);
     }
 
+    /// Enables or disables latch (hold).
+    ///
+    /// While latched, releasing keys leaves their notes in the arp set; the
+    /// first key pressed after all keys were released starts a new set.
+    /// Unlatching drops the notes whose keys are no longer down.
+    pub fn set_latch(&mut self, latch: bool) {
+        self.latch = latch;
+        if !latch {
+            let keys_down = &self.keys_down;
+            self.held_notes.retain(|n| keys_down.contains(&n.note));
+            self.update_initial_notes();
+            if self.held_notes.is_empty() {
+                self.state = ArpState::Idle;
+            }
+        }
+    }
+
+    /// Checks if latch is enabled.
+    pub fn is_latched(&self) -> bool {
+        self.latch
+    }
+
+    /// Sets ratcheting: steps that fire several evenly spaced retriggers.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Triggers in a ratcheted step (1-4, 1 = off)
+    /// * `probability` - Chance (0.0-1.0) that a step is ratcheted
+    pub fn set_ratchet(&mut self, max: u8, probability: f32) {
+        self.ratchet_max = max.clamp(1, 4);
+        self.ratchet_probability = probability.clamp(0.0, 1.0);
+    }
+
+    /// Gets the ratchet settings as (max, probability).
+    pub fn ratchet(&self) -> (u8, f32) {
+        (self.ratchet_max, self.ratchet_probability)
+    }
+
     /// Adds a note to the held notes.
     pub fn note_on(&mut self, note: u8, _velocity: u8) {
-        let order_index = self.held_notes.len();
+        // Latched notes from released keys give way to a fresh chord
+        if self.state == ArpState::Held {
+            self.held_notes.clear();
+            self.state = ArpState::Playing;
+        }
+        if !self.keys_down.contains(&note) {
+            self.keys_down.push(note);
+        }
+
+        let order_index = self.next_order_index;
+        self.next_order_index += 1;
         self.held_notes.push(ArpNote::new(note, order_index));
         self.held_notes.sort();
 
         if self.state == ArpState::Idle {
             self.start();
         } else {
-            self.initial_notes = self.held_notes.clone();
+            self.update_initial_notes();
             self.position = 0;
         }
     }
 
-    /// Removes a note from the held notes.
+    /// Removes a note from the held notes (unless latched).
     pub fn note_off(&mut self, note: u8) {
+        self.keys_down.retain(|&key| key != note);
+
+        if self.latch {
+            if self.keys_down.is_empty() && self.state == ArpState::Playing {
+                self.state = ArpState::Held;
+            }
+            return;
+        }
+
         self.held_notes.retain(|n| n.note != note);
+        self.update_initial_notes();
 
         if self.held_notes.is_empty() {
             self.state = ArpState::Idle;
@@ -355,7 +513,7 @@ impl Arpeggiator {
     /// Starts the arpeggiator.
     fn start(&mut self) {
         self.state = ArpState::Playing;
-        self.initial_notes = self.held_notes.clone();
+        self.update_initial_notes();
         self.position = 0;
         self.direction_up = true;
         self.samples_until_next = 0.0;
@@ -365,10 +523,19 @@ impl Arpeggiator {
     fn stop(&mut self) {
         self.state = ArpState::Idle;
         self.held_notes.clear();
+        self.keys_down.clear();
         self.initial_notes.clear();
+        self.played_notes.clear();
         self.position = 0;
     }
 
+    /// Copies the held notes into the playing set, reusing its storage.
+    fn update_initial_notes(&mut self) {
+        self.initial_notes.clone_from(&self.held_notes);
+        self.played_notes.clone_from(&self.held_notes);
+        self.played_notes.sort_unstable_by_key(|n| n.order_index);
+    }
+
     /// Gets the next note to play based on the current MODE.
     fn get_next_note(&mut self) -> Option<u8> {
         if self.initial_notes.is_empty() {
@@ -384,14 +551,22 @@ impl Arpeggiator {
             self.config.arp_length as usize
         };
 
+        // Held notes are kept sorted by pitch; Order replays them as pressed
+        let chord = if mode == ArpMode::Order {
+            &self.played_notes
+        } else {
+            &self.initial_notes
+        };
+
+        // Repeat the chord in each octave of the RANGE
         let mut extended_notes: Vec<ArpNote> = Vec::new();
 
         for octave in 0..range {
             let octave_offset = (octave * 12) as u8;
-            for (i, note) in self.initial_notes.iter().enumerate() {
+            for note in chord {
                 let new_note = note.note.saturating_add(octave_offset);
                 if new_note <= 127 {
-                    extended_notes.push(ArpNote::new(new_note, i));
+                    extended_notes.push(ArpNote::new(new_note, note.order_index));
                 }
             }
         }
@@ -453,15 +628,24 @@ impl Arpeggiator {
             }
 
             ArpMode::Random => {
-                let idx = (self.position * 7919 + 17) % total_notes;
+                let last = self
+                    .last_note
+                    .and_then(|last| extended_notes.iter().position(|n| n.note == last));
+                let idx = match last {
+                    // Pick among the other notes so none plays twice in a row
+                    Some(last) if total_notes > 1 => {
+                        (last + 1 + self.next_random() as usize % (total_notes - 1)) % total_notes
+                    }
+                    _ => self.next_random() as usize % total_notes,
+                };
                 self.position += 1;
                 Some(extended_notes[idx].note)
             }
 
             ArpMode::Order => {
-                let idx = self.position % num_notes;
+                let idx = self.position % total_notes;
                 self.position += 1;
-                self.initial_notes.get(idx).map(|n| n.note)
+                Some(extended_notes[idx].note)
             }
 
             ArpMode::Chord => {
@@ -480,9 +664,20 @@ impl Arpeggiator {
             }
         };
 
+        self.last_note = note;
         note
     }
 
+    /// Advances the xorshift generator used by Random mode.
+    fn next_random(&mut self) -> u32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+        x
+    }
+
     /// Gets all notes to play (for Chord pattern).
     pub fn get_current_chord(&self) -> Vec<u8> {
         self.initial_notes.iter().map(|n| n.note).collect()
@@ -491,38 +686,86 @@ impl Arpeggiator {
     /// Processes one sample and returns a note trigger if it's time.
     ///
     /// Returns `Some((note, velocity))` when a new note should trigger,
-    /// or `None` if no note is playing.
+    /// or `None` if no note is playing. Use [`Arpeggiator::process_events`]
+    /// to also receive the gated note-offs.
     pub fn process(&mut self) -> Option<(u8, u8)> {
-        if !self.config.enabled {
-            return None;
+        self.process_events().note_on
+    }
+
+    /// Processes one sample and returns the note-off and note-on due on it.
+    ///
+    /// Each note is released `gate_length * step_duration` after it starts;
+    /// ratcheted steps split the step (and the gate) between their retriggers.
+    pub fn process_events(&mut self) -> ArpEvents {
+        let mut events = ArpEvents::default();
+
+        if self.gated_note.is_some() {
+            self.samples_until_off -= 1.0;
+            if self.samples_until_off <= 0.0 || !self.config.enabled {
+                events.note_off = self.gated_note.take();
+            }
         }
 
-        let mode = ArpMode::from_u8(self.config.mode);
+        if !self.config.enabled {
+            self.ratchets_left = 0;
+            return events;
+        }
 
-        if mode == ArpMode::Chord {
-            self.samples_until_next -= 1.0;
-            if self.samples_until_next <= 0.0 {
-                self.samples_until_next = self.samples_per_step;
-                let chord = self.get_current_chord();
-                if !chord.is_empty() {
-                    return Some((chord[0], 100));
+        // Retriggers of a ratcheted step; the last one ends before the next step
+        if self.ratchets_left > 0 {
+            self.samples_until_ratchet -= 1.0;
+            if self.samples_until_ratchet <= 0.0 {
+                self.samples_until_ratchet += self.ratchet_interval;
+                self.ratchets_left -= 1;
+                if let Some(note) = self.last_note {
+                    self.trigger(note, self.ratchet_interval, &mut events);
                 }
             }
-            return None;
         }
 
         self.samples_until_next -= 1.0;
+        if self.samples_until_next > 0.0 {
+            return events;
+        }
+        self.samples_until_next = self.samples_per_step;
+
+        let note = if ArpMode::from_u8(self.config.mode) == ArpMode::Chord {
+            self.get_current_chord().first().copied()
+        } else {
+            self.get_next_note()
+        };
+
+        if let Some(note) = note {
+            let ratchets = if self.ratchet_max > 1 && self.random_unit() < self.ratchet_probability
+            {
+                self.ratchet_max
+            } else {
+                1
+            };
+            self.ratchet_interval = self.samples_per_step / ratchets as f32;
+            self.samples_until_ratchet = self.ratchet_interval;
+            self.ratchets_left = ratchets - 1;
+            self.last_note = Some(note);
+            self.trigger(note, self.ratchet_interval, &mut events);
+        }
 
-        if self.samples_until_next <= 0.0 {
-            self.samples_until_next = self.samples_per_step;
+        events
+    }
 
-            if let Some(note) = self.get_next_note() {
-                let velocity = 100;
-                return Some((note, velocity));
-            }
+    /// Starts `note`, gated for `gate_length` of `span` samples.
+    fn trigger(&mut self, note: u8, span: f32, events: &mut ArpEvents) {
+        // A note still sounding (only possible when the step shrank) is cut
+        if let Some(previous) = self.gated_note.take() {
+            events.note_off = Some(previous);
         }
+        self.gated_note = Some(note);
+        self.samples_until_off = self.gate_length() * span;
+        events.note_on = Some((note, 100));
+    }
 
-        None
+    /// Random value in 0.0..1.0 for ratchet decisions.
+    fn random_unit(&mut self) -> f32 {
+        (self.next_random() >> 8) as f32 / (1u32 << 24) as f32
     }
 
     /// Returns the current state as a string for UI display.
@@ -800,4 +1043,245 @@ mod tests {
         arp.set_arp_length(8);
         assert_eq!(arp.config.arp_length, 8);
     }
+
+    #[test]
+    fn test_speed_syncs_to_bpm() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        assert_eq!(arp.speed(), ArpSpeed::Eighth);
+        assert!((arp.step_duration() - 0.25).abs() < 1e-6);
+
+        arp.set_speed(4);
+        arp.set_bpm(60.0);
+        assert_eq!(arp.speed(), ArpSpeed::Sixteenth);
+        assert!((arp.step_duration() - 0.25).abs() < 1e-6);
+        assert!((arp.samples_per_step - 11025.0).abs() < 1e-2);
+    }
+
+    /// Sample offsets of (note-on, note-off) pairs over `samples` samples.
+    fn gate_offsets(arp: &mut Arpeggiator, samples: usize) -> Vec<(usize, usize)> {
+        let mut starts = Vec::new();
+        let mut pairs = Vec::new();
+        for i in 0..samples {
+            let events = arp.process_events();
+            if events.note_off.is_some() {
+                pairs.push((starts.remove(0), i));
+            }
+            if events.note_on.is_some() {
+                starts.push(i);
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_gate_length_sets_note_off_offset() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+        let step = arp.step_duration() * 44100.0;
+
+        for gate in [0.1, 0.5, 0.8] {
+            arp.set_gate_length(gate);
+            assert!((arp.gate_length() - gate).abs() < 1e-6);
+            let pairs = gate_offsets(&mut arp, 44100);
+            assert!(pairs.len() >= 3);
+            for (on, off) in pairs.into_iter().skip(1) {
+                let expected = gate * step;
+                assert!(
+                    ((off - on) as f32 - expected).abs() <= 1.0,
+                    "gate {gate}: {} samples, expected {expected}",
+                    off - on
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_gate_is_legato() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        arp.set_gate_length(1.0);
+        arp.note_on(60, 100);
+        arp.note_on(67, 100);
+
+        // Every release lands on the sample the next note starts
+        let mut releases = 0;
+        for _ in 0..44100 {
+            let events = arp.process_events();
+            if events.note_off.is_some() {
+                assert!(events.note_on.is_some());
+                releases += 1;
+            }
+        }
+        assert!(releases > 0);
+
+        arp.set_gate_length(0.0);
+        assert_eq!(arp.gate_length(), 0.1);
+    }
+
+    /// The first `count` notes triggered by the arpeggiator.
+    fn triggered_notes(arp: &mut Arpeggiator, count: usize) -> Vec<u8> {
+        let mut notes = Vec::new();
+        while notes.len() < count {
+            if let Some((note, _)) = arp.process() {
+                notes.push(note);
+            }
+        }
+        notes
+    }
+
+    #[test]
+    fn test_octave_range_repeats_chord_an_octave_up() {
+        let config = ArpConfig {
+            speed: 5,
+            range: 2,
+            ..Default::default()
+        };
+        let mut arp = Arpeggiator::with_config(config, 44100.0, 120.0);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+        arp.note_on(67, 100);
+
+        assert_eq!(
+            triggered_notes(&mut arp, 12),
+            [60, 64, 67, 72, 76, 79, 60, 64, 67, 72, 76, 79]
+        );
+    }
+
+    #[test]
+    fn test_order_mode_plays_as_pressed() {
+        let config = ArpConfig {
+            mode: 5,
+            speed: 5,
+            range: 2,
+            ..Default::default()
+        };
+        let mut arp = Arpeggiator::with_config(config, 44100.0, 120.0);
+        arp.note_on(67, 100);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+
+        assert_eq!(triggered_notes(&mut arp, 6), [67, 60, 64, 79, 72, 76]);
+
+        // Releasing a note keeps the press order of the rest
+        arp.note_off(60);
+        arp.note_on(62, 100);
+        assert_eq!(triggered_notes(&mut arp, 3), [67, 64, 62]);
+    }
+
+    #[test]
+    fn test_random_mode_never_repeats_a_note() {
+        let config = ArpConfig {
+            mode: 4,
+            speed: 5,
+            ..Default::default()
+        };
+        let mut arp = Arpeggiator::with_config(config, 44100.0, 120.0);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+
+        let notes = triggered_notes(&mut arp, 64);
+        assert!(notes.windows(2).all(|w| w[0] != w[1]), "{:?}", notes);
+
+        // A single held note has nothing else to choose
+        arp.note_off(64);
+        assert!(triggered_notes(&mut arp, 4).iter().all(|&n| n == 60));
+    }
+
+    /// Number of notes triggered over `samples` samples.
+    fn count_triggers(arp: &mut Arpeggiator, samples: usize) -> usize {
+        (0..samples).filter(|_| arp.process().is_some()).count()
+    }
+
+    #[test]
+    fn test_latch_keeps_playing_after_release() {
+        let config = ArpConfig {
+            speed: 5,
+            ..Default::default()
+        };
+        let mut arp = Arpeggiator::with_config(config, 44100.0, 120.0);
+
+        // Unlatched: releasing all keys stops the arp
+        arp.note_on(60, 100);
+        arp.note_off(60);
+        assert_eq!(count_triggers(&mut arp, 44100), 0);
+
+        arp.set_latch(true);
+        assert!(arp.is_latched());
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+        arp.note_off(60);
+        arp.note_off(64);
+        assert!(count_triggers(&mut arp, 44100) > 0);
+        assert_eq!(arp.get_current_chord(), [60, 64]);
+
+        // A new press after the release starts a fresh set...
+        arp.note_on(67, 100);
+        assert_eq!(arp.get_current_chord(), [67]);
+        // ...and notes pressed while keys are down add to it
+        arp.note_on(71, 100);
+        assert_eq!(arp.get_current_chord(), [67, 71]);
+    }
+
+    #[test]
+    fn test_unlatch_drops_released_notes() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        arp.set_latch(true);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+        arp.note_off(60);
+
+        // 64 is still down, so it survives unlatching
+        arp.set_latch(false);
+        assert_eq!(arp.get_current_chord(), [64]);
+
+        arp.set_latch(true);
+        arp.note_off(64);
+        arp.set_latch(false);
+        assert!(arp.get_current_chord().is_empty());
+        assert_eq!(count_triggers(&mut arp, 44100), 0);
+    }
+
+    #[test]
+    fn test_ratchet_splits_step_evenly() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        arp.set_ratchet(3, 1.0);
+        arp.set_gate_length(0.5);
+        arp.note_on(60, 100);
+        arp.note_on(64, 100);
+        let step = (arp.step_duration() * 44100.0) as usize;
+
+        let mut ons = Vec::new();
+        let mut offs = 0;
+        for i in 0..step * 2 {
+            let events = arp.process_events();
+            if let Some((note, _)) = events.note_on {
+                ons.push((i, note));
+            }
+            if events.note_off.is_some() {
+                offs += 1;
+            }
+        }
+
+        // Three evenly spaced retriggers of each step's note, all inside the step
+        assert_eq!(ons.len(), 6);
+        for (i, &(offset, note)) in ons.iter().enumerate() {
+            let expected = (i / 3) * step + (i % 3) * step / 3;
+            assert!(offset.abs_diff(expected) <= 1, "{offset} vs {expected}");
+            assert_eq!(note, if i < 3 { 60 } else { 64 });
+        }
+        // Each retrigger is released before the next one
+        assert!(offs >= 5);
+    }
+
+    #[test]
+    fn test_ratchet_count_one_is_normal() {
+        let mut arp = Arpeggiator::with_config(ArpConfig::default(), 44100.0, 120.0);
+        arp.set_ratchet(1, 1.0);
+        arp.note_on(60, 100);
+        let step = (arp.step_duration() * 44100.0) as usize;
+        assert_eq!(count_triggers(&mut arp, step * 4), 4);
+
+        arp.set_ratchet(9, 2.0);
+        assert_eq!(arp.ratchet(), (4, 1.0));
+    }
 }
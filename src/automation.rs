@@ -0,0 +1,165 @@
+//! Parameter Automation Module
+//!
+//! Timestamped parameter values recorded from `Synth::set_parameter` and played
+//! back with `Synth::apply_automation`. Positions are in samples from the
+//! start of the recording.
+//!
+//! A lane can hold points for several parameters at once; each parameter is
+//! interpolated independently, so lanes for different parameters can be
+//! played back side by side.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One recorded parameter value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutomationPoint {
+    /// Position in samples from the start of the lane
+    pub sample_position: u64,
+
+    /// Parameter value at that position
+    pub value: f32,
+}
+
+/// Recorded automation for one or more parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutomationLane {
+    /// Points per parameter ID, sorted by position
+    points: BTreeMap<i32, Vec<AutomationPoint>>,
+}
+
+impl AutomationLane {
+    /// Creates an empty lane.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a point, replacing any point for the same parameter and position.
+    ///
+    /// # Arguments
+    ///
+    /// * `param_id` - Parameter ID (e.g. `PARAM_ZDF_CUTOFF`)
+    /// * `sample_position` - Position in samples
+    /// * `value` - Parameter value
+    pub fn add_point(&mut self, param_id: i32, sample_position: u64, value: f32) {
+        let points = self.points.entry(param_id).or_default();
+        let point = AutomationPoint {
+            sample_position,
+            value,
+        };
+
+        match points.binary_search_by_key(&sample_position, |p| p.sample_position) {
+            Ok(index) => points[index] = point,
+            Err(index) => points.insert(index, point),
+        }
+    }
+
+    /// Gets the parameter IDs that have points.
+    pub fn params(&self) -> impl Iterator<Item = i32> + '_ {
+        self.points.keys().copied()
+    }
+
+    /// Gets the points of one parameter, sorted by position.
+    pub fn points(&self, param_id: i32) -> &[AutomationPoint] {
+        self.points.get(&param_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Checks if the lane has no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Gets the position of the last point of any parameter.
+    pub fn length(&self) -> u64 {
+        self.points
+            .values()
+            .filter_map(|points| points.last())
+            .map(|p| p.sample_position)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Gets a parameter's value at a position, interpolating linearly.
+    ///
+    /// Before the first point the first value holds, after the last point
+    /// the last value holds.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the parameter has no points
+    pub fn value_at(&self, param_id: i32, sample_position: u64) -> Option<f32> {
+        let points = self.points(param_id);
+        let next = points.partition_point(|p| p.sample_position <= sample_position);
+
+        match (points.get(next.wrapping_sub(1)), points.get(next)) {
+            (Some(before), Some(after)) => {
+                let span = (after.sample_position - before.sample_position) as f64;
+                let t = (sample_position - before.sample_position) as f64 / span;
+                Some(before.value + (after.value - before.value) * t as f32)
+            }
+            (Some(before), None) => Some(before.value),
+            (None, Some(after)) => Some(after.value),
+            (None, None) => None,
+        }
+    }
+
+    /// Gets a parameter's value at a position without interpolation.
+    ///
+    /// Used for switches, where a halfway value has no meaning.
+    ///
+    /// # Returns
+    ///
+    /// The value of the latest point at or before the position (the first
+    /// value before it), or `None` if the parameter has no points
+    pub fn step_value_at(&self, param_id: i32, sample_position: u64) -> Option<f32> {
+        let points = self.points(param_id);
+        let next = points.partition_point(|p| p.sample_position <= sample_position);
+        points.get(next.saturating_sub(1)).map(|point| point.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_at_interpolates_between_points() {
+        let mut lane = AutomationLane::new();
+        lane.add_point(1, 100, 0.0);
+        lane.add_point(1, 300, 1.0);
+
+        assert_eq!(lane.value_at(1, 0), Some(0.0));
+        assert_eq!(lane.value_at(1, 100), Some(0.0));
+        assert_eq!(lane.value_at(1, 200), Some(0.5));
+        assert_eq!(lane.value_at(1, 250), Some(0.75));
+        assert_eq!(lane.value_at(1, 1000), Some(1.0));
+        assert_eq!(lane.value_at(2, 200), None);
+    }
+
+    #[test]
+    fn test_step_value_at_holds_previous_point() {
+        let mut lane = AutomationLane::new();
+        lane.add_point(1, 100, 0.0);
+        lane.add_point(1, 300, 1.0);
+
+        assert_eq!(lane.step_value_at(1, 50), Some(0.0));
+        assert_eq!(lane.step_value_at(1, 299), Some(0.0));
+        assert_eq!(lane.step_value_at(1, 300), Some(1.0));
+    }
+
+    #[test]
+    fn test_params_are_independent() {
+        let mut lane = AutomationLane::new();
+        lane.add_point(1, 0, 10.0);
+        lane.add_point(2, 50, 5.0);
+        lane.add_point(1, 100, 20.0);
+        // Same position replaces the value
+        lane.add_point(2, 50, 6.0);
+
+        assert_eq!(lane.params().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(lane.points(2).len(), 1);
+        assert_eq!(lane.value_at(1, 50), Some(15.0));
+        assert_eq!(lane.value_at(2, 50), Some(6.0));
+        assert_eq!(lane.length(), 100);
+    }
+}
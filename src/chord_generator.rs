@@ -68,10 +68,49 @@ pub struct Chord {
     pub chord_type: ChordType,
     /// Chord extensions (9th, 11th, 13th) - stored as semitone offsets
     pub extensions: Vec<u8>,
+    /// Inversion (0 = root position, 1 = first inversion, ...)
+    pub inversion: u8,
     /// Duration in beats
     pub duration: f32,
 }
 
+impl Chord {
+    /// Semitone offsets of the chord tones above the root, in root position.
+    pub fn intervals(&self) -> &'static [u8] {
+        match self.chord_type {
+            ChordType::Major => &[0, 4, 7],
+            ChordType::Minor => &[0, 3, 7],
+            ChordType::Major7 => &[0, 4, 7, 11],
+            ChordType::Minor7 => &[0, 3, 7, 10],
+            ChordType::Dominant7 => &[0, 4, 7, 10],
+            ChordType::Diminished => &[0, 3, 6],
+            ChordType::Diminished7 => &[0, 3, 6, 9],
+            ChordType::Augmented => &[0, 4, 8],
+            ChordType::Sus2 => &[0, 2, 7],
+            ChordType::Sus4 => &[0, 5, 7],
+        }
+    }
+
+    /// MIDI notes of the voiced chord tones, lowest first.
+    ///
+    /// The inversion raises the lowest tones of the root-position chord by an
+    /// octave. Extensions are not voiced.
+    pub fn notes(&self) -> Vec<u8> {
+        let intervals = self.intervals();
+        let inversion = self.inversion as usize % intervals.len();
+        let mut notes: Vec<u8> = intervals
+            .iter()
+            .enumerate()
+            .map(|(i, &interval)| {
+                let octave = if i < inversion { 12 } else { 0 };
+                (self.root as u16 + interval as u16 + octave).min(127) as u8
+            })
+            .collect();
+        notes.sort_unstable();
+        notes
+    }
+}
+
 /// Chord style enumeration.
 ///
 /// Pre-configured styles for chord progression generation with appropriate parameters
@@ -127,9 +166,9 @@ pub struct Key {
 /// Common chord progression patterns.
 ///
 /// Pre-defined progression templates for different styles.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProgressionPattern {
-    /// I - IV - V - vi (The "Axis of Awesome" progression)
+    /// I - V - vi - IV (The "Axis of Awesome" progression)
     PopPillar,
     /// I - vi - IV - V (Another pop classic)
     StandardPop,
@@ -149,6 +188,48 @@ pub enum ProgressionPattern {
     PopMinor,
     /// I - iii - IV - V (Jazz minor)
     JazzMinor,
+    /// I7 x4 - IV7 x2 - I7 x2 - V7 - IV7 - I7 - V7 (12-bar blues)
+    TwelveBarBlues,
+}
+
+impl ProgressionPattern {
+    /// Scale degrees of one cycle of the pattern, one chord per bar.
+    fn degrees(&self) -> &'static [usize] {
+        match self {
+            ProgressionPattern::PopPillar => &[1, 5, 6, 4],
+            ProgressionPattern::StandardPop => &[1, 6, 4, 5],
+            // vi turns the cadence around into the next ii
+            ProgressionPattern::TwoFiveOne => &[2, 5, 1, 6],
+            ProgressionPattern::Circle => &[1, 6, 2, 5, 3, 7, 4, 1],
+            ProgressionPattern::RnBFlow => &[1, 4, 2, 5],
+            ProgressionPattern::NeoSoul => &[1, 6, 3, 7],
+            ProgressionPattern::MinorDescent => &[1, 7, 6, 5],
+            ProgressionPattern::RockDriver => &[1, 4, 1, 5],
+            ProgressionPattern::PopMinor => &[6, 4, 1, 5],
+            ProgressionPattern::JazzMinor => &[1, 3, 4, 5],
+            ProgressionPattern::TwelveBarBlues => &[1, 1, 1, 1, 4, 4, 1, 1, 5, 4, 1, 5],
+        }
+    }
+
+    /// Whether the pattern is voiced with 7th chords.
+    fn uses_sevenths(&self) -> bool {
+        matches!(
+            self,
+            ProgressionPattern::TwoFiveOne
+                | ProgressionPattern::RnBFlow
+                | ProgressionPattern::NeoSoul
+                | ProgressionPattern::JazzMinor
+                | ProgressionPattern::TwelveBarBlues
+        )
+    }
+
+    /// Whether chords may be replaced by secondary dominants.
+    fn uses_secondary_dominants(&self) -> bool {
+        matches!(
+            self,
+            ProgressionPattern::TwoFiveOne | ProgressionPattern::JazzMinor
+        )
+    }
 }
 
 /// Chord progression generator.
@@ -397,6 +478,7 @@ impl ChordGenerator {
                     root,
                     chord_type,
                     extensions: vec![],
+                    inversion: 0,
                     duration,
                 }
             })
@@ -422,6 +504,7 @@ impl ChordGenerator {
                     root,
                     chord_type,
                     extensions,
+                    inversion: 0,
                     duration: 4.0,
                 }
             })
@@ -448,6 +531,7 @@ impl ChordGenerator {
                     root,
                     chord_type,
                     extensions,
+                    inversion: 0,
                     duration: 8.0,
                 }
             })
@@ -472,6 +556,7 @@ impl ChordGenerator {
                     root,
                     chord_type,
                     extensions: vec![],
+                    inversion: 0,
                     duration: 4.0,
                 }
             })
@@ -496,6 +581,7 @@ impl ChordGenerator {
                     root,
                     chord_type,
                     extensions: vec![],
+                    inversion: 0,
                     duration: 4.0,
                 }
             })
@@ -655,6 +741,7 @@ impl ChordGenerator {
                 root,
                 chord_type,
                 extensions: vec![],
+                inversion: 0,
                 duration,
             });
         }
@@ -701,8 +788,177 @@ impl ChordGenerator {
             ProgressionPattern::JazzMinor => {
                 self.build_progression(&[1, 3, 4, 5], ChordType::Minor, 4.0)
             }
+            ProgressionPattern::TwelveBarBlues => {
+                self.build_progression(&[1, 1, 1, 1, 4, 4, 1, 1, 5, 4, 1, 5], ChordType::Major, 4.0)
+            }
+        }
+    }
+
+    /// Choose inversions that minimize voice movement between chords.
+    ///
+    /// The first chord is the anchor and stays in root position. Each
+    /// following chord takes the inversion (with its root moved up to an
+    /// octave either way) whose voices move the fewest semitones from the
+    /// previous chord. Seventh chords lead all four voices.
+    ///
+    /// # Arguments
+    ///
+    /// * `chords` - The progression to revoice in place
+    pub fn voice_lead(chords: &mut [Chord]) {
+        let Some((first, rest)) = chords.split_first_mut() else {
+            return;
+        };
+        first.inversion = 0;
+        let mut previous = first.notes();
+
+        for chord in rest {
+            let mut best: Option<(u32, u8, u8)> = None;
+            for root in [
+                chord.root.checked_sub(12),
+                Some(chord.root),
+                chord.root.checked_add(12),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|&root| root <= 127 - 12)
+            {
+                for inversion in 0..chord.intervals().len() as u8 {
+                    let candidate = Chord {
+                        root,
+                        inversion,
+                        ..chord.clone()
+                    };
+                    let movement = voice_movement(&previous, &candidate.notes());
+                    if best.is_none_or(|(least, _, _)| movement < least) {
+                        best = Some((movement, root, inversion));
+                    }
+                }
+            }
+
+            if let Some((_, root, inversion)) = best {
+                chord.root = root;
+                chord.inversion = inversion;
+            }
+            previous = chord.notes();
         }
     }
+
+    /// Realize a progression pattern with diatonic chords of a key.
+    ///
+    /// Chord qualities are built by stacking thirds on the key's scale, so
+    /// minor keys get i, ii°, III, iv, v, VI and VII. A v that resolves to
+    /// the tonic in a natural minor key takes the raised leading tone (V).
+    /// Jazz patterns turn chords that resolve down a fifth to a non-tonic
+    /// chord into secondary dominants (e.g. VI7 before ii), and the blues
+    /// uses dominant 7ths on its major chords.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to realize the pattern in
+    /// * `pattern` - The progression pattern, repeated to fill the bars
+    /// * `bars` - Number of bars (one chord per bar)
+    ///
+    /// # Returns
+    ///
+    /// One 4-beat chord per bar
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wavelet::chord_generator::{ChordGenerator, Key, ProgressionPattern, Scale};
+    ///
+    /// let key = Key { root: 60, scale: Scale::Major };
+    /// let chords = ChordGenerator::generate_progression(key, ProgressionPattern::PopPillar, 4);
+    /// assert_eq!(chords[2].to_string(), "A4m");
+    /// ```
+    pub fn generate_progression(key: Key, pattern: ProgressionPattern, bars: usize) -> Vec<Chord> {
+        let degrees = pattern.degrees();
+        let scale = scale_intervals(key.scale);
+        let sevenths = pattern.uses_sevenths();
+        let root_of = |intervals: &[u8; 7], degree: usize| {
+            (key.root as u16 + intervals[degree - 1] as u16).min(127) as u8
+        };
+
+        (0..bars)
+            .map(|bar| {
+                let degree = degrees[bar % degrees.len()];
+                let next = degrees[(bar + 1) % degrees.len()];
+
+                let intervals = if key.scale == Scale::Minor && degree == 5 && next == 1 {
+                    scale_intervals(Scale::HarmonicMinor)
+                } else {
+                    scale
+                };
+                let mut chord_type = diatonic_chord_type(&intervals, degree - 1, sevenths);
+
+                let root = root_of(&intervals, degree);
+                let falls_a_fifth =
+                    (root as i32 - root_of(&scale, next) as i32).rem_euclid(12) == 7;
+                if pattern.uses_secondary_dominants() && falls_a_fifth && degree != 2 && next != 1 {
+                    chord_type = ChordType::Dominant7;
+                }
+                if pattern == ProgressionPattern::TwelveBarBlues && chord_type == ChordType::Major7
+                {
+                    chord_type = ChordType::Dominant7;
+                }
+
+                Chord {
+                    root,
+                    chord_type,
+                    extensions: vec![],
+                    inversion: 0,
+                    duration: 4.0,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Summed absolute semitone movement from one voicing to the next.
+///
+/// Voices are paired lowest to lowest; when the chords have different sizes,
+/// the extra voices pair with the other chord's top voice.
+fn voice_movement(from: &[u8], to: &[u8]) -> u32 {
+    let voices = from.len().max(to.len());
+    (0..voices)
+        .map(|i| {
+            let a = from[i.min(from.len() - 1)];
+            let b = to[i.min(to.len() - 1)];
+            a.abs_diff(b) as u32
+        })
+        .sum()
+}
+
+/// Semitone offsets of the seven degrees of a scale.
+fn scale_intervals(scale: Scale) -> [u8; 7] {
+    match scale {
+        Scale::Major => [0, 2, 4, 5, 7, 9, 11],
+        Scale::Minor => [0, 2, 3, 5, 7, 8, 10],
+        Scale::HarmonicMinor => [0, 2, 3, 5, 7, 8, 11],
+        Scale::Dorian => [0, 2, 3, 5, 7, 9, 10],
+        Scale::Mixolydian => [0, 2, 4, 5, 7, 9, 10],
+    }
+}
+
+/// Chord quality built by stacking thirds on a scale degree (0-based).
+fn diatonic_chord_type(intervals: &[u8; 7], degree: usize, seventh: bool) -> ChordType {
+    let above_root = |steps: usize| {
+        let index = degree + steps;
+        intervals[index % 7] + 12 * (index / 7) as u8 - intervals[degree]
+    };
+
+    match (above_root(2), above_root(4), seventh.then(|| above_root(6))) {
+        (4, 7, None) => ChordType::Major,
+        (3, 7, None) => ChordType::Minor,
+        (3, 6, None) => ChordType::Diminished,
+        (4, 8, _) => ChordType::Augmented,
+        (4, 7, Some(11)) => ChordType::Major7,
+        (4, 7, Some(_)) => ChordType::Dominant7,
+        (3, 7, Some(_)) => ChordType::Minor7,
+        // Half-diminished is voiced as a diminished 7th, as in the jazz presets
+        (3, 6, Some(_)) => ChordType::Diminished7,
+        _ => ChordType::Major,
+    }
 }
 
 /// Convert chord type to string for display.
@@ -828,6 +1084,7 @@ mod tests {
             root: 60,
             chord_type: ChordType::Major,
             extensions: vec![],
+            inversion: 0,
             duration: 4.0,
         };
         assert_eq!(format!("{}", chord), "C4");
@@ -836,6 +1093,7 @@ mod tests {
             root: 62, // D4
             chord_type: ChordType::Minor7,
             extensions: vec![],
+            inversion: 0,
             duration: 4.0,
         };
         assert_eq!(format!("{}", chord7), "D4m7");
@@ -964,7 +1222,7 @@ mod tests {
         let mut gen = ChordGenerator::new(key, 120.0);
 
         for pattern in patterns {
-            let prog = gen.generate_from_pattern(pattern.clone());
+            let prog = gen.generate_from_pattern(pattern);
             assert!(
                 !prog.is_empty(),
                 "Pattern {:?} produced empty progression",
@@ -1021,6 +1279,7 @@ mod tests {
                 root: 60,
                 chord_type,
                 extensions: vec![],
+                inversion: 0,
                 duration: 4.0,
             };
             assert_eq!(
@@ -1055,4 +1314,132 @@ mod tests {
         let roots_g: Vec<u8> = prog_g.iter().map(|c| c.root % 12).collect();
         assert_ne!(roots_c, roots_g, "Same pattern in C and G should differ");
     }
+
+    #[test]
+    fn test_generate_progression_pop_in_c_major() {
+        let key = Key {
+            root: 60,
+            scale: Scale::Major,
+        };
+        let chords = ChordGenerator::generate_progression(key, ProgressionPattern::PopPillar, 6);
+        let names: Vec<String> = chords.iter().map(|c| c.to_string()).collect();
+        // Pattern repeats to fill the bars
+        assert_eq!(names, ["C4", "G4", "A4m", "F4", "C4", "G4"]);
+        assert!(chords.iter().all(|c| c.duration == 4.0));
+    }
+
+    #[test]
+    fn test_generate_from_pattern_keeps_two_five_one() {
+        let key = Key {
+            root: 60,
+            scale: Scale::Major,
+        };
+        let mut generator = ChordGenerator::new(key, 120.0);
+
+        // The turnaround chord only belongs to generate_progression
+        let roots = |chords: Vec<Chord>| chords.iter().map(|c| c.root).collect::<Vec<u8>>();
+        let cadence = generator.generate_from_pattern(ProgressionPattern::TwoFiveOne);
+        assert_eq!(roots(cadence), [62, 67, 60]);
+        let bars = ChordGenerator::generate_progression(key, ProgressionPattern::TwoFiveOne, 4);
+        assert_eq!(roots(bars), [62, 67, 60, 69]);
+    }
+
+    #[test]
+    fn test_generate_progression_minor_qualities() {
+        let key = Key {
+            root: 57, // A3
+            scale: Scale::Minor,
+        };
+        // i - VII - VI - V: the V resolving to i gets the raised leading tone
+        let chords = ChordGenerator::generate_progression(key, ProgressionPattern::MinorDescent, 4);
+        let names: Vec<String> = chords.iter().map(|c| c.to_string()).collect();
+        assert_eq!(names, ["A3m", "G4", "F4", "E4"]);
+
+        // i - VI - III - VII with 7ths
+        let chords = ChordGenerator::generate_progression(key, ProgressionPattern::NeoSoul, 4);
+        let types: Vec<ChordType> = chords.iter().map(|c| c.chord_type).collect();
+        assert_eq!(
+            types,
+            [
+                ChordType::Minor7,
+                ChordType::Major7,
+                ChordType::Major7,
+                ChordType::Dominant7
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_progression_jazz_and_blues() {
+        let key = Key {
+            root: 60,
+            scale: Scale::Major,
+        };
+        // ii7 - V7 - Imaj7 - VI7 (secondary dominant of ii)
+        let chords = ChordGenerator::generate_progression(key, ProgressionPattern::TwoFiveOne, 4);
+        let names: Vec<String> = chords.iter().map(|c| c.to_string()).collect();
+        assert_eq!(names, ["D4m7", "G47", "C4maj7", "A47"]);
+
+        let blues =
+            ChordGenerator::generate_progression(key, ProgressionPattern::TwelveBarBlues, 12);
+        assert!(blues.iter().all(|c| c.chord_type == ChordType::Dominant7));
+        let roots: Vec<u8> = blues.iter().map(|c| c.root).collect();
+        assert_eq!(roots, [60, 60, 60, 60, 65, 65, 60, 60, 67, 65, 60, 67]);
+    }
+
+    #[test]
+    fn test_chord_notes_and_inversions() {
+        let mut chord = Chord {
+            root: 60,
+            chord_type: ChordType::Major,
+            extensions: vec![],
+            inversion: 0,
+            duration: 4.0,
+        };
+        assert_eq!(chord.notes(), [60, 64, 67]);
+        chord.inversion = 1;
+        assert_eq!(chord.notes(), [64, 67, 72]);
+        chord.inversion = 2;
+        assert_eq!(chord.notes(), [67, 72, 76]);
+    }
+
+    #[test]
+    fn test_voice_lead_reduces_movement() {
+        let key = Key {
+            root: 60,
+            scale: Scale::Major,
+        };
+        let total_movement = |chords: &[Chord]| -> u32 {
+            chords
+                .windows(2)
+                .map(|w| voice_movement(&w[0].notes(), &w[1].notes()))
+                .sum()
+        };
+
+        for pattern in [
+            ProgressionPattern::PopPillar,
+            ProgressionPattern::Circle,
+            ProgressionPattern::TwoFiveOne,
+        ] {
+            let plain = ChordGenerator::generate_progression(key, pattern, 8);
+            let mut led = plain.clone();
+            ChordGenerator::voice_lead(&mut led);
+
+            assert!(
+                total_movement(&led) < total_movement(&plain),
+                "{:?}: {} vs {}",
+                pattern,
+                total_movement(&led),
+                total_movement(&plain)
+            );
+            // The first chord is the anchor
+            assert_eq!(led[0], plain[0]);
+            // Same chords, only the voicing changes
+            for (a, b) in led.iter().zip(&plain) {
+                assert_eq!(a.chord_type, b.chord_type);
+                assert_eq!(a.root % 12, b.root % 12);
+                assert_eq!(a.notes().len(), b.intervals().len());
+            }
+        }
+    }
 }
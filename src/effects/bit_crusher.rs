@@ -16,10 +16,20 @@
 // Parameters:
 // - BIT REDUCTION: Bit depth (1-24 bit)
 // - SAMPLE RATE: Decimation ratio (1x - 4x)
+// - JITTER: Random variation of the hold period (unstable converter clock)
+// - DITHER: Noise added before quantization (decorrelates the quantization error)
 //
 // Sound characteristics:
 // - Low bit depth: Produces quantization noise, rough texture
 // - Decimation: Produces aliasing frequencies, metallic texture
+// - Jitter: Smears the aliasing into noise, like a vintage converter
+// - Dither: Turns quantization distortion into a steady hiss
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Seed of the jitter/dither random source on a new crusher
+const DEFAULT_SEED: u64 = 0x5EED;
 
 /// Decimation mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +44,17 @@ pub enum DecimationMode {
     Linear,
 }
 
+/// Quantization dither
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherMode {
+    /// No dither (error follows the signal, harsh distortion)
+    #[default]
+    None,
+
+    /// Triangular (TPDF) dither of ±1 quantization step
+    Triangular,
+}
+
 /// Bit Crusher configuration
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BitCrusherConfig {
@@ -88,6 +109,18 @@ pub struct BitCrusher {
 
     /// Quantization step size
     quantize_step: f32,
+
+    /// Hold period variation (0.0 = steady clock, 1.0 = ±1 interval)
+    jitter: f32,
+
+    /// Samples left until the next jittered hold point
+    hold_countdown: usize,
+
+    /// Quantization dither
+    dither: DitherMode,
+
+    /// Random source for jitter and dither
+    rng: StdRng,
 }
 
 impl Default for BitCrusher {
@@ -112,6 +145,10 @@ impl BitCrusher {
             decimation_interval: 1,
             output_gain: 1.0,
             quantize_step: 1.0 / (2.0f32.powf(23.0)), // 24-bit half
+            jitter: 0.0,
+            hold_countdown: 0,
+            dither: DitherMode::None,
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
         };
 
         crusher.update_parameters();
@@ -157,6 +194,34 @@ impl BitCrusher {
         self.config.decimation_mode = mode;
     }
 
+    /// Set clock jitter (0.0 - 1.0)
+    ///
+    /// Each hold period is randomly lengthened or shortened by up to
+    /// `amount` decimation intervals. 0.0 keeps the steady clock.
+    pub fn set_jitter(&mut self, amount: f32) {
+        self.jitter = amount.clamp(0.0, 1.0);
+    }
+
+    /// Get clock jitter
+    pub fn jitter(&self) -> f32 {
+        self.jitter
+    }
+
+    /// Set quantization dither
+    pub fn set_dither(&mut self, dither: DitherMode) {
+        self.dither = dither;
+    }
+
+    /// Get quantization dither
+    pub fn dither(&self) -> DitherMode {
+        self.dither
+    }
+
+    /// Reseed the random source used by jitter and dither
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
     /// Update internal parameters
     fn update_parameters(&mut self) {
         // Calculate decimation interval
@@ -191,15 +256,45 @@ impl BitCrusher {
         }
     }
 
+    /// Check if a jittered clock takes a new sample now
+    fn jittered_hold_point(&mut self) -> bool {
+        if self.hold_countdown > 0 {
+            self.hold_countdown -= 1;
+            return false;
+        }
+
+        let interval = self.decimation_interval as f32;
+        let spread = self.jitter * interval;
+        let period = (interval + self.rng.gen_range(-spread..=spread)).round();
+        self.hold_countdown = (period as usize).max(1) - 1;
+        true
+    }
+
+    /// Triangular dither noise for the current quantization step
+    fn dither_noise(&mut self) -> f32 {
+        match self.dither {
+            DitherMode::None => 0.0,
+            // Difference of two uniform values: triangular over ±1 step
+            DitherMode::Triangular => {
+                (self.rng.gen::<f32>() - self.rng.gen::<f32>()) * self.quantize_step
+            }
+        }
+    }
+
     /// Process sample
     #[inline]
     pub fn process(&mut self, input: f32) -> f32 {
-        let is_decimation_point = self.sample_counter.is_multiple_of(self.decimation_interval);
+        let is_decimation_point = if self.jitter > 0.0 {
+            self.jittered_hold_point()
+        } else {
+            self.sample_counter.is_multiple_of(self.decimation_interval)
+        };
 
         let processed = match (is_decimation_point, self.config.decimation_mode) {
             (true, _) => {
                 // Decimation point: quantize and update output
-                let quantized = self.quantize(input);
+                let dithered = input + self.dither_noise();
+                let quantized = self.quantize(dithered);
                 self.last_output = quantized * self.output_gain;
                 self.last_output
             }
@@ -232,6 +327,7 @@ impl BitCrusher {
     /// 重置状态
     pub fn reset(&mut self) {
         self.sample_counter = 0;
+        self.hold_countdown = 0;
         self.last_output = 0.0;
     }
 
@@ -292,10 +388,25 @@ impl StereoBitCrusher {
         self.crusher_r.set_config(config_r);
     }
 
+    /// 设置时钟抖动 (0.0 - 1.0)
+    pub fn set_jitter(&mut self, amount: f32) {
+        self.crusher_l.set_jitter(amount);
+        self.crusher_r.set_jitter(amount);
+    }
+
+    /// 设置量化抖动
+    pub fn set_dither(&mut self, dither: DitherMode) {
+        self.crusher_l.set_dither(dither);
+        self.crusher_r.set_dither(dither);
+    }
+
     /// 处理立体声样本
     #[inline]
     pub fn process(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
-        self.crusher_l.process_stereo(input_left, input_right)
+        (
+            self.crusher_l.process(input_left),
+            self.crusher_r.process(input_right),
+        )
     }
 
     /// 重置
@@ -733,4 +844,87 @@ mod tests {
             assert!(right.is_finite());
         }
     }
+
+    #[test]
+    fn test_stereo_bit_crusher_channels_hold_separately() {
+        let mut crusher = StereoBitCrusher::new();
+        crusher.set_sample_rate(44100.0);
+        crusher.set_config(BitCrusherConfig {
+            sample_rate_reduction: 4.0,
+            ..Default::default()
+        });
+
+        // A held left sample must never leak into the right channel
+        for _ in 0..64 {
+            let (left, right) = crusher.process(0.5, -0.5);
+            assert!(left > 0.0);
+            assert!(right < 0.0);
+        }
+    }
+
+    // ============ Jitter / Dither ============
+
+    #[test]
+    fn test_bit_crusher_triangular_dither_decorrelates_error() {
+        use crate::audio_analysis::measure_stereo_correlation;
+
+        // A sine below half a step: undithered it rounds away completely
+        let input: Vec<f32> = (0..44100)
+            .map(|i| (2.0 * PI * 440.0 * i as f32 / 44100.0).sin() * 0.003)
+            .collect();
+
+        let error_correlation = |dither: DitherMode| {
+            let mut crusher = BitCrusher::new_with_sample_rate(44100.0);
+            crusher.set_bit_depth(8.0);
+            crusher.set_sample_rate_reduction(1.0);
+            crusher.set_dither(dither);
+            let error: Vec<f32> = input.iter().map(|&s| crusher.process(s) - s).collect();
+            measure_stereo_correlation(&input, &error)
+        };
+
+        let plain = error_correlation(DitherMode::None);
+        let dithered = error_correlation(DitherMode::Triangular);
+        assert!(plain < -0.99, "undithered correlation {plain}");
+        assert!(dithered.abs() < 0.1, "dithered correlation {dithered}");
+    }
+
+    #[test]
+    fn test_bit_crusher_jitter_varies_hold_period() {
+        let input: Vec<f32> = (0..4096).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        let render = |jitter: f32, seed: u64| {
+            let mut crusher = BitCrusher::new_with_sample_rate(44100.0);
+            crusher.set_bit_depth(24.0);
+            crusher.set_sample_rate_reduction(4.0);
+            crusher.set_jitter(jitter);
+            crusher.set_seed(seed);
+            input
+                .iter()
+                .map(|&s| crusher.process(s))
+                .collect::<Vec<f32>>()
+        };
+
+        // Lengths of the runs of held values (the dry/wet mix can round
+        // the last bit)
+        let hold_lengths = |output: &[f32]| {
+            let mut lengths = vec![1];
+            for pair in output.windows(2) {
+                if (pair[0] - pair[1]).abs() < 1e-6 {
+                    *lengths.last_mut().unwrap() += 1;
+                } else {
+                    lengths.push(1);
+                }
+            }
+            lengths
+        };
+
+        let steady = render(0.0, 1);
+        assert!(hold_lengths(&steady).iter().all(|&len| len == 4));
+
+        let jittered = render(0.5, 1);
+        assert_eq!(jittered, render(0.5, 1), "same seed must repeat");
+        assert_ne!(jittered, render(0.5, 2));
+        let lengths = hold_lengths(&jittered);
+        assert!(lengths.iter().all(|&len| (2..=6).contains(&len)));
+        assert!(lengths.iter().any(|&len| len != 4));
+    }
 }
@@ -14,9 +14,26 @@
 //! - **Depth**: Amount of delay modulation (0 - 100%)
 //! - **Mix**: Wet/dry balance (0% = dry, 100% = wet)
 //! - **Feedback**: Regeneration amount for more intense effects
+//! - **Voices**: Number of modulated taps (1 - 3) for an ensemble sound
 
 use std::f32::consts::PI;
 
+use super::flush_denormal;
+
+/// Maximum number of chorus voices (modulated delay taps per channel).
+pub const MAX_CHORUS_VOICES: u8 = 3;
+
+/// LFO rate multipliers for the extra ensemble voices (voices 2 and 3).
+///
+/// Deliberately non-harmonic so the taps never line up.
+const ENSEMBLE_RATE_MULTS: [f32; 2] = [1.37, 0.61];
+
+/// Depth multipliers for the extra ensemble voices (voices 2 and 3).
+const ENSEMBLE_DEPTH_MULTS: [f32; 2] = [0.8, 0.65];
+
+/// Initial LFO phases for the extra ensemble voices (voices 2 and 3).
+const ENSEMBLE_START_PHASES: [f32; 2] = [1.0 / 3.0, 2.0 / 3.0];
+
 /// Stereo chorus effect with LFO-modulated delay lines.
 ///
 /// The chorus effect creates a natural "doubling" or "widening" effect
@@ -75,6 +92,12 @@ pub struct Chorus {
     /// Feedback amount (0.0 - 0.9)
     feedback: f32,
 
+    /// Number of active voices (1 - 3)
+    voices: u8,
+
+    /// LFO phases of the extra ensemble voices (0.0 - 1.0)
+    voice_phases: [f32; 2],
+
     /// Sample rate
     sample_rate: f32,
 
@@ -113,6 +136,8 @@ impl Chorus {
             depth: 0.5,            // 50% depth
             mix: 0.4,              // 40% wet
             feedback: 0.2,         // 20% feedback
+            voices: 1,
+            voice_phases: ENSEMBLE_START_PHASES,
             sample_rate,
             enabled: true,
         }
@@ -151,6 +176,8 @@ impl Chorus {
             depth,
             mix,
             feedback: 0.2,
+            voices: 1,
+            voice_phases: ENSEMBLE_START_PHASES,
             sample_rate,
             enabled: true,
         }
@@ -235,6 +262,54 @@ impl Chorus {
     fn calculate_delay(base_delay: usize, max_modulation: f32, lfo_value: f32) -> f32 {
         base_delay as f32 + lfo_value * max_modulation
     }
+
+    /// Reads the extra ensemble voice taps from both delay lines.
+    ///
+    /// The right channel uses the inverted LFO so each extra voice also
+    /// spreads across the stereo field.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_modulation` - Maximum modulation of the primary voice in samples
+    ///
+    /// # Returns
+    ///
+    /// Summed (left, right) taps of voices 2 and up (0.0 when `voices` is 1)
+    fn read_extra_voices(&self, max_modulation: f32) -> (f32, f32) {
+        let mut left = 0.0;
+        let mut right = 0.0;
+
+        let extra_voices = self.voices as usize - 1;
+        for (&phase, depth_mult) in self
+            .voice_phases
+            .iter()
+            .zip(ENSEMBLE_DEPTH_MULTS)
+            .take(extra_voices)
+        {
+            let lfo = Self::lfo_sine(phase);
+            let modulation = max_modulation * depth_mult;
+
+            let left_delay = Self::calculate_delay(self.base_delay_samples, modulation, lfo);
+            let right_delay = Self::calculate_delay(self.base_delay_samples, modulation, -lfo);
+
+            left +=
+                Self::read_interpolated(&self.left_buffer, self.left_write_pos as f32 - left_delay);
+            right += Self::read_interpolated(
+                &self.right_buffer,
+                self.right_write_pos as f32 - right_delay,
+            );
+        }
+
+        (left, right)
+    }
+
+    /// Advances the LFO phases of the extra ensemble voices.
+    #[inline]
+    fn advance_voice_phases(&mut self) {
+        for (phase, mult) in self.voice_phases.iter_mut().zip(ENSEMBLE_RATE_MULTS) {
+            *phase = Self::advance_phase(*phase, self.rate_hz * mult, self.sample_rate);
+        }
+    }
 }
 
 impl super::Effect for Chorus {
@@ -262,17 +337,21 @@ impl super::Effect for Chorus {
         let left_read_pos = self.left_write_pos as f32 - left_delay;
         let right_read_pos = self.right_write_pos as f32 - right_delay;
 
-        // Read delayed samples
-        let left_delayed = Self::read_interpolated(&self.left_buffer, left_read_pos);
-        let right_delayed = Self::read_interpolated(&self.right_buffer, right_read_pos);
+        // Read delayed samples, summing and normalizing the ensemble voices
+        let (extra_left, extra_right) = self.read_extra_voices(max_modulation);
+        let voices = self.voices as f32;
+        let left_delayed =
+            (Self::read_interpolated(&self.left_buffer, left_read_pos) + extra_left) / voices;
+        let right_delayed =
+            (Self::read_interpolated(&self.right_buffer, right_read_pos) + extra_right) / voices;
 
         // Mix feedback
         let left_feedback = left_delayed * self.feedback;
         let right_feedback = right_delayed * self.feedback;
 
         // Write to buffers with feedback
-        self.left_buffer[self.left_write_pos] = input + left_feedback;
-        self.right_buffer[self.right_write_pos] = input + right_feedback;
+        self.left_buffer[self.left_write_pos] = flush_denormal(input + left_feedback);
+        self.right_buffer[self.right_write_pos] = flush_denormal(input + right_feedback);
 
         // Advance write positions
         self.left_write_pos = (self.left_write_pos + 1) % self.max_delay_samples;
@@ -285,6 +364,7 @@ impl super::Effect for Chorus {
             self.rate_hz * self.right_rate_mult,
             self.sample_rate,
         );
+        self.advance_voice_phases();
 
         // Mix wet and dry (output mono from stereo input)
         let wet = (left_delayed + right_delayed) * 0.5;
@@ -315,6 +395,7 @@ impl super::Effect for Chorus {
         self.right_write_pos = self.base_delay_samples;
         self.left_phase = 0.0;
         self.right_phase = 0.0;
+        self.voice_phases = ENSEMBLE_START_PHASES;
     }
 
     /// Sets the wet/dry mix.
@@ -338,6 +419,36 @@ impl super::Effect for Chorus {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    /// Sets a parameter by identifier.
+    fn set_param(&mut self, id: super::EffectParameterId, value: f32) {
+        use super::EffectParameterId;
+
+        match id {
+            EffectParameterId::ChorusRate => self.set_rate(value),
+            EffectParameterId::ChorusDepth => self.set_depth(value),
+            EffectParameterId::ChorusFeedback => self.set_feedback(value),
+            EffectParameterId::Mix => self.set_mix(value),
+            _ => {}
+        }
+    }
+
+    /// Gets a parameter by identifier.
+    fn get_param(&self, id: super::EffectParameterId) -> Option<f32> {
+        use super::EffectParameterId;
+
+        match id {
+            EffectParameterId::ChorusRate => Some(self.rate_hz),
+            EffectParameterId::ChorusDepth => Some(self.depth),
+            EffectParameterId::ChorusFeedback => Some(self.feedback),
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> super::EffectType {
+        super::EffectType::Chorus
+    }
 }
 
 impl Chorus {
@@ -368,17 +479,24 @@ impl Chorus {
             let left_read_pos = self.left_write_pos as f32 - left_delay;
             let right_read_pos = self.right_write_pos as f32 - right_delay;
 
-            // Read delayed samples
-            let left_delayed = Self::read_interpolated(&self.left_buffer, left_read_pos);
-            let right_delayed = Self::read_interpolated(&self.right_buffer, right_read_pos);
+            // Read delayed samples, summing and normalizing the ensemble voices
+            let (extra_left, extra_right) = self.read_extra_voices(max_modulation);
+            let voices = self.voices as f32;
+            let left_delayed =
+                (Self::read_interpolated(&self.left_buffer, left_read_pos) + extra_left) / voices;
+            let right_delayed = (Self::read_interpolated(&self.right_buffer, right_read_pos)
+                + extra_right)
+                / voices;
 
             // Mix wet and dry
             let left_wet = *l * (1.0 - self.mix) + left_delayed * self.mix;
             let right_wet = *r * (1.0 - self.mix) + right_delayed * self.mix;
 
             // Write to buffers
-            self.left_buffer[self.left_write_pos] = *l + left_delayed * self.feedback;
-            self.right_buffer[self.right_write_pos] = *r + right_delayed * self.feedback;
+            self.left_buffer[self.left_write_pos] =
+                flush_denormal(*l + left_delayed * self.feedback);
+            self.right_buffer[self.right_write_pos] =
+                flush_denormal(*r + right_delayed * self.feedback);
 
             // Advance write positions
             self.left_write_pos = (self.left_write_pos + 1) % self.max_delay_samples;
@@ -391,6 +509,7 @@ impl Chorus {
                 self.rate_hz * self.right_rate_mult,
                 self.sample_rate,
             );
+            self.advance_voice_phases();
 
             *l = left_wet;
             *r = right_wet;
@@ -417,9 +536,13 @@ impl Chorus {
 
     /// Sets the feedback amount.
     ///
+    /// The delayed (wet) signal is fed back into the delay lines, turning
+    /// the chorus into a resonant, flanger-like sweep at higher settings.
+    /// At 0.0 the delay lines only ever hold the dry input.
+    ///
     /// # Arguments
     ///
-    /// * `feedback` - Feedback amount (0.0 - 0.9)
+    /// * `feedback` - Feedback amount (0.0 - 0.9, clamped for stability)
     pub fn set_feedback(&mut self, feedback: f32) {
         self.feedback = feedback.clamp(0.0, 0.9);
     }
@@ -433,6 +556,19 @@ impl Chorus {
         self.right_rate_mult = width.clamp(1.0, 1.2);
     }
 
+    /// Sets the number of chorus voices.
+    ///
+    /// Voices 2 and 3 are extra delay taps with staggered LFO rates, phases
+    /// and depths, giving a string-ensemble style chorus. The taps are summed
+    /// and normalized so the wet level does not grow with the voice count.
+    ///
+    /// # Arguments
+    ///
+    /// * `voices` - Number of voices (1 - 3); 1 is the classic single-voice chorus
+    pub fn set_voices(&mut self, voices: u8) {
+        self.voices = voices.clamp(1, MAX_CHORUS_VOICES);
+    }
+
     /// Gets the current LFO rate in Hz.
     pub fn rate(&self) -> f32 {
         self.rate_hz
@@ -447,6 +583,223 @@ impl Chorus {
     pub fn feedback(&self) -> f32 {
         self.feedback
     }
+
+    /// Gets the number of active voices.
+    pub fn voices(&self) -> u8 {
+        self.voices
+    }
+}
+
+/// Stereo chorus with a shared LFO whose right-channel phase is offset.
+///
+/// Unlike [`Chorus`], which detunes the right LFO rate, `StereoChorus`
+/// runs both delay-line LFOs at the same rate and shifts the right channel's
+/// phase by `width * 180°`. A width of 0.5 gives quadrature (90°) modulation
+/// and 1.0 gives fully inverted (180°) modulation. At width 0 both channels
+/// are modulated identically, so the output stays mono-compatible.
+#[derive(Debug, Clone)]
+pub struct StereoChorus {
+    /// Left channel delay buffer
+    left_buffer: Vec<f32>,
+
+    /// Right channel delay buffer
+    right_buffer: Vec<f32>,
+
+    /// Shared write position
+    write_pos: usize,
+
+    /// Shared LFO phase (0.0 - 1.0)
+    phase: f32,
+
+    /// Base delay time in samples
+    base_delay_samples: usize,
+
+    /// Maximum delay time in samples
+    max_delay_samples: usize,
+
+    /// LFO rate in Hz
+    rate_hz: f32,
+
+    /// LFO depth (0.0 - 1.0)
+    depth: f32,
+
+    /// Wet/dry mix (0.0 - 1.0)
+    mix: f32,
+
+    /// Feedback amount (0.0 - 0.9)
+    feedback: f32,
+
+    /// Stereo width (0.0 = mono, 1.0 = right LFO 180° out of phase)
+    width: f32,
+
+    /// Sample rate
+    sample_rate: f32,
+}
+
+impl StereoChorus {
+    /// Creates a new stereo chorus.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_rate` - Audio sample rate in Hz
+    ///
+    /// # Returns
+    ///
+    /// A new StereoChorus with the same defaults as [`Chorus`] and a
+    /// 90° (width 0.5) phase offset
+    pub fn new(sample_rate: f32) -> Self {
+        let max_delay_ms = 50.0;
+        let base_delay_ms = 25.0;
+
+        let max_delay_samples = (max_delay_ms / 1000.0 * sample_rate) as usize;
+        let base_delay_samples = (base_delay_ms / 1000.0 * sample_rate) as usize;
+
+        Self {
+            left_buffer: vec![0.0; max_delay_samples],
+            right_buffer: vec![0.0; max_delay_samples],
+            write_pos: base_delay_samples,
+            phase: 0.0,
+            base_delay_samples,
+            max_delay_samples,
+            rate_hz: 0.5,
+            depth: 0.5,
+            mix: 0.4,
+            feedback: 0.2,
+            width: 0.5,
+            sample_rate,
+        }
+    }
+
+    /// Processes a single stereo sample pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_left` - Left input sample
+    /// * `input_right` - Right input sample
+    ///
+    /// # Returns
+    ///
+    /// Processed (left, right) samples
+    #[inline]
+    pub fn process(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
+        // Right LFO is offset by up to half a cycle
+        let right_phase = (self.phase + self.width * 0.5).fract();
+        let left_lfo = Chorus::lfo_sine(self.phase);
+        let right_lfo = Chorus::lfo_sine(right_phase);
+
+        let max_modulation = self.depth * (self.max_delay_samples - self.base_delay_samples) as f32;
+
+        let left_delay = Chorus::calculate_delay(self.base_delay_samples, max_modulation, left_lfo);
+        let right_delay =
+            Chorus::calculate_delay(self.base_delay_samples, max_modulation, right_lfo);
+
+        let left_delayed =
+            Chorus::read_interpolated(&self.left_buffer, self.write_pos as f32 - left_delay);
+        let right_delayed =
+            Chorus::read_interpolated(&self.right_buffer, self.write_pos as f32 - right_delay);
+
+        self.left_buffer[self.write_pos] =
+            flush_denormal(input_left + left_delayed * self.feedback);
+        self.right_buffer[self.write_pos] =
+            flush_denormal(input_right + right_delayed * self.feedback);
+
+        self.write_pos = (self.write_pos + 1) % self.max_delay_samples;
+        self.phase = Chorus::advance_phase(self.phase, self.rate_hz, self.sample_rate);
+
+        (
+            input_left * (1.0 - self.mix) + left_delayed * self.mix,
+            input_right * (1.0 - self.mix) + right_delayed * self.mix,
+        )
+    }
+
+    /// Processes stereo buffers in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Left channel samples
+    /// * `right` - Right channel samples
+    pub fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        assert_eq!(left.len(), right.len());
+
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            let (out_l, out_r) = self.process(*l, *r);
+            *l = out_l;
+            *r = out_r;
+        }
+    }
+
+    /// Clears the delay lines and resets the LFO phase.
+    pub fn reset(&mut self) {
+        self.left_buffer.fill(0.0);
+        self.right_buffer.fill(0.0);
+        self.write_pos = self.base_delay_samples;
+        self.phase = 0.0;
+    }
+
+    /// Sets the LFO rate in Hz.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_hz` - LFO frequency (0.1 - 10.0 Hz)
+    pub fn set_rate(&mut self, rate_hz: f32) {
+        self.rate_hz = rate_hz.clamp(0.1, 10.0);
+    }
+
+    /// Sets the modulation depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Modulation depth (0.0 - 1.0)
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.clamp(0.0, 1.0);
+    }
+
+    /// Sets the wet/dry mix.
+    ///
+    /// # Arguments
+    ///
+    /// * `mix` - Wet/dry mix (0.0 - 1.0)
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    /// Sets the feedback amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `feedback` - Feedback amount (0.0 - 0.9, clamped for stability)
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.9);
+    }
+
+    /// Sets the stereo width as an LFO phase offset between channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - 0.0 = identical channels, 0.5 = 90°, 1.0 = 180°
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width.clamp(0.0, 1.0);
+    }
+
+    /// Gets the current LFO rate in Hz.
+    pub fn rate(&self) -> f32 {
+        self.rate_hz
+    }
+
+    /// Gets the current modulation depth.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Gets the current feedback amount.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Gets the current stereo width.
+    pub fn width(&self) -> f32 {
+        self.width
+    }
 }
 
 #[cfg(test)]
@@ -642,4 +995,210 @@ mod tests {
         let delay = Chorus::calculate_delay(base, max_mod, -1.0);
         assert!((delay - 50.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_chorus_param_round_trip() {
+        use super::super::EffectParameterId;
+
+        let mut chorus = Chorus::new(44100.0);
+
+        chorus.set_param(EffectParameterId::Mix, 0.75);
+        assert_eq!(chorus.get_param(EffectParameterId::Mix), Some(0.75));
+
+        chorus.set_param(EffectParameterId::ChorusRate, 3.0);
+        assert_eq!(chorus.get_param(EffectParameterId::ChorusRate), Some(3.0));
+
+        // Values are clamped by the underlying setters
+        chorus.set_param(EffectParameterId::ChorusFeedback, 2.0);
+        assert_eq!(
+            chorus.get_param(EffectParameterId::ChorusFeedback),
+            Some(0.9)
+        );
+
+        // Parameters the chorus does not own report None
+        assert_eq!(chorus.get_param(EffectParameterId::FilterCutoff), None);
+    }
+
+    // --- StereoChorus ---
+
+    /// Runs a sine through a stereo chorus and returns the two outputs.
+    fn stereo_chorus_output(width: f32) -> (Vec<f32>, Vec<f32>) {
+        let sample_rate = 8000.0;
+        let mut chorus = StereoChorus::new(sample_rate);
+        chorus.set_rate(2.0);
+        chorus.set_depth(1.0);
+        chorus.set_mix(0.5);
+        chorus.set_width(width);
+
+        let mut left: Vec<f32> = (0..4000)
+            .map(|i| (2.0 * PI * 440.0 * i as f32 / sample_rate).sin() * 0.5)
+            .collect();
+        let mut right = left.clone();
+        chorus.process_stereo(&mut left, &mut right);
+        (left, right)
+    }
+
+    #[test]
+    fn test_stereo_chorus_width_separates_channels() {
+        let (left, right) = stereo_chorus_output(0.5);
+        let max_diff = left
+            .iter()
+            .zip(right.iter())
+            .map(|(l, r)| (l - r).abs())
+            .fold(0.0f32, f32::max);
+        assert!(
+            max_diff > 0.05,
+            "channels should differ, max diff {max_diff}"
+        );
+
+        let (left, right) = stereo_chorus_output(1.0);
+        assert!(left.iter().zip(right.iter()).any(|(l, r)| l != r));
+    }
+
+    #[test]
+    fn test_stereo_chorus_zero_width_is_mono() {
+        let (left, right) = stereo_chorus_output(0.0);
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn test_stereo_chorus_parameter_limits() {
+        let mut chorus = StereoChorus::new(44100.0);
+
+        chorus.set_feedback(2.0);
+        assert_eq!(chorus.feedback(), 0.9);
+
+        chorus.set_width(-1.0);
+        assert_eq!(chorus.width(), 0.0);
+        chorus.set_width(3.0);
+        assert_eq!(chorus.width(), 1.0);
+
+        // Maximum feedback must stay bounded
+        chorus.set_depth(1.0);
+        let mut peak = 0.0f32;
+        for i in 0..44100 {
+            let input = if i % 1000 == 0 { 1.0 } else { 0.0 };
+            let (l, r) = chorus.process(input, input);
+            peak = peak.max(l.abs()).max(r.abs());
+        }
+        assert!(peak.is_finite() && peak < 10.0);
+    }
+
+    // --- Ensemble voices ---
+
+    /// Counts how often a chorused sine's block envelope crosses its mean.
+    ///
+    /// Each voice beats against the others, so a richer ensemble makes the
+    /// envelope's difference from its mean change sign more often.
+    fn envelope_crossings(voices: u8) -> (usize, f32) {
+        let sample_rate = 8000.0;
+        let mut chorus = Chorus::new(sample_rate);
+        chorus.set_voices(voices);
+        chorus.set_rate(2.0);
+        chorus.set_depth(1.0);
+        chorus.set_feedback(0.0);
+        chorus.set_mix(1.0);
+
+        let output: Vec<f32> = (0..32000)
+            .map(|i| chorus.process((2.0 * PI * 220.0 * i as f32 / sample_rate).sin() * 0.8))
+            .collect();
+        let peak = output.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+
+        let envelope: Vec<f32> = output[800..]
+            .chunks(80)
+            .map(|block| block.iter().fold(0.0f32, |m, x| m.max(x.abs())))
+            .collect();
+        let mean = envelope.iter().sum::<f32>() / envelope.len() as f32;
+        let crossings = envelope
+            .windows(2)
+            .filter(|w| (w[0] > mean) != (w[1] > mean))
+            .count();
+
+        (crossings, peak)
+    }
+
+    #[test]
+    fn test_chorus_voices_increase_modulation_richness() {
+        let (one, peak_one) = envelope_crossings(1);
+        let (two, peak_two) = envelope_crossings(2);
+        let (three, peak_three) = envelope_crossings(3);
+
+        assert!(
+            one < two && two < three,
+            "crossings should grow with voices: {one}, {two}, {three}"
+        );
+
+        // Summed voices are normalized, so the input level is never exceeded
+        for peak in [peak_one, peak_two, peak_three] {
+            assert!(peak <= 0.8 + 1e-3, "chorus output clipped: {peak}");
+        }
+    }
+
+    #[test]
+    fn test_chorus_set_voices() {
+        let mut chorus = Chorus::new(44100.0);
+        assert_eq!(chorus.voices(), 1);
+
+        chorus.set_voices(3);
+        assert_eq!(chorus.voices(), 3);
+
+        chorus.set_voices(0);
+        assert_eq!(chorus.voices(), 1);
+        chorus.set_voices(10);
+        assert_eq!(chorus.voices(), MAX_CHORUS_VOICES);
+    }
+
+    // --- Feedback ---
+
+    /// Feeds an impulse through a static (depth 0) chorus and returns the
+    /// index of the last output sample above -60 dB.
+    fn impulse_tail_length(feedback: f32) -> usize {
+        let mut chorus = Chorus::new(1000.0);
+        chorus.set_depth(0.0);
+        chorus.set_mix(1.0);
+        chorus.set_feedback(feedback);
+
+        (0..2000)
+            .map(|i| chorus.process(if i == 0 { 1.0 } else { 0.0 }))
+            .enumerate()
+            .filter(|(_, y)| y.abs() > 1e-3)
+            .map(|(i, _)| i)
+            .last()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_chorus_feedback_lengthens_impulse_decay() {
+        let dry = impulse_tail_length(0.0);
+        let light = impulse_tail_length(0.3);
+        let heavy = impulse_tail_length(0.9);
+
+        assert!(light > dry, "feedback should add repeats: {dry} vs {light}");
+        assert!(
+            heavy > light,
+            "more feedback should ring longer: {light} vs {heavy}"
+        );
+    }
+
+    #[test]
+    fn test_chorus_zero_feedback_is_single_echo() {
+        let mut chorus = Chorus::new(1000.0);
+        chorus.set_depth(0.0);
+        chorus.set_mix(1.0);
+        chorus.set_feedback(0.0);
+
+        let output: Vec<f32> = (0..200)
+            .map(|i| chorus.process(if i == 0 { 1.0 } else { 0.0 }))
+            .collect();
+
+        // A 25 ms delay at 1 kHz: exactly one echo, no regeneration
+        let echoes: Vec<usize> = output
+            .iter()
+            .enumerate()
+            .filter(|(_, y)| **y != 0.0)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(echoes, vec![25]);
+        assert_eq!(output[25], 1.0);
+    }
 }
@@ -2,6 +2,10 @@
 // 参考: Elektron Tonverk Filter Bank
 //
 // Filter Bank (滤波器组) 提供8个并行滤波器，每个都有独立的频率和增益控制
+//
+// 共振峰模式: 用3个并联带通谐振器模拟元音 (A, E, I, O, U) 的共振峰。
+// 内部LFO在元音之间扫动中心频率，产生"说话"般的 talk-box 效果；
+// 立体声处理时右声道的LFO相位落后1/4周期。
 
 use std::f32::consts::PI;
 
@@ -14,6 +18,67 @@ pub enum FilterBankType {
     BandPass,
 }
 
+/// 共振峰模式的元音
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormantVowel {
+    #[default]
+    A,
+    E,
+    I,
+    O,
+    U,
+}
+
+impl FormantVowel {
+    /// 全部元音，按LFO扫动顺序排列
+    pub const ALL: [FormantVowel; 5] = [
+        FormantVowel::A,
+        FormantVowel::E,
+        FormantVowel::I,
+        FormantVowel::O,
+        FormantVowel::U,
+    ];
+}
+
+/// 单个共振峰: (中心频率 Hz, 相对电平 dB, 带宽 Hz)
+type Formant = (f32, f32, f32);
+
+/// 各元音的前三个共振峰 (男低音)，顺序与 `FormantVowel::ALL` 一致
+const VOWEL_FORMANTS: [[Formant; 3]; 5] = [
+    [
+        (600.0, 0.0, 60.0),
+        (1040.0, -7.0, 70.0),
+        (2250.0, -9.0, 110.0),
+    ],
+    [
+        (400.0, 0.0, 40.0),
+        (1620.0, -12.0, 80.0),
+        (2400.0, -9.0, 100.0),
+    ],
+    [
+        (250.0, 0.0, 60.0),
+        (1750.0, -30.0, 90.0),
+        (2600.0, -16.0, 100.0),
+    ],
+    [
+        (400.0, 0.0, 40.0),
+        (750.0, -11.0, 80.0),
+        (2400.0, -21.0, 100.0),
+    ],
+    [
+        (350.0, 0.0, 40.0),
+        (600.0, -20.0, 80.0),
+        (2400.0, -32.0, 100.0),
+    ],
+];
+
+/// 增益归一化的参考带宽 (Hz)
+/// 归一化后 Σ gain² × bandwidth 恒等于该值，宽带输入的输出电平不随元音变化
+const FORMANT_REFERENCE_BANDWIDTH: f32 = 100.0;
+
+/// 共振峰系数的更新间隔 (样本)
+const FORMANT_UPDATE_INTERVAL: u32 = 32;
+
 /// 单个滤波器带配置
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FilterBandConfig {
@@ -203,6 +268,19 @@ impl BiquadFilter {
         self.a2 /= norm;
     }
 
+    fn configure_band_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        // 峰值增益恒为0dB的带通
+        let omega = 2.0 * PI * freq / sample_rate;
+        let alpha = omega.sin() / (2.0 * q);
+        let norm = 1.0 + alpha;
+
+        self.b0 = alpha / norm;
+        self.b1 = 0.0;
+        self.b2 = -alpha / norm;
+        self.a1 = -2.0 * omega.cos() / norm;
+        self.a2 = (1.0 - alpha) / norm;
+    }
+
     #[inline]
     fn process(&mut self, input: f32) -> f32 {
         let output = self.b0 * input + self.b1 * self.x1 + self.b2 * self.x2
@@ -235,6 +313,30 @@ pub struct FilterBank {
     filters: [BiquadFilter; 8],
     input_gain: f32,
     output_gain: f32,
+
+    /// 共振峰模式的静态元音 (None = 普通滤波器组模式)
+    formant_vowel: Option<FormantVowel>,
+
+    /// 动画LFO速率 (Hz)
+    animation_rate: f32,
+
+    /// 动画深度 (0.0 = 静态元音, 1.0 = 扫过全部元音)
+    animation_depth: f32,
+
+    /// 动画LFO相位 (0.0 - 1.0)
+    animation_phase: f32,
+
+    /// 距下次更新共振峰系数的样本数
+    formant_countdown: u32,
+
+    /// 左/右声道的共振峰带通滤波器
+    formant_filters: [[BiquadFilter; 3]; 2],
+
+    /// 左/右声道的共振峰中心频率
+    formant_frequencies: [[f32; 3]; 2],
+
+    /// 左/右声道的共振峰增益 (已归一化)
+    formant_gains: [[f32; 3]; 2],
 }
 
 impl Default for FilterBank {
@@ -256,14 +358,147 @@ impl FilterBank {
             filters,
             input_gain: 1.0,
             output_gain: 1.0,
+            formant_vowel: None,
+            animation_rate: 0.5,
+            animation_depth: 0.0,
+            animation_phase: 0.0,
+            formant_countdown: 0,
+            formant_filters: [[BiquadFilter::new(); 3]; 2],
+            formant_frequencies: [[0.0; 3]; 2],
+            formant_gains: [[0.0; 3]; 2],
         };
         bank.update_filters();
+        bank.update_formants();
         bank
     }
 
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate = sample_rate;
         self.update_filters();
+        self.update_formants();
+    }
+
+    /// 切换共振峰模式
+    ///
+    /// `Some(vowel)` 启用共振峰模式，并以该元音作为动画的起点；
+    /// `None` 回到普通滤波器组模式。
+    pub fn set_formant_vowel(&mut self, vowel: Option<FormantVowel>) {
+        if self.formant_vowel.is_none() {
+            for filter in self.formant_filters.iter_mut().flatten() {
+                filter.reset();
+            }
+        }
+        self.formant_vowel = vowel;
+        self.update_formants();
+    }
+
+    pub fn formant_vowel(&self) -> Option<FormantVowel> {
+        self.formant_vowel
+    }
+
+    /// 设置共振峰动画
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_hz` - LFO速率 (0 - 20 Hz)
+    /// * `depth` - 扫动深度 (0.0 = 保持静态元音, 1.0 = 扫过全部5个元音)
+    pub fn set_animation(&mut self, rate_hz: f32, depth: f32) {
+        self.animation_rate = rate_hz.clamp(0.0, 20.0);
+        self.animation_depth = depth.clamp(0.0, 1.0);
+        self.update_formants();
+    }
+
+    pub fn animation_rate(&self) -> f32 {
+        self.animation_rate
+    }
+
+    pub fn animation_depth(&self) -> f32 {
+        self.animation_depth
+    }
+
+    /// 获取左/右声道当前的共振峰中心频率 (Hz)
+    pub fn formant_frequencies(&self) -> [[f32; 3]; 2] {
+        self.formant_frequencies
+    }
+
+    /// 按LFO位置重新计算左右声道的共振峰系数
+    fn update_formants(&mut self) {
+        self.formant_countdown = FORMANT_UPDATE_INTERVAL;
+        let base = self.formant_vowel.unwrap_or_default() as usize as f32;
+        let span = (VOWEL_FORMANTS.len() - 1) as f32;
+
+        for channel in 0..2 {
+            // 右声道落后1/4周期
+            let phase = self.animation_phase - channel as f32 * 0.25;
+            let lfo = 0.5 - 0.5 * (2.0 * PI * phase).cos();
+
+            // 超出U之后折返 (A-E-I-O-U-O-I-...)
+            let position = (base + self.animation_depth * span * lfo) % (2.0 * span);
+            let position = if position > span {
+                2.0 * span - position
+            } else {
+                position
+            };
+            let index = (position as usize).min(VOWEL_FORMANTS.len() - 2);
+            let t = position - index as f32;
+
+            let mut amplitudes = [0.0; 3];
+            let mut bandwidths = [0.0; 3];
+            for k in 0..3 {
+                let (freq_a, level_a, bw_a) = VOWEL_FORMANTS[index][k];
+                let (freq_b, level_b, bw_b) = VOWEL_FORMANTS[index + 1][k];
+                // 频率按对数插值
+                let freq = freq_a * (freq_b / freq_a).powf(t);
+                let freq = freq.min(self.sample_rate * 0.45);
+                amplitudes[k] = 10.0f32.powf((level_a + (level_b - level_a) * t) / 20.0);
+                bandwidths[k] = bw_a + (bw_b - bw_a) * t;
+
+                self.formant_frequencies[channel][k] = freq;
+                self.formant_filters[channel][k].configure_band_pass(
+                    freq,
+                    freq / bandwidths[k],
+                    self.sample_rate,
+                );
+            }
+
+            let energy: f32 = amplitudes
+                .iter()
+                .zip(&bandwidths)
+                .map(|(a, bw)| a * a * bw)
+                .sum();
+            let norm = (FORMANT_REFERENCE_BANDWIDTH / energy).sqrt();
+            for (gain, amplitude) in self.formant_gains[channel].iter_mut().zip(amplitudes) {
+                *gain = amplitude * norm;
+            }
+        }
+    }
+
+    /// 推进动画LFO一个样本
+    #[inline]
+    fn advance_animation(&mut self) {
+        if self.animation_depth <= 0.0 || self.animation_rate <= 0.0 {
+            return;
+        }
+        self.animation_phase += self.animation_rate / self.sample_rate;
+        if self.animation_phase >= 1.0 {
+            self.animation_phase -= 1.0;
+        }
+        self.formant_countdown -= 1;
+        if self.formant_countdown == 0 {
+            self.update_formants();
+        }
+    }
+
+    #[inline]
+    fn process_formant(&mut self, channel: usize, input: f32) -> f32 {
+        let mut sum = 0.0;
+        for (filter, gain) in self.formant_filters[channel]
+            .iter_mut()
+            .zip(&self.formant_gains[channel])
+        {
+            sum += filter.process(input) * gain;
+        }
+        (sum * self.output_gain).clamp(-10.0, 10.0)
     }
 
     pub fn set_config(&mut self, config: FilterBankConfig) {
@@ -320,6 +555,10 @@ impl FilterBank {
     #[inline]
     pub fn process(&mut self, input: f32) -> f32 {
         let input = input * self.input_gain;
+        if self.formant_vowel.is_some() {
+            self.advance_animation();
+            return self.process_formant(0, input);
+        }
         let mut sum = 0.0;
         for filter in &mut self.filters {
             sum += filter.process(input);
@@ -331,6 +570,13 @@ impl FilterBank {
 
     #[inline]
     pub fn process_stereo(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
+        if self.formant_vowel.is_some() {
+            self.advance_animation();
+            return (
+                self.process_formant(0, input_left * self.input_gain),
+                self.process_formant(1, input_right * self.input_gain),
+            );
+        }
         (self.process(input_left), self.process(input_right))
     }
 
@@ -338,6 +584,11 @@ impl FilterBank {
         for filter in &mut self.filters {
             filter.reset();
         }
+        for filter in self.formant_filters.iter_mut().flatten() {
+            filter.reset();
+        }
+        self.animation_phase = 0.0;
+        self.update_formants();
     }
     pub fn get_latency(&self) -> usize {
         0
@@ -521,4 +772,59 @@ mod tests {
             "Gain should be clamped to -12.0"
         );
     }
+
+    // ============ 共振峰模式测试 ============
+
+    #[test]
+    fn test_formant_animation_moves_band_centres() {
+        let mut bank = FilterBank::new_with_sample_rate(44100.0);
+        bank.set_formant_vowel(Some(FormantVowel::A));
+        let start = bank.formant_frequencies();
+
+        // 深度0: 保持静态元音
+        for _ in 0..4410 {
+            bank.process_stereo(0.1, 0.1);
+        }
+        assert_eq!(bank.formant_frequencies(), start);
+
+        bank.set_animation(2.0, 1.0);
+        let mut first_formants = Vec::new();
+        for _ in 0..10 {
+            for _ in 0..2205 {
+                bank.process_stereo(0.1, 0.1);
+            }
+            first_formants.push(bank.formant_frequencies()[0][0]);
+        }
+        let lowest = first_formants.iter().cloned().fold(f32::MAX, f32::min);
+        let highest = first_formants.iter().cloned().fold(0.0, f32::max);
+        // 扫过A (600 Hz) 到 I (250 Hz) 之间的F1
+        assert!(lowest < 400.0 && highest > 500.0, "F1 {first_formants:?}");
+
+        // 右声道落后1/4周期
+        let [left, right] = bank.formant_frequencies();
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn test_formant_level_is_normalized_across_vowels() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let noise: Vec<f32> = (0..44100).map(|_| rng.gen_range(-0.5..0.5)).collect();
+
+        let levels: Vec<f32> = FormantVowel::ALL
+            .iter()
+            .map(|&vowel| {
+                let mut bank = FilterBank::new_with_sample_rate(44100.0);
+                bank.set_formant_vowel(Some(vowel));
+                let output: Vec<f32> = noise.iter().map(|&s| bank.process(s)).collect();
+                measure_rms(&output[4410..])
+            })
+            .collect();
+
+        let loudest = levels.iter().cloned().fold(0.0, f32::max);
+        let quietest = levels.iter().cloned().fold(f32::MAX, f32::min);
+        // 电平差小于3dB
+        assert!(quietest > 0.0 && loudest / quietest < 1.41, "{levels:?}");
+    }
 }
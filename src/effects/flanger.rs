@@ -10,9 +10,15 @@
 // - FEEDBACK: 反馈量 (可正可负)
 // - MANUAL: 手动控制延迟中心值
 // - MIX: 干湿比
+// - CROSS FEEDBACK: 立体声交叉反馈 (仅StereoFlanger)
+//
+// 交叉反馈: 左延迟线的输出送入右声道的反馈路径，反之亦然。两条延迟线以反相LFO扫动，
+// 相对延迟在扫动中穿过零点 (through-zero)，产生强烈的"喷气机"扫频效果。
 
 use std::f32::consts::PI;
 
+use super::{Effect, EffectParameterId, EffectType};
+
 /// Flanger配置
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FlangerConfig {
@@ -93,9 +99,9 @@ impl CombFilter {
 
     /// 设置目标延迟
     fn set_delay(&mut self, delay: f32) {
-        // delay 0.0 - 1.0 映射到 min_delay - max_delay
-        self.target_delay =
-            self.min_delay as f32 + (self.max_delay - self.min_delay) as f32 * delay;
+        // delay 0.0 - 1.0 映射到 min_delay - max_delay (MANUAL + DEPTH 可能超过1)
+        self.target_delay = self.min_delay as f32
+            + (self.max_delay - self.min_delay) as f32 * delay.clamp(0.0, 1.0);
     }
 
     /// 处理样本
@@ -148,6 +154,9 @@ pub struct Flanger {
 
     /// 采样率
     sample_rate: f32,
+
+    /// 是否启用
+    enabled: bool,
 }
 
 impl Default for Flanger {
@@ -170,6 +179,7 @@ impl Flanger {
             lfo_phase: 0.0,
             lfo_increment: 0.0,
             sample_rate,
+            enabled: true,
         };
 
         flanger.comb_filter.set_delay_range(0.1, 20.0); // 0.1ms - 20ms
@@ -298,6 +308,61 @@ impl Flanger {
     }
 }
 
+impl Effect for Flanger {
+    fn process(&mut self, input: f32) -> f32 {
+        Flanger::process(self, input)
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        if self.enabled {
+            Flanger::process(self, input)
+        } else {
+            input
+        }
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = Flanger::process(self, *sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        Flanger::reset(self);
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        Flanger::set_mix(self, mix);
+    }
+
+    /// 强度映射到调制深度
+    fn set_intensity(&mut self, intensity: f32) {
+        self.set_depth(intensity);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            Flanger::set_mix(self, value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        (id == EffectParameterId::Mix).then_some(self.config.mix)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Flanger
+    }
+}
+
 /// 立体声Flanger (带更多立体声控制)
 #[derive(Debug, Clone)]
 pub struct StereoFlanger {
@@ -309,6 +374,12 @@ pub struct StereoFlanger {
 
     /// 立体声偏移 (0.0 = 相同, 1.0 = 最大偏移)
     stereo_offset: f32,
+
+    /// 交叉反馈量 (-0.9 - 0.9)
+    cross_feedback: f32,
+
+    /// 上一个样本的左/右延迟输出 (交叉反馈用)
+    last_delayed: (f32, f32),
 }
 
 impl Default for StereoFlanger {
@@ -323,7 +394,9 @@ impl StereoFlanger {
         Self {
             flanger_l: Flanger::new(),
             flanger_r: Flanger::new(),
-            stereo_offset: 0.5,
+            stereo_offset: 1.0,
+            cross_feedback: 0.0,
+            last_delayed: (0.0, 0.0),
         }
     }
 
@@ -386,20 +459,121 @@ impl StereoFlanger {
         self.stereo_offset = offset.clamp(0.0, 1.0);
     }
 
+    /// 设置交叉反馈 (-0.9 - 0.9, 0.0 = 关闭)
+    ///
+    /// 处理时总环路增益 (反馈 + 交叉反馈) 会被限制在1以下以保证稳定
+    pub fn set_cross_feedback(&mut self, amount: f32) {
+        self.cross_feedback = amount.clamp(-0.9, 0.9);
+    }
+
+    /// 获取交叉反馈量
+    pub fn cross_feedback(&self) -> f32 {
+        self.cross_feedback
+    }
+
     /// 处理立体声样本
+    ///
+    /// 两个声道共用左声道的延迟线，右声道的LFO按立体声偏移错开相位
+    /// (默认反相)。交叉反馈把对方声道上一个样本的湿信号送入本声道；
+    /// 为0时与原立体声Flanger的输出一致，调节时不会切换处理路径
     #[inline]
     pub fn process(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
-        // 右声道使用相位偏移
-        let _phase_offset = PI * self.stereo_offset;
+        let flanger = &mut self.flanger_l;
+        flanger.lfo_phase += flanger.lfo_increment;
+        if flanger.lfo_phase > 2.0 * PI {
+            flanger.lfo_phase -= 2.0 * PI;
+        }
+        let lfo_left = flanger.lfo_phase.sin();
+        // 右声道LFO相位偏移 (1.0 = 反相)
+        let lfo_right = (flanger.lfo_phase + PI * self.stereo_offset).sin();
+        let config = flanger.config;
+
+        // 限制总环路增益，防止自激发散
+        let feedback = config.feedback;
+        let max_cross = (0.98 - feedback.abs()).max(0.0);
+        let cross = self.cross_feedback.clamp(-max_cross, max_cross);
 
-        // 直接处理
-        self.flanger_l.process_stereo(input_left, input_right)
+        let (last_left, last_right) = self.last_delayed;
+        flanger
+            .comb_filter
+            .set_delay(config.manual + config.depth * 0.5 * (1.0 + lfo_left));
+        let wet_left = flanger
+            .comb_filter
+            .process(input_left + last_right * cross, feedback);
+
+        flanger
+            .comb_filter
+            .set_delay(config.manual + config.depth * 0.5 * (1.0 + lfo_right));
+        let wet_right = flanger
+            .comb_filter
+            .process(input_right + last_left * cross, feedback);
+        self.last_delayed = (wet_left, wet_right);
+
+        let dry = 1.0 - config.mix;
+        (
+            input_left * dry + wet_left * config.mix,
+            input_right * dry + wet_right * config.mix,
+        )
     }
 
     /// 重置
     pub fn reset(&mut self) {
         self.flanger_l.reset();
         self.flanger_r.reset();
+        self.last_delayed = (0.0, 0.0);
+    }
+}
+
+impl Effect for StereoFlanger {
+    /// 单声道处理只使用左声道
+    fn process(&mut self, input: f32) -> f32 {
+        self.flanger_l.process(input)
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        self.flanger_l.process_with_bypass(input)
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.flanger_l.process(*sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        StereoFlanger::reset(self);
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        StereoFlanger::set_mix(self, mix);
+    }
+
+    /// 强度映射到调制深度
+    fn set_intensity(&mut self, intensity: f32) {
+        self.set_depth(intensity);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.flanger_l.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.flanger_l.enabled = enabled;
+        self.flanger_r.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            StereoFlanger::set_mix(self, value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        self.flanger_l.get_param(id)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Flanger
     }
 }
 
@@ -547,7 +721,7 @@ mod tests {
     #[test]
     fn test_stereo_flanger_creation() {
         let flanger = StereoFlanger::new();
-        assert_eq!(flanger.stereo_offset, 0.5);
+        assert_eq!(flanger.stereo_offset, 1.0);
     }
 
     #[test]
@@ -576,4 +750,102 @@ mod tests {
             assert!(output.is_finite());
         }
     }
+
+    #[test]
+    fn test_stereo_flanger_cross_feedback_diverges_channels() {
+        use crate::audio_analysis::measure_rms;
+
+        // 同一单声道信号送入左右声道，测量左右输出差值
+        let side_rms = |cross: f32| {
+            let mut flanger = StereoFlanger::new_with_sample_rate(44100.0);
+            flanger.set_rate(0.5);
+            flanger.set_depth(0.8);
+            flanger.set_mix(0.5);
+            flanger.set_cross_feedback(cross);
+
+            let side: Vec<f32> = (0..44100)
+                .map(|i| {
+                    let t = i as f32 / 44100.0;
+                    let input = (2.0 * PI * 220.0 * t).sin() * 0.3
+                        + (2.0 * PI * 1370.0 * t).sin() * 0.2
+                        + (2.0 * PI * 3100.0 * t).sin() * 0.1;
+                    let (left, right) = flanger.process(input, input);
+                    assert!(left.is_finite() && right.is_finite());
+                    left - right
+                })
+                .collect();
+            measure_rms(&side)
+        };
+
+        let amounts = [0.1, 0.4, 0.8];
+        let sides: Vec<f32> = amounts.iter().map(|&cross| side_rms(cross)).collect();
+        assert!(sides[0] > 0.01, "cross feedback should split the channels");
+        for pair in sides.windows(2) {
+            assert!(pair[1] > pair[0], "side RMS {:?}", sides);
+        }
+        assert!(sides[2] > sides[0] * 1.2, "side RMS {:?}", sides);
+    }
+
+    #[test]
+    fn test_stereo_flanger_cross_feedback_stays_stable() {
+        let mut flanger = StereoFlanger::new_with_sample_rate(44100.0);
+        flanger.set_feedback(1.0);
+        flanger.set_cross_feedback(5.0);
+        assert_eq!(flanger.cross_feedback(), 0.9);
+
+        let mut peak = 0.0f32;
+        for i in 0..88200 {
+            let input = if i < 100 { 1.0 } else { 0.0 };
+            let (left, right) = flanger.process(input, -input);
+            peak = peak.max(left.abs()).max(right.abs());
+        }
+        assert!(peak.is_finite() && peak <= 2.0, "peak {}", peak);
+    }
+
+    #[test]
+    fn test_stereo_flanger_cross_feedback_continuous_at_zero() {
+        let render = |cross: f32| {
+            let mut flanger = StereoFlanger::new_with_sample_rate(44100.0);
+            flanger.set_depth(0.8);
+            flanger.set_mix(0.5);
+            flanger.set_cross_feedback(cross);
+            (0..44100)
+                .map(|i| {
+                    let input = (2.0 * PI * 330.0 * i as f32 / 44100.0).sin() * 0.5;
+                    flanger.process(input, input)
+                })
+                .collect::<Vec<(f32, f32)>>()
+        };
+
+        // A touch of cross feedback only nudges the output
+        let off = render(0.0);
+        let slight = render(0.01);
+        let max_diff = off
+            .iter()
+            .zip(&slight)
+            .map(|(a, b)| (a.0 - b.0).abs().max((a.1 - b.1).abs()))
+            .fold(0.0f32, f32::max);
+        assert!(max_diff < 0.02, "max difference {}", max_diff);
+    }
+
+    #[test]
+    fn test_stereo_flanger_without_cross_feedback_matches_original() {
+        // The stereo flanger used to run Flanger::process_stereo directly
+        let mut original = Flanger::new_with_sample_rate(44100.0);
+        let mut flanger = StereoFlanger::new_with_sample_rate(44100.0);
+        original.set_depth(0.8);
+        original.set_feedback(0.5);
+        flanger.set_depth(0.8);
+        flanger.set_feedback(0.5);
+
+        for i in 0..44100 {
+            let t = i as f32 / 44100.0;
+            let left = (2.0 * PI * 330.0 * t).sin() * 0.5;
+            let right = (2.0 * PI * 550.0 * t).sin() * 0.3;
+            let expected = original.process_stereo(left, right);
+            let (out_left, out_right) = flanger.process(left, right);
+            assert!((out_left - expected.0).abs() < 1e-4, "sample {}", i);
+            assert!((out_right - expected.1).abs() < 1e-4, "sample {}", i);
+        }
+    }
 }
@@ -19,6 +19,24 @@
 // - SPEED: 播放速度 (0.25x - 4x)
 // - REVERSE: 反向播放
 // - TYPE: Freeze/Stutter/Slice
+//
+// 频谱冻结 (Spectral Freeze):
+// - latch() 对最近的输入做FFT，锁存每个频点的幅度和相位增量
+// - 之后按锁存的相位增量逐帧重新合成 (overlap-add)，得到无限延续的音色
+// - FREEZE MIX 在实时信号和冻结频谱之间平滑交叉淡化
+
+use std::f32::consts::PI;
+
+use crate::analysis::{fft, fft_twiddles, Complex};
+
+/// 频谱冻结的FFT长度
+const SPECTRAL_FFT_SIZE: usize = 2048;
+
+/// 频谱冻结的帧移 (75% 重叠)
+const SPECTRAL_HOP: usize = SPECTRAL_FFT_SIZE / 4;
+
+/// FREEZE MIX 从0到1的淡化时间 (秒)，避免咔嗒声
+const FREEZE_MIX_FADE_S: f32 = 0.02;
 
 /// Freeze/Stutter类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -72,6 +90,154 @@ impl Default for FreezeConfig {
     }
 }
 
+/// 锁存的频谱及其逐帧重新合成的状态
+#[derive(Debug, Clone)]
+struct SpectralFreeze {
+    /// 分析/合成窗 (Hann)
+    window: Vec<f32>,
+
+    /// 每个频点的锁存幅度
+    magnitudes: Vec<f32>,
+
+    /// 每个频点的当前合成相位
+    phases: Vec<f32>,
+
+    /// 每个频点每帧的相位增量
+    phase_steps: Vec<f32>,
+
+    /// FFT旋转因子
+    twiddles: Vec<Complex>,
+
+    /// FFT工作区
+    spectrum: Vec<Complex>,
+
+    /// overlap-add 输出累加器 (环形)
+    output: Vec<f32>,
+
+    /// 累加器读取位置
+    output_pos: usize,
+
+    /// 距离下一帧合成的样本数
+    hop_countdown: usize,
+
+    /// 是否已锁存频谱
+    latched: bool,
+}
+
+impl SpectralFreeze {
+    fn new() -> Self {
+        let bins = SPECTRAL_FFT_SIZE / 2 + 1;
+        let window = (0..SPECTRAL_FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / SPECTRAL_FFT_SIZE as f32).cos())
+            .collect();
+
+        Self {
+            window,
+            magnitudes: vec![0.0; bins],
+            phases: vec![0.0; bins],
+            phase_steps: vec![0.0; bins],
+            twiddles: fft_twiddles(SPECTRAL_FFT_SIZE),
+            spectrum: vec![Complex::default(); SPECTRAL_FFT_SIZE],
+            output: vec![0.0; SPECTRAL_FFT_SIZE],
+            output_pos: 0,
+            hop_countdown: SPECTRAL_HOP,
+            latched: false,
+        }
+    }
+
+    /// 对环形缓冲区中截止到 `end` 的一帧加窗并做FFT (结果在 spectrum)
+    fn analyze(&mut self, buffer: &[f32], end: usize) {
+        let len = buffer.len();
+        for i in 0..SPECTRAL_FFT_SIZE {
+            let idx = (end + len - SPECTRAL_FFT_SIZE + i) % len;
+            self.spectrum[i] = Complex {
+                re: buffer[idx] * self.window[i],
+                im: 0.0,
+            };
+        }
+        fft(&mut self.spectrum, &self.twiddles, false);
+    }
+
+    /// 锁存最近的输入 (`buffer` 中截止到 `end` 的样本)
+    fn latch(&mut self, buffer: &[f32], end: usize) {
+        // 相隔一个帧移的两帧: 相位差就是每帧的相位增量
+        self.analyze(buffer, end + buffer.len() - SPECTRAL_HOP);
+        for k in 0..self.phases.len() {
+            self.phase_steps[k] = self.spectrum[k].im.atan2(self.spectrum[k].re);
+        }
+
+        self.analyze(buffer, end);
+        for k in 0..self.phases.len() {
+            let bin = self.spectrum[k];
+            let phase = bin.im.atan2(bin.re);
+            self.magnitudes[k] = (bin.re * bin.re + bin.im * bin.im).sqrt();
+            self.phase_steps[k] = phase - self.phase_steps[k];
+            self.phases[k] = phase;
+        }
+
+        self.output.fill(0.0);
+        self.output_pos = 0;
+        self.hop_countdown = SPECTRAL_HOP;
+        self.latched = true;
+        self.synthesize_frame();
+    }
+
+    /// 合成下一帧并叠加到输出累加器
+    fn synthesize_frame(&mut self) {
+        let n = SPECTRAL_FFT_SIZE;
+        for k in 0..self.phases.len() {
+            let (sin, cos) = self.phases[k].sin_cos();
+            let bin = Complex {
+                re: self.magnitudes[k] * cos,
+                im: self.magnitudes[k] * sin,
+            };
+            self.spectrum[k] = bin;
+            // 共轭对称，逆变换得到实数信号
+            if k > 0 && k < n / 2 {
+                self.spectrum[n - k] = Complex {
+                    re: bin.re,
+                    im: -bin.im,
+                };
+            }
+            self.phases[k] = (self.phases[k] + self.phase_steps[k]) % (2.0 * PI);
+        }
+        fft(&mut self.spectrum, &self.twiddles, true);
+
+        // Hann² 在75%重叠下的叠加和为1.5
+        let scale = 1.0 / 1.5;
+        for i in 0..n {
+            let idx = (self.output_pos + i) % n;
+            self.output[idx] += self.spectrum[i].re * self.window[i] * scale;
+        }
+    }
+
+    /// 输出一个冻结样本
+    fn next_sample(&mut self) -> f32 {
+        if !self.latched {
+            return 0.0;
+        }
+
+        let sample = self.output[self.output_pos];
+        self.output[self.output_pos] = 0.0;
+        self.output_pos = (self.output_pos + 1) % SPECTRAL_FFT_SIZE;
+
+        self.hop_countdown -= 1;
+        if self.hop_countdown == 0 {
+            self.hop_countdown = SPECTRAL_HOP;
+            self.synthesize_frame();
+        }
+        sample
+    }
+
+    fn reset(&mut self) {
+        self.magnitudes.fill(0.0);
+        self.output.fill(0.0);
+        self.output_pos = 0;
+        self.hop_countdown = SPECTRAL_HOP;
+        self.latched = false;
+    }
+}
+
 /// Freeze/Stutter效果器
 #[derive(Debug, Clone)]
 pub struct Freeze {
@@ -98,6 +264,15 @@ pub struct Freeze {
 
     /// 随机种子
     rng_seed: u64,
+
+    /// 频谱冻结
+    spectral: SpectralFreeze,
+
+    /// 目标 FREEZE MIX
+    freeze_mix: f32,
+
+    /// 当前 FREEZE MIX (向目标值平滑)
+    smoothed_freeze_mix: f32,
 }
 
 impl Default for Freeze {
@@ -115,7 +290,8 @@ impl Freeze {
     /// 创建带采样率的Freeze
     pub fn new_with_sample_rate(sample_rate: f32) -> Self {
         let _samples_per_second = sample_rate as usize;
-        let buffer_size = sample_rate as usize; // 1秒缓冲区
+        // 1秒缓冲区，至少容纳一帧频谱冻结
+        let buffer_size = (sample_rate as usize).max(SPECTRAL_FFT_SIZE);
 
         Self {
             config: FreezeConfig::default(),
@@ -126,6 +302,9 @@ impl Freeze {
             is_frozen: false,
             slice_start: 0,
             rng_seed: 12345,
+            spectral: SpectralFreeze::new(),
+            freeze_mix: 0.0,
+            smoothed_freeze_mix: 0.0,
         }
     }
 
@@ -134,7 +313,7 @@ impl Freeze {
         self.sample_rate = sample_rate;
 
         // 重新分配缓冲区
-        let buffer_size = sample_rate as usize;
+        let buffer_size = (sample_rate as usize).max(SPECTRAL_FFT_SIZE);
         self.buffer = vec![0.0; buffer_size];
         self.write_pos = 0;
         self.read_pos = 0.0;
@@ -194,6 +373,31 @@ impl Freeze {
         self.is_frozen = frozen;
     }
 
+    /// 锁存当前频谱
+    ///
+    /// 对最近的输入做FFT并锁存，之后冻结频谱无限延续，直到下一次锁存或重置。
+    /// 静音时锁存得到静音。需要配合 `set_freeze_mix` 才能听到。
+    pub fn latch(&mut self) {
+        self.spectral.latch(&self.buffer, self.write_pos);
+    }
+
+    /// 是否已锁存频谱
+    pub fn is_latched(&self) -> bool {
+        self.spectral.latched
+    }
+
+    /// 设置实时信号与冻结频谱的交叉淡化 (0.0 = 实时, 1.0 = 冻结)
+    ///
+    /// 实时信号是常规 Freeze/Stutter 处理后的输出。新值在约20ms内平滑过渡，不会产生咔嗒声。
+    pub fn set_freeze_mix(&mut self, t: f32) {
+        self.freeze_mix = t.clamp(0.0, 1.0);
+    }
+
+    /// 获取目标 FREEZE MIX
+    pub fn freeze_mix(&self) -> f32 {
+        self.freeze_mix
+    }
+
     /// 更新长度参数
     fn update_length(&mut self) {
         if self.config.length > self.buffer.len() {
@@ -221,7 +425,13 @@ impl Freeze {
         };
 
         // 混合干湿信号
-        input + (output - input) * self.config.mix
+        let live = input + (output - input) * self.config.mix;
+
+        // 与冻结频谱交叉淡化
+        let step = 1.0 / (FREEZE_MIX_FADE_S * self.sample_rate);
+        self.smoothed_freeze_mix += (self.freeze_mix - self.smoothed_freeze_mix).clamp(-step, step);
+        let frozen = self.spectral.next_sample();
+        live + (frozen - live) * self.smoothed_freeze_mix
     }
 
     /// Freeze模式处理
@@ -307,6 +517,8 @@ impl Freeze {
         self.read_pos = 0.0;
         self.is_frozen = false;
         self.slice_start = 0;
+        self.spectral.reset();
+        self.smoothed_freeze_mix = self.freeze_mix;
     }
 
     /// 清空缓冲区
@@ -676,4 +888,79 @@ mod tests {
         freeze.set_length_ms(10000.0);
         assert!(freeze.config.length <= freeze.buffer.len());
     }
+
+    // ============ 频谱冻结测试 ============
+
+    /// 理论: 锁存音调后输入静音，冻结频谱应该保持原音调
+    #[test]
+    fn test_spectral_freeze_holds_latched_tone() {
+        use crate::analysis::dominant_frequency;
+
+        let mut freeze = Freeze::new_with_sample_rate(44100.0);
+        for i in 0..44100 {
+            freeze.process((2.0 * PI * 440.0 * i as f32 / 44100.0).sin() * 0.5);
+        }
+        freeze.latch();
+        freeze.set_freeze_mix(1.0);
+
+        let output: Vec<f32> = (0..44100).map(|_| freeze.process(0.0)).collect();
+        let tail = &output[22050..];
+
+        // 0.5振幅正弦波的RMS约为0.354
+        let rms = measure_rms(tail);
+        assert!((rms - 0.354).abs() < 0.05, "Frozen tone RMS = {}", rms);
+        let freq = dominant_frequency(tail, 44100.0);
+        assert!((freq - 440.0).abs() < 3.0, "Frozen tone at {} Hz", freq);
+    }
+
+    /// 理论: 静音时锁存得到静音; FREEZE MIX 平滑过渡不产生跳变
+    #[test]
+    fn test_spectral_freeze_silent_latch_and_smooth_mix() {
+        let mut freeze = Freeze::new_with_sample_rate(44100.0);
+        freeze.set_mix(0.0);
+        freeze.latch();
+        freeze.set_freeze_mix(1.0);
+        for _ in 0..4410 {
+            assert_eq!(freeze.process(0.0), 0.0);
+        }
+
+        // 恒定的实时信号切换到静音的冻结频谱: 输出逐步下降
+        freeze.set_freeze_mix(0.0);
+        let mut previous = 0.0;
+        for _ in 0..2000 {
+            previous = freeze.process(0.5);
+        }
+        assert!((previous - 0.5).abs() < 1e-6);
+        freeze.set_freeze_mix(1.0);
+        for _ in 0..2000 {
+            let output = freeze.process(0.5);
+            assert!(
+                (output - previous).abs() < 0.01,
+                "{} -> {}",
+                previous,
+                output
+            );
+            previous = output;
+        }
+        assert!(previous.abs() < 1e-6);
+    }
+
+    /// 理论: 采样率低于FFT长度时缓冲区仍能容纳一帧，锁存不会越界
+    #[test]
+    fn test_spectral_freeze_low_sample_rate() {
+        let mut freeze = Freeze::new_with_sample_rate(1000.0);
+        assert_eq!(freeze.buffer.len(), SPECTRAL_FFT_SIZE);
+        freeze.latch();
+
+        freeze.set_sample_rate(500.0);
+        assert_eq!(freeze.buffer.len(), SPECTRAL_FFT_SIZE);
+        for i in 0..500 {
+            freeze.process((i as f32 * 0.3).sin());
+        }
+        freeze.latch();
+        freeze.set_freeze_mix(1.0);
+        for _ in 0..1000 {
+            assert!(freeze.process(0.0).is_finite());
+        }
+    }
 }
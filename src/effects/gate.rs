@@ -0,0 +1,354 @@
+//! Noise Gate / Expander Module
+//!
+//! Attenuates the signal while its level sits below a threshold. With a high
+//! ratio it acts as a gate (cleaning up noisy sample tails, gated reverb
+//! drums); with a low ratio it is a gentle downward expander.
+//!
+//! # Key Parameters
+//!
+//! - **Threshold**: Level below which the signal is attenuated (dB)
+//! - **Ratio**: Downward expansion ratio (1:1 = off, 100:1 = hard gate)
+//! - **Attack**: How quickly the gate opens
+//! - **Hold**: How long the gate stays open after the level drops
+//! - **Release**: How quickly the gate closes once the hold runs out
+
+use super::{Effect, EffectParameterId, EffectType};
+
+/// Lowest gain the gate applies, in dB (treated as silence)
+const FLOOR_DB: f32 = -80.0;
+
+/// Decay time of the peak level detector in seconds
+const DETECTOR_RELEASE_S: f32 = 0.01;
+
+/// Noise gate with downward expansion and hold
+#[derive(Debug, Clone)]
+pub struct Gate {
+    /// Threshold in dB (signals below this are attenuated)
+    threshold_db: f32,
+
+    /// Expansion ratio (1:1 = no expansion)
+    ratio: f32,
+
+    /// Attack time in seconds
+    attack_s: f32,
+
+    /// Hold time in seconds
+    hold_s: f32,
+
+    /// Release time in seconds
+    release_s: f32,
+
+    /// Peak level detector state (linear)
+    envelope: f32,
+
+    /// Current (smoothed) gain
+    gain: f32,
+
+    /// Samples left before the gate may start closing
+    hold_counter: usize,
+
+    /// Sample rate
+    sample_rate: f32,
+
+    /// Wet/dry mix
+    mix: f32,
+
+    /// Whether the effect is enabled
+    enabled: bool,
+}
+
+impl Default for Gate {
+    fn default() -> Self {
+        Self::new(44100.0)
+    }
+}
+
+impl Gate {
+    /// Creates a new gate with default settings.
+    /// Default: threshold = -40dB, ratio = 10:1, attack = 1ms, hold = 50ms, release = 100ms
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            threshold_db: -40.0,
+            ratio: 10.0,
+            attack_s: 0.001,
+            hold_s: 0.05,
+            release_s: 0.1,
+            envelope: 0.0,
+            gain: 1.0,
+            hold_counter: 0,
+            sample_rate,
+            mix: 1.0,
+            enabled: true,
+        }
+    }
+
+    /// Converts dB to linear gain.
+    #[inline]
+    fn db_to_linear(db: f32) -> f32 {
+        if db <= FLOOR_DB {
+            0.0
+        } else {
+            10.0f32.powf(db / 20.0)
+        }
+    }
+
+    /// Converts linear gain to dB.
+    #[inline]
+    fn linear_to_db(gain: f32) -> f32 {
+        if gain <= 0.0001 {
+            FLOOR_DB
+        } else {
+            20.0 * gain.log10()
+        }
+    }
+
+    /// Calculates the expansion gain for a detector level below threshold.
+    fn expansion_gain(&self, level_db: f32) -> f32 {
+        let gain_db = (level_db - self.threshold_db) * (self.ratio - 1.0);
+        Self::db_to_linear(gain_db.max(FLOOR_DB))
+    }
+
+    /// Sets the threshold in dB.
+    pub fn set_threshold(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db.clamp(-80.0, 0.0);
+    }
+
+    /// Sets the expansion ratio (1:1 to 100:1).
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(1.0, 100.0);
+    }
+
+    /// Sets the attack time in seconds.
+    pub fn set_attack(&mut self, attack_s: f32) {
+        self.attack_s = attack_s.clamp(0.0001, 0.5);
+    }
+
+    /// Sets the hold time in seconds.
+    pub fn set_hold(&mut self, hold_s: f32) {
+        self.hold_s = hold_s.clamp(0.0, 2.0);
+    }
+
+    /// Sets the release time in seconds.
+    pub fn set_release(&mut self, release_s: f32) {
+        self.release_s = release_s.clamp(0.001, 2.0);
+    }
+
+    /// Gets the threshold in dB.
+    pub fn threshold(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Gets the expansion ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Gets the hold time in seconds.
+    pub fn hold(&self) -> f32 {
+        self.hold_s
+    }
+
+    /// Gets the current gain applied by the gate (0.0 = closed, 1.0 = open).
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+}
+
+impl Effect for Gate {
+    fn process(&mut self, input: f32) -> f32 {
+        // Peak detector: instant rise, short decay so zero crossings don't chatter
+        let detector_coef = (-1.0 / (DETECTOR_RELEASE_S * self.sample_rate)).exp();
+        self.envelope = input.abs().max(self.envelope * detector_coef);
+        let level_db = Self::linear_to_db(self.envelope);
+
+        // Every sample above threshold restarts the hold timer
+        let target_gain = if level_db >= self.threshold_db {
+            self.hold_counter = (self.hold_s * self.sample_rate) as usize;
+            1.0
+        } else if self.hold_counter > 0 {
+            self.hold_counter -= 1;
+            1.0
+        } else {
+            self.expansion_gain(level_db)
+        };
+
+        // Smooth the gain with attack (opening) and release (closing)
+        let coef = if target_gain > self.gain {
+            (-1.0 / (self.attack_s * self.sample_rate)).exp()
+        } else {
+            (-1.0 / (self.release_s * self.sample_rate)).exp()
+        };
+        self.gain = coef * self.gain + (1.0 - coef) * target_gain;
+
+        // Mix wet/dry
+        let output = input * self.gain;
+        input * (1.0 - self.mix) + output * self.mix
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        if self.enabled {
+            self.process(input)
+        } else {
+            input
+        }
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.process(*sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.envelope = 0.0;
+        self.gain = 1.0;
+        self.hold_counter = 0;
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    fn set_intensity(&mut self, intensity: f32) {
+        // Map intensity to threshold and ratio
+        self.set_threshold(-80.0 + intensity * 60.0); // -80dB to -20dB
+        self.set_ratio(1.0 + intensity * 99.0); // 1:1 to 100:1
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            self.set_mix(value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Gate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    /// A 440 Hz sine at the given peak amplitude.
+    fn sine(amplitude: f32, samples: usize) -> Vec<f32> {
+        (0..samples)
+            .map(|i| (2.0 * PI * 440.0 * i as f32 / SAMPLE_RATE).sin() * amplitude)
+            .collect()
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0, |m, x| m.max(x.abs()))
+    }
+
+    #[test]
+    fn test_gate_attenuates_quiet_signal_after_release() {
+        let mut gate = Gate::new(SAMPLE_RATE);
+        gate.set_threshold(-40.0);
+
+        // -60 dB input, well below threshold
+        let mut samples = sine(0.001, 44100);
+        gate.process_buffer(&mut samples);
+
+        // After hold (50ms) and release (100ms) the tail is strongly attenuated
+        let tail = &samples[22050..];
+        assert!(peak(tail) < 0.001 * 0.01, "tail peak {}", peak(tail));
+        assert!(gate.gain() < 0.01);
+    }
+
+    #[test]
+    fn test_gate_passes_signal_above_threshold() {
+        let mut gate = Gate::new(SAMPLE_RATE);
+        gate.set_threshold(-40.0);
+
+        let input = sine(0.5, 44100);
+        let mut output = input.clone();
+        gate.process_buffer(&mut output);
+
+        // Skip the first 10ms, where the sine rises from silence
+        for (x, y) in input.iter().zip(&output).skip(441) {
+            assert!((x - y).abs() < 1e-4, "{x} -> {y}");
+        }
+    }
+
+    #[test]
+    fn test_gate_hold_resets_on_each_crossing() {
+        let mut gate = Gate::new(SAMPLE_RATE);
+        gate.set_threshold(-40.0);
+        gate.set_hold(0.05);
+        gate.set_release(0.01);
+
+        // Bursts every 40ms, shorter than the hold: the gate never closes
+        let burst = (0.01 * SAMPLE_RATE) as usize;
+        let period = (0.04 * SAMPLE_RATE) as usize;
+        let mut min_gain = 1.0f32;
+        for i in 0..period * 20 {
+            let input = if i % period < burst { 0.5 } else { 0.0 };
+            gate.process(input);
+            min_gain = min_gain.min(gate.gain());
+        }
+        assert!(min_gain > 0.999, "gate chattered, min gain {min_gain}");
+
+        // Once the bursts stop, the gate closes after hold + release
+        for _ in 0..(0.2 * SAMPLE_RATE) as usize {
+            gate.process(0.0);
+        }
+        assert!(gate.gain() < 0.01);
+    }
+
+    #[test]
+    fn test_gate_low_ratio_expands_gently() {
+        let mut hard = Gate::new(SAMPLE_RATE);
+        hard.set_threshold(-40.0);
+        hard.set_ratio(100.0);
+
+        let mut soft = Gate::new(SAMPLE_RATE);
+        soft.set_threshold(-40.0);
+        soft.set_ratio(2.0);
+
+        // -50 dB input: 10 dB below threshold
+        let mut hard_out = sine(0.00316, 44100);
+        let mut soft_out = hard_out.clone();
+        hard.process_buffer(&mut hard_out);
+        soft.process_buffer(&mut soft_out);
+
+        assert!(peak(&soft_out[22050..]) > peak(&hard_out[22050..]) * 10.0);
+        // 2:1 below threshold costs roughly another 10 dB
+        assert!(
+            soft.gain() > 0.2 && soft.gain() < 0.5,
+            "gain {}",
+            soft.gain()
+        );
+    }
+
+    #[test]
+    fn test_gate_parameter_limits() {
+        let mut gate = Gate::new(SAMPLE_RATE);
+
+        gate.set_threshold(10.0);
+        assert_eq!(gate.threshold(), 0.0);
+        gate.set_ratio(0.5);
+        assert_eq!(gate.ratio(), 1.0);
+        gate.set_hold(-1.0);
+        assert_eq!(gate.hold(), 0.0);
+        assert_eq!(gate.effect_type(), EffectType::Gate);
+    }
+}
@@ -0,0 +1,332 @@
+//! Lookahead Brickwall Limiter Module
+//!
+//! Keeps the signal below a ceiling without clipping. The input is delayed
+//! by a short lookahead window while the gain computer sees every peak
+//! early, so the gain is already down when the peak leaves the delay line.
+//!
+//! # Key Parameters
+//!
+//! - **Ceiling**: Highest output level (dBFS), never exceeded
+//! - **Release**: How quickly the gain recovers after a peak
+//!
+//! The left and right channels share one gain so limiting doesn't shift the
+//! stereo image.
+
+use std::collections::VecDeque;
+
+use super::{Effect, EffectParameterId, EffectType};
+
+/// Default ceiling in dBFS
+pub const DEFAULT_LIMITER_CEILING_DB: f32 = -0.3;
+
+/// Lookahead (and latency) in seconds
+const LOOKAHEAD_S: f32 = 0.005;
+
+/// Lookahead brickwall limiter with linked stereo gain
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    /// Ceiling in dBFS
+    ceiling_db: f32,
+
+    /// Ceiling as linear amplitude
+    ceiling_gain: f32,
+
+    /// Release time in seconds
+    release_s: f32,
+
+    /// Per-sample release smoothing coefficient
+    release_coef: f32,
+
+    /// Lookahead length in samples
+    lookahead: usize,
+
+    /// Delayed (left, right) input
+    delay: Vec<(f32, f32)>,
+
+    /// Recent gains, averaged to smooth the attack
+    gain_history: Vec<f32>,
+
+    /// Running sum of `gain_history`
+    gain_sum: f64,
+
+    /// Write position shared by `delay` and `gain_history`
+    pos: usize,
+
+    /// Sliding minimum of required gains as (sample index, gain)
+    min_window: VecDeque<(usize, f32)>,
+
+    /// Samples processed, indexes `min_window`
+    counter: usize,
+
+    /// Gain after instant attack and smoothed release
+    held_gain: f32,
+
+    /// Sample rate
+    sample_rate: f32,
+
+    /// Wet/dry mix
+    mix: f32,
+
+    /// Whether the effect is enabled
+    enabled: bool,
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self::new(44100.0)
+    }
+}
+
+impl Limiter {
+    /// Creates a new limiter.
+    /// Default: ceiling = -0.3 dBFS, release = 50ms, lookahead = 5ms
+    pub fn new(sample_rate: f32) -> Self {
+        let lookahead = ((LOOKAHEAD_S * sample_rate) as usize).max(1);
+        let mut limiter = Self {
+            ceiling_db: DEFAULT_LIMITER_CEILING_DB,
+            ceiling_gain: 1.0,
+            release_s: 0.05,
+            release_coef: 0.0,
+            lookahead,
+            delay: vec![(0.0, 0.0); lookahead],
+            gain_history: vec![1.0; lookahead],
+            gain_sum: lookahead as f64,
+            pos: 0,
+            min_window: VecDeque::with_capacity(lookahead + 2),
+            counter: 0,
+            held_gain: 1.0,
+            sample_rate,
+            mix: 1.0,
+            enabled: true,
+        };
+        limiter.set_ceiling(DEFAULT_LIMITER_CEILING_DB);
+        limiter.set_release(0.05);
+        limiter
+    }
+
+    /// Sets the ceiling in dBFS (-30 to 0).
+    pub fn set_ceiling(&mut self, ceiling_db: f32) {
+        self.ceiling_db = ceiling_db.clamp(-30.0, 0.0);
+        self.ceiling_gain = 10.0f32.powf(self.ceiling_db / 20.0);
+    }
+
+    /// Sets the release time in seconds.
+    pub fn set_release(&mut self, release_s: f32) {
+        self.release_s = release_s.clamp(0.001, 2.0);
+        self.release_coef = (-1.0 / (self.release_s * self.sample_rate)).exp();
+    }
+
+    /// Gets the ceiling in dBFS.
+    pub fn ceiling(&self) -> f32 {
+        self.ceiling_db
+    }
+
+    /// Gets the release time in seconds.
+    pub fn release(&self) -> f32 {
+        self.release_s
+    }
+
+    /// Gets the lookahead latency in samples.
+    pub fn latency(&self) -> usize {
+        self.lookahead
+    }
+
+    /// Gets the gain applied to the sample leaving the delay line.
+    pub fn gain(&self) -> f32 {
+        (self.gain_sum / self.lookahead as f64) as f32
+    }
+
+    /// Processes a stereo sample pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Left input sample
+    /// * `right` - Right input sample
+    ///
+    /// # Returns
+    ///
+    /// Limited (left, right) samples, delayed by `latency()`
+    pub fn process_stereo(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let peak = left.abs().max(right.abs());
+        let required = if peak > self.ceiling_gain {
+            self.ceiling_gain / peak
+        } else {
+            1.0
+        };
+
+        // Minimum required gain over the lookahead window plus this sample
+        while self.min_window.back().is_some_and(|&(_, g)| g >= required) {
+            self.min_window.pop_back();
+        }
+        self.min_window.push_back((self.counter, required));
+        while self
+            .min_window
+            .front()
+            .is_some_and(|&(i, _)| self.counter.wrapping_sub(i) > self.lookahead)
+        {
+            self.min_window.pop_front();
+        }
+        let target = self.min_window.front().map_or(1.0, |&(_, g)| g);
+        self.counter = self.counter.wrapping_add(1);
+
+        // Drop at once, recover with the release time
+        self.held_gain = if target < self.held_gain {
+            target
+        } else {
+            target - (target - self.held_gain) * self.release_coef
+        };
+
+        // Averaging over the lookahead ramps the gain down before the peak
+        self.gain_sum += (self.held_gain - self.gain_history[self.pos]) as f64;
+        self.gain_history[self.pos] = self.held_gain;
+        let gain = self.gain();
+
+        let (delayed_left, delayed_right) = self.delay[self.pos];
+        self.delay[self.pos] = (left, right);
+        self.pos = (self.pos + 1) % self.lookahead;
+
+        // The clamp only catches rounding in the running average
+        let wet_left = (delayed_left * gain).clamp(-self.ceiling_gain, self.ceiling_gain);
+        let wet_right = (delayed_right * gain).clamp(-self.ceiling_gain, self.ceiling_gain);
+        (
+            delayed_left * (1.0 - self.mix) + wet_left * self.mix,
+            delayed_right * (1.0 - self.mix) + wet_right * self.mix,
+        )
+    }
+}
+
+impl Effect for Limiter {
+    fn process(&mut self, input: f32) -> f32 {
+        self.process_stereo(input, input).0
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        if self.enabled {
+            self.process(input)
+        } else {
+            input
+        }
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.process(*sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.delay.fill((0.0, 0.0));
+        self.gain_history.fill(1.0);
+        self.gain_sum = self.lookahead as f64;
+        self.pos = 0;
+        self.min_window.clear();
+        self.held_gain = 1.0;
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    fn set_intensity(&mut self, intensity: f32) {
+        // Map intensity to ceiling: 0 dBFS down to -12 dBFS
+        self.set_ceiling(-intensity.clamp(0.0, 1.0) * 12.0);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            self.set_mix(value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Limiter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0, |m, x| m.max(x.abs()))
+    }
+
+    #[test]
+    fn test_limiter_holds_hot_signal_below_ceiling() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+        let ceiling = 10.0f32.powf(DEFAULT_LIMITER_CEILING_DB / 20.0);
+
+        // +12 dBFS sine with sudden bursts up to +24 dBFS
+        let mut samples: Vec<f32> = (0..44100)
+            .map(|i| {
+                let burst = if i % 5000 < 50 { 4.0 } else { 1.0 };
+                (2.0 * PI * 220.0 * i as f32 / SAMPLE_RATE).sin() * 4.0 * burst
+            })
+            .collect();
+        limiter.process_buffer(&mut samples);
+
+        assert!(peak(&samples) <= ceiling, "peak {}", peak(&samples));
+        // The body of the sine is limited, not silenced
+        assert!(peak(&samples[22050..]) > ceiling * 0.9);
+    }
+
+    #[test]
+    fn test_limiter_passes_quiet_signal_delayed() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+        let input: Vec<f32> = (0..4410)
+            .map(|i| (2.0 * PI * 440.0 * i as f32 / SAMPLE_RATE).sin() * 0.5)
+            .collect();
+        let mut output = input.clone();
+        limiter.process_buffer(&mut output);
+
+        let latency = limiter.latency();
+        for (x, y) in input.iter().zip(&output[latency..]) {
+            assert!((x - y).abs() < 1e-6, "{x} -> {y}");
+        }
+    }
+
+    #[test]
+    fn test_limiter_links_stereo_gain() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+        let mut last = (0.0, 0.0);
+        for _ in 0..2000 {
+            last = limiter.process_stereo(2.0, 0.5);
+        }
+
+        // The quiet channel is turned down by the same gain as the loud one
+        assert!((last.0 / last.1 - 4.0).abs() < 1e-3);
+        assert!(last.0 <= 1.0);
+    }
+
+    #[test]
+    fn test_limiter_parameter_limits() {
+        let mut limiter = Limiter::new(SAMPLE_RATE);
+        assert_eq!(limiter.ceiling(), DEFAULT_LIMITER_CEILING_DB);
+
+        limiter.set_ceiling(6.0);
+        assert_eq!(limiter.ceiling(), 0.0);
+        limiter.set_ceiling(-100.0);
+        assert_eq!(limiter.ceiling(), -30.0);
+        limiter.set_release(0.0);
+        assert_eq!(limiter.release(), 0.001);
+        assert_eq!(limiter.effect_type(), EffectType::Limiter);
+    }
+}
@@ -12,6 +12,10 @@
 //! - **Phaser**: Sweeping phase cancellation
 //! - **Flanger**: Modulated comb filtering
 //! - **Saturation**: Analog-style soft clipping and harmonic enhancement
+//! - **Stereo Widener**: Mid-side width control with bass-mono
+//! - **Gate**: Noise gate / downward expander with hold
+//! - **Convolution Reverb**: Realistic spaces from a recorded impulse response
+//! - **Limiter**: Lookahead brickwall limiter
 
 #![allow(dead_code)] // Reserve fields for future use
 
@@ -68,20 +72,32 @@ pub mod daisy_delay;
 
 pub mod rumsklang_reverb;
 
-// Track effects module is temporarily disabled for compilation
-// pub mod track_effects;
+pub mod stereo_widener;
 
-pub use bit_crusher::{BitCrusher, BitCrusherConfig, DecimationMode, StereoBitCrusher};
-pub use chorus::Chorus;
+pub mod gate;
+
+pub mod convolution_reverb;
+
+pub mod limiter;
+
+pub mod track_effects;
+
+pub use bit_crusher::{BitCrusher, BitCrusherConfig, DecimationMode, DitherMode, StereoBitCrusher};
+pub use chorus::{Chorus, StereoChorus};
 pub use chrono_pitch::ChronoPitch;
 pub use comb_filter::CombFilter;
+pub use convolution_reverb::ConvolutionReverb;
 pub use degrader::Degrader;
 pub use dirtshaper::Dirtshaper;
-pub use filter_bank::{FilterBandConfig, FilterBank, FilterBankConfig, FilterBankType};
+pub use filter_bank::{
+    FilterBandConfig, FilterBank, FilterBankConfig, FilterBankType, FormantVowel,
+};
 pub use filterbank::Filterbank;
 pub use flanger::{Flanger, FlangerConfig, StereoFlanger};
 pub use freeze::{Freeze, FreezeConfig, FreezeType};
+pub use gate::Gate;
 pub use infinite_flanger::InfiniteFlanger;
+pub use limiter::{Limiter, DEFAULT_LIMITER_CEILING_DB};
 pub use panoramic_chorus::PanoramicChorus;
 pub use phase_98::Phase98;
 pub use phaser::{Phaser, PhaserConfig, StereoPhaser};
@@ -91,24 +107,21 @@ pub use ring_modulator::{
 pub use saturation::{saturate, Saturation, SaturationConfig};
 pub use saturator_delay::SaturatorDelay;
 pub use simple_eq::SimpleEq;
+pub use stereo_widener::StereoWidener;
+pub use track_effects::{
+    EffectFactory, EffectParameterId, PerTrackEffectsManager, TrackEffectSlot,
+    TrackEffectSlotConfig, TrackEffects, TrackEffectsError, MAX_EFFECT_SLOTS, TRACK_COUNT,
+};
 pub use tremolo::{Tremolo, TremoloConfig, TremoloWaveform};
 pub use warp::{StereoWarp, Warp, WarpConfig, WarpMode};
-// pub use track_effects::{
-//     TrackEffectSlot,
-//     TrackEffectSlotConfig,
-//     TrackEffects,
-//     TrackEffectsError,
-//     EffectParameterId,
-//     PerTrackEffectsManager,
-//     MAX_EFFECT_SLOTS,
-//     TRACK_COUNT,
-// };
 
 // Re-export BiquadFilter from filter module for convenience
 pub use crate::filter::{BiquadFilter, FilterConfig, FilterType};
 
+pub(crate) use crate::filter::flush_denormal;
+
 /// Enumeration of supported effect types.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EffectType {
     /// Bypass - no effect
     Bypass,
@@ -184,6 +197,18 @@ pub enum EffectType {
 
     /// Rumsklang Reverb
     RumsklangReverb,
+
+    /// Mid-side stereo widener
+    StereoWidener,
+
+    /// Noise gate / expander
+    Gate,
+
+    /// Impulse-response convolution reverb
+    ConvolutionReverb,
+
+    /// Lookahead brickwall limiter
+    Limiter,
 }
 
 /// Configuration structure for effect parameters.
@@ -304,6 +329,43 @@ pub trait Effect {
     ///
     /// * `enabled` - Whether the effect should be active
     fn set_enabled(&mut self, enabled: bool);
+
+    /// Set a parameter by identifier.
+    ///
+    /// Each effect maps the identifiers it understands onto its own
+    /// parameters and ignores the rest. Used by parameter locks.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Parameter identifier
+    /// * `value` - Parameter value (range depends on the identifier)
+    fn set_param(&mut self, _id: EffectParameterId, _value: f32) {}
+
+    /// Get a parameter by identifier.
+    ///
+    /// Values use the same units as `set_param`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Parameter identifier
+    ///
+    /// # Returns
+    ///
+    /// The current value, or None if the effect has no such parameter
+    fn get_param(&self, _id: EffectParameterId) -> Option<f32> {
+        None
+    }
+
+    /// Get the type of this effect.
+    ///
+    /// Effects without a dedicated `EffectType` variant report `Bypass`.
+    ///
+    /// # Returns
+    ///
+    /// The effect type identifier
+    fn effect_type(&self) -> EffectType {
+        EffectType::Bypass
+    }
 }
 
 /// Simple delay effect with feedback.
@@ -370,7 +432,7 @@ impl Effect for Delay {
         let delayed = self.buffer[self.read_pos];
 
         // Write input plus feedback to buffer
-        self.buffer[self.write_pos] = input + delayed * self.feedback;
+        self.buffer[self.write_pos] = flush_denormal(input + delayed * self.feedback);
 
         // Advance positions
         self.write_pos = (self.write_pos + 1) % self.buffer.len();
@@ -415,6 +477,20 @@ impl Effect for Delay {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            self.set_mix(value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        (id == EffectParameterId::Mix).then_some(self.mix)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Delay
+    }
 }
 
 /// Simple reverb effect using a series of comb filters.
@@ -481,7 +557,7 @@ impl Effect for Reverb {
             let write_pos = self.write_pos[i];
 
             let delayed = buffer[write_pos % delay];
-            buffer[write_pos % delay] = input + delayed * self.decay;
+            buffer[write_pos % delay] = flush_denormal(input + delayed * self.decay);
             self.write_pos[i] = (write_pos + 1) % delay;
 
             output += delayed;
@@ -528,6 +604,37 @@ impl Effect for Reverb {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            self.set_mix(value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        (id == EffectParameterId::Mix).then_some(self.mix)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Reverb
+    }
+}
+
+/// Waveshaping curves available to the distortion effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistortionShape {
+    /// Smooth sine-based soft clipping
+    #[default]
+    SoftClip,
+
+    /// Hard limiting at ±1
+    HardClip,
+
+    /// Reflects the signal back each time it crosses ±1
+    Wavefold,
+
+    /// Asymmetric tube-style curve that adds even harmonics
+    Tube,
 }
 
 /// Distortion effect using waveshaping.
@@ -536,18 +643,46 @@ pub struct Distortion {
     /// Distortion amount
     amount: f32,
 
+    /// Waveshaping curve
+    shape: DistortionShape,
+
     /// Wet/dry mix
     mix: f32,
 
+    /// Whether the DC blocker runs after the waveshaper
+    dc_block: bool,
+
+    /// DC blocker pole coefficient
+    dc_coef: f32,
+
+    /// DC blocker previous input
+    dc_x1: f32,
+
+    /// DC blocker previous output
+    dc_y1: f32,
+
+    /// Sample rate
+    sample_rate: f32,
+
     /// Whether the effect is enabled
     enabled: bool,
 }
 
+/// Cutoff of the post-waveshaper DC blocker in Hz.
+const DC_BLOCK_CUTOFF_HZ: f32 = 10.0;
+
 impl Default for Distortion {
     fn default() -> Self {
+        let sample_rate = 44100.0;
         Self {
             amount: 0.5,
+            shape: DistortionShape::SoftClip,
             mix: 0.5,
+            dc_block: true,
+            dc_coef: Self::dc_coefficient(sample_rate),
+            dc_x1: 0.0,
+            dc_y1: 0.0,
+            sample_rate,
             enabled: true,
         }
     }
@@ -559,19 +694,85 @@ impl Distortion {
         Self::default()
     }
 
+    /// Sets the waveshaping curve.
+    pub fn set_shape(&mut self, shape: DistortionShape) {
+        self.shape = shape;
+    }
+
+    /// Gets the current waveshaping curve.
+    pub fn shape(&self) -> DistortionShape {
+        self.shape
+    }
+
+    /// Enables or disables the DC blocker after the waveshaper.
+    pub fn set_dc_block(&mut self, enabled: bool) {
+        self.dc_block = enabled;
+    }
+
+    /// Checks if the DC blocker is enabled.
+    pub fn dc_block(&self) -> bool {
+        self.dc_block
+    }
+
+    /// Sets the sample rate and recalculates the DC blocker coefficient.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.dc_coef = Self::dc_coefficient(sample_rate);
+    }
+
+    /// One-pole high-pass coefficient for the DC blocker.
+    fn dc_coefficient(sample_rate: f32) -> f32 {
+        1.0 - 2.0 * PI * DC_BLOCK_CUTOFF_HZ / sample_rate
+    }
+
+    /// Removes the DC offset left behind by asymmetric curves.
+    #[inline]
+    fn apply_dc_block(&mut self, sample: f32) -> f32 {
+        let output = sample - self.dc_x1 + self.dc_coef * self.dc_y1;
+        self.dc_x1 = sample;
+        self.dc_y1 = output;
+        output
+    }
+
     /// Applies waveshaping curve to input sample.
     fn apply_curve(&self, sample: f32) -> f32 {
         let x = sample.clamp(-1.0, 1.0);
         let k = self.amount * 20.0; // Gain factor
 
-        // Soft clipping curve
-        (PI * k * x).sin() / (PI + k * x.abs())
+        match self.shape {
+            // Soft clipping curve
+            DistortionShape::SoftClip => (PI * k * x).sin() / (PI + k * x.abs()),
+
+            DistortionShape::HardClip => (x * (1.0 + k)).clamp(-1.0, 1.0),
+
+            DistortionShape::Wavefold => {
+                let mut y = x * (1.0 + k);
+                // Gain is bounded, so this settles within a few reflections
+                while y.abs() > 1.0 {
+                    y = if y > 1.0 { 2.0 - y } else { -2.0 - y };
+                }
+                y
+            }
+
+            DistortionShape::Tube => {
+                // Positive half clips harder than negative half
+                let y = x * (1.0 + k);
+                if y >= 0.0 {
+                    y.tanh()
+                } else {
+                    (0.5 * y).tanh()
+                }
+            }
+        }
     }
 }
 
 impl Effect for Distortion {
     fn process(&mut self, input: f32) -> f32 {
-        let distorted = self.apply_curve(input);
+        let mut distorted = self.apply_curve(input);
+        if self.dc_block {
+            distorted = self.apply_dc_block(distorted);
+        }
         input * (1.0 - self.mix) + distorted * self.mix
     }
 
@@ -590,7 +791,8 @@ impl Effect for Distortion {
     }
 
     fn reset(&mut self) {
-        // No state to reset for stateless waveshaping
+        self.dc_x1 = 0.0;
+        self.dc_y1 = 0.0;
     }
 
     fn set_mix(&mut self, mix: f32) {
@@ -608,6 +810,26 @@ impl Effect for Distortion {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        match id {
+            EffectParameterId::DistortionAmount => self.set_intensity(value),
+            EffectParameterId::Mix => self.set_mix(value),
+            _ => {}
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::DistortionAmount => Some(self.amount),
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Distortion
+    }
 }
 
 /// Multiband sidechain envelope compressor (MSEC)
@@ -819,6 +1041,32 @@ impl Effect for Compressor {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        match id {
+            EffectParameterId::CompressorThreshold => self.set_threshold(value),
+            EffectParameterId::CompressorRatio => self.set_ratio(value),
+            EffectParameterId::CompressorAttack => self.set_attack(value),
+            EffectParameterId::CompressorRelease => self.set_release(value),
+            EffectParameterId::Mix => self.set_mix(value),
+            _ => {}
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::CompressorThreshold => Some(self.threshold_db),
+            EffectParameterId::CompressorRatio => Some(self.ratio),
+            EffectParameterId::CompressorAttack => Some(self.attack_s),
+            EffectParameterId::CompressorRelease => Some(self.release_s),
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Compressor
+    }
 }
 
 /// Wrapper type for effect management.
@@ -850,11 +1098,31 @@ pub struct EffectProcessor {
 
     /// Biquad filter effect instance
     biquad_filter: BiquadFilter,
+
+    /// Phaser effect instance
+    phaser: StereoPhaser,
+
+    /// Flanger effect instance
+    flanger: StereoFlanger,
+
+    /// Processing order used by `process_chain`
+    chain: Vec<EffectType>,
+
+    /// Right-channel twin running the mono-only effects in `process_stereo`
+    /// (None on the twin itself)
+    right: Option<Box<EffectProcessor>>,
 }
 
 impl EffectProcessor {
     /// Creates a new effect processor.
     pub fn new(sample_rate: f32) -> Self {
+        let mut processor = Self::new_channel(sample_rate);
+        processor.right = Some(Box::new(Self::new_channel(sample_rate)));
+        processor
+    }
+
+    /// Creates a processor for one channel, without a right-channel twin.
+    fn new_channel(sample_rate: f32) -> Self {
         let config = FilterConfig {
             filter_type: FilterType::LowPass,
             cutoff_frequency: 1000.0,
@@ -873,18 +1141,142 @@ impl EffectProcessor {
             chorus: Chorus::new(sample_rate),
             simple_eq: SimpleEq::new(sample_rate),
             biquad_filter: BiquadFilter::with_config(config),
+            phaser: StereoPhaser::new_with_sample_rate(sample_rate),
+            flanger: StereoFlanger::new_with_sample_rate(sample_rate),
+            chain: Vec::new(),
+            right: None,
         }
     }
 
     /// Sets the active effect type.
     pub fn set_effect_type(&mut self, effect_type: EffectType) {
         self.effect_type = effect_type;
+        self.mirror(|right| right.set_effect_type(effect_type));
     }
 
     /// Gets the current effect type.
     pub fn effect_type(&self) -> EffectType {
         self.effect_type
     }
+
+    /// Sets the serial processing order used by `process_chain`.
+    ///
+    /// Effect types without an instance in this processor are skipped.
+    pub fn set_chain(&mut self, order: Vec<EffectType>) {
+        self.mirror(|right| right.set_chain(order.clone()));
+        self.chain = order;
+    }
+
+    /// Gets the serial processing order.
+    pub fn chain(&self) -> &[EffectType] {
+        &self.chain
+    }
+
+    /// Enables or disables a single stage of the chain.
+    pub fn set_effect_enabled(&mut self, effect_type: EffectType, enabled: bool) {
+        if let Some(effect) = self.effect_mut(effect_type) {
+            effect.set_enabled(enabled);
+        }
+        self.mirror(|right| right.set_effect_enabled(effect_type, enabled));
+    }
+
+    /// Checks if a single stage of the chain is enabled.
+    pub fn is_effect_enabled(&self, effect_type: EffectType) -> bool {
+        self.effect(effect_type).is_some_and(|e| e.is_enabled())
+    }
+
+    /// Processes a sample through every enabled stage of the chain in order.
+    pub fn process_chain(&mut self, input: f32) -> f32 {
+        let mut output = input;
+        for i in 0..self.chain.len() {
+            if let Some(effect) = self.effect_mut(self.chain[i]) {
+                output = effect.process_with_bypass(output);
+            }
+        }
+        output
+    }
+
+    /// Processes a buffer through every enabled stage of the chain in order.
+    pub fn process_chain_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.process_chain(*sample);
+        }
+    }
+
+    /// Processes a stereo sample pair through the active effect.
+    ///
+    /// Phaser, flanger and chorus run their stereo variants. Every other
+    /// effect runs once per channel, the right channel on a twin instance so
+    /// both channels keep their own state. A disabled effect passes both
+    /// channels through.
+    ///
+    /// # Returns
+    ///
+    /// Processed (left, right) samples
+    pub fn process_stereo(&mut self, left: f32, right: f32) -> (f32, f32) {
+        if !self.is_enabled() {
+            return (left, right);
+        }
+
+        match self.effect_type {
+            EffectType::Phaser => self.phaser.process(left, right),
+            EffectType::Flanger => self.flanger.process(left, right),
+            EffectType::Chorus => {
+                let (mut left, mut right) = ([left], [right]);
+                self.chorus.process_stereo(&mut left, &mut right);
+                (left[0], right[0])
+            }
+            _ => {
+                let left = self.process(left);
+                let right = match self.right.as_deref_mut() {
+                    Some(twin) => twin.process(right),
+                    None => right,
+                };
+                (left, right)
+            }
+        }
+    }
+
+    /// Applies a setting to the right-channel twin, if there is one.
+    fn mirror(&mut self, apply: impl FnOnce(&mut EffectProcessor)) {
+        if let Some(right) = self.right.as_deref_mut() {
+            apply(right);
+        }
+    }
+
+    /// Gets the effect instance for a type, if this processor holds one.
+    fn effect(&self, effect_type: EffectType) -> Option<&dyn Effect> {
+        match effect_type {
+            EffectType::Delay => Some(&self.delay),
+            EffectType::Reverb => Some(&self.reverb),
+            EffectType::Distortion => Some(&self.distortion),
+            EffectType::Compressor => Some(&self.compressor),
+            EffectType::Saturation => Some(&self.saturation),
+            EffectType::Chorus => Some(&self.chorus),
+            EffectType::SimpleEQ => Some(&self.simple_eq),
+            EffectType::Filter => Some(&self.biquad_filter),
+            EffectType::Phaser => Some(&self.phaser),
+            EffectType::Flanger => Some(&self.flanger),
+            _ => None,
+        }
+    }
+
+    /// Gets the mutable effect instance for a type, if this processor holds one.
+    fn effect_mut(&mut self, effect_type: EffectType) -> Option<&mut dyn Effect> {
+        match effect_type {
+            EffectType::Delay => Some(&mut self.delay),
+            EffectType::Reverb => Some(&mut self.reverb),
+            EffectType::Distortion => Some(&mut self.distortion),
+            EffectType::Compressor => Some(&mut self.compressor),
+            EffectType::Saturation => Some(&mut self.saturation),
+            EffectType::Chorus => Some(&mut self.chorus),
+            EffectType::SimpleEQ => Some(&mut self.simple_eq),
+            EffectType::Filter => Some(&mut self.biquad_filter),
+            EffectType::Phaser => Some(&mut self.phaser),
+            EffectType::Flanger => Some(&mut self.flanger),
+            _ => None,
+        }
+    }
 }
 
 impl Effect for EffectProcessor {
@@ -898,6 +1290,8 @@ impl Effect for EffectProcessor {
             EffectType::Chorus => self.chorus.process(input),
             EffectType::SimpleEQ => self.simple_eq.process(input),
             EffectType::Filter => self.biquad_filter.process(input),
+            EffectType::Phaser => self.phaser.process_mono(input),
+            EffectType::Flanger => Effect::process(&mut self.flanger, input),
             _ => input, // Placeholder for unimplemented effects
         }
     }
@@ -925,6 +1319,9 @@ impl Effect for EffectProcessor {
         self.chorus.reset();
         self.simple_eq.reset();
         self.biquad_filter.reset();
+        self.phaser.reset();
+        self.flanger.reset();
+        self.mirror(|right| right.reset());
     }
 
     fn set_mix(&mut self, mix: f32) {
@@ -937,8 +1334,11 @@ impl Effect for EffectProcessor {
             EffectType::Chorus => self.chorus.set_mix(mix),
             EffectType::SimpleEQ => self.simple_eq.set_mix(mix),
             EffectType::Filter => self.biquad_filter.set_mix(mix),
+            EffectType::Phaser => self.phaser.set_mix(mix),
+            EffectType::Flanger => self.flanger.set_mix(mix),
             _ => {}
         }
+        self.mirror(|right| right.set_mix(mix));
     }
 
     fn set_intensity(&mut self, intensity: f32) {
@@ -951,8 +1351,11 @@ impl Effect for EffectProcessor {
             EffectType::Chorus => self.chorus.set_intensity(intensity),
             EffectType::SimpleEQ => self.simple_eq.set_intensity(intensity),
             EffectType::Filter => self.biquad_filter.set_intensity(intensity),
+            EffectType::Phaser => self.phaser.set_intensity(intensity),
+            EffectType::Flanger => self.flanger.set_intensity(intensity),
             _ => {}
         }
+        self.mirror(|right| right.set_intensity(intensity));
     }
 
     fn is_enabled(&self) -> bool {
@@ -965,6 +1368,8 @@ impl Effect for EffectProcessor {
             EffectType::Chorus => self.chorus.is_enabled(),
             EffectType::SimpleEQ => self.simple_eq.is_enabled(),
             EffectType::Filter => self.biquad_filter.is_enabled(),
+            EffectType::Phaser => self.phaser.is_enabled(),
+            EffectType::Flanger => self.flanger.is_enabled(),
             _ => false,
         }
     }
@@ -979,8 +1384,27 @@ impl Effect for EffectProcessor {
             EffectType::Chorus => self.chorus.set_enabled(enabled),
             EffectType::SimpleEQ => self.simple_eq.set_enabled(enabled),
             EffectType::Filter => self.biquad_filter.set_enabled(enabled),
+            EffectType::Phaser => self.phaser.set_enabled(enabled),
+            EffectType::Flanger => self.flanger.set_enabled(enabled),
             _ => {}
         }
+        self.mirror(|right| right.set_enabled(enabled));
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if let Some(effect) = self.effect_mut(self.effect_type) {
+            effect.set_param(id, value);
+        }
+        self.mirror(|right| right.set_param(id, value));
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        self.effect(self.effect_type)
+            .and_then(|effect| effect.get_param(id))
+    }
+
+    fn effect_type(&self) -> EffectType {
+        self.effect_type
     }
 }
 
@@ -1298,6 +1722,106 @@ mod tests {
         }
     }
 
+    // --- EffectProcessor: chain applies every enabled stage in order ---
+    #[test]
+    fn test_effect_processor_chain_serial() {
+        let sample_rate = 44100.0;
+        let signal = generate_sine(220.0, sample_rate, 2048);
+
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_chain(vec![
+            EffectType::Delay,
+            EffectType::Reverb,
+            EffectType::Saturation,
+        ]);
+        assert_eq!(fx.chain().len(), 3);
+
+        let mut chained = signal.clone();
+        fx.process_chain_buffer(&mut chained);
+
+        // Same stages run by hand in the same order
+        let mut delay = Delay::new(sample_rate);
+        let mut reverb = Reverb::new(sample_rate);
+        let mut saturation = Saturation::new();
+        let expected: Vec<f32> = signal
+            .iter()
+            .map(|&s| saturation.process(reverb.process(delay.process(s))))
+            .collect();
+
+        for (i, (a, b)) in chained.iter().zip(expected.iter()).enumerate() {
+            assert!((a - b).abs() < 1e-6, "sample {}: {} vs {}", i, a, b);
+        }
+    }
+
+    // --- EffectProcessor: disabled chain stages are skipped ---
+    #[test]
+    fn test_effect_processor_chain_skips_disabled() {
+        let sample_rate = 44100.0;
+        let signal = generate_sine(220.0, sample_rate, 512);
+
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_chain(vec![EffectType::Delay, EffectType::Saturation]);
+        fx.set_effect_enabled(EffectType::Delay, false);
+        assert!(!fx.is_effect_enabled(EffectType::Delay));
+        assert!(fx.is_effect_enabled(EffectType::Saturation));
+
+        let mut saturation = Saturation::new();
+        for &s in &signal {
+            let out = fx.process_chain(s);
+            assert!((out - saturation.process(s)).abs() < 1e-6);
+        }
+
+        // Legacy single-effect path is unaffected by the chain
+        fx.set_effect_type(EffectType::Saturation);
+        let single = fx.process(0.5);
+        assert!(single.is_finite());
+    }
+
+    // --- Track effects: manager is usable through the re-exports ---
+    #[test]
+    fn test_per_track_effects_manager_public_api() {
+        let mut manager = PerTrackEffectsManager::new(44100.0);
+
+        let track = manager.track_effects(2).expect("track 2 exists");
+        track.add_effect(0, EffectType::Saturation).unwrap();
+        track
+            .add_param_lock(0, EffectParameterId::Mix, 0.8)
+            .unwrap();
+        assert_eq!(track.effect_type(0), Ok(Some(EffectType::Saturation)));
+        assert_eq!(
+            track.add_effect(MAX_EFFECT_SLOTS, EffectType::Delay),
+            Err(TrackEffectsError::InvalidSlotIndex)
+        );
+
+        assert!(manager.track_effects(TRACK_COUNT as u8).is_none());
+        assert_eq!(manager.total_active_effects(), 1);
+        assert!(manager.process_track(2, 0.5).is_finite());
+    }
+
+    // --- Effect trait: concrete effects report their type ---
+    #[test]
+    fn test_effect_type_reporting() {
+        let sample_rate = 44100.0;
+        assert_eq!(Delay::new(sample_rate).effect_type(), EffectType::Delay);
+        assert_eq!(Reverb::new(sample_rate).effect_type(), EffectType::Reverb);
+        assert_eq!(Distortion::new().effect_type(), EffectType::Distortion);
+        assert_eq!(
+            Compressor::new(sample_rate).effect_type(),
+            EffectType::Compressor
+        );
+        assert_eq!(Saturation::new().effect_type(), EffectType::Saturation);
+        assert_eq!(Chorus::new(sample_rate).effect_type(), EffectType::Chorus);
+        assert_eq!(
+            SimpleEq::new(sample_rate).effect_type(),
+            EffectType::SimpleEQ
+        );
+        assert_eq!(BiquadFilter::new().effect_type(), EffectType::Filter);
+
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_effect_type(EffectType::Compressor);
+        assert_eq!(Effect::effect_type(&fx), EffectType::Compressor);
+    }
+
     // --- Chorus: modulates the signal (output differs from input) ---
     #[test]
     fn test_chorus_modulates_signal() {
@@ -1344,6 +1868,113 @@ mod tests {
         assert_eq!(chorus.rate(), 2.1);
     }
 
+    // --- Distortion: every shape stays bounded ---
+    #[test]
+    fn test_distortion_shapes_bounded() {
+        let shapes = [
+            DistortionShape::SoftClip,
+            DistortionShape::HardClip,
+            DistortionShape::Wavefold,
+            DistortionShape::Tube,
+        ];
+
+        for &shape in &shapes {
+            let mut dist = Distortion::new();
+            dist.set_shape(shape);
+            dist.set_mix(1.0);
+
+            for drive in 0..=10 {
+                dist.set_intensity(drive as f32 / 10.0);
+                for i in -20..=20 {
+                    let input = i as f32 / 10.0;
+                    let out = dist.process(input);
+                    assert!(
+                        out.is_finite() && out.abs() <= 1.5,
+                        "{:?} output {} out of bounds for input {}",
+                        shape,
+                        out,
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    // --- Distortion: wavefold reflects instead of clipping ---
+    #[test]
+    fn test_distortion_wavefold_reflects() {
+        let mut dist = Distortion::new();
+        dist.set_shape(DistortionShape::Wavefold);
+        dist.set_mix(1.0);
+        dist.set_intensity(0.05); // Gain of 2
+        dist.set_dc_block(false);
+
+        // 0.75 * 2 = 1.5 folds back to 0.5
+        assert!((dist.process(0.75) - 0.5).abs() < 1e-5);
+        assert!((dist.process(-0.75) + 0.5).abs() < 1e-5);
+
+        // Hard clip sits at the rail for the same input
+        dist.set_shape(DistortionShape::HardClip);
+        assert!((dist.process(0.75) - 1.0).abs() < 1e-5);
+    }
+
+    // --- Distortion: tube curve is asymmetric ---
+    #[test]
+    fn test_distortion_tube_asymmetric() {
+        let mut dist = Distortion::new();
+        dist.set_shape(DistortionShape::Tube);
+        dist.set_mix(1.0);
+        dist.set_intensity(0.05);
+        dist.set_dc_block(false);
+
+        let pos = dist.process(0.5);
+        let neg = dist.process(-0.5);
+        assert!(
+            (pos + neg).abs() > 0.01,
+            "Tube should be asymmetric: +{} vs {}",
+            pos,
+            neg
+        );
+    }
+
+    // --- Distortion: DC blocker removes asymmetric offset ---
+    #[test]
+    fn test_distortion_dc_block_removes_offset() {
+        let sample_rate = 44100.0;
+        let biased: Vec<f32> = generate_sine(220.0, sample_rate, 44100)
+            .iter()
+            .map(|s| 0.3 + 0.5 * s)
+            .collect();
+
+        let tail_mean = |dist: &mut Distortion| {
+            let output: Vec<f32> = biased.iter().map(|&s| dist.process(s)).collect();
+            let tail = &output[output.len() - 4410..];
+            tail.iter().sum::<f32>() / tail.len() as f32
+        };
+
+        let mut dist = Distortion::new();
+        dist.set_shape(DistortionShape::Tube);
+        dist.set_mix(1.0);
+        dist.set_intensity(0.3);
+
+        assert!(dist.dc_block());
+        let blocked = tail_mean(&mut dist);
+        assert!(
+            blocked.abs() < 0.01,
+            "DC should converge to 0, got {}",
+            blocked
+        );
+
+        dist.set_dc_block(false);
+        dist.reset();
+        let unblocked = tail_mean(&mut dist);
+        assert!(
+            unblocked.abs() > 0.1,
+            "Without blocker the offset remains, got {}",
+            unblocked
+        );
+    }
+
     // --- Compressor: reset restores unity gain ---
     #[test]
     fn test_compressor_reset() {
@@ -1354,4 +1985,87 @@ mod tests {
         comp.reset();
         assert_eq!(comp.gain_reduction, 1.0);
     }
+
+    #[test]
+    fn test_reverb_tail_decays_to_exact_zero() {
+        let mut reverb = Reverb::new(44100.0);
+        reverb.set_decay(0.7);
+        reverb.process(1.0);
+
+        // Each comb pass scales the tail by 0.7, which passes the flush
+        // threshold after about 130 passes (~4 seconds)
+        for _ in 0..44100 * 10 {
+            reverb.process(0.0);
+        }
+        assert!(reverb.buffers.iter().flatten().all(|&x| x == 0.0));
+    }
+
+    // --- EffectProcessor: stereo output ---
+    #[test]
+    fn test_effect_processor_stereo_phaser_decorrelates() {
+        use crate::audio_analysis::measure_stereo_correlation;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let sample_rate = 44100.0;
+        let mut rng = StdRng::seed_from_u64(3);
+        let input: Vec<f32> = (0..44100).map(|_| rng.gen_range(-0.5..0.5)).collect();
+
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_effect_type(EffectType::Phaser);
+        fx.set_intensity(1.0);
+        let (left, right): (Vec<f32>, Vec<f32>) =
+            input.iter().map(|&x| fx.process_stereo(x, x)).unzip();
+        let correlation = measure_stereo_correlation(&left, &right);
+        assert!(correlation < 0.95, "phaser correlation {correlation}");
+
+        // Mono-only effects apply identically to both channels
+        fx.set_effect_type(EffectType::Distortion);
+        fx.set_intensity(0.8);
+        for &x in &input[..4410] {
+            let (left, right) = fx.process_stereo(x, x);
+            assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn test_effect_processor_stereo_keeps_both_channels_across_switch() {
+        let sample_rate = 44100.0;
+        let input = generate_sine(440.0, sample_rate, 8820);
+
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_effect_type(EffectType::Delay);
+        for &x in &input[..4410] {
+            fx.process_stereo(x, -x);
+        }
+
+        // Switch mid-stream: the twin follows, so the right channel keeps
+        // the new effect instead of going silent
+        fx.set_effect_type(EffectType::Reverb);
+        fx.set_mix(0.5);
+        let (left, right): (Vec<f32>, Vec<f32>) = input[4410..]
+            .iter()
+            .map(|&x| fx.process_stereo(x, -x))
+            .unzip();
+        assert!(rms(&left) > 0.1 && rms(&right) > 0.1);
+        for (l, r) in left.iter().zip(&right) {
+            assert!((l + r).abs() < 1e-4, "{l} {r}");
+        }
+    }
+
+    #[test]
+    fn test_effect_processor_stereo_twin_has_own_history() {
+        let sample_rate = 44100.0;
+        let input = generate_sine(440.0, sample_rate, 4410);
+
+        // Mono processing fills the left delay line only
+        let mut fx = EffectProcessor::new(sample_rate);
+        fx.set_effect_type(EffectType::Delay);
+        for &x in &input {
+            fx.process(x);
+        }
+
+        // The right channel starts from silence, not from the left history
+        let right: Vec<f32> = (0..44100).map(|_| fx.process_stereo(0.0, 0.0).1).collect();
+        assert_eq!(rms(&right), 0.0);
+    }
 }
@@ -13,6 +13,8 @@
 
 use std::f32::consts::PI;
 
+use super::{Effect, EffectParameterId, EffectType};
+
 #[cfg(test)]
 use crate::audio_analysis;
 
@@ -128,6 +130,9 @@ pub struct Phaser {
 
     /// 上一个输出 (用于反馈)
     last_output: f32,
+
+    /// 是否启用
+    enabled: bool,
 }
 
 impl Default for Phaser {
@@ -139,7 +144,7 @@ impl Default for Phaser {
 impl Phaser {
     /// 创建新的Phaser
     pub fn new() -> Self {
-        Self {
+        let mut phaser = Self {
             config: PhaserConfig::default(),
             sample_rate: 44100.0,
             filters: Vec::new(),
@@ -148,7 +153,11 @@ impl Phaser {
             min_frequency: 200.0,
             max_frequency: 8000.0,
             last_output: 0.0,
-        }
+            enabled: true,
+        };
+        phaser.update_lfo_increment();
+        phaser.resize_filters();
+        phaser
     }
 
     /// 创建带采样率的Phaser
@@ -320,6 +329,61 @@ impl Phaser {
     }
 }
 
+impl Effect for Phaser {
+    fn process(&mut self, input: f32) -> f32 {
+        Phaser::process(self, input)
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        if self.enabled {
+            Phaser::process(self, input)
+        } else {
+            input
+        }
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = Phaser::process(self, *sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        Phaser::reset(self);
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        Phaser::set_mix(self, mix);
+    }
+
+    /// 强度映射到深度
+    fn set_intensity(&mut self, intensity: f32) {
+        self.set_depth(intensity);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            Phaser::set_mix(self, value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        (id == EffectParameterId::Mix).then_some(self.config.mix)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Phaser
+    }
+}
+
 /// 立体声Phaser (带L/R独立控制)
 #[derive(Debug, Clone)]
 pub struct StereoPhaser {
@@ -335,11 +399,13 @@ pub struct StereoPhaser {
 
 impl Default for StereoPhaser {
     fn default() -> Self {
-        Self {
+        let mut phaser = Self {
             phaser_l: Phaser::new(),
             phaser_r: Phaser::new(),
             stereo_width: 0.5,
-        }
+        };
+        phaser.apply_phase_offset();
+        phaser
     }
 }
 
@@ -376,10 +442,12 @@ impl StereoPhaser {
     /// 设置立体声宽度
     pub fn set_stereo_width(&mut self, width: f32) {
         self.stereo_width = width.clamp(0.0, 1.0);
+        self.apply_phase_offset();
+    }
 
-        // 右声道使用稍有不同的LFO
-        let _phase_offset = PI * self.stereo_width;
-        // 这里简化处理，实际应该使用不同的LFO相位
+    /// 右声道LFO相位领先左声道 π × 立体声宽度
+    fn apply_phase_offset(&mut self) {
+        self.phaser_r.lfo_phase = (self.phaser_l.lfo_phase + PI * self.stereo_width) % (2.0 * PI);
     }
 
     /// 设置速率
@@ -403,7 +471,11 @@ impl StereoPhaser {
     /// 处理立体声样本
     #[inline]
     pub fn process(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
-        self.phaser_l.process_stereo(input_left, input_right)
+        // 每个声道独立的全通链，LFO相位偏移产生立体声扫动
+        (
+            self.phaser_l.process(input_left),
+            self.phaser_r.process(input_right),
+        )
     }
 
     /// 处理单声道样本
@@ -416,6 +488,60 @@ impl StereoPhaser {
     pub fn reset(&mut self) {
         self.phaser_l.reset();
         self.phaser_r.reset();
+        self.apply_phase_offset();
+    }
+}
+
+impl Effect for StereoPhaser {
+    /// 单声道处理只使用左声道
+    fn process(&mut self, input: f32) -> f32 {
+        self.process_mono(input)
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        self.phaser_l.process_with_bypass(input)
+    }
+
+    fn process_buffer(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            *sample = self.process_mono(*sample);
+        }
+    }
+
+    fn reset(&mut self) {
+        StereoPhaser::reset(self);
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        StereoPhaser::set_mix(self, mix);
+    }
+
+    /// 强度映射到深度
+    fn set_intensity(&mut self, intensity: f32) {
+        self.set_depth(intensity);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.phaser_l.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.phaser_l.enabled = enabled;
+        self.phaser_r.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            StereoPhaser::set_mix(self, value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        self.phaser_l.get_param(id)
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Phaser
     }
 }
 
@@ -431,6 +557,10 @@ mod tests {
         assert_eq!(phaser.config.feedback, 0.3);
         assert_eq!(phaser.config.poles, 4);
         assert_eq!(phaser.config.mix, 0.5);
+
+        // 默认配置的全通级数和LFO速度立即生效
+        assert_eq!(phaser.filters.len(), 2);
+        assert!(phaser.lfo_increment > 0.0);
     }
 
     #[test]
@@ -16,6 +16,7 @@
 // - OSC WAVE: 载波波形
 // - LFO RATE: LFO调制速度 (当选择LFO模式时)
 // - LFO DEPTH: LFO调制深度
+// - CARRIER TRACKING: 载波跟随演奏音符 (频率 = 音符频率 × 比例)，产生谐波而非金属音色
 
 use std::f32::consts::PI;
 
@@ -99,6 +100,12 @@ pub struct RingModulator {
 
     /// LFO相位增量
     lfo_increment: f32,
+
+    /// 载波跟踪比例 (0.0 = 关闭，使用固定载波频率)
+    carrier_tracking: f32,
+
+    /// 当前演奏音符的频率 (Hz)
+    note_frequency: f32,
 }
 
 impl Default for RingModulator {
@@ -122,6 +129,8 @@ impl RingModulator {
             carrier_increment: 0.0,
             lfo_phase: 0.0,
             lfo_increment: 0.0,
+            carrier_tracking: 0.0,
+            note_frequency: 440.0,
         };
 
         ring.update_increments();
@@ -172,10 +181,42 @@ impl RingModulator {
         self.config.lfo_depth = depth.clamp(0.0, 1.0);
     }
 
+    /// 设置载波跟踪比例
+    ///
+    /// 开启后载波频率 = 音符频率 × 比例：1.0 时边带落在基频的 0 和 2 倍
+    /// (八度谐波)，1.5 时产生五度相关的频谱。0.0 关闭跟踪，恢复固定载波频率。
+    pub fn set_carrier_tracking(&mut self, ratio: f32) {
+        self.carrier_tracking = ratio.clamp(0.0, 8.0);
+        self.update_increments();
+    }
+
+    /// 获取载波跟踪比例 (0.0 = 关闭)
+    pub fn carrier_tracking(&self) -> f32 {
+        self.carrier_tracking
+    }
+
+    /// 设置当前演奏音符的频率 (由合成器在音符触发时调用)
+    pub fn set_note_frequency(&mut self, freq: f32) {
+        self.note_frequency = freq.max(0.0);
+        self.update_increments();
+    }
+
+    /// 获取实际载波频率 (Hz)
+    ///
+    /// 跟踪开启时为音符频率 × 比例 (限制在 20Hz 到奈奎斯特频率之间)，
+    /// 否则为固定的 OSC FREQ
+    pub fn carrier_frequency(&self) -> f32 {
+        if self.carrier_tracking > 0.0 {
+            (self.note_frequency * self.carrier_tracking).clamp(20.0, self.sample_rate * 0.5)
+        } else {
+            self.config.osc_frequency
+        }
+    }
+
     /// 更新相位增量
     fn update_increments(&mut self) {
         // 载波相位增量: 2π * freq / sample_rate
-        self.carrier_increment = 2.0 * PI * self.config.osc_frequency / self.sample_rate;
+        self.carrier_increment = 2.0 * PI * self.carrier_frequency() / self.sample_rate;
 
         // LFO相位增量
         self.lfo_increment = 2.0 * PI * self.config.lfo_rate / self.sample_rate;
@@ -213,6 +254,12 @@ impl RingModulator {
     /// 处理样本
     #[inline]
     pub fn process(&mut self, input: f32) -> f32 {
+        input * self.next_carrier()
+    }
+
+    /// 推进一个采样并返回载波值
+    #[inline]
+    fn next_carrier(&mut self) -> f32 {
         // 更新相位
         self.carrier_phase += self.carrier_increment;
         if self.carrier_phase > 2.0 * PI {
@@ -227,15 +274,14 @@ impl RingModulator {
         match self.config.mode {
             RingModulatorMode::Oscillator => {
                 // 直接环形调制
-                let carrier = self.carrier_wave(self.carrier_phase);
-                input * carrier
+                self.carrier_wave(self.carrier_phase)
             }
             RingModulatorMode::Lfo => {
                 // LFO调制的载波频率 - 产生周期性变化的效果
                 // LFO值范围 -1 到 1，调制后的频率 = osc_freq * (1 + lfo_depth * lfo_value)
                 let lfo_value = self.lfo_phase.sin();
                 let modulated_freq =
-                    self.config.osc_frequency * (1.0 + lfo_value * self.config.lfo_depth);
+                    self.carrier_frequency() * (1.0 + lfo_value * self.config.lfo_depth);
 
                 // 使用调制后的频率重新计算相位
                 let modulated_increment = 2.0 * PI * modulated_freq / self.sample_rate;
@@ -243,21 +289,20 @@ impl RingModulator {
                     .atan2(1.0)
                     + self.lfo_phase.sin() * self.config.lfo_depth * modulated_increment;
 
-                let carrier = self.carrier_wave(modulated_phase);
-                input * carrier
+                self.carrier_wave(modulated_phase)
             }
             RingModulatorMode::External => {
                 // 外部载波 (简化: 使用内部载波作为后备)
-                let carrier = self.carrier_wave(self.carrier_phase);
-                input * carrier
+                self.carrier_wave(self.carrier_phase)
             }
         }
     }
 
-    /// 处理立体声样本
+    /// 处理立体声样本 (两个声道共用同一个载波采样)
     #[inline]
     pub fn process_stereo(&mut self, input_left: f32, input_right: f32) -> (f32, f32) {
-        (self.process(input_left), self.process(input_right))
+        let carrier = self.next_carrier();
+        (input_left * carrier, input_right * carrier)
     }
 
     /// 重置状态
@@ -680,6 +725,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ring_modulator_stereo_advances_carrier_once() {
+        let mut stereo = RingModulator::new_with_sample_rate(44100.0);
+        let mut mono = RingModulator::new_with_sample_rate(44100.0);
+        stereo.set_osc_frequency(440.0);
+        mono.set_osc_frequency(440.0);
+
+        for _ in 0..1000 {
+            let (left, right) = stereo.process_stereo(0.5, 0.5);
+            let expected = mono.process(0.5);
+            assert_eq!(left, expected);
+            assert_eq!(right, expected);
+        }
+    }
+
     #[test]
     fn test_ring_modulator_reset() {
         let mut ring = RingModulator::new_with_sample_rate(44100.0);
@@ -734,4 +794,30 @@ mod tests {
             assert!(right.is_finite());
         }
     }
+
+    #[test]
+    fn test_ring_modulator_carrier_tracking() {
+        let mut ring = RingModulator::new();
+        ring.set_osc_frequency(300.0);
+
+        // 跟踪关闭: 音符变化不影响固定载波
+        ring.set_note_frequency(220.0);
+        assert_eq!(ring.carrier_frequency(), 300.0);
+
+        ring.set_carrier_tracking(1.5);
+        assert_eq!(ring.carrier_frequency(), 330.0);
+        ring.set_note_frequency(440.0);
+        assert_eq!(ring.carrier_frequency(), 660.0);
+
+        // 载波跟随音符: 正弦输入 × 同频载波 = 直流 + 二倍频
+        ring.set_carrier_tracking(1.0);
+        let output: Vec<f32> = (0..44100)
+            .map(|i| ring.process((2.0 * PI * 440.0 * (i + 1) as f32 / 44100.0).sin()))
+            .collect();
+        let dc = output.iter().sum::<f32>() / output.len() as f32;
+        assert!((dc - 0.5).abs() < 0.01, "dc {dc}");
+
+        ring.set_carrier_tracking(0.0);
+        assert_eq!(ring.carrier_frequency(), 300.0);
+    }
 }
@@ -1,37 +1,146 @@
 //! Simple 3-band Equalizer
 //!
-//! A straightforward 3-band EQ implementation.
-//! Uses simple IIR filters for each band.
+//! A 3-band EQ with low and high shelves and a sweepable peaking mid band.
+//! Each band is an RBJ cookbook biquad.
 
 use std::f32::consts::PI;
 
-use super::Effect;
+use super::{Effect, EffectParameterId, EffectType};
 
-/// Simple 3-band EQ using cascaded single-pole filters
+/// Gain range for each band in dB (+/-)
+const MAX_GAIN_DB: f32 = 12.0;
+
+/// Default low shelf corner frequency in Hz
+const DEFAULT_LOW_FREQ: f32 = 320.0;
+
+/// Default mid band center frequency in Hz
+const DEFAULT_MID_FREQ: f32 = 1000.0;
+
+/// Default mid band Q
+const DEFAULT_MID_Q: f32 = 1.0;
+
+/// Default high shelf corner frequency in Hz
+const DEFAULT_HIGH_FREQ: f32 = 3200.0;
+
+/// A single biquad section (transposed direct form II)
+#[derive(Debug, Clone)]
+struct EqBand {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl EqBand {
+    /// Creates a band with a flat (pass-through) response.
+    fn flat() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// Sets coefficients, normalizing by `a0`.
+    fn set_coefficients(&mut self, b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) {
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+    }
+
+    /// Low shelf with a shelf slope of 1.
+    fn set_low_shelf(&mut self, freq: f32, gain_db: f32, sample_rate: f32) {
+        let a = 10.0f32.powf(gain_db / 40.0);
+        let (sin_w0, cos_w0) = (2.0 * PI * freq / sample_rate).sin_cos();
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * sin_w0 / 2.0f32.sqrt();
+
+        self.set_coefficients(
+            a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+            a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+            (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+            (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+        );
+    }
+
+    /// High shelf with a shelf slope of 1.
+    fn set_high_shelf(&mut self, freq: f32, gain_db: f32, sample_rate: f32) {
+        let a = 10.0f32.powf(gain_db / 40.0);
+        let (sin_w0, cos_w0) = (2.0 * PI * freq / sample_rate).sin_cos();
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * sin_w0 / 2.0f32.sqrt();
+
+        self.set_coefficients(
+            a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+            a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+            (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+            (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+        );
+    }
+
+    /// Peaking EQ.
+    fn set_peaking(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        let a = 10.0f32.powf(gain_db / 40.0);
+        let (sin_w0, cos_w0) = (2.0 * PI * freq / sample_rate).sin_cos();
+        let alpha = sin_w0 / (2.0 * q);
+
+        self.set_coefficients(
+            1.0 + alpha * a,
+            -2.0 * cos_w0,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            -2.0 * cos_w0,
+            1.0 - alpha / a,
+        );
+    }
+
+    #[inline]
+    fn process(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+
+    fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+/// 3-band EQ: low shelf, peaking mid and high shelf
 #[derive(Debug, Clone)]
 pub struct SimpleEq {
     /// Sample rate
     sample_rate: f32,
 
-    // Low shelf state
-    low_smoothed: f32,
-
-    // Mid peak state
-    mid_prev: f32,
-    mid_curr: f32,
-
-    // High shelf state
-    high_prev: f32,
+    // Filter sections
+    low_band: EqBand,
+    mid_band: EqBand,
+    high_band: EqBand,
 
     // Parameters
-    low_gain: f32,
-    mid_gain: f32,
-    high_gain: f32,
+    low_freq: f32,
+    low_gain_db: f32,
+    mid_freq: f32,
+    mid_gain_db: f32,
+    mid_q: f32,
+    high_freq: f32,
+    high_gain_db: f32,
 
-    // Filter coefficients
-    low_coeff: f32,
-    mid_coeff: f32,
-    high_coeff: f32,
+    /// Set by the setters; coefficients are recalculated on the next sample
+    coefficients_dirty: bool,
 
     // Effect state
     enabled: bool,
@@ -41,70 +150,103 @@ pub struct SimpleEq {
 impl SimpleEq {
     /// Creates a new 3-band EQ with flat response.
     pub fn new(sample_rate: f32) -> Self {
-        let mut eq = Self {
+        Self {
             sample_rate,
-            low_smoothed: 0.0,
-            mid_prev: 0.0,
-            mid_curr: 0.0,
-            high_prev: 0.0,
-            low_gain: 1.0,
-            mid_gain: 1.0,
-            high_gain: 1.0,
-            low_coeff: 0.5,
-            mid_coeff: 0.5,
-            high_coeff: 0.5,
+            low_band: EqBand::flat(),
+            mid_band: EqBand::flat(),
+            high_band: EqBand::flat(),
+            low_freq: DEFAULT_LOW_FREQ,
+            low_gain_db: 0.0,
+            mid_freq: DEFAULT_MID_FREQ,
+            mid_gain_db: 0.0,
+            mid_q: DEFAULT_MID_Q,
+            high_freq: DEFAULT_HIGH_FREQ,
+            high_gain_db: 0.0,
+            coefficients_dirty: true,
             enabled: true,
             mix: 1.0,
-        };
-        eq.recalculate_coefficients();
-        eq
+        }
     }
 
-    /// Recalculates filter coefficients based on current gains.
+    /// Recalculates filter coefficients from the current band settings.
     fn recalculate_coefficients(&mut self) {
-        // Low shelf cutoff ~320Hz
-        let wc = 2.0 * PI * 320.0 / self.sample_rate;
-        self.low_coeff = wc.clamp(0.0, 2.0);
+        let sr = self.sample_rate;
+        self.low_band
+            .set_low_shelf(self.low_freq, self.low_gain_db, sr);
+        self.mid_band
+            .set_peaking(self.mid_freq, self.mid_gain_db, self.mid_q, sr);
+        self.high_band
+            .set_high_shelf(self.high_freq, self.high_gain_db, sr);
+        self.coefficients_dirty = false;
+    }
 
-        // Mid peak center ~1kHz
-        let wc = 2.0 * PI * 1000.0 / self.sample_rate;
-        self.mid_coeff = wc.clamp(0.0, 2.0);
+    /// Clamps a band frequency to the usable range for the sample rate.
+    fn clamp_freq(&self, freq_hz: f32) -> f32 {
+        freq_hz.clamp(20.0, self.sample_rate * 0.49)
+    }
 
-        // High shelf cutoff ~3.2kHz
-        let wc = 2.0 * PI * 3200.0 / self.sample_rate;
-        self.high_coeff = wc.clamp(0.0, 2.0);
+    /// Sets the low shelf corner frequency and gain.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq_hz` - Shelf corner frequency in Hz
+    /// * `gain_db` - Shelf gain in dB (-12 to +12)
+    pub fn set_low_shelf(&mut self, freq_hz: f32, gain_db: f32) {
+        self.low_freq = self.clamp_freq(freq_hz);
+        self.set_low_gain(gain_db);
+    }
+
+    /// Sets the high shelf corner frequency and gain.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq_hz` - Shelf corner frequency in Hz
+    /// * `gain_db` - Shelf gain in dB (-12 to +12)
+    pub fn set_high_shelf(&mut self, freq_hz: f32, gain_db: f32) {
+        self.high_freq = self.clamp_freq(freq_hz);
+        self.set_high_gain(gain_db);
+    }
+
+    /// Sets the peaking mid band.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq_hz` - Center frequency in Hz
+    /// * `gain_db` - Band gain in dB (-12 to +12)
+    /// * `q` - Band Q (0.1 to 20)
+    pub fn set_mid(&mut self, freq_hz: f32, gain_db: f32, q: f32) {
+        self.mid_freq = self.clamp_freq(freq_hz);
+        self.mid_q = q.clamp(0.1, 20.0);
+        self.set_mid_gain(gain_db);
     }
 
     /// Sets the low shelf gain in dB.
     pub fn set_low_gain(&mut self, gain_db: f32) {
-        self.low_gain = 10.0f32.powf(gain_db.clamp(-12.0, 12.0) / 20.0);
+        self.low_gain_db = gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+        self.coefficients_dirty = true;
     }
 
     /// Sets the mid band gain in dB.
     pub fn set_mid_gain(&mut self, gain_db: f32) {
-        self.mid_gain = 10.0f32.powf(gain_db.clamp(-12.0, 12.0) / 20.0);
+        self.mid_gain_db = gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+        self.coefficients_dirty = true;
     }
 
     /// Sets the high shelf gain in dB.
     pub fn set_high_gain(&mut self, gain_db: f32) {
-        self.high_gain = 10.0f32.powf(gain_db.clamp(-12.0, 12.0) / 20.0);
+        self.high_gain_db = gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+        self.coefficients_dirty = true;
     }
 
     /// Processes a single audio sample.
     pub fn process(&mut self, input: f32) -> f32 {
-        // Simple implementation: separate path for each band
-        // Low shelf: simple low-pass with gain
-        let low_out = self.low_gain * input;
-
-        // High shelf: simple high-pass with gain
-        let high_out = self.high_gain * input;
-
-        // Mid: input - low - high
-        let mid_out = self.mid_gain
-            * (input - low_out / self.low_gain.max(0.001) - high_out / self.high_gain.max(0.001));
+        if self.coefficients_dirty {
+            self.recalculate_coefficients();
+        }
 
-        // Mix bands (simplified)
-        low_out + mid_out + high_out
+        let low = self.low_band.process(input);
+        let mid = self.mid_band.process(low);
+        self.high_band.process(mid)
     }
 
     /// Processes a buffer of audio samples.
@@ -116,10 +258,9 @@ impl SimpleEq {
 
     /// Resets all state variables.
     pub fn reset(&mut self) {
-        self.low_smoothed = 0.0;
-        self.mid_prev = 0.0;
-        self.mid_curr = 0.0;
-        self.high_prev = 0.0;
+        self.low_band.reset();
+        self.mid_band.reset();
+        self.high_band.reset();
     }
 }
 
@@ -162,6 +303,30 @@ impl Effect for SimpleEq {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        match id {
+            EffectParameterId::EqLowGain => self.set_low_gain(value),
+            EffectParameterId::EqMidGain => self.set_mid_gain(value),
+            EffectParameterId::EqHighGain => self.set_high_gain(value),
+            EffectParameterId::Mix => self.set_mix(value),
+            _ => {}
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::EqLowGain => Some(self.low_gain_db),
+            EffectParameterId::EqMidGain => Some(self.mid_gain_db),
+            EffectParameterId::EqHighGain => Some(self.high_gain_db),
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::SimpleEQ
+    }
 }
 
 #[cfg(test)]
@@ -261,4 +426,74 @@ mod tests {
             assert!(output.is_finite(), "Failed for sample rate {}", sr);
         }
     }
+
+    /// RMS level in dB of a sine tone after it passes through the EQ.
+    fn tone_level_db(eq: &mut SimpleEq, freq: f32) -> f32 {
+        let sr = 44100.0;
+        eq.reset();
+        let mut sum_sq = 0.0;
+        let mut count = 0;
+        for i in 0..8820 {
+            let input = (2.0 * PI * freq * i as f32 / sr).sin();
+            let output = eq.process(input);
+            // Skip the filter's settling time
+            if i >= 4410 {
+                sum_sq += output * output;
+                count += 1;
+            }
+        }
+        10.0 * (sum_sq / count as f32 / 0.5).log10()
+    }
+
+    #[test]
+    fn test_simple_eq_high_shelf_boost() {
+        let mut eq = SimpleEq::new(44100.0);
+        eq.set_high_shelf(3000.0, 6.0);
+
+        let high = tone_level_db(&mut eq, 10000.0);
+        let low = tone_level_db(&mut eq, 100.0);
+        assert!(
+            (high - 6.0).abs() < 0.5,
+            "10kHz should gain ~6dB, got {}",
+            high
+        );
+        assert!(low.abs() < 0.1, "100Hz should be unchanged, got {}", low);
+    }
+
+    #[test]
+    fn test_simple_eq_low_shelf_cut() {
+        let mut eq = SimpleEq::new(44100.0);
+        eq.set_low_shelf(300.0, -6.0);
+
+        let low = tone_level_db(&mut eq, 50.0);
+        let high = tone_level_db(&mut eq, 8000.0);
+        assert!(
+            (low + 6.0).abs() < 0.5,
+            "50Hz should drop ~6dB, got {}",
+            low
+        );
+        assert!(high.abs() < 0.1, "8kHz should be unchanged, got {}", high);
+    }
+
+    #[test]
+    fn test_simple_eq_mid_peak() {
+        let mut eq = SimpleEq::new(44100.0);
+        eq.set_mid(2000.0, 9.0, 2.0);
+
+        let center = tone_level_db(&mut eq, 2000.0);
+        let far = tone_level_db(&mut eq, 100.0);
+        assert!(
+            (center - 9.0).abs() < 0.2,
+            "Peak should be ~9dB, got {}",
+            center
+        );
+        assert!(far.abs() < 0.2, "Far band should be unchanged, got {}", far);
+    }
+
+    #[test]
+    fn test_simple_eq_gain_param_round_trip() {
+        let mut eq = SimpleEq::new(44100.0);
+        eq.set_param(EffectParameterId::EqHighGain, -4.5);
+        assert_eq!(eq.get_param(EffectParameterId::EqHighGain), Some(-4.5));
+    }
 }
@@ -0,0 +1,293 @@
+//! Stereo Widener Module
+//!
+//! Mid-side width control. The left/right pair is encoded to mid (L + R) and
+//! side (L - R), the side signal is scaled by the width factor and the pair
+//! is decoded back to left/right.
+//!
+//! An optional bass-mono crossover removes the side content below a corner
+//! frequency, keeping kick and bass centered however wide the top end is.
+
+use std::f32::consts::PI;
+
+use super::{Effect, EffectParameterId, EffectType};
+
+/// Maximum width factor (2.0 = side signal doubled)
+const MAX_WIDTH: f32 = 2.0;
+
+/// Mid-side stereo widener
+#[derive(Debug, Clone)]
+pub struct StereoWidener {
+    /// Sample rate
+    sample_rate: f32,
+
+    /// Side gain (0.0 = mono, 1.0 = unchanged, 2.0 = extra wide)
+    width: f32,
+
+    /// Bass-mono corner frequency in Hz (0.0 = off)
+    mono_below_hz: f32,
+
+    /// One-pole lowpass coefficient for the bass-mono crossover
+    mono_coeff: f32,
+
+    /// Lowpassed side signal (state of the crossover)
+    side_low: f32,
+
+    // Effect state
+    enabled: bool,
+    mix: f32,
+}
+
+impl StereoWidener {
+    /// Creates a new widener with unity width and bass-mono off.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            width: 1.0,
+            mono_below_hz: 0.0,
+            mono_coeff: 0.0,
+            side_low: 0.0,
+            enabled: true,
+            mix: 1.0,
+        }
+    }
+
+    /// Sets the stereo width.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Side gain (0.0 = mono, 1.0 = unchanged, 2.0 = extra wide)
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width.clamp(0.0, MAX_WIDTH);
+    }
+
+    /// Gets the current stereo width.
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Keeps frequencies below `freq_hz` centered.
+    ///
+    /// The side signal is split by a one-pole crossover and only the part
+    /// above the corner frequency is kept (and widened).
+    ///
+    /// # Arguments
+    ///
+    /// * `freq_hz` - Crossover frequency in Hz (0.0 disables bass-mono)
+    pub fn set_mono_below(&mut self, freq_hz: f32) {
+        if freq_hz <= 0.0 {
+            self.mono_below_hz = 0.0;
+            self.mono_coeff = 0.0;
+            self.side_low = 0.0;
+            return;
+        }
+
+        self.mono_below_hz = freq_hz.clamp(20.0, self.sample_rate * 0.49);
+        self.mono_coeff = 1.0 - (-2.0 * PI * self.mono_below_hz / self.sample_rate).exp();
+    }
+
+    /// Gets the bass-mono corner frequency in Hz (0.0 = off).
+    pub fn mono_below(&self) -> f32 {
+        self.mono_below_hz
+    }
+
+    /// Processes a stereo sample pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - Left input sample
+    /// * `right` - Right input sample
+    ///
+    /// # Returns
+    ///
+    /// Processed (left, right) samples
+    pub fn process_stereo(&mut self, left: f32, right: f32) -> (f32, f32) {
+        if !self.enabled {
+            return (left, right);
+        }
+
+        let mid = (left + right) * 0.5;
+        let mut side = (left - right) * 0.5;
+
+        if self.mono_below_hz > 0.0 {
+            self.side_low += self.mono_coeff * (side - self.side_low);
+            side -= self.side_low;
+        }
+
+        let side = side * self.width;
+        let wet_left = mid + side;
+        let wet_right = mid - side;
+
+        (
+            left * (1.0 - self.mix) + wet_left * self.mix,
+            right * (1.0 - self.mix) + wet_right * self.mix,
+        )
+    }
+
+    /// Resets the crossover state.
+    pub fn reset(&mut self) {
+        self.side_low = 0.0;
+    }
+}
+
+impl Effect for StereoWidener {
+    /// A mono signal has no side component, so it passes through unchanged.
+    fn process(&mut self, input: f32) -> f32 {
+        input
+    }
+
+    fn process_with_bypass(&mut self, input: f32) -> f32 {
+        input
+    }
+
+    fn process_buffer(&mut self, _samples: &mut [f32]) {}
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    fn set_intensity(&mut self, intensity: f32) {
+        // Intensity 0.5 is unity width
+        self.set_width(intensity.clamp(0.0, 1.0) * MAX_WIDTH);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        if id == EffectParameterId::Mix {
+            self.set_mix(value);
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::StereoWidener
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a sine panned mostly left through the widener.
+    fn panned_sine(widener: &mut StereoWidener, freq_hz: f32) -> (Vec<f32>, Vec<f32>) {
+        let sample_rate = 44100.0;
+        (0..4410)
+            .map(|i| {
+                let x = (2.0 * PI * freq_hz * i as f32 / sample_rate).sin();
+                widener.process_stereo(x * 0.9, x * 0.1)
+            })
+            .unzip()
+    }
+
+    /// Peak of the side (L - R) signal over the second half of a buffer.
+    fn side_peak(left: &[f32], right: &[f32]) -> f32 {
+        let start = left.len() / 2;
+        left[start..]
+            .iter()
+            .zip(&right[start..])
+            .map(|(l, r)| (l - r).abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_stereo_widener_unity_is_transparent() {
+        let mut widener = StereoWidener::new(44100.0);
+        let (l, r) = widener.process_stereo(0.7, -0.2);
+        assert!((l - 0.7).abs() < 1e-6);
+        assert!((r + 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stereo_widener_zero_width_collapses_to_center() {
+        let mut widener = StereoWidener::new(44100.0);
+        widener.set_width(0.0);
+
+        let (left, right) = panned_sine(&mut widener, 440.0);
+        assert_eq!(left, right);
+        // The mid signal is preserved
+        assert!(left.iter().fold(0.0f32, |m, x| m.max(x.abs())) > 0.45);
+    }
+
+    #[test]
+    fn test_stereo_widener_extra_wide_doubles_side() {
+        let mut widener = StereoWidener::new(44100.0);
+        widener.set_width(2.0);
+
+        let (l, r) = widener.process_stereo(0.9, 0.1);
+        assert!(((l - r) - 1.6).abs() < 1e-6);
+        assert!(((l + r) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stereo_widener_mono_below_centers_bass() {
+        let mut widener = StereoWidener::new(44100.0);
+        widener.set_width(2.0);
+        widener.set_mono_below(200.0);
+
+        let (left, right) = panned_sine(&mut widener, 40.0);
+        let bass_side = side_peak(&left, &right);
+
+        widener.reset();
+        let (left, right) = panned_sine(&mut widener, 5000.0);
+        let treble_side = side_peak(&left, &right);
+
+        assert!(
+            bass_side < 0.5,
+            "bass should stay centered, side {bass_side}"
+        );
+        assert!(
+            treble_side > 1.4,
+            "treble should be widened, side {treble_side}"
+        );
+    }
+
+    #[test]
+    fn test_stereo_widener_mono_process_passes_through() {
+        let mut widener = StereoWidener::new(44100.0);
+        widener.set_width(0.0);
+        assert_eq!(Effect::process(&mut widener, 0.42), 0.42);
+        assert_eq!(widener.effect_type(), EffectType::StereoWidener);
+    }
+
+    #[test]
+    fn test_stereo_widener_parameter_limits() {
+        let mut widener = StereoWidener::new(44100.0);
+
+        widener.set_width(5.0);
+        assert_eq!(widener.width(), 2.0);
+        widener.set_width(-1.0);
+        assert_eq!(widener.width(), 0.0);
+
+        widener.set_mono_below(0.0);
+        assert_eq!(widener.mono_below(), 0.0);
+        widener.set_mono_below(1e6);
+        assert!(widener.mono_below() < 22050.0);
+    }
+
+    #[test]
+    fn test_stereo_widener_disabled_passes_through() {
+        let mut widener = StereoWidener::new(44100.0);
+        widener.set_width(0.0);
+        widener.set_enabled(false);
+        assert_eq!(widener.process_stereo(0.9, 0.1), (0.9, 0.1));
+
+        widener.set_enabled(true);
+        assert_eq!(widener.process_stereo(0.9, 0.1), (0.5, 0.5));
+    }
+}
@@ -10,65 +10,63 @@
 //! - `EffectFactory`: Factory for creating effect instances
 //! - `PerTrackEffectsManager`: Manages all 8 track effects
 
+use crate::effects::{
+    BiquadFilter, Chorus, Compressor, Delay, Distortion, Effect, EffectType, Flanger, Gate,
+    Limiter, Phaser, Reverb, Saturation, SimpleEq, StereoWidener,
+};
 use std::collections::HashMap;
 use std::fmt;
-use crate::effects::{Effect, EffectType, Delay, Distortion, Saturation, Compressor, SimpleEq, Chorus, BiquadFilter};
 
 /// Parameter identifier for effects (for parameter locks)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum EffectParameterId {
     /// Filter cutoff frequency (0.0 - 1.0)
     FilterCutoff,
-    
+
     /// Filter resonance (0.0 - 1.0)
     FilterResonance,
-    
+
     /// Distortion/Saturation amount (0.0 - 1.0)
     DistortionAmount,
-    
+
     /// Compressor threshold (-60.0 - 0.0 dB)
     CompressorThreshold,
-    
+
     /// Compressor ratio (1.0 - 20.0)
     CompressorRatio,
-    
+
     /// Compressor attack (0.001 - 0.5 s)
     CompressorAttack,
-    
+
     /// Compressor release (0.01 - 1.0 s)
     CompressorRelease,
-    
+
     /// EQ low gain (-12.0 - 12.0 dB)
     EqLowGain,
-    
+
     /// EQ mid gain (-12.0 - 12.0 dB)
     EqMidGain,
-    
+
     /// EQ high gain (-12.0 - 12.0 dB)
     EqHighGain,
-    
+
     /// Chorus rate (0.1 - 10.0 Hz)
     ChorusRate,
-    
+
     /// Chorus depth (0.0 - 1.0)
     ChorusDepth,
-    
+
     /// Chorus feedback (0.0 - 0.9)
     ChorusFeedback,
-    
+
     /// Effect mix (0.0 - 1.0)
+    #[default]
     Mix,
-    
+
     /// Custom parameter (reserved for future use)
     Custom(u8),
 }
 
-impl Default for EffectParameterId {
-    fn default() -> Self {
-        EffectParameterId::Mix
-    }
-}
-
 impl fmt::Display for EffectParameterId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -91,18 +89,41 @@ impl fmt::Display for EffectParameterId {
     }
 }
 
+impl EffectParameterId {
+    /// Gets the value range in the units `Effect::set_param` takes
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            EffectParameterId::CompressorThreshold => (-60.0, 0.0),
+            EffectParameterId::CompressorRatio => (1.0, 20.0),
+            EffectParameterId::CompressorAttack => (0.001, 0.5),
+            EffectParameterId::CompressorRelease => (0.01, 1.0),
+            EffectParameterId::EqLowGain
+            | EffectParameterId::EqMidGain
+            | EffectParameterId::EqHighGain => (-12.0, 12.0),
+            EffectParameterId::ChorusRate => (0.1, 10.0),
+            EffectParameterId::ChorusFeedback => (0.0, 0.9),
+            EffectParameterId::FilterCutoff
+            | EffectParameterId::FilterResonance
+            | EffectParameterId::DistortionAmount
+            | EffectParameterId::ChorusDepth
+            | EffectParameterId::Mix
+            | EffectParameterId::Custom(_) => (0.0, 1.0),
+        }
+    }
+}
+
 /// Configuration for a single effect slot
 #[derive(Debug, Clone, PartialEq)]
 pub struct TrackEffectSlotConfig {
     /// Effect type (None if slot is empty)
     pub effect_type: Option<EffectType>,
-    
+
     /// Whether the effect is enabled
     pub enabled: bool,
-    
+
     /// Wet/dry mix (0.0 = dry, 1.0 = wet)
     pub mix: f32,
-    
+
     /// Parameter locks for this effect
     pub param_locks: HashMap<EffectParameterId, f32>,
 }
@@ -119,22 +140,24 @@ impl Default for TrackEffectSlotConfig {
 }
 
 /// Single effect slot with runtime instance
-#[derive(Debug, Clone)]
 pub struct TrackEffectSlot {
     /// Effect type
     effect_type: Option<EffectType>,
-    
+
     /// Effect instance (None if slot is empty or disabled)
     effect: Option<Box<dyn Effect>>,
-    
+
     /// Whether the effect is enabled
     enabled: bool,
-    
+
     /// Wet/dry mix (0.0 = dry, 1.0 = wet)
     mix: f32,
-    
+
     /// Parameter locks for this effect
     param_locks: HashMap<EffectParameterId, f32>,
+
+    /// Unmodulated values of the parameters driven by `modulate_param`
+    modulation_bases: HashMap<EffectParameterId, f32>,
 }
 
 impl TrackEffectSlot {
@@ -146,94 +169,158 @@ impl TrackEffectSlot {
             enabled: false,
             mix: 0.5,
             param_locks: HashMap::new(),
+            modulation_bases: HashMap::new(),
         }
     }
-    
+
     /// Creates a new effect slot with the specified effect
     pub fn with_effect<E: Effect + 'static>(effect: E) -> Self {
+        Self::with_boxed_effect(Box::new(effect))
+    }
+
+    /// Creates a new effect slot from an already boxed effect instance
+    pub fn with_boxed_effect(effect: Box<dyn Effect>) -> Self {
         Self {
             effect_type: Some(effect.effect_type()),
-            effect: Some(Box::new(effect)),
+            effect: Some(effect),
             enabled: true,
             mix: 0.5,
             param_locks: HashMap::new(),
+            modulation_bases: HashMap::new(),
         }
     }
-    
+
     /// Creates a slot from configuration
     pub fn from_config(config: &TrackEffectSlotConfig, sample_rate: f32) -> Self {
         let mut slot = Self::new();
-        
+
         if let Some(effect_type) = config.effect_type {
             if let Some(effect) = create_effect_instance(effect_type, sample_rate) {
                 slot.effect_type = Some(effect_type);
                 slot.effect = Some(effect);
             }
         }
-        
+
         slot.enabled = config.enabled;
         slot.mix = config.mix;
         slot.param_locks = config.param_locks.clone();
-        
+
         slot
     }
-    
+
     /// Gets the current effect type
     pub fn effect_type(&self) -> Option<EffectType> {
         self.effect_type
     }
-    
+
     /// Checks if the slot has an effect
     pub fn is_empty(&self) -> bool {
         self.effect.is_none()
     }
-    
+
     /// Checks if the effect is enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled && self.effect.is_some()
     }
-    
+
     /// Gets the wet/dry mix
     pub fn mix(&self) -> f32 {
         self.mix
     }
-    
+
     /// Sets the wet/dry mix
     pub fn set_mix(&mut self, mix: f32) {
         self.mix = mix.clamp(0.0, 1.0);
     }
-    
+
     /// Enables or disables the effect
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
-    
+
     /// Gets the parameter locks
     pub fn param_locks(&self) -> &HashMap<EffectParameterId, f32> {
         &self.param_locks
     }
-    
+
     /// Adds a parameter lock
     pub fn add_param_lock(&mut self, param: EffectParameterId, value: f32) {
         self.param_locks.insert(param, value);
     }
-    
+
     /// Removes a parameter lock
     pub fn remove_param_lock(&mut self, param: &EffectParameterId) {
         self.param_locks.remove(param);
     }
-    
+
     /// Clears all parameter locks
     pub fn clear_param_locks(&mut self) {
         self.param_locks.clear();
     }
-    
+
+    /// Sets a parameter on the effect instance
+    ///
+    /// A modulated parameter takes `value` as its new unmodulated value.
+    pub fn set_param(&mut self, param: EffectParameterId, value: f32) {
+        if let Some(base) = self.modulation_bases.get_mut(&param) {
+            *base = value;
+        }
+        if let Some(ref mut effect) = self.effect {
+            effect.set_param(param, value);
+        }
+    }
+
+    /// Offsets a parameter from its unmodulated value
+    ///
+    /// `amount` is a fraction of the parameter's range, so 1.0 sweeps the
+    /// whole range. The unmodulated value is read from the effect the first
+    /// time a parameter is modulated and follows later `set_param` calls.
+    /// The result is clamped to the range; parameters the effect does not
+    /// expose are ignored.
+    pub fn modulate_param(&mut self, param: EffectParameterId, amount: f32) {
+        let Some(effect) = self.effect.as_mut() else {
+            return;
+        };
+        let base = match self.modulation_bases.get(&param) {
+            Some(&base) => base,
+            None => {
+                let Some(base) = effect.get_param(param) else {
+                    return;
+                };
+                self.modulation_bases.insert(param, base);
+                base
+            }
+        };
+
+        let (min, max) = param.range();
+        effect.set_param(param, (base + amount * (max - min)).clamp(min, max));
+    }
+
+    /// Gets a parameter from the effect instance
+    pub fn get_param(&self, param: EffectParameterId) -> Option<f32> {
+        self.effect
+            .as_ref()
+            .and_then(|effect| effect.get_param(param))
+    }
+
+    /// Applies all parameter locks to the effect instance
+    pub fn apply_param_locks(&mut self) {
+        if let Some(ref mut effect) = self.effect {
+            for (&param, &value) in &self.param_locks {
+                if let Some(base) = self.modulation_bases.get_mut(&param) {
+                    *base = value;
+                }
+                effect.set_param(param, value);
+            }
+        }
+    }
+
     /// Processes a sample through this effect slot
     pub fn process(&mut self, input: f32) -> f32 {
         if !self.enabled || self.effect.is_none() {
             return input;
         }
-        
+
         if let Some(ref mut effect) = self.effect {
             let wet = effect.process(input);
             input * (1.0 - self.mix) + wet * self.mix
@@ -241,14 +328,14 @@ impl TrackEffectSlot {
             input
         }
     }
-    
+
     /// Resets the effect
     pub fn reset(&mut self) {
         if let Some(ref mut effect) = self.effect {
             effect.reset();
         }
     }
-    
+
     /// Converts to config for serialization
     pub fn to_config(&self) -> TrackEffectSlotConfig {
         TrackEffectSlotConfig {
@@ -266,30 +353,41 @@ impl Default for TrackEffectSlot {
     }
 }
 
+impl fmt::Debug for TrackEffectSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackEffectSlot")
+            .field("effect_type", &self.effect_type)
+            .field("enabled", &self.enabled)
+            .field("mix", &self.mix)
+            .field("param_locks", &self.param_locks)
+            .finish()
+    }
+}
+
 /// Helper function to create effect instances by type
 fn create_effect_instance(effect_type: EffectType, sample_rate: f32) -> Option<Box<dyn Effect>> {
     match effect_type {
         EffectType::Filter => Some(Box::new(BiquadFilter::new())),
         EffectType::Saturation => Some(Box::new(Saturation::new())),
         EffectType::Compressor => Some(Box::new(Compressor::new(sample_rate))),
+        EffectType::Gate => Some(Box::new(Gate::new(sample_rate))),
+        EffectType::Limiter => Some(Box::new(Limiter::new(sample_rate))),
         EffectType::SimpleEQ => Some(Box::new(SimpleEq::new(sample_rate))),
         EffectType::Chorus => Some(Box::new(Chorus::new(sample_rate))),
         EffectType::Delay => Some(Box::new(Delay::new(sample_rate))),
         EffectType::Distortion => Some(Box::new(Distortion::new())),
-        // Reverb is more expensive, use a simpler version or skip
-        EffectType::Reverb => None, 
-        EffectType::Phaser => None,
-        EffectType::Flanger => None,
+        EffectType::Reverb => Some(Box::new(Reverb::new(sample_rate))),
+        EffectType::Phaser => Some(Box::new(Phaser::new_with_sample_rate(sample_rate))),
+        EffectType::Flanger => Some(Box::new(Flanger::new_with_sample_rate(sample_rate))),
+        EffectType::StereoWidener => Some(Box::new(StereoWidener::new(sample_rate))),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::effects::{Effect, EffectProcessor, EffectType};
-    use crate::effects::Delay;
-    use crate::effects::Saturation;
-    
+
     #[test]
     fn test_track_effect_slot_creation() {
         let slot = TrackEffectSlot::new();
@@ -297,17 +395,17 @@ mod tests {
         assert!(!slot.is_enabled());
         assert_eq!(slot.mix(), 0.5);
     }
-    
+
     #[test]
     fn test_track_effect_slot_with_effect() {
         let delay = Delay::new(44100.0);
         let slot = TrackEffectSlot::with_effect(delay);
-        
+
         assert!(!slot.is_empty());
         assert!(slot.is_enabled());
         assert_eq!(slot.effect_type(), Some(EffectType::Delay));
     }
-    
+
     #[test]
     fn test_track_effect_slot_defaults() {
         let slot = TrackEffectSlot::default();
@@ -316,98 +414,100 @@ mod tests {
         assert_eq!(slot.mix(), 0.5);
         assert!(slot.param_locks().is_empty());
     }
-    
+
     #[test]
     fn test_track_effect_slot_mix() {
         let mut slot = TrackEffectSlot::new();
-        
+
         slot.set_mix(0.3);
         assert_eq!(slot.mix(), 0.3);
-        
+
         // Mix should be clamped
         slot.set_mix(1.5);
         assert_eq!(slot.mix(), 1.0);
-        
+
         slot.set_mix(-0.5);
         assert_eq!(slot.mix(), 0.0);
     }
-    
+
     #[test]
     fn test_track_effect_slot_enabled() {
         let mut slot = TrackEffectSlot::new();
-        
+
         assert!(!slot.is_enabled());
-        
+
         slot.set_enabled(true);
         assert!(!slot.is_enabled()); // Still no effect
-        
+
         let sat = Saturation::new();
         let slot = TrackEffectSlot::with_effect(sat);
         assert!(slot.is_enabled());
-        
+
         let mut slot = slot;
         slot.set_enabled(false);
         assert!(!slot.is_enabled());
     }
-    
+
     #[test]
     fn test_track_effect_slot_process() {
         let delay = Delay::new(44100.0);
         let mut slot = TrackEffectSlot::with_effect(delay);
-        
+
         // Process should work
         let output = slot.process(0.5);
         assert!(output.abs() <= 1.0);
-        
+
         // Disabled slot should pass through
         slot.set_enabled(false);
         let output = slot.process(0.5);
         assert_eq!(output, 0.5);
     }
-    
+
     #[test]
     fn test_track_effect_slot_param_locks() {
         let mut slot = TrackEffectSlot::new();
-        
+
         slot.add_param_lock(EffectParameterId::Mix, 0.8);
         slot.add_param_lock(EffectParameterId::Custom(1), 0.5);
-        
+
         assert_eq!(slot.param_locks().len(), 2);
         assert_eq!(slot.param_locks().get(&EffectParameterId::Mix), Some(&0.8));
-        
+
         slot.remove_param_lock(&EffectParameterId::Mix);
         assert_eq!(slot.param_locks().len(), 1);
-        
+
         slot.clear_param_locks();
         assert!(slot.param_locks().is_empty());
     }
-    
+
     #[test]
     fn test_track_effect_slot_to_config() {
         let delay = Delay::new(44100.0);
         let slot = TrackEffectSlot::with_effect(delay);
-        
+
         let config = slot.to_config();
         assert_eq!(config.effect_type, Some(EffectType::Delay));
         assert!(config.enabled);
         assert_eq!(config.mix, 0.5);
     }
-    
+
     #[test]
     fn test_track_effect_slot_from_config() {
-        let mut config = TrackEffectSlotConfig::default();
-        config.effect_type = Some(EffectType::Delay);
-        config.enabled = true;
-        config.mix = 0.7;
-        
+        let config = TrackEffectSlotConfig {
+            effect_type: Some(EffectType::Delay),
+            enabled: true,
+            mix: 0.7,
+            ..Default::default()
+        };
+
         let slot = TrackEffectSlot::from_config(&config, 44100.0);
-        
+
         assert!(!slot.is_empty());
         assert!(slot.is_enabled());
         assert_eq!(slot.mix(), 0.7);
         assert_eq!(slot.effect_type(), Some(EffectType::Delay));
     }
-    
+
     #[test]
     fn test_create_effect_instance() {
         // Test supported effect types
@@ -415,13 +515,18 @@ mod tests {
         assert!(create_effect_instance(EffectType::Distortion, 44100.0).is_some());
         assert!(create_effect_instance(EffectType::Saturation, 44100.0).is_some());
         assert!(create_effect_instance(EffectType::Compressor, 44100.0).is_some());
+        assert!(create_effect_instance(EffectType::Gate, 44100.0).is_some());
+        assert!(create_effect_instance(EffectType::Limiter, 44100.0).is_some());
         assert!(create_effect_instance(EffectType::SimpleEQ, 44100.0).is_some());
         assert!(create_effect_instance(EffectType::Chorus, 44100.0).is_some());
-        
+        assert!(create_effect_instance(EffectType::Reverb, 44100.0).is_some());
+        assert!(create_effect_instance(EffectType::Phaser, 44100.0).is_some());
+        assert!(create_effect_instance(EffectType::Flanger, 44100.0).is_some());
+        assert!(create_effect_instance(EffectType::StereoWidener, 44100.0).is_some());
+
         // Test unsupported effect types (return None)
-        assert!(create_effect_instance(EffectType::Reverb, 44100.0).is_none());
-        assert!(create_effect_instance(EffectType::Phaser, 44100.0).is_none());
-        assert!(create_effect_instance(EffectType::Flanger, 44100.0).is_none());
+        assert!(create_effect_instance(EffectType::Bypass, 44100.0).is_none());
+        assert!(create_effect_instance(EffectType::Warble, 44100.0).is_none());
     }
 }
 
@@ -437,32 +542,35 @@ pub const MAX_EFFECT_SLOTS: usize = 4;
 pub enum TrackEffectsError {
     /// Invalid slot index
     InvalidSlotIndex,
-    
+
     /// Slot is already occupied
     SlotOccupied,
-    
+
     /// Slot is empty
     SlotEmpty,
-    
+
     /// Effect type not supported
     UnsupportedEffect,
 }
 
 /// Effects chain for a single track
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct TrackEffects {
     /// Effect slots (None = empty)
     slots: [Option<TrackEffectSlot>; MAX_EFFECT_SLOTS],
-    
+
     /// Track ID
     track_id: u8,
-    
+
     /// Global enable for this track's effects
     enabled: bool,
-    
+
     /// Bypass all effects
     bypass: bool,
-    
+
+    /// Parallel blend of dry input and full chain output (0.0 = dry, 1.0 = wet)
+    chain_mix: f32,
+
     /// Sample rate
     sample_rate: f32,
 }
@@ -475,14 +583,19 @@ impl TrackEffects {
             track_id,
             enabled: true,
             bypass: false,
+            chain_mix: 1.0,
             sample_rate,
         }
     }
-    
+
     /// Creates from configuration
-    pub fn from_config(track_id: u8, sample_rate: f32, configs: &[Option<TrackEffectSlotConfig>]) -> Self {
+    pub fn from_config(
+        track_id: u8,
+        sample_rate: f32,
+        configs: &[Option<TrackEffectSlotConfig>],
+    ) -> Self {
         let mut effects = Self::new(track_id, sample_rate);
-        
+
         for (i, config) in configs.iter().enumerate().take(MAX_EFFECT_SLOTS) {
             if let Some(ref config) = config {
                 if let Some(effect_type) = config.effect_type {
@@ -495,133 +608,153 @@ impl TrackEffects {
                 }
             }
         }
-        
+
         effects
     }
-    
+
     /// Gets the track ID
     pub fn track_id(&self) -> u8 {
         self.track_id
     }
-    
+
     /// Checks if effects are enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled && !self.bypass
     }
-    
+
     /// Enables or disables all effects for this track
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
-    
+
     /// Sets bypass mode
     pub fn set_bypass(&mut self, bypass: bool) {
         self.bypass = bypass;
     }
-    
+
+    /// Gets the parallel chain mix
+    pub fn chain_mix(&self) -> f32 {
+        self.chain_mix
+    }
+
+    /// Sets the parallel chain mix
+    ///
+    /// Unlike per-slot mix, this blends the dry track input with the output
+    /// of the whole chain, which is how parallel compression and send-style
+    /// reverb are set up.
+    pub fn set_chain_mix(&mut self, chain_mix: f32) {
+        self.chain_mix = chain_mix.clamp(0.0, 1.0);
+    }
+
     /// Adds an effect to a slot
-    pub fn add_effect(&mut self, slot_index: usize, effect_type: EffectType) -> Result<(), TrackEffectsError> {
+    pub fn add_effect(
+        &mut self,
+        slot_index: usize,
+        effect_type: EffectType,
+    ) -> Result<(), TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         if self.slots[slot_index].is_some() {
             return Err(TrackEffectsError::SlotOccupied);
         }
-        
+
         if let Some(effect) = create_effect_instance(effect_type, self.sample_rate) {
-            self.slots[slot_index] = Some(TrackEffectSlot::with_effect(effect));
+            self.slots[slot_index] = Some(TrackEffectSlot::with_boxed_effect(effect));
             Ok(())
         } else {
             Err(TrackEffectsError::UnsupportedEffect)
         }
     }
-    
+
     /// Removes an effect from a slot
     pub fn remove_effect(&mut self, slot_index: usize) -> Result<(), TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         if self.slots[slot_index].is_none() {
             return Err(TrackEffectsError::SlotEmpty);
         }
-        
+
         self.slots[slot_index] = None;
         Ok(())
     }
-    
+
     /// Gets the effect type in a slot
     pub fn effect_type(&self, slot_index: usize) -> Result<Option<EffectType>, TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
-        Ok(self.slots[slot_index].as_ref().and_then(|s| s.effect_type()))
+
+        Ok(self.slots[slot_index]
+            .as_ref()
+            .and_then(|s| s.effect_type()))
     }
-    
+
     /// Gets the number of active effects
     pub fn active_effect_count(&self) -> usize {
-        self.slots.iter().filter(|s| s.as_ref().map(|s| s.is_enabled()).unwrap_or(false)).count()
+        self.slots
+            .iter()
+            .filter(|s| s.as_ref().map(|s| s.is_enabled()).unwrap_or(false))
+            .count()
     }
-    
+
     /// Processes a sample through the entire effects chain
     pub fn process(&mut self, input: f32) -> f32 {
         if !self.is_enabled() {
             return input;
         }
-        
+
         let mut output = input;
-        
-        for slot in &mut self.slots {
-            if let Some(ref mut effect_slot) = slot {
-                if effect_slot.is_enabled() {
-                    output = effect_slot.process(output);
-                }
+
+        for effect_slot in self.slots.iter_mut().flatten() {
+            if effect_slot.is_enabled() {
+                output = effect_slot.process(output);
             }
         }
-        
-        output
+
+        // Blend the dry input back in around the whole chain
+        input * (1.0 - self.chain_mix) + output * self.chain_mix
     }
-    
+
     /// Processes a buffer of samples
     pub fn process_buffer(&mut self, samples: &mut [f32]) {
         if !self.is_enabled() {
             return;
         }
-        
+
         for sample in samples.iter_mut() {
             *sample = self.process(*sample);
         }
     }
-    
+
     /// Resets all effects
     pub fn reset(&mut self) {
-        for slot in &mut self.slots {
-            if let Some(ref mut effect_slot) = slot {
-                effect_slot.reset();
-            }
+        for effect_slot in self.slots.iter_mut().flatten() {
+            effect_slot.reset();
         }
     }
-    
+
     /// Gets the mix for a slot
     pub fn mix(&self, slot_index: usize) -> Result<f32, TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         self.slots[slot_index]
             .as_ref()
             .map(|s| s.mix())
             .ok_or(TrackEffectsError::SlotEmpty)
     }
-    
+
     /// Sets the mix for a slot
     pub fn set_mix(&mut self, slot_index: usize, mix: f32) -> Result<(), TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         if let Some(ref mut slot) = self.slots[slot_index] {
             slot.set_mix(mix);
             Ok(())
@@ -629,13 +762,17 @@ impl TrackEffects {
             Err(TrackEffectsError::SlotEmpty)
         }
     }
-    
+
     /// Enables or disables a specific effect slot
-    pub fn set_slot_enabled(&mut self, slot_index: usize, enabled: bool) -> Result<(), TrackEffectsError> {
+    pub fn set_slot_enabled(
+        &mut self,
+        slot_index: usize,
+        enabled: bool,
+    ) -> Result<(), TrackEffectsError> {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         if let Some(ref mut slot) = self.slots[slot_index] {
             slot.set_enabled(enabled);
             Ok(())
@@ -643,7 +780,7 @@ impl TrackEffects {
             Err(TrackEffectsError::SlotEmpty)
         }
     }
-    
+
     /// Adds a parameter lock to a slot
     pub fn add_param_lock(
         &mut self,
@@ -654,7 +791,7 @@ impl TrackEffects {
         if slot_index >= MAX_EFFECT_SLOTS {
             return Err(TrackEffectsError::InvalidSlotIndex);
         }
-        
+
         if let Some(ref mut slot) = self.slots[slot_index] {
             slot.add_param_lock(param, value);
             Ok(())
@@ -662,32 +799,86 @@ impl TrackEffects {
             Err(TrackEffectsError::SlotEmpty)
         }
     }
-    
+
+    /// Sets a parameter on the effect in a slot
+    pub fn set_param(
+        &mut self,
+        slot_index: usize,
+        param: EffectParameterId,
+        value: f32,
+    ) -> Result<(), TrackEffectsError> {
+        if slot_index >= MAX_EFFECT_SLOTS {
+            return Err(TrackEffectsError::InvalidSlotIndex);
+        }
+
+        if let Some(ref mut slot) = self.slots[slot_index] {
+            slot.set_param(param, value);
+            Ok(())
+        } else {
+            Err(TrackEffectsError::SlotEmpty)
+        }
+    }
+
+    /// Offsets a parameter of the effect in a slot from its unmodulated value
+    ///
+    /// See [`TrackEffectSlot::modulate_param`].
+    pub fn modulate_param(
+        &mut self,
+        slot_index: usize,
+        param: EffectParameterId,
+        amount: f32,
+    ) -> Result<(), TrackEffectsError> {
+        if slot_index >= MAX_EFFECT_SLOTS {
+            return Err(TrackEffectsError::InvalidSlotIndex);
+        }
+
+        if let Some(ref mut slot) = self.slots[slot_index] {
+            slot.modulate_param(param, amount);
+            Ok(())
+        } else {
+            Err(TrackEffectsError::SlotEmpty)
+        }
+    }
+
+    /// Gets a parameter from the effect in a slot
+    ///
+    /// Returns `Ok(None)` if the effect does not expose `param`.
+    pub fn get_param(
+        &self,
+        slot_index: usize,
+        param: EffectParameterId,
+    ) -> Result<Option<f32>, TrackEffectsError> {
+        if slot_index >= MAX_EFFECT_SLOTS {
+            return Err(TrackEffectsError::InvalidSlotIndex);
+        }
+
+        self.slots[slot_index]
+            .as_ref()
+            .map(|s| s.get_param(param))
+            .ok_or(TrackEffectsError::SlotEmpty)
+    }
+
     /// Applies parameter locks for a given step
-    pub fn apply_param_locks(&mut self, step: u8) {
-        // This will be implemented when integrating with the parameter lock system
-        // For now, just iterate through slots
-        for slot in &mut self.slots {
-            if let Some(ref mut effect_slot) = slot {
-                for (param, value) in effect_slot.param_locks().clone() {
-                    // TODO: Apply parameter lock value
-                    // This requires integrating with specific effect parameter setters
-                    let _ = (param, value, step);
-                }
-            }
+    ///
+    /// Locks are currently stored per slot rather than per step, so every
+    /// slot's locks are applied regardless of `step`.
+    pub fn apply_param_locks(&mut self, _step: u8) {
+        for effect_slot in self.slots.iter_mut().flatten() {
+            effect_slot.apply_param_locks();
         }
     }
-    
+
     /// Converts to configurations for serialization
     pub fn to_configs(&self) -> [Option<TrackEffectSlotConfig>; MAX_EFFECT_SLOTS] {
-        let mut configs: [Option<TrackEffectSlotConfig>; MAX_EFFECT_SLOTS] = [None, None, None, None];
-        
+        let mut configs: [Option<TrackEffectSlotConfig>; MAX_EFFECT_SLOTS] =
+            [None, None, None, None];
+
         for (i, slot) in self.slots.iter().enumerate() {
             if let Some(ref effect_slot) = slot {
                 configs[i] = Some(effect_slot.to_config());
             }
         }
-        
+
         configs
     }
 }
@@ -701,178 +892,215 @@ impl Default for TrackEffects {
 #[cfg(test)]
 mod track_effects_tests {
     use super::*;
-    use crate::effects::Delay;
-    use crate::effects::Distortion;
-    
+
     #[test]
     fn test_track_effects_creation() {
         let effects = TrackEffects::new(0, 44100.0);
-        
+
         assert_eq!(effects.track_id(), 0);
         assert!(effects.is_enabled());
         assert!(!effects.bypass);
         assert_eq!(effects.active_effect_count(), 0);
     }
-    
+
     #[test]
     fn test_track_effects_from_config() {
         let mut configs: [Option<TrackEffectSlotConfig>; 4] = [None, None, None, None];
-        
-        let mut config = TrackEffectSlotConfig::default();
-        config.effect_type = Some(EffectType::Delay);
-        config.enabled = true;
-        config.mix = 0.7;
+
+        let config = TrackEffectSlotConfig {
+            effect_type: Some(EffectType::Delay),
+            enabled: true,
+            mix: 0.7,
+            ..Default::default()
+        };
         configs[0] = Some(config);
-        
+
         let effects = TrackEffects::from_config(3, 44100.0, &configs);
-        
+
         assert_eq!(effects.track_id(), 3);
         assert_eq!(effects.active_effect_count(), 1);
         assert_eq!(effects.effect_type(0), Ok(Some(EffectType::Delay)));
     }
-    
+
     #[test]
     fn test_track_effects_add_effect() {
         let mut effects = TrackEffects::new(1, 44100.0);
-        
+
         let result = effects.add_effect(0, EffectType::Delay);
         assert!(result.is_ok());
         assert_eq!(effects.active_effect_count(), 1);
         assert_eq!(effects.effect_type(0), Ok(Some(EffectType::Delay)));
-        
+
         // Add another effect
         let result = effects.add_effect(1, EffectType::Distortion);
         assert!(result.is_ok());
         assert_eq!(effects.active_effect_count(), 2);
     }
-    
+
     #[test]
     fn test_track_effects_add_effect_errors() {
         let mut effects = TrackEffects::new(0, 44100.0);
-        
+
         // Invalid slot index
         let result = effects.add_effect(10, EffectType::Delay);
         assert_eq!(result, Err(TrackEffectsError::InvalidSlotIndex));
-        
+
         // Slot already occupied
         effects.add_effect(0, EffectType::Delay).unwrap();
         let result = effects.add_effect(0, EffectType::Distortion);
         assert_eq!(result, Err(TrackEffectsError::SlotOccupied));
-        
+
         // Unsupported effect
-        let result = effects.add_effect(1, EffectType::Reverb);
+        let result = effects.add_effect(1, EffectType::Warble);
         assert_eq!(result, Err(TrackEffectsError::UnsupportedEffect));
     }
-    
+
     #[test]
     fn test_track_effects_remove_effect() {
         let mut effects = TrackEffects::new(0, 44100.0);
-        
+
         effects.add_effect(0, EffectType::Delay).unwrap();
         assert_eq!(effects.active_effect_count(), 1);
-        
+
         let result = effects.remove_effect(0);
         assert!(result.is_ok());
         assert_eq!(effects.active_effect_count(), 0);
         assert_eq!(effects.effect_type(0), Ok(None));
-        
+
         // Remove from empty slot
         let result = effects.remove_effect(0);
         assert_eq!(result, Err(TrackEffectsError::SlotEmpty));
     }
-    
+
     #[test]
     fn test_track_effects_process() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
-        
+
         // Process should work
         let output = effects.process(0.5);
         assert!(output.abs() <= 1.0);
-        
+
         // Disabled track should pass through
         effects.set_enabled(false);
         let output = effects.process(0.5);
         assert_eq!(output, 0.5);
     }
-    
+
     #[test]
     fn test_track_effects_bypass() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
-        
+
         // Enabled and not bypassed
         assert!(effects.is_enabled());
-        
+
         // Set bypass
         effects.set_bypass(true);
         assert!(!effects.is_enabled());
         let output = effects.process(0.5);
         assert_eq!(output, 0.5);
     }
-    
+
     #[test]
     fn test_track_effects_mix() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
-        
+
         // Get mix
         let mix = effects.mix(0);
         assert_eq!(mix, Ok(0.5));
-        
+
         // Set mix
         let result = effects.set_mix(0, 0.8);
         assert!(result.is_ok());
         assert_eq!(effects.mix(0), Ok(0.8));
-        
+
         // Set mix on empty slot
         let result = effects.set_mix(1, 0.5);
         assert_eq!(result, Err(TrackEffectsError::SlotEmpty));
     }
-    
+
     #[test]
     fn test_track_effects_slot_enabled() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
         effects.add_effect(1, EffectType::Distortion).unwrap();
-        
+
         // Disable slot 1
         let result = effects.set_slot_enabled(1, false);
         assert!(result.is_ok());
-        
+
         // Only slot 0 should be active
         assert_eq!(effects.active_effect_count(), 1);
     }
-    
+
     #[test]
     fn test_track_effects_param_locks() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
-        
+
         let result = effects.add_param_lock(0, EffectParameterId::Mix, 0.8);
         assert!(result.is_ok());
-        
+
         // Add to empty slot
         let result = effects.add_param_lock(1, EffectParameterId::Mix, 0.5);
         assert_eq!(result, Err(TrackEffectsError::SlotEmpty));
     }
-    
+
+    #[test]
+    fn test_track_effects_apply_param_locks() {
+        // Energy of a 5 kHz tone after the filter slot
+        fn filtered_energy(effects: &mut TrackEffects) -> f32 {
+            effects.reset();
+            (0..4410)
+                .map(|i| {
+                    let t = i as f32 / 44100.0;
+                    effects.process((2.0 * std::f32::consts::PI * 5000.0 * t).sin())
+                })
+                .skip(441)
+                .map(|s| s * s)
+                .sum()
+        }
+
+        let mut effects = TrackEffects::new(0, 44100.0);
+        effects.add_effect(0, EffectType::Filter).unwrap();
+        effects.set_mix(0, 1.0).unwrap();
+
+        let open = filtered_energy(&mut effects);
+
+        // Locking the cutoff does nothing until the locks are applied
+        effects
+            .add_param_lock(0, EffectParameterId::FilterCutoff, 0.2)
+            .unwrap();
+        assert_eq!(filtered_energy(&mut effects), open);
+
+        effects.apply_param_locks(0);
+        let locked = filtered_energy(&mut effects);
+        assert!(
+            locked < open * 0.1,
+            "Cutoff lock should darken the filter: open={}, locked={}",
+            open,
+            locked
+        );
+    }
+
     #[test]
     fn test_track_effects_reset() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
         effects.add_effect(1, EffectType::Distortion).unwrap();
-        
+
         // Process some samples to change state
         for _ in 0..100 {
             effects.process(0.8);
         }
-        
+
         // Reset should not change structure
         effects.reset();
         assert_eq!(effects.active_effect_count(), 2);
     }
-    
+
     #[test]
     fn test_track_effects_to_configs() {
         let mut effects = TrackEffects::new(0, 44100.0);
@@ -880,42 +1108,97 @@ mod track_effects_tests {
         effects.add_effect(1, EffectType::Distortion).unwrap();
         effects.set_mix(0, 0.7).unwrap();
         effects.set_mix(1, 0.3).unwrap();
-        
+
         let configs = effects.to_configs();
-        
+
         assert!(configs[0].is_some());
         assert!(configs[1].is_some());
         assert!(configs[2].is_none());
         assert!(configs[3].is_none());
-        
+
         assert_eq!(configs[0].as_ref().unwrap().mix, 0.7);
         assert_eq!(configs[1].as_ref().unwrap().mix, 0.3);
     }
-    
+
     #[test]
     fn test_track_effects_multi_slot_processing() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
         effects.add_effect(1, EffectType::Distortion).unwrap();
-        
+
         // Process should go through both effects
         let output = effects.process(0.5);
         assert!(output.abs() <= 1.0);
-        
+
         // If we disable first slot, still process second
         effects.set_slot_enabled(0, false).unwrap();
         let output = effects.process(0.5);
         assert!(output.abs() <= 1.0);
     }
-    
+
+    #[test]
+    fn test_track_effects_reverb_phaser_flanger_slots() {
+        let mut effects = TrackEffects::new(0, 44100.0);
+        effects.add_effect(0, EffectType::Reverb).unwrap();
+        effects.add_effect(1, EffectType::Phaser).unwrap();
+        effects.add_effect(2, EffectType::Flanger).unwrap();
+
+        assert_eq!(effects.effect_type(0), Ok(Some(EffectType::Reverb)));
+        assert_eq!(effects.effect_type(1), Ok(Some(EffectType::Phaser)));
+        assert_eq!(effects.effect_type(2), Ok(Some(EffectType::Flanger)));
+        assert_eq!(effects.active_effect_count(), 3);
+
+        let mut changed = false;
+        for i in 0..2048 {
+            let input = (i as f32 * 0.05).sin() * 0.5;
+            let output = effects.process(input);
+            assert!(output.is_finite());
+            changed |= (output - input).abs() > 1e-4;
+        }
+        assert!(changed, "Chain should alter the signal");
+    }
+
+    #[test]
+    fn test_track_effects_chain_mix() {
+        let mut effects = TrackEffects::new(0, 44100.0);
+        effects.add_effect(0, EffectType::Distortion).unwrap();
+        effects.add_effect(1, EffectType::Saturation).unwrap();
+        effects.set_mix(0, 1.0).unwrap();
+        assert_eq!(effects.chain_mix(), 1.0);
+
+        // Fully dry chain returns the input exactly
+        effects.set_chain_mix(0.0);
+        let mut buffer = [0.5, -0.3, 0.7, -0.9];
+        let dry = buffer;
+        effects.process_buffer(&mut buffer);
+        assert_eq!(buffer, dry);
+
+        // Fully wet chain differs from the input
+        effects.set_chain_mix(1.0);
+        let wet = effects.process(0.5);
+        assert_ne!(wet, 0.5);
+
+        // Half mix sits between dry and wet
+        effects.reset();
+        let wet = effects.process(0.5);
+        effects.reset();
+        effects.set_chain_mix(0.5);
+        let half = effects.process(0.5);
+        assert!((half - (0.5 + wet) * 0.5).abs() < 1e-6);
+
+        // Chain mix is clamped
+        effects.set_chain_mix(2.0);
+        assert_eq!(effects.chain_mix(), 1.0);
+    }
+
     #[test]
     fn test_track_effects_process_buffer() {
         let mut effects = TrackEffects::new(0, 44100.0);
         effects.add_effect(0, EffectType::Delay).unwrap();
-        
+
         let mut buffer = [0.5, 0.3, 0.7, 0.4];
         effects.process_buffer(&mut buffer);
-        
+
         // Buffer should be processed
         for &sample in &buffer {
             assert!(sample.abs() <= 1.0);
@@ -931,11 +1214,10 @@ mod track_effects_tests {
 type EffectBuilder = Box<dyn Fn(f32) -> Option<Box<dyn Effect>> + Send + Sync>;
 
 /// Factory for creating and managing effect instances
-#[derive(Debug, Clone)]
 pub struct EffectFactory {
     /// Registered effect builders
     builders: HashMap<EffectType, EffectBuilder>,
-    
+
     /// Sample rate for new effects
     sample_rate: f32,
 }
@@ -947,88 +1229,130 @@ impl EffectFactory {
             builders: HashMap::new(),
             sample_rate,
         };
-        
+
         // Register default effects
         factory.register_default_effects();
-        
+
         factory
     }
-    
+
     /// Registers all default effect types
     fn register_default_effects(&mut self) {
         // Use register_custom for effects that need custom creation
         // Delay
-        self.register_custom(EffectType::Delay, Box::new(|sr| {
-            Some(Box::new(Delay::new(sr)))
-        }));
-        
+        self.register_custom(
+            EffectType::Delay,
+            Box::new(|sr| Some(Box::new(Delay::new(sr)))),
+        );
+
         // Distortion
-        self.register_custom(EffectType::Distortion, Box::new(|_sr| {
-            Some(Box::new(Distortion::new()))
-        }));
-        
+        self.register_custom(
+            EffectType::Distortion,
+            Box::new(|_sr| Some(Box::new(Distortion::new()))),
+        );
+
         // Saturation
-        self.register_custom(EffectType::Saturation, Box::new(|_sr| {
-            Some(Box::new(Saturation::new()))
-        }));
-        
+        self.register_custom(
+            EffectType::Saturation,
+            Box::new(|_sr| Some(Box::new(Saturation::new()))),
+        );
+
         // Compressor
-        self.register_custom(EffectType::Compressor, Box::new(|sr| {
-            Some(Box::new(Compressor::new(sr)))
-        }));
-        
+        self.register_custom(
+            EffectType::Compressor,
+            Box::new(|sr| Some(Box::new(Compressor::new(sr)))),
+        );
+
+        // Gate
+        self.register_custom(
+            EffectType::Gate,
+            Box::new(|sr| Some(Box::new(Gate::new(sr)))),
+        );
+
+        // Limiter
+        self.register_custom(
+            EffectType::Limiter,
+            Box::new(|sr| Some(Box::new(Limiter::new(sr)))),
+        );
+
         // SimpleEQ
-        self.register_custom(EffectType::SimpleEQ, Box::new(|sr| {
-            Some(Box::new(SimpleEq::new(sr)))
-        }));
-        
+        self.register_custom(
+            EffectType::SimpleEQ,
+            Box::new(|sr| Some(Box::new(SimpleEq::new(sr)))),
+        );
+
         // Chorus
-        self.register_custom(EffectType::Chorus, Box::new(|sr| {
-            Some(Box::new(Chorus::new(sr)))
-        }));
+        self.register_custom(
+            EffectType::Chorus,
+            Box::new(|sr| Some(Box::new(Chorus::new(sr)))),
+        );
+
+        // Reverb
+        self.register_custom(
+            EffectType::Reverb,
+            Box::new(|sr| Some(Box::new(Reverb::new(sr)))),
+        );
+
+        // Phaser
+        self.register_custom(
+            EffectType::Phaser,
+            Box::new(|sr| Some(Box::new(Phaser::new_with_sample_rate(sr)))),
+        );
+
+        // Flanger
+        self.register_custom(
+            EffectType::Flanger,
+            Box::new(|sr| Some(Box::new(Flanger::new_with_sample_rate(sr)))),
+        );
+
+        // Filter
+        self.register_custom(
+            EffectType::Filter,
+            Box::new(|_sr| Some(Box::new(BiquadFilter::new()))),
+        );
+
+        // Stereo widener
+        self.register_custom(
+            EffectType::StereoWidener,
+            Box::new(|sr| Some(Box::new(StereoWidener::new(sr)))),
+        );
     }
-    
+
     /// Registers a new effect type
-    pub fn register<E: Effect + Default + 'static>(
-        &mut self,
-        effect_type: EffectType,
-    ) {
-        self.register_custom(effect_type, Box::new(move |_sr| {
-            Some(Box::new(E::default()))
-        }));
+    pub fn register<E: Effect + Default + 'static>(&mut self, effect_type: EffectType) {
+        self.register_custom(
+            effect_type,
+            Box::new(move |_sr| Some(Box::new(E::default()))),
+        );
     }
-    
+
     /// Registers a custom effect builder
-    pub fn register_custom(
-        &mut self,
-        effect_type: EffectType,
-        builder: EffectBuilder,
-    ) {
+    pub fn register_custom(&mut self, effect_type: EffectType, builder: EffectBuilder) {
         self.builders.insert(effect_type, builder);
     }
-    
+
     /// Creates an effect instance
     pub fn create_effect(&self, effect_type: EffectType) -> Option<Box<dyn Effect>> {
         self.builders
             .get(&effect_type)
             .and_then(|builder| builder(self.sample_rate))
     }
-    
+
     /// Checks if an effect type is registered
     pub fn is_registered(&self, effect_type: EffectType) -> bool {
         self.builders.contains_key(&effect_type)
     }
-    
+
     /// Gets all registered effect types
     pub fn registered_types(&self) -> Vec<EffectType> {
         self.builders.keys().cloned().collect()
     }
-    
+
     /// Gets the sample rate
     pub fn sample_rate(&self) -> f32 {
         self.sample_rate
     }
-    
+
     /// Sets the sample rate
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate = sample_rate;
@@ -1041,21 +1365,30 @@ impl Default for EffectFactory {
     }
 }
 
+impl fmt::Debug for EffectFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffectFactory")
+            .field("registered_types", &self.registered_types())
+            .field("sample_rate", &self.sample_rate)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod effect_factory_tests {
     use super::*;
-    
+
     #[test]
     fn test_effect_factory_creation() {
         let factory = EffectFactory::new(44100.0);
-        
+
         assert_eq!(factory.sample_rate(), 44100.0);
     }
-    
+
     #[test]
     fn test_effect_factory_create_effects() {
         let factory = EffectFactory::new(44100.0);
-        
+
         // Test creating various effects
         assert!(factory.create_effect(EffectType::Delay).is_some());
         assert!(factory.create_effect(EffectType::Distortion).is_some());
@@ -1064,54 +1397,64 @@ mod effect_factory_tests {
         assert!(factory.create_effect(EffectType::SimpleEQ).is_some());
         assert!(factory.create_effect(EffectType::Chorus).is_some());
         assert!(factory.create_effect(EffectType::Filter).is_some());
-        
+        assert!(factory.create_effect(EffectType::Reverb).is_some());
+        assert!(factory.create_effect(EffectType::Phaser).is_some());
+        assert!(factory.create_effect(EffectType::Flanger).is_some());
+        assert!(factory.create_effect(EffectType::StereoWidener).is_some());
+
         // Test unregistered effects
-        assert!(factory.create_effect(EffectType::Reverb).is_none());
-        assert!(factory.create_effect(EffectType::Phaser).is_none());
-        assert!(factory.create_effect(EffectType::Flanger).is_none());
+        assert!(factory.create_effect(EffectType::Warble).is_none());
     }
-    
+
     #[test]
     fn test_effect_factory_is_registered() {
         let factory = EffectFactory::new(44100.0);
-        
+
         assert!(factory.is_registered(EffectType::Delay));
         assert!(factory.is_registered(EffectType::Distortion));
-        assert!(!factory.is_registered(EffectType::Reverb));
-        assert!(!factory.is_registered(EffectType::Phaser));
+        assert!(factory.is_registered(EffectType::Reverb));
+        assert!(factory.is_registered(EffectType::Phaser));
+        assert!(!factory.is_registered(EffectType::Warble));
     }
-    
+
     #[test]
     fn test_effect_factory_registered_types() {
         let factory = EffectFactory::new(44100.0);
         let types = factory.registered_types();
-        
+
         assert!(types.contains(&EffectType::Delay));
         assert!(types.contains(&EffectType::Distortion));
         assert!(types.contains(&EffectType::Saturation));
         assert!(types.contains(&EffectType::Compressor));
+        assert!(types.contains(&EffectType::Gate));
+        assert!(types.contains(&EffectType::Limiter));
         assert!(types.contains(&EffectType::SimpleEQ));
         assert!(types.contains(&EffectType::Chorus));
         assert!(types.contains(&EffectType::Filter));
-        assert!(!types.contains(&EffectType::Reverb));
+        assert!(types.contains(&EffectType::Reverb));
+        assert!(types.contains(&EffectType::Flanger));
+        assert!(!types.contains(&EffectType::Warble));
     }
-    
+
     #[test]
     fn test_effect_factory_custom_register() {
         let mut factory = EffectFactory::new(44100.0);
-        
+
         // Register a custom builder
-        factory.register_custom(EffectType::Reverb, Box::new(|_sr| {
-            None // Still returns None, but registered
-        }));
-        
-        assert!(factory.is_registered(EffectType::Reverb));
+        factory.register_custom(
+            EffectType::Warble,
+            Box::new(|_sr| {
+                None // Still returns None, but registered
+            }),
+        );
+
+        assert!(factory.is_registered(EffectType::Warble));
     }
-    
+
     #[test]
     fn test_effect_factory_sample_rate() {
         let mut factory = EffectFactory::new(44100.0);
-        
+
         factory.set_sample_rate(48000.0);
         assert_eq!(factory.sample_rate(), 48000.0);
     }
@@ -1125,17 +1468,17 @@ mod effect_factory_tests {
 pub const TRACK_COUNT: usize = 8;
 
 /// Manager for all track effects
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct PerTrackEffectsManager {
     /// Effects for each track
     track_effects: [TrackEffects; TRACK_COUNT],
-    
+
     /// Effect factory
     factory: EffectFactory,
-    
+
     /// Global bypass
     global_bypass: bool,
-    
+
     /// Sample rate
     sample_rate: f32,
 }
@@ -1143,13 +1486,9 @@ pub struct PerTrackEffectsManager {
 impl PerTrackEffectsManager {
     /// Creates a new effects manager
     pub fn new(sample_rate: f32) -> Self {
-        let mut track_effects = [TrackEffects::new(0, sample_rate); TRACK_COUNT];
-        
         // Initialize each track with correct ID
-        for (i, track) in track_effects.iter_mut().enumerate() {
-            *track = TrackEffects::new(i as u8, sample_rate);
-        }
-        
+        let track_effects = std::array::from_fn(|i| TrackEffects::new(i as u8, sample_rate));
+
         Self {
             track_effects,
             factory: EffectFactory::new(sample_rate),
@@ -1157,27 +1496,24 @@ impl PerTrackEffectsManager {
             sample_rate,
         }
     }
-    
+
     /// Creates from track configurations
     pub fn from_track_configs(
         sample_rate: f32,
         track_configs: &[Option<[Option<TrackEffectSlotConfig>; MAX_EFFECT_SLOTS]>; TRACK_COUNT],
     ) -> Self {
         let mut manager = Self::new(sample_rate);
-        
+
         for (track_id, config) in track_configs.iter().enumerate().take(TRACK_COUNT) {
             if let Some(ref configs) = config {
-                manager.track_effects[track_id] = TrackEffects::from_config(
-                    track_id as u8,
-                    sample_rate,
-                    configs,
-                );
+                manager.track_effects[track_id] =
+                    TrackEffects::from_config(track_id as u8, sample_rate, configs);
             }
         }
-        
+
         manager
     }
-    
+
     /// Gets effects for a specific track
     pub fn track_effects(&mut self, track_id: u8) -> Option<&mut TrackEffects> {
         if track_id < TRACK_COUNT as u8 {
@@ -1186,63 +1522,63 @@ impl PerTrackEffectsManager {
             None
         }
     }
-    
+
     /// Processes audio for a specific track
     pub fn process_track(&mut self, track_id: u8, input: f32) -> f32 {
         if self.global_bypass {
             return input;
         }
-        
+
         if let Some(track) = self.track_effects(track_id) {
             track.process(input)
         } else {
             input
         }
     }
-    
+
     /// Processes a buffer for a specific track
     pub fn process_track_buffer(&mut self, track_id: u8, samples: &mut [f32]) {
         if self.global_bypass {
             return;
         }
-        
+
         if let Some(track) = self.track_effects(track_id) {
             track.process_buffer(samples);
         }
     }
-    
+
     /// Sets global bypass
     pub fn set_global_bypass(&mut self, bypass: bool) {
         self.global_bypass = bypass;
     }
-    
+
     /// Checks if global bypass is enabled
     pub fn is_global_bypass(&self) -> bool {
         self.global_bypass
     }
-    
+
     /// Gets the factory
     pub fn factory(&self) -> &EffectFactory {
         &self.factory
     }
-    
+
     /// Gets a mutable factory
     pub fn factory_mut(&mut self) -> &mut EffectFactory {
         &mut self.factory
     }
-    
+
     /// Gets the sample rate
     pub fn sample_rate(&self) -> f32 {
         self.sample_rate
     }
-    
+
     /// Resets all effects
     pub fn reset(&mut self) {
         for track in &mut self.track_effects {
             track.reset();
         }
     }
-    
+
     /// Gets total number of active effects
     pub fn total_active_effects(&self) -> usize {
         self.track_effects
@@ -1250,11 +1586,11 @@ impl PerTrackEffectsManager {
             .map(|t| t.active_effect_count())
             .sum()
     }
-    
+
     /// Gets all effect types in use
     pub fn active_effect_types(&self) -> Vec<EffectType> {
         let mut types = Vec::new();
-        
+
         for track in &self.track_effects {
             for slot in 0..MAX_EFFECT_SLOTS {
                 if let Ok(Some(effect_type)) = track.effect_type(slot) {
@@ -1264,7 +1600,7 @@ impl PerTrackEffectsManager {
                 }
             }
         }
-        
+
         types
     }
 }
@@ -1278,87 +1614,87 @@ impl Default for PerTrackEffectsManager {
 #[cfg(test)]
 mod per_track_effects_manager_tests {
     use super::*;
-    
+
     #[test]
     fn test_per_track_effects_manager_creation() {
         let manager = PerTrackEffectsManager::new(44100.0);
-        
+
         assert_eq!(manager.sample_rate(), 44100.0);
         assert!(!manager.is_global_bypass());
         assert_eq!(manager.total_active_effects(), 0);
     }
-    
+
     #[test]
     fn test_per_track_effects_process_track() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Add effect to track 0
         if let Some(track) = manager.track_effects(0) {
             let _ = track.add_effect(0, EffectType::Delay);
         }
-        
+
         // Process should work
         let output = manager.process_track(0, 0.5);
         assert!(output.abs() <= 1.0);
-        
+
         // Global bypass should pass through
         manager.set_global_bypass(true);
         let output = manager.process_track(0, 0.5);
         assert_eq!(output, 0.5);
     }
-    
+
     #[test]
     fn test_per_track_effects_process_track_buffer() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Add effect to track 0
         if let Some(track) = manager.track_effects(0) {
             let _ = track.add_effect(0, EffectType::Delay);
         }
-        
+
         let mut buffer = [0.5, 0.3, 0.7, 0.4];
         manager.process_track_buffer(0, &mut buffer);
-        
+
         for &sample in &buffer {
             assert!(sample.abs() <= 1.0);
         }
     }
-    
+
     #[test]
     fn test_per_track_effects_invalid_track() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Invalid track should pass through
         let output = manager.process_track(99, 0.5);
         assert_eq!(output, 0.5);
     }
-    
+
     #[test]
     fn test_per_track_effects_reset() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Add effects to multiple tracks
         for track_id in 0..8 {
             if let Some(track) = manager.track_effects(track_id) {
                 let _ = track.add_effect(0, EffectType::Delay);
             }
         }
-        
+
         assert_eq!(manager.total_active_effects(), 8);
-        
+
         manager.reset();
-        
+
         // Reset should keep effects but reset their state
         assert_eq!(manager.total_active_effects(), 8);
     }
-    
+
     #[test]
     fn test_per_track_effects_active_effect_types() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // No effects yet
         assert!(manager.active_effect_types().is_empty());
-        
+
         // Add effects
         if let Some(track) = manager.track_effects(0) {
             let _ = track.add_effect(0, EffectType::Delay);
@@ -1366,18 +1702,18 @@ mod per_track_effects_manager_tests {
         if let Some(track) = manager.track_effects(1) {
             let _ = track.add_effect(0, EffectType::Distortion);
         }
-        
+
         let types = manager.active_effect_types();
-        
+
         assert!(types.contains(&EffectType::Delay));
         assert!(types.contains(&EffectType::Distortion));
         assert_eq!(types.len(), 2);
     }
-    
+
     #[test]
     fn test_per_track_effects_multi_track() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Add different effects to different tracks
         for track_id in 0..8 {
             if let Some(track) = manager.track_effects(track_id) {
@@ -1389,41 +1725,43 @@ mod per_track_effects_manager_tests {
                 let _ = track.add_effect(0, effect_type);
             }
         }
-        
+
         assert_eq!(manager.total_active_effects(), 8);
-        
+
         // Process each track
         for track_id in 0..8 {
             let output = manager.process_track(track_id, 0.5);
             assert!(output.abs() <= 1.0, "Track {} processing failed", track_id);
         }
     }
-    
+
     #[test]
     fn test_per_track_effects_factory() {
         let mut manager = PerTrackEffectsManager::new(44100.0);
-        
+
         // Factory should be accessible
         assert!(manager.factory().is_registered(EffectType::Delay));
-        
+
         // Factory should be mutable
         manager.factory_mut().set_sample_rate(48000.0);
         assert_eq!(manager.factory().sample_rate(), 48000.0);
     }
-    
+
     #[test]
     fn test_per_track_effects_from_configs() {
-        let mut track_configs: [Option<[Option<TrackEffectSlotConfig>; 4]>; 8] = [None; 8];
-        
+        let mut track_configs: [Option<[Option<TrackEffectSlotConfig>; 4]>; 8] = Default::default();
+
         // Configure track 0
-        let mut config = TrackEffectSlotConfig::default();
-        config.effect_type = Some(EffectType::Delay);
-        config.enabled = true;
-        config.mix = 0.7;
+        let config = TrackEffectSlotConfig {
+            effect_type: Some(EffectType::Delay),
+            enabled: true,
+            mix: 0.7,
+            ..Default::default()
+        };
         track_configs[0] = Some([Some(config), None, None, None]);
-        
-        let manager = PerTrackEffectsManager::from_track_configs(44100.0, &track_configs);
-        
+
+        let mut manager = PerTrackEffectsManager::from_track_configs(44100.0, &track_configs);
+
         assert!(manager.track_effects(0).is_some());
         if let Some(track) = manager.track_effects(0) {
             assert_eq!(track.active_effect_count(), 1);
@@ -3,6 +3,11 @@
 //!
 //! Amplitude modulation effect using LFO-controlled gain reduction
 //!
+//! In stereo mode the LFO pans the signal between left and right with an
+//! equal-power law instead of dipping its volume (vintage-amp stereo
+//! tremolo). The two channels move in opposite phase and their combined
+//! power stays constant, so the level doesn't pump and no gain exceeds one.
+//!
 
 use crate::lfo::{Lfo, LfoConfig, LfoRate};
 use crate::oscillator::Waveform;
@@ -24,6 +29,9 @@ pub struct TremoloConfig {
     pub mix: f64,
     /// Enabled state
     pub enabled: bool,
+    /// Auto-pan: the LFO pans between L and R instead of modulating volume
+    #[serde(default)]
+    pub stereo_mode: bool,
 }
 
 impl Default for TremoloConfig {
@@ -35,6 +43,7 @@ impl Default for TremoloConfig {
             stereo_width: 0.0,
             mix: 1.0,
             enabled: true,
+            stereo_mode: false,
         }
     }
 }
@@ -168,6 +177,21 @@ impl Tremolo {
         self.config.enabled = enabled;
     }
 
+    /// Enable/disable stereo auto-pan mode
+    pub fn set_stereo_mode(&mut self, stereo_mode: bool) {
+        self.config.stereo_mode = stereo_mode;
+    }
+
+    /// Get equal-power pan gains for an LFO value
+    ///
+    /// Returns (left, right) gains with `left² + right² = 1`, so the level
+    /// stays steady and neither channel is ever boosted.
+    fn pan_gains(&self, lfo: f32) -> (f32, f32) {
+        let position = self.config.depth as f32 * lfo;
+        let angle = (position + 1.0) * PI / 4.0;
+        (angle.cos(), angle.sin())
+    }
+
     /// Process stereo audio buffer
     pub fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
         assert_eq!(left.len(), right.len());
@@ -179,6 +203,19 @@ impl Tremolo {
         let depth = self.config.depth as f32;
         let mix = self.config.mix as f32;
 
+        if self.config.stereo_mode {
+            for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+                // One LFO drives both channels in opposite phase
+                let lfo = self.lfo_left.process();
+                let (gain_l, gain_r) = self.pan_gains(lfo);
+                self.lfo_right.process();
+
+                *l *= gain_l * mix + (1.0 - mix);
+                *r *= gain_r * mix + (1.0 - mix);
+            }
+            return;
+        }
+
         for (l, r) in left.iter_mut().zip(right.iter_mut()) {
             // Get LFO values (range -1.0 to 1.0)
             let lfo_l = self.lfo_left.process();
@@ -209,6 +246,11 @@ impl Tremolo {
         // Get LFO value (range -1.0 to 1.0)
         let lfo = self.lfo_left.process();
 
+        // A mono signal has nothing to pan
+        if self.config.stereo_mode {
+            return input;
+        }
+
         // Convert to modulation factor
         let mod_factor = 1.0 - depth * (lfo * 0.5 + 0.5);
 
@@ -399,6 +441,7 @@ mod tests {
             stereo_width: 0.5,
             mix: 0.8,
             enabled: true,
+            stereo_mode: false,
         };
 
         let tremolo = Tremolo::with_config(config, 44100.0);
@@ -436,4 +479,51 @@ mod tests {
         // Should process without error
         assert!(output.abs() <= 1.0);
     }
+
+    #[test]
+    fn test_tremolo_stereo_mode_pans_in_opposite_phase() {
+        let mut tremolo = Tremolo::new(44100.0);
+        tremolo.set_rate(5.0);
+        tremolo.set_depth(1.0);
+        tremolo.set_stereo_mode(true);
+
+        let mut left = vec![1.0; 44100];
+        let mut right = vec![1.0; 44100];
+        tremolo.process_stereo(&mut left, &mut right);
+
+        // Opposite phase: as one channel rises the other falls
+        let mean_l = left.iter().sum::<f32>() / left.len() as f32;
+        let mean_r = right.iter().sum::<f32>() / right.len() as f32;
+        let covariance: f32 = left
+            .iter()
+            .zip(&right)
+            .map(|(l, r)| (l - mean_l) * (r - mean_r))
+            .sum();
+        assert!(covariance < 0.0);
+        let peak = left.iter().chain(&right).cloned().fold(0.0, f32::max);
+        assert!(peak > 0.99 && peak <= 1.0, "peak {peak}");
+        assert!(left.iter().cloned().fold(f32::MAX, f32::min) < 0.01);
+
+        // The combined power stays steady: no volume pumping
+        for (l, r) in left.iter().zip(&right) {
+            assert!((l * l + r * r - 1.0).abs() < 1e-4, "{l} {r}");
+        }
+    }
+
+    #[test]
+    fn test_tremolo_stereo_mode_zero_depth_centers() {
+        let mut tremolo = Tremolo::new(44100.0);
+        tremolo.set_depth(0.0);
+        tremolo.set_stereo_mode(true);
+
+        let mut left = vec![1.0; 256];
+        let mut right = vec![1.0; 256];
+        tremolo.process_stereo(&mut left, &mut right);
+
+        // Centered equal-power pan: -3 dB on both channels
+        for (l, r) in left.iter().zip(&right) {
+            assert!((l - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+            assert!((r - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        }
+    }
 }
@@ -19,11 +19,17 @@
 //!
 //! For pitch shifting, we use a simple delay-line modulation approach
 //! which provides real-time processing with minimal latency.
+//!
+//! Changing the mode crossfades from the old curve to the new one over
+//! `MODE_CROSSFADE_MS`, so switching at runtime doesn't click.
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use std::f32::consts::PI;
 
+/// Crossfade time when switching modes, in milliseconds
+const MODE_CROSSFADE_MS: f32 = 10.0;
+
 /// Warp effect configuration
 #[derive(Debug, Clone, Copy)]
 pub struct WarpConfig {
@@ -83,6 +89,21 @@ pub enum WarpMode {
     Granular,
 }
 
+impl WarpMode {
+    /// All modes, in `mode_gains` order
+    const ALL: [WarpMode; 4] = [
+        WarpMode::TimeWarp,
+        WarpMode::PitchShift,
+        WarpMode::RingMod,
+        WarpMode::Granular,
+    ];
+
+    /// Gets the index of the mode in `ALL`
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// Warp effect processor
 #[derive(Debug, Clone)]
 pub struct Warp {
@@ -125,6 +146,10 @@ pub struct Warp {
     /// Current grain window
     current_window: Vec<f32>,
 
+    /// Output gain of each mode, indexed by `WarpMode::index`; all but the
+    /// current mode are 0 except during a mode crossfade
+    mode_gains: [f32; 4],
+
     /// Whether effect is enabled
     enabled: bool,
 }
@@ -207,6 +232,7 @@ impl Warp {
             rng: StdRng::from_entropy(),
             prev_grain: vec![0.0; grain_size],
             current_window: vec![0.0; grain_size],
+            mode_gains: Self::settled_gains(WarpMode::TimeWarp),
             enabled: true,
         }
     }
@@ -230,6 +256,7 @@ impl Warp {
             rng: StdRng::from_entropy(),
             prev_grain: vec![0.0; grain_size],
             current_window: vec![0.0; grain_size],
+            mode_gains: Self::settled_gains(config.mode),
             enabled: true,
         };
 
@@ -276,17 +303,52 @@ impl Warp {
         lfo
     }
 
+    /// Gets mode gains with only `mode` audible
+    fn settled_gains(mode: WarpMode) -> [f32; 4] {
+        let mut gains = [0.0; 4];
+        gains[mode.index()] = 1.0;
+        gains
+    }
+
     /// Processes a single sample through the warp effect
     fn process_sample(&mut self, input: f32) -> f32 {
         // Write input to buffer
         self.input_buffer[self.write_pos] = input;
         self.write_pos = (self.write_pos + 1) % self.input_buffer.len();
 
+        let lfo = self.get_lfo_value();
+
+        // Move every mode's gain toward its target; during a crossfade more
+        // than one mode is audible and the gains are normalized to sum to 1
+        let step = 1000.0 / (MODE_CROSSFADE_MS * self.config.sample_rate);
+        let mut output = 0.0;
+        let mut total_gain = 0.0;
+        for mode in WarpMode::ALL {
+            let gain = &mut self.mode_gains[mode.index()];
+            *gain = if mode == self.mode {
+                (*gain + step).min(1.0)
+            } else {
+                (*gain - step).max(0.0)
+            };
+
+            let gain = *gain;
+            if gain > 0.0 {
+                output += self.mode_output(mode, input, lfo) * gain;
+                total_gain += gain;
+            }
+        }
+        let output = output / total_gain;
+
+        // Mix wet/dry
+        input * (1.0 - self.config.mix) + output * self.config.mix
+    }
+
+    /// Gets the wet output of one mode
+    fn mode_output(&mut self, mode: WarpMode, input: f32, lfo: f32) -> f32 {
         // Calculate read position based on mode and LFO
         let warp_amount = self.config.amount;
-        let lfo = self.get_lfo_value();
 
-        let (_delayed, output) = match self.mode {
+        let (_delayed, output) = match mode {
             WarpMode::TimeWarp => {
                 // Time warp: modulate read speed
                 let speed = 1.0 + (lfo - 0.5) * warp_amount * 2.0;
@@ -324,8 +386,7 @@ impl Warp {
             }
         };
 
-        // Mix wet/dry
-        input * (1.0 - self.config.mix) + output * self.config.mix
+        output
     }
 }
 
@@ -365,6 +426,12 @@ impl StereoWarp {
 
         (mid + side, mid - side)
     }
+
+    /// Sets the warp mode of both channels, crossfading like `Warp::set_mode`
+    pub fn set_mode(&mut self, mode: WarpMode) {
+        self.left.set_mode(mode);
+        self.right.set_mode(mode);
+    }
 }
 
 impl Warp {
@@ -400,6 +467,7 @@ impl Warp {
         self.write_pos = 0;
         self.lfo_phase = 0.0;
         self.rng = StdRng::from_entropy();
+        self.mode_gains = Self::settled_gains(self.mode);
         self.generate_window();
     }
 
@@ -426,6 +494,10 @@ impl Warp {
 
 impl Warp {
     /// Sets the warp mode
+    ///
+    /// The output crossfades to the new mode over `MODE_CROSSFADE_MS`.
+    /// Switching again mid-fade continues from the current blend, so rapid
+    /// switches never jump. Setting the current mode does nothing.
     pub fn set_mode(&mut self, mode: WarpMode) {
         self.mode = mode;
         self.config.mode = mode;
@@ -685,4 +757,47 @@ mod tests {
         let ring_mod = WarpConfig::ring_mod(44100.0);
         assert_eq!(ring_mod.mode, WarpMode::RingMod);
     }
+
+    #[test]
+    fn test_warp_mode_switch_is_click_free() {
+        let sample_rate = 44100.0;
+        let mut warp = Warp::with_config(WarpConfig {
+            mode: WarpMode::RingMod,
+            mix: 1.0,
+            sample_rate,
+            ..WarpConfig::default()
+        });
+        let mut phase = 0.0f32;
+        let mut sine = move || {
+            phase += 2.0 * PI * 200.0 / sample_rate;
+            phase.sin() * 0.5
+        };
+
+        // Settle the delay line before switching
+        for _ in 0..22050 {
+            warp.process(sine());
+        }
+
+        let mut previous = warp.process(sine());
+        let mut max_step = 0.0f32;
+        // Switch, then switch again twice mid-fade (a fade is 441 samples)
+        for (mode, samples) in [
+            (WarpMode::PitchShift, 100),
+            (WarpMode::RingMod, 50),
+            (WarpMode::PitchShift, 1000),
+        ] {
+            warp.set_mode(mode);
+            for _ in 0..samples {
+                let output = warp.process(sine());
+                max_step = max_step.max((output - previous).abs());
+                previous = output;
+            }
+        }
+        assert_eq!(warp.mode_gains, Warp::settled_gains(WarpMode::PitchShift));
+        assert!(max_step < 0.05, "step {max_step}");
+
+        // Setting the current mode leaves the gains alone
+        warp.set_mode(WarpMode::PitchShift);
+        assert_eq!(warp.mode_gains, Warp::settled_gains(WarpMode::PitchShift));
+    }
 }
@@ -5,6 +5,9 @@
 
 #![allow(dead_code)] // Reserve envelope fields for future features
 
+/// Exponent applied at full curve shape (+/-1.0).
+const CURVE_STEEPNESS: f32 = 5.0;
+
 /// Enumeration of possible envelope stages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnvelopeStage {
@@ -17,6 +20,9 @@ pub enum EnvelopeStage {
     /// Rising to peak level
     Attack,
 
+    /// Holding at peak level before decay
+    Hold,
+
     /// Falling to sustain level
     Decay,
 
@@ -30,12 +36,29 @@ pub enum EnvelopeStage {
     Finished,
 }
 
+/// Looping behavior of an envelope while the note is held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnvLoopMode {
+    /// One-shot ADSR, sustains until note off
+    #[default]
+    Off,
+
+    /// Loop attack and decay, skipping sustain and release
+    LoopAD,
+
+    /// Loop the whole envelope; sustain lasts for the sustain hold time
+    LoopFull,
+}
+
 /// Configuration for envelope timing and levels.
 #[derive(Debug, Clone, Copy)]
 pub struct EnvelopeConfig {
     /// Attack time in seconds
     pub attack: f32,
 
+    /// Hold time at peak level in seconds (0.0 = no hold stage)
+    pub hold: f32,
+
     /// Decay time in seconds
     pub decay: f32,
 
@@ -62,6 +85,7 @@ impl Default for EnvelopeConfig {
     fn default() -> Self {
         Self {
             attack: 0.01,
+            hold: 0.0,
             decay: 0.2,
             sustain: 0.7,
             release: 0.3,
@@ -74,6 +98,9 @@ impl Default for EnvelopeConfig {
 }
 
 /// ADSR (Attack, Decay, Sustain, Release) envelope generator.
+///
+/// An optional hold time turns it into an AHDSR, keeping the peak level
+/// between attack and decay.
 #[derive(Debug, Clone)]
 pub struct AdsrEnvelope {
     /// Current envelope stage
@@ -82,8 +109,26 @@ pub struct AdsrEnvelope {
     /// Current envelope level
     current_level: f32,
 
+    /// Linear ramp position the curve shapes are applied to
+    linear_level: f32,
+
+    /// Level the release stage started from
+    release_start: f32,
+
+    /// Looping behavior while the note is held
+    loop_mode: EnvLoopMode,
+
+    /// Whether the note is held (between note_on and note_off)
+    note_held: bool,
+
+    /// Segment curvature (-1.0 = slow start, 0.0 = linear, 1.0 = fast start)
+    attack_curve: f32,
+    decay_curve: f32,
+    release_curve: f32,
+
     /// Envelope parameters in samples
     attack: f32,
+    hold: f32,
     decay: f32,
     sustain: f32,
     release: f32,
@@ -109,7 +154,15 @@ impl AdsrEnvelope {
         Self {
             stage: EnvelopeStage::Idle,
             current_level: 0.0,
+            linear_level: 0.0,
+            release_start: 0.0,
+            loop_mode: EnvLoopMode::Off,
+            note_held: false,
+            attack_curve: 0.0,
+            decay_curve: 0.0,
+            release_curve: 0.0,
             attack: config.attack * samples_per_second,
+            hold: config.hold * samples_per_second,
             decay: config.decay * samples_per_second,
             sustain: config.sustain,
             release: config.release * samples_per_second,
@@ -125,16 +178,33 @@ impl AdsrEnvelope {
     pub fn note_on(&mut self) {
         self.stage = EnvelopeStage::Delay;
         self.stage_time = 0.0;
+        self.note_held = true;
+
+        // Resume the attack ramp from wherever the output currently is
+        if self.attack_curve != 0.0 && self.peak > 0.0 {
+            let progress = (self.current_level / self.peak).clamp(0.0, 1.0);
+            self.linear_level = self.peak * curve_inverse(progress, self.attack_curve);
+        } else {
+            self.linear_level = self.current_level;
+        }
     }
 
     /// Releases the envelope (note off event).
     pub fn note_off(&mut self) {
+        self.note_held = false;
         if self.stage != EnvelopeStage::Idle && self.stage != EnvelopeStage::Release {
-            self.stage = EnvelopeStage::Release;
-            self.stage_time = 0.0;
+            self.enter_release();
         }
     }
 
+    /// Starts the release stage from the current output level.
+    fn enter_release(&mut self) {
+        self.stage = EnvelopeStage::Release;
+        self.stage_time = 0.0;
+        self.linear_level = self.current_level;
+        self.release_start = self.current_level;
+    }
+
     /// Processes one sample of the envelope.
     pub fn process(&mut self) -> f32 {
         self.advance_stage();
@@ -146,6 +216,11 @@ impl AdsrEnvelope {
         (0..count).map(|_| self.process()).collect()
     }
 
+    /// Gets the current output level without advancing the envelope.
+    pub fn level(&self) -> f32 {
+        self.current_level
+    }
+
     /// Gets the current envelope stage.
     pub fn stage(&self) -> EnvelopeStage {
         self.stage
@@ -158,8 +233,10 @@ impl AdsrEnvelope {
 
     /// Resets the envelope to idle state.
     pub fn reset(&mut self) {
+        self.note_held = false;
         self.stage = EnvelopeStage::Idle;
         self.current_level = 0.0;
+        self.linear_level = 0.0;
         self.stage_time = 0.0;
     }
 
@@ -168,6 +245,15 @@ impl AdsrEnvelope {
         self.attack = attack * self.sample_rate;
     }
 
+    /// Sets the hold time at peak level between attack and decay.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_s` - Hold time in seconds (0.0 skips the hold stage)
+    pub fn set_hold(&mut self, time_s: f32) {
+        self.hold = time_s.max(0.0) * self.sample_rate;
+    }
+
     /// Sets the decay time.
     pub fn set_decay(&mut self, decay: f32) {
         self.decay = decay * self.sample_rate;
@@ -183,13 +269,71 @@ impl AdsrEnvelope {
         self.release = release * self.sample_rate;
     }
 
+    /// Sets the loop mode.
+    ///
+    /// Looping envelopes restart their attack automatically while the note
+    /// is held, making them usable as modulation sources. `note_off` always
+    /// moves to the release stage, which then finishes normally.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Off, attack/decay loop or full loop
+    pub fn set_loop_mode(&mut self, mode: EnvLoopMode) {
+        self.loop_mode = mode;
+    }
+
+    /// Gets the loop mode.
+    pub fn loop_mode(&self) -> EnvLoopMode {
+        self.loop_mode
+    }
+
+    /// Sets the attack curvature.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - -1.0 (slow start) through 0.0 (linear) to 1.0 (fast start, analog-like)
+    pub fn set_attack_curve(&mut self, shape: f32) {
+        self.attack_curve = shape.clamp(-1.0, 1.0);
+    }
+
+    /// Sets the decay curvature.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - -1.0 (slow start) through 0.0 (linear) to 1.0 (fast start, analog-like)
+    pub fn set_decay_curve(&mut self, shape: f32) {
+        self.decay_curve = shape.clamp(-1.0, 1.0);
+    }
+
+    /// Sets the release curvature.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - -1.0 (slow start) through 0.0 (linear) to 1.0 (fast start, analog-like)
+    pub fn set_release_curve(&mut self, shape: f32) {
+        self.release_curve = shape.clamp(-1.0, 1.0);
+    }
+
+    /// Maps the linear ramp position onto the curved segment from `from` to `to`.
+    fn shaped(&self, from: f32, to: f32, shape: f32) -> f32 {
+        if shape == 0.0 || from == to {
+            return self.linear_level;
+        }
+
+        let progress = ((self.linear_level - from) / (to - from)).clamp(0.0, 1.0);
+        from + (to - from) * curve(progress, shape)
+    }
+
     /// Internal method to advance the envelope stage.
+    ///
+    /// Stages ramp `linear_level` linearly; the output level is that ramp
+    /// bent by the stage's curve shape.
     fn advance_stage(&mut self) {
         self.stage_time += 1.0;
 
         match self.stage {
             EnvelopeStage::Idle => {
-                self.current_level = 0.0;
+                self.linear_level = 0.0;
             }
 
             EnvelopeStage::Delay => {
@@ -201,13 +345,26 @@ impl AdsrEnvelope {
 
             EnvelopeStage::Attack => {
                 if self.attack > 0.0 {
-                    self.current_level += (self.peak - 0.0) / self.attack;
+                    self.linear_level += (self.peak - 0.0) / self.attack;
                 } else {
-                    self.current_level = self.peak;
+                    self.linear_level = self.peak;
+                }
+
+                if self.linear_level >= self.peak {
+                    self.linear_level = self.peak;
+                    self.stage = if self.hold > 0.0 {
+                        EnvelopeStage::Hold
+                    } else {
+                        EnvelopeStage::Decay
+                    };
+                    self.stage_time = 0.0;
                 }
+            }
 
-                if self.current_level >= self.peak {
-                    self.current_level = self.peak;
+            EnvelopeStage::Hold => {
+                self.linear_level = self.peak;
+
+                if self.stage_time >= self.hold {
                     self.stage = EnvelopeStage::Decay;
                     self.stage_time = 0.0;
                 }
@@ -215,47 +372,78 @@ impl AdsrEnvelope {
 
             EnvelopeStage::Decay => {
                 if self.decay > 0.0 {
-                    self.current_level += (self.sustain - self.peak) / self.decay;
+                    self.linear_level += (self.sustain - self.peak) / self.decay;
                 } else {
-                    self.current_level = self.sustain;
+                    self.linear_level = self.sustain;
                 }
 
-                if self.current_level <= self.sustain {
-                    self.current_level = self.sustain;
-                    self.stage = EnvelopeStage::Sustain;
+                if self.linear_level <= self.sustain {
+                    self.linear_level = self.sustain;
+                    self.stage = if self.loop_mode == EnvLoopMode::LoopAD && self.note_held {
+                        EnvelopeStage::Attack
+                    } else {
+                        EnvelopeStage::Sustain
+                    };
                     self.stage_time = 0.0;
                 }
             }
 
             EnvelopeStage::Sustain => {
                 // Sustain level is held until note off
-                self.current_level = self.sustain;
+                self.linear_level = self.sustain;
 
                 if self.stage_time >= self.sustain_hold {
-                    // Stay at sustain, waiting for note_off
+                    // Stay at sustain, waiting for note_off, unless looping
+                    if self.loop_mode == EnvLoopMode::LoopFull && self.note_held {
+                        self.enter_release();
+                    }
                 }
             }
 
             EnvelopeStage::Release => {
                 if self.release > 0.0 {
-                    self.current_level += (0.0 - self.sustain) / self.release;
+                    self.linear_level += (0.0 - self.sustain) / self.release;
                 } else {
-                    self.current_level = 0.0;
+                    self.linear_level = 0.0;
                 }
 
-                if self.current_level <= 0.0 {
-                    self.current_level = 0.0;
-                    self.stage = EnvelopeStage::Finished;
+                if self.linear_level <= 0.0 {
+                    self.linear_level = 0.0;
+                    if self.loop_mode == EnvLoopMode::LoopFull && self.note_held {
+                        self.stage = EnvelopeStage::Attack;
+                        self.stage_time = 0.0;
+                    } else {
+                        self.stage = EnvelopeStage::Finished;
+                    }
                 }
             }
 
             EnvelopeStage::Finished => {
-                self.current_level = 0.0;
+                self.linear_level = 0.0;
             }
         }
+
+        self.current_level = match self.stage {
+            EnvelopeStage::Attack => self.shaped(0.0, self.peak, self.attack_curve),
+            EnvelopeStage::Decay => self.shaped(self.peak, self.sustain, self.decay_curve),
+            EnvelopeStage::Release => self.shaped(self.release_start, 0.0, self.release_curve),
+            _ => self.linear_level,
+        };
     }
 }
 
+/// Curve of a segment at `progress` (0.0 to 1.0); positive shapes start fast.
+fn curve(progress: f32, shape: f32) -> f32 {
+    let k = shape * CURVE_STEEPNESS;
+    (1.0 - (-k * progress).exp()) / (1.0 - (-k).exp())
+}
+
+/// Inverse of [`curve`]: the progress at which the curve reaches `level`.
+fn curve_inverse(level: f32, shape: f32) -> f32 {
+    let k = shape * CURVE_STEEPNESS;
+    -(1.0 - level * (1.0 - (-k).exp())).ln() / k
+}
+
 impl Default for AdsrEnvelope {
     fn default() -> Self {
         Self::new()
@@ -302,6 +490,7 @@ mod tests {
     fn make_env(attack: f32, decay: f32, sustain: f32, release: f32, sr: f32) -> AdsrEnvelope {
         AdsrEnvelope::with_config(EnvelopeConfig {
             attack,
+            hold: 0.0,
             decay,
             sustain,
             release,
@@ -477,6 +666,7 @@ mod tests {
     fn test_delay_phase() {
         let mut env = AdsrEnvelope::with_config(EnvelopeConfig {
             attack: 0.01,
+            hold: 0.0,
             decay: 0.01,
             sustain: 0.5,
             release: 0.01,
@@ -555,4 +745,167 @@ mod tests {
             level
         );
     }
+
+    // --- Curves: shape 0 is the linear envelope ---
+    #[test]
+    fn test_zero_curve_matches_linear() {
+        let mut linear = make_env(0.01, 0.02, 0.5, 0.05, 1000.0);
+        let mut curved = make_env(0.01, 0.02, 0.5, 0.05, 1000.0);
+        curved.set_attack_curve(0.0);
+        curved.set_decay_curve(0.0);
+        curved.set_release_curve(0.0);
+
+        linear.note_on();
+        curved.note_on();
+        let attack = curved.process_samples(60);
+        assert_eq!(linear.process_samples(60), attack);
+
+        // 10-sample attack steps 0.1 per sample after the Delay -> Attack sample
+        for (k, level) in attack[1..10].iter().enumerate() {
+            assert!((level - 0.1 * (k + 1) as f32).abs() < 1e-5);
+        }
+
+        linear.note_off();
+        curved.note_off();
+        assert_eq!(linear.process_samples(60), curved.process_samples(60));
+    }
+
+    // --- Curves: positive shape front-loads the attack ---
+    #[test]
+    fn test_positive_attack_curve_front_loads() {
+        let mut linear = make_env(0.1, 0.1, 0.5, 0.1, 1000.0);
+        let mut curved = make_env(0.1, 0.1, 0.5, 0.1, 1000.0);
+        curved.set_attack_curve(1.0);
+
+        linear.note_on();
+        curved.note_on();
+        let linear_attack = linear.process_samples(100);
+        let curved_attack = curved.process_samples(100);
+
+        // Same stage timing, but the curved attack is higher part way through
+        assert_eq!(linear.stage(), curved.stage());
+        assert!(curved_attack[25] > linear_attack[25] + 0.2);
+        for w in curved_attack.windows(2) {
+            assert!(w[1] >= w[0], "Curved attack should stay monotonic");
+        }
+    }
+
+    // --- Curves: releasing mid-attack continues from the curved level ---
+    #[test]
+    fn test_curved_release_is_continuous() {
+        let mut env = make_env(0.1, 0.1, 0.5, 0.1, 1000.0);
+        env.set_attack_curve(0.8);
+        env.set_release_curve(0.8);
+        env.note_on();
+        let before = *env.process_samples(30).last().unwrap();
+        env.note_off();
+        let after = env.process();
+        assert!(after <= before && before - after < 0.1);
+    }
+
+    // --- Hold: delays the decay onset by the hold time ---
+    #[test]
+    fn test_hold_delays_decay() {
+        let mut plain = make_env(0.01, 0.05, 0.5, 0.05, 1000.0);
+        let mut held = make_env(0.01, 0.05, 0.5, 0.05, 1000.0);
+        held.set_hold(0.02); // 20 samples at 1000 Hz
+
+        let decay_onset = |env: &mut AdsrEnvelope| {
+            env.note_on();
+            let levels = env.process_samples(200);
+            let peak = levels.iter().position(|&l| l >= 1.0).unwrap();
+            peak + levels[peak..].iter().position(|&l| l < 1.0).unwrap()
+        };
+
+        assert_eq!(decay_onset(&mut held), decay_onset(&mut plain) + 20);
+    }
+
+    // --- Hold: the stage is reported and holds the peak level ---
+    #[test]
+    fn test_hold_stage_at_peak() {
+        let mut env = make_env(0.0, 0.05, 0.5, 0.05, 1000.0);
+        env.set_hold(0.01);
+        env.note_on();
+        env.process_samples(3);
+        assert_eq!(env.stage(), EnvelopeStage::Hold);
+        assert_eq!(env.process(), 1.0);
+
+        // A note-off during hold goes straight to release
+        env.note_off();
+        assert_eq!(env.stage(), EnvelopeStage::Release);
+    }
+
+    // --- Loop: Off sustains exactly as before ---
+    #[test]
+    fn test_loop_off_unchanged() {
+        let mut plain = make_env(0.01, 0.02, 0.5, 0.05, 1000.0);
+        let mut looped = make_env(0.01, 0.02, 0.5, 0.05, 1000.0);
+        looped.set_loop_mode(EnvLoopMode::Off);
+
+        plain.note_on();
+        looped.note_on();
+        assert_eq!(plain.process_samples(500), looped.process_samples(500));
+        assert_eq!(looped.stage(), EnvelopeStage::Sustain);
+    }
+
+    // --- Loop: LoopAD retriggers the attack at the end of decay ---
+    #[test]
+    fn test_loop_ad_retriggers() {
+        let mut env = make_env(0.01, 0.02, 0.2, 0.05, 1000.0);
+        env.set_loop_mode(EnvLoopMode::LoopAD);
+        env.note_on();
+
+        let levels = env.process_samples(200);
+        let peaks = levels
+            .windows(2)
+            .filter(|w| w[0] < 1.0 && w[1] >= 1.0)
+            .count();
+        assert!(peaks >= 5, "Expected repeated attacks, got {} peaks", peaks);
+        assert!(levels[50..].iter().all(|&l| l >= 0.2 - 1e-6));
+    }
+
+    // --- Loop: LoopFull runs through release and back to attack ---
+    #[test]
+    fn test_loop_full_cycles_through_release() {
+        let mut env = AdsrEnvelope::with_config(EnvelopeConfig {
+            attack: 0.01,
+            decay: 0.01,
+            sustain: 0.5,
+            release: 0.01,
+            sustain_hold: 0.01,
+            sample_rate: 1000.0,
+            ..Default::default()
+        });
+        env.set_loop_mode(EnvLoopMode::LoopFull);
+        env.note_on();
+
+        let mut stages = Vec::new();
+        for _ in 0..100 {
+            env.process();
+            if stages.last() != Some(&env.stage()) {
+                stages.push(env.stage());
+            }
+        }
+        let releases = stages
+            .iter()
+            .filter(|&&s| s == EnvelopeStage::Release)
+            .count();
+        assert!(releases >= 2, "Stages: {:?}", stages);
+        assert!(env.is_active());
+    }
+
+    // --- Loop: note_off still forces the release and finishes ---
+    #[test]
+    fn test_loop_note_off_releases() {
+        for mode in [EnvLoopMode::LoopAD, EnvLoopMode::LoopFull] {
+            let mut env = make_env(0.01, 0.01, 0.5, 0.02, 1000.0);
+            env.set_loop_mode(mode);
+            env.note_on();
+            env.process_samples(55);
+            env.note_off();
+            assert_eq!(env.stage(), EnvelopeStage::Release);
+            env.process_samples(100);
+            assert_eq!(env.stage(), EnvelopeStage::Finished);
+        }
+    }
 }
@@ -28,6 +28,23 @@
 
 use std::f32::consts::PI;
 
+/// Magnitude below which feedback state is flushed to zero
+const DENORMAL_THRESHOLD: f32 = 1e-20;
+
+/// Flushes near-silent feedback state to zero.
+///
+/// A decaying feedback loop would otherwise end up in denormal floats,
+/// which are many times slower to process on some CPUs. Values this small
+/// are far below audibility.
+#[inline]
+pub(crate) fn flush_denormal(x: f32) -> f32 {
+    if x.abs() < DENORMAL_THRESHOLD {
+        0.0
+    } else {
+        x
+    }
+}
+
 /// Enumeration of supported filter types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FilterType {
@@ -377,6 +394,22 @@ impl Filter {
     }
 }
 
+/// MIDI note at which keyboard tracking leaves the cutoff unchanged (C4).
+const KEYTRACK_CENTER_NOTE: u8 = 60;
+
+/// Ladder resonance at which the filter starts to self-oscillate.
+pub const ZDF_SELF_OSCILLATION: f32 = 4.0;
+
+/// Maximum ladder resonance, slightly past the self-oscillation threshold.
+pub const ZDF_MAX_RESONANCE: f32 = 4.5;
+
+/// Default cutoff and resonance smoothing time constant in milliseconds.
+pub const ZDF_DEFAULT_SMOOTHING_MS: f32 = 5.0;
+
+/// Tiny input excitation standing in for analog circuit noise, so a
+/// self-oscillating filter starts ringing even on digital silence.
+const SELF_OSCILLATION_SEED: f32 = 1e-6;
+
 /// Enumeration of ZDF (Zero-Delay Feedback) filter modes.
 /// ZDF filters use a ladder topology similar to classic analog synthesizers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -389,18 +422,27 @@ pub enum ZdfFilterMode {
 
     /// 2-pole highpass filter (12 dB/octave)
     HighPass2,
+
+    /// 4-pole highpass filter (24 dB/octave)
+    HighPass4,
+
+    /// 2-pole bandpass filter (6 dB/octave each side)
+    BandPass2,
+
+    /// 4-pole bandpass filter (12 dB/octave each side)
+    BandPass4,
 }
 
 /// Configuration structure for ZDF filter parameters.
 #[derive(Debug, Clone, Copy)]
 pub struct ZdfFilterConfig {
-    /// Filter mode (lowpass 2/4 pole, highpass)
+    /// Filter mode (lowpass, highpass or bandpass, 2 or 4 pole)
     pub mode: ZdfFilterMode,
 
     /// Cutoff frequency in Hz (typically 20 to 20000)
     pub cutoff_frequency: f32,
 
-    /// Resonance factor (0.0 to ~4.0 for self-oscillation)
+    /// Resonance factor (0.0 to 4.5; self-oscillates from 4.0)
     pub resonance: f32,
 
     /// Drive amount for analog saturation (0.0 to ~10.0)
@@ -431,18 +473,25 @@ impl Default for ZdfFilterConfig {
 ///
 /// The filter uses a series of 4 one-pole lowpass sections (integrators) connected
 /// in a feedback loop. The feedback signal is calculated without delay, creating
-/// a more accurate simulation of analog circuitry.
+/// a more accurate simulation of analog circuitry. From a resonance of 4.0 the
+/// loop self-oscillates into a sine at the cutoff frequency; the saturating
+/// ladder input keeps the oscillation bounded.
 ///
 /// # Characteristics
 ///
 /// - **LowPass4**: Classic Moog ladder sound with 24 dB/octave rolloff
 /// - **LowPass2**: Softer 12 dB/octave lowpass
-/// - **HighPass2**: 12 dB/octave highpass with similar character
+/// - **HighPass2/HighPass4**: 12/24 dB/octave highpass with similar character
+/// - **BandPass2/BandPass4**: Bandpass around the cutoff
+///
+/// The highpass and bandpass responses are binomial mixes of the ladder
+/// input and stage outputs (the "ladder as multimode" trick), so they share
+/// the ladder's resonance and drive behavior.
 ///
 /// # Parameters
 ///
 /// - **Cutoff**: Frequency where filtering begins (20 Hz to 20 kHz)
-/// - **Resonance**: Emphasizes frequencies near cutoff (0.0 to ~4.0)
+/// - **Resonance**: Emphasizes frequencies near cutoff (0.0 to 4.5)
 /// - **Drive**: Input gain that creates harmonic saturation
 #[derive(Debug, Clone)]
 pub struct ZdfFilter {
@@ -455,20 +504,41 @@ pub struct ZdfFilter {
     /// Current filter mode
     mode: ZdfFilterMode,
 
-    /// Current cutoff frequency
+    /// Target cutoff frequency
     cutoff: f32,
 
-    /// Current resonance value
+    /// Target resonance value
     resonance: f32,
 
+    /// Cutoff the filter is running at, gliding towards `cutoff`
+    smoothed_cutoff: f32,
+
+    /// Resonance the filter is running at, gliding towards `resonance`
+    smoothed_resonance: f32,
+
+    /// Smoothing time constant in milliseconds (0.0 = no smoothing)
+    smoothing_ms: f32,
+
+    /// One-pole smoothing coefficient derived from `smoothing_ms`
+    smoothing_coeff: f32,
+
     /// Current drive amount
     drive: f32,
 
-    /// Pre-calculated frequency coefficient (fc)
+    /// Pre-calculated one-pole gain G = g / (1 + g), g = tan(pi * fc / fs)
     fc: f32,
 
+    /// Whether to make up the low-pass passband level lost to resonance
+    gain_comp: bool,
+
     /// Sample rate for internal calculations
     sample_rate: f32,
+
+    /// Keyboard tracking amount (1.0 = cutoff follows pitch 1:1)
+    keytrack: f32,
+
+    /// MIDI note the cutoff is tracking
+    key_note: u8,
 }
 
 impl ZdfFilter {
@@ -487,6 +557,7 @@ impl ZdfFilter {
     ///
     /// A configured ZdfFilter instance
     pub fn with_config(config: ZdfFilterConfig) -> Self {
+        let resonance = config.resonance.clamp(0.0, ZDF_MAX_RESONANCE);
         let mut filter = Self {
             v0: 0.0,
             v1: 0.0,
@@ -494,12 +565,20 @@ impl ZdfFilter {
             v3: 0.0,
             mode: config.mode,
             cutoff: config.cutoff_frequency,
-            resonance: config.resonance,
+            resonance,
+            smoothed_cutoff: config.cutoff_frequency,
+            smoothed_resonance: resonance,
+            smoothing_ms: ZDF_DEFAULT_SMOOTHING_MS,
+            smoothing_coeff: 0.0,
             drive: config.drive,
             fc: 0.0,
+            gain_comp: false,
             sample_rate: config.sample_rate,
+            keytrack: 0.0,
+            key_note: KEYTRACK_CENTER_NOTE,
         };
 
+        filter.calculate_smoothing_coeff();
         filter.calculate_coefficients();
         filter
     }
@@ -518,49 +597,44 @@ impl ZdfFilter {
     ///
     /// Filtered output sample
     pub fn process_sample(&mut self, input: f32) -> f32 {
-        // Apply drive to input signal
-        let driven_input = input * (1.0 + self.drive);
+        self.smooth_parameters();
 
-        // Calculate feedback compensation factor
-        // This prevents the filter from becoming unstable at high resonance
-        let feedback_comp = 1.0 + self.resonance;
-
-        // Calculate the input to the first integrator stage
-        // The feedback is subtracted from input, then compensated
-        let input_stage = (driven_input - self.resonance * self.v3) / feedback_comp;
+        // Apply drive to input signal
+        let mut driven_input = input * (1.0 + self.drive);
+        if self.smoothed_resonance >= ZDF_SELF_OSCILLATION {
+            driven_input += SELF_OSCILLATION_SEED;
+        }
 
-        // Apply hyperbolic tangent for soft clipping (analog saturation in each stage)
-        // This adds smooth harmonic content similar to analog circuitry
-        let s0 = input_stage.tanh();
+        let g = self.fc;
+        let k = self.smoothed_resonance;
 
-        // First integrator stage
-        // v1(n) = v1(n-1) + fc * (s0 - v1(n-1))
-        // Using trapezoidal integration for better accuracy
-        let s1 = (s0 - self.v1) * self.fc + self.v1;
-        let v1_new = s1.tanh();
+        // The ladder output is linear in its input: y4 = g^4 * u + sigma,
+        // where sigma is the contribution of the stored integrator states
+        let sigma = (1.0 - g) * (g * g * g * self.v0 + g * g * self.v1 + g * self.v2 + self.v3);
 
-        // Second integrator stage
-        let s2 = (v1_new - self.v2) * self.fc + self.v2;
-        let v2_new = s2.tanh();
+        // Solve u = x - k * y4 for the ladder input without a unit delay,
+        // then apply hyperbolic tangent soft clipping (analog saturation)
+        let u = ((driven_input - k * sigma) / (1.0 + k * g * g * g * g)).tanh();
 
-        // Third integrator stage
-        let s3 = (v2_new - self.v3) * self.fc + self.v3;
-        let v3_new = s3.tanh();
+        // Four trapezoidal one-pole lowpass stages
+        let y1 = tpt_one_pole(&mut self.v0, g, u);
+        let y2 = tpt_one_pole(&mut self.v1, g, y1);
+        let y3 = tpt_one_pole(&mut self.v2, g, y2);
+        let y4 = tpt_one_pole(&mut self.v3, g, y3);
 
-        // Update state variables
-        self.v0 = s0;
-        self.v1 = v1_new;
-        self.v2 = v2_new;
-        self.v3 = v3_new;
+        // Resonance lowers the low-pass passband to 1 / (1 + k)
+        let comp = if self.gain_comp { 1.0 + k } else { 1.0 };
 
         // Select output based on filter mode
         match self.mode {
-            ZdfFilterMode::LowPass4 => v3_new,
-            ZdfFilterMode::LowPass2 => v2_new,
-            ZdfFilterMode::HighPass2 => {
-                // Highpass = input - lowpass2
-                s0 - v2_new
-            }
+            ZdfFilterMode::LowPass4 => y4 * comp,
+            ZdfFilterMode::LowPass2 => y2 * comp,
+            // (1 - L)^2 and (1 - L)^4, where L is one lowpass stage
+            ZdfFilterMode::HighPass2 => u - 2.0 * y1 + y2,
+            ZdfFilterMode::HighPass4 => u - 4.0 * y1 + 6.0 * y2 - 4.0 * y3 + y4,
+            // 2L(1 - L) and 4L^2(1 - L)^2
+            ZdfFilterMode::BandPass2 => 2.0 * (y1 - y2),
+            ZdfFilterMode::BandPass4 => 4.0 * (y2 - 2.0 * y3 + y4),
         }
     }
 
@@ -586,22 +660,78 @@ impl ZdfFilter {
 
     /// Sets the cutoff frequency.
     ///
+    /// The filter glides to the new cutoff over the smoothing time.
+    ///
     /// # Arguments
     ///
     /// * `cutoff` - New cutoff frequency in Hz (20 to sample_rate/2)
     pub fn set_cutoff(&mut self, cutoff: f32) {
         self.cutoff = cutoff.clamp(20.0, self.sample_rate / 2.0);
-        self.calculate_coefficients();
     }
 
     /// Sets the resonance (Q) value.
     ///
+    /// The filter glides to the new resonance over the smoothing time.
+    ///
     /// # Arguments
     ///
-    /// * `resonance` - New resonance value (0.0 to ~4.0)
+    /// * `resonance` - New resonance value (0.0 to 4.5; self-oscillates from 4.0)
     pub fn set_resonance(&mut self, resonance: f32) {
-        self.resonance = resonance.max(0.0);
-        self.calculate_coefficients();
+        self.resonance = resonance.clamp(0.0, ZDF_MAX_RESONANCE);
+    }
+
+    /// Sets the cutoff and resonance smoothing time.
+    ///
+    /// Parameter changes approach their target with a one-pole lag, which
+    /// avoids zipper noise when automating in coarse steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_ms` - Time constant in milliseconds (0.0 = jump immediately)
+    pub fn set_smoothing_time(&mut self, time_ms: f32) {
+        self.smoothing_ms = time_ms.max(0.0);
+        self.calculate_smoothing_coeff();
+    }
+
+    /// Gets the target cutoff frequency in Hz.
+    pub fn cutoff(&self) -> f32 {
+        self.cutoff
+    }
+
+    /// Gets the keyboard tracking amount.
+    pub fn keytrack(&self) -> f32 {
+        self.keytrack
+    }
+
+    /// Gets the target resonance.
+    pub fn resonance(&self) -> f32 {
+        self.resonance
+    }
+
+    /// Gets the cutoff the filter is currently running at.
+    ///
+    /// # Returns
+    ///
+    /// Smoothed cutoff frequency in Hz (before keyboard tracking)
+    pub fn smoothed_cutoff(&self) -> f32 {
+        self.smoothed_cutoff
+    }
+
+    /// Enables or disables resonance gain compensation.
+    ///
+    /// A ladder's low-pass passband drops as resonance rises; compensation
+    /// scales the low-pass outputs back up by the same factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to compensate the passband level
+    pub fn set_gain_comp(&mut self, enabled: bool) {
+        self.gain_comp = enabled;
+    }
+
+    /// Checks if resonance gain compensation is enabled.
+    pub fn gain_comp(&self) -> bool {
+        self.gain_comp
     }
 
     /// Sets the drive amount for analog saturation.
@@ -613,6 +743,45 @@ impl ZdfFilter {
         self.drive = drive.max(0.0);
     }
 
+    /// Gets the drive amount.
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Sets how strongly the cutoff follows the played note.
+    ///
+    /// The cutoff is unchanged at middle C; at 1.0 each octave above
+    /// doubles it, so the filter brightness stays even across the keyboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Keytrack amount (0.0 = off, 1.0 = full tracking)
+    pub fn set_keytrack(&mut self, amount: f32) {
+        self.keytrack = amount.clamp(0.0, 2.0);
+        self.calculate_coefficients();
+    }
+
+    /// Sets the MIDI note the cutoff tracks (call on note-on).
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - MIDI note number (0-127)
+    pub fn set_note(&mut self, note: u8) {
+        self.key_note = note.min(127);
+        self.calculate_coefficients();
+    }
+
+    /// Gets the cutoff after keyboard tracking.
+    ///
+    /// # Returns
+    ///
+    /// Effective cutoff frequency in Hz
+    pub fn effective_cutoff(&self) -> f32 {
+        let octaves = (self.key_note as f32 - KEYTRACK_CENTER_NOTE as f32) / 12.0;
+        (self.smoothed_cutoff * 2.0f32.powf(octaves * self.keytrack))
+            .clamp(20.0, self.sample_rate / 2.0)
+    }
+
     /// Sets the sample rate and recalculates coefficients.
     ///
     /// # Arguments
@@ -620,34 +789,73 @@ impl ZdfFilter {
     /// * `sample_rate` - New sample rate in Hz
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate = sample_rate;
+        self.calculate_smoothing_coeff();
         self.calculate_coefficients();
     }
 
-    /// Resets the filter state to zero.
+    /// Resets the filter state to zero and snaps parameters to their targets.
     pub fn reset(&mut self) {
         self.v0 = 0.0;
         self.v1 = 0.0;
         self.v2 = 0.0;
         self.v3 = 0.0;
+        self.smoothed_cutoff = self.cutoff;
+        self.smoothed_resonance = self.resonance;
+        self.calculate_coefficients();
+    }
+
+    /// Advances the cutoff and resonance smoothers by one sample.
+    fn smooth_parameters(&mut self) {
+        if self.smoothed_cutoff == self.cutoff && self.smoothed_resonance == self.resonance {
+            return;
+        }
+
+        let rate = 1.0 - self.smoothing_coeff;
+        self.smoothed_cutoff += (self.cutoff - self.smoothed_cutoff) * rate;
+        self.smoothed_resonance += (self.resonance - self.smoothed_resonance) * rate;
+
+        // Snap once inaudibly close so the coefficients stop being recomputed
+        if (self.cutoff - self.smoothed_cutoff).abs() < self.cutoff * 1e-4 {
+            self.smoothed_cutoff = self.cutoff;
+        }
+        if (self.resonance - self.smoothed_resonance).abs() < 1e-5 {
+            self.smoothed_resonance = self.resonance;
+        }
+
+        self.calculate_coefficients();
+    }
+
+    /// Calculates the one-pole smoothing coefficient from the smoothing time.
+    fn calculate_smoothing_coeff(&mut self) {
+        let samples = self.smoothing_ms * 0.001 * self.sample_rate;
+        self.smoothing_coeff = if samples > 0.0 {
+            (-1.0 / samples).exp()
+        } else {
+            0.0
+        };
     }
 
     /// Calculates the frequency coefficient from cutoff frequency.
     ///
-    /// Uses the formula: fc = 2 * sin(pi * cutoff / sample_rate)
-    /// This provides accurate frequency tracking across the audible range.
+    /// Uses the bilinear-transform prewarped gain g = tan(pi * cutoff / sample_rate),
+    /// so the cutoff (and the self-oscillation pitch) lands exactly on frequency.
     fn calculate_coefficients(&mut self) {
-        // Calculate normalized frequency
-        let normalized_freq = self.cutoff / self.sample_rate;
-
-        // Calculate frequency coefficient using sin approximation
-        // This provides accurate cutoff tracking
-        self.fc = 2.0 * (PI * normalized_freq).sin();
+        // Calculate normalized frequency, kept below Nyquist where tan() blows up
+        let normalized_freq = (self.effective_cutoff() / self.sample_rate).min(0.49);
 
-        // Clamp to prevent instability
-        self.fc = self.fc.clamp(0.0, 2.0);
+        let g = (PI * normalized_freq).tan();
+        self.fc = g / (1.0 + g);
     }
 }
 
+/// One trapezoidal (TPT) one-pole lowpass step; returns the stage output.
+fn tpt_one_pole(state: &mut f32, g: f32, input: f32) -> f32 {
+    let v = (input - *state) * g;
+    let output = v + *state;
+    *state = flush_denormal(output + v);
+    output
+}
+
 impl Default for ZdfFilter {
     fn default() -> Self {
         Self::new()
@@ -751,6 +959,19 @@ mod tests {
         (sum_sq / signal.len() as f32).sqrt()
     }
 
+    // --- Denormal guard ---
+    #[test]
+    fn test_flush_denormal_zeroes_tiny_values() {
+        let denormal = f32::MIN_POSITIVE / 4.0;
+        assert!(denormal.is_subnormal());
+
+        assert_eq!(flush_denormal(denormal), 0.0);
+        assert_eq!(flush_denormal(-denormal), 0.0);
+        assert_eq!(flush_denormal(1e-25), 0.0);
+        assert_eq!(flush_denormal(1e-6), 1e-6);
+        assert_eq!(flush_denormal(-0.5), -0.5);
+    }
+
     // --- Biquad: Lowpass attenuates high frequencies ---
     #[test]
     fn test_lowpass_attenuates_high_frequencies() {
@@ -1070,6 +1291,193 @@ mod tests {
         );
     }
 
+    // --- ZDF: Full keytrack doubles the cutoff per octave ---
+    #[test]
+    fn test_zdf_keytrack_octave_doubles_cutoff() {
+        let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+            cutoff_frequency: 800.0,
+            ..Default::default()
+        });
+
+        // No tracking by default
+        zdf.set_note(72);
+        assert_eq!(zdf.effective_cutoff(), 800.0);
+
+        zdf.set_keytrack(1.0);
+        zdf.set_note(60);
+        let base = zdf.effective_cutoff();
+        zdf.set_note(72);
+        assert!((zdf.effective_cutoff() - 2.0 * base).abs() < 1e-2);
+
+        zdf.set_keytrack(0.5);
+        zdf.set_note(84);
+        assert!((zdf.effective_cutoff() - 2.0 * base).abs() < 1e-2);
+    }
+
+    // --- Helper: steady-state RMS of a ZDF mode for a sine input ---
+    fn zdf_mode_rms(mode: ZdfFilterMode, freq: f32) -> f32 {
+        let sample_rate = 44100.0;
+        let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+            mode,
+            cutoff_frequency: 1000.0,
+            resonance: 0.5,
+            drive: 0.0,
+            sample_rate,
+        });
+        let out: Vec<f32> = generate_sine(freq, sample_rate, 8192)
+            .iter()
+            .map(|&s| zdf.process_sample(s * 0.1))
+            .collect();
+        rms(&out[4096..]) / (0.1 * std::f32::consts::FRAC_1_SQRT_2)
+    }
+
+    // --- ZDF: Highpass modes block DC and pass highs, unlike lowpass ---
+    #[test]
+    fn test_zdf_highpass_modes() {
+        for mode in [ZdfFilterMode::HighPass2, ZdfFilterMode::HighPass4] {
+            let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+                mode,
+                cutoff_frequency: 1000.0,
+                resonance: 0.5,
+                ..Default::default()
+            });
+            let dc: Vec<f32> = (0..8192).map(|_| zdf.process_sample(0.1)).collect();
+            assert!(dc[8191].abs() < 1e-4, "{:?} should block DC", mode);
+
+            let high = zdf_mode_rms(mode, 12000.0);
+            let low = zdf_mode_rms(mode, 100.0);
+            assert!(high > 0.5, "{:?} should pass highs, gain {}", mode, high);
+            assert!(low < 0.05, "{:?} should cut lows, gain {}", mode, low);
+        }
+
+        // Lowpass is the opposite
+        assert!(zdf_mode_rms(ZdfFilterMode::LowPass4, 100.0) > 0.5);
+        assert!(zdf_mode_rms(ZdfFilterMode::LowPass4, 12000.0) < 0.05);
+    }
+
+    // --- ZDF: Bandpass modes peak around the cutoff ---
+    #[test]
+    fn test_zdf_bandpass_modes() {
+        for mode in [ZdfFilterMode::BandPass2, ZdfFilterMode::BandPass4] {
+            let center = zdf_mode_rms(mode, 1000.0);
+            assert!(
+                center > zdf_mode_rms(mode, 100.0) * 2.0,
+                "{:?} low skirt",
+                mode
+            );
+            assert!(
+                center > zdf_mode_rms(mode, 10000.0) * 2.0,
+                "{:?} high skirt",
+                mode
+            );
+        }
+    }
+
+    // --- ZDF: Past the threshold the filter rings on silence at the cutoff ---
+    #[test]
+    fn test_zdf_self_oscillation() {
+        let sample_rate = 44100.0;
+        let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+            mode: ZdfFilterMode::LowPass4,
+            cutoff_frequency: 1000.0,
+            resonance: ZDF_MAX_RESONANCE,
+            drive: 0.0,
+            sample_rate,
+        });
+
+        let out: Vec<f32> = (0..88200).map(|_| zdf.process_sample(0.0)).collect();
+        let tail = &out[44100..];
+        assert!(
+            rms(tail) > 0.05,
+            "Expected sustained oscillation, RMS={}",
+            rms(tail)
+        );
+        assert!(out.iter().all(|s| s.is_finite() && s.abs() < 2.0));
+
+        let crossings = tail
+            .windows(2)
+            .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+            .count();
+        assert!(
+            (crossings as f32 - 1000.0).abs() < 50.0,
+            "Oscillation should sit at the cutoff, got {} Hz",
+            crossings
+        );
+
+        // Below the threshold silence stays silent
+        let mut quiet = ZdfFilter::with_config(ZdfFilterConfig {
+            resonance: 3.5,
+            ..Default::default()
+        });
+        assert!((0..4410).all(|_| quiet.process_sample(0.0) == 0.0));
+    }
+
+    // --- ZDF: Gain compensation restores the passband at high resonance ---
+    #[test]
+    fn test_zdf_gain_comp_keeps_passband() {
+        let sample_rate = 44100.0;
+        let signal: Vec<f32> = generate_sine(100.0, sample_rate, 8192)
+            .iter()
+            .map(|s| s * 0.1)
+            .collect();
+
+        let passband = |gain_comp: bool| {
+            let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+                mode: ZdfFilterMode::LowPass4,
+                cutoff_frequency: 2000.0,
+                resonance: 3.0,
+                drive: 0.0,
+                sample_rate,
+            });
+            zdf.set_gain_comp(gain_comp);
+            let out: Vec<f32> = signal.iter().map(|&s| zdf.process_sample(s)).collect();
+            rms(&out[2048..]) / rms(&signal[2048..])
+        };
+
+        assert!(passband(false) < 0.3, "Uncompensated ladder loses level");
+        let compensated = passband(true);
+        assert!(
+            (compensated - 1.0).abs() < 0.1,
+            "Compensated passband gain {}",
+            compensated
+        );
+    }
+
+    // --- ZDF: Cutoff steps glide over the smoothing time ---
+    #[test]
+    fn test_zdf_cutoff_smoothing() {
+        let sample_rate = 48000.0;
+        let mut zdf = ZdfFilter::with_config(ZdfFilterConfig {
+            cutoff_frequency: 200.0,
+            sample_rate,
+            ..Default::default()
+        });
+
+        zdf.set_cutoff(5200.0);
+        assert_eq!(zdf.smoothed_cutoff(), 200.0, "No jump before processing");
+
+        // One time constant (5 ms = 240 samples) covers ~63% of the step
+        zdf.process_sample(0.0);
+        assert!(zdf.smoothed_cutoff() < 300.0);
+        for _ in 1..240 {
+            zdf.process_sample(0.0);
+        }
+        let progress = (zdf.smoothed_cutoff() - 200.0) / 5000.0;
+        assert!((progress - 0.632).abs() < 0.01, "Progress {}", progress);
+
+        // Settled after a few more time constants
+        for _ in 0..2400 {
+            zdf.process_sample(0.0);
+        }
+        assert_eq!(zdf.smoothed_cutoff(), 5200.0);
+
+        // Zero smoothing time jumps straight to the target
+        zdf.set_smoothing_time(0.0);
+        zdf.set_cutoff(1000.0);
+        zdf.process_sample(0.0);
+        assert_eq!(zdf.smoothed_cutoff(), 1000.0);
+    }
+
     // --- ZDF: Wrapper bypass ---
     #[test]
     fn test_zdf_wrapper_bypass_passthrough() {
@@ -1119,10 +1527,49 @@ mod tests {
             diff
         );
     }
+
+    #[test]
+    fn test_biquad_set_param_cutoff_and_resonance() {
+        use crate::effects::{Effect, EffectParameterId};
+
+        let mut filter = BiquadFilter::new();
+
+        filter.set_param(EffectParameterId::FilterCutoff, 0.0);
+        assert!((filter.cutoff - 20.0).abs() < 0.01);
+
+        filter.set_param(EffectParameterId::FilterCutoff, 0.5);
+        assert!((filter.cutoff - 632.46).abs() < 0.1);
+
+        filter.set_param(EffectParameterId::FilterResonance, 1.0);
+        assert!((filter.resonance - 20.0).abs() < 0.001);
+
+        // Parameters the filter does not own are ignored
+        filter.set_param(EffectParameterId::ChorusRate, 5.0);
+        assert!((filter.cutoff - 632.46).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_biquad_get_param_round_trip() {
+        use crate::effects::{Effect, EffectParameterId};
+
+        let mut filter = BiquadFilter::new();
+
+        filter.set_param(EffectParameterId::FilterCutoff, 0.3);
+        let cutoff = filter.get_param(EffectParameterId::FilterCutoff).unwrap();
+        assert!((cutoff - 0.3).abs() < 1e-4);
+
+        filter.set_param(EffectParameterId::FilterResonance, 0.6);
+        let resonance = filter
+            .get_param(EffectParameterId::FilterResonance)
+            .unwrap();
+        assert!((resonance - 0.6).abs() < 1e-4);
+
+        assert_eq!(filter.get_param(EffectParameterId::EqLowGain), None);
+    }
 }
 
 // Import Effect trait for BiquadFilter implementation
-use crate::effects::Effect;
+use crate::effects::{Effect, EffectParameterId, EffectType};
 
 /// Effect trait implementation for BiquadFilter
 impl Effect for BiquadFilter {
@@ -1165,4 +1612,29 @@ impl Effect for BiquadFilter {
     fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    fn set_param(&mut self, id: EffectParameterId, value: f32) {
+        match id {
+            // Normalized cutoff maps exponentially onto 20 Hz - 20 kHz
+            EffectParameterId::FilterCutoff => {
+                self.set_cutoff(20.0 * 1000.0f32.powf(value.clamp(0.0, 1.0)))
+            }
+            EffectParameterId::FilterResonance => self.set_intensity(value.clamp(0.0, 1.0)),
+            EffectParameterId::Mix => self.set_mix(value),
+            _ => {}
+        }
+    }
+
+    fn get_param(&self, id: EffectParameterId) -> Option<f32> {
+        match id {
+            EffectParameterId::FilterCutoff => Some((self.cutoff / 20.0).log10() / 3.0),
+            EffectParameterId::FilterResonance => Some((self.resonance - 0.1) / 19.9),
+            EffectParameterId::Mix => Some(self.mix),
+            _ => None,
+        }
+    }
+
+    fn effect_type(&self) -> EffectType {
+        EffectType::Filter
+    }
 }
@@ -20,6 +20,76 @@
 //! - **Trill**: Rapid alternation between two notes
 
 use crate::oscillator::{Oscillator, OscillatorConfig, OversampleFactor, Waveform};
+use std::fmt;
+
+/// Tempo assumed for synced rates until a host tempo is set.
+pub const DEFAULT_BPM: f32 = 120.0;
+
+/// Musical note length one tempo-synced LFO cycle lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteDivision {
+    /// 1/1 (whole note)
+    Whole,
+    /// 1/2 (half note)
+    Half,
+    #[default]
+    /// 1/4 (quarter note)
+    Quarter,
+    /// 1/4T (quarter-note triplet)
+    QuarterTriplet,
+    /// 1/8. (dotted eighth note)
+    DottedEighth,
+    /// 1/8 (eighth note)
+    Eighth,
+    /// 1/8T (eighth-note triplet)
+    EighthTriplet,
+    /// 1/16 (sixteenth note)
+    Sixteenth,
+    /// 1/16T (sixteenth-note triplet)
+    SixteenthTriplet,
+    /// 1/32 (thirty-second note)
+    ThirtySecond,
+}
+
+impl fmt::Display for NoteDivision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoteDivision::Whole => write!(f, "1/1"),
+            NoteDivision::Half => write!(f, "1/2"),
+            NoteDivision::Quarter => write!(f, "1/4"),
+            NoteDivision::QuarterTriplet => write!(f, "1/4T"),
+            NoteDivision::DottedEighth => write!(f, "1/8."),
+            NoteDivision::Eighth => write!(f, "1/8"),
+            NoteDivision::EighthTriplet => write!(f, "1/8T"),
+            NoteDivision::Sixteenth => write!(f, "1/16"),
+            NoteDivision::SixteenthTriplet => write!(f, "1/16T"),
+            NoteDivision::ThirtySecond => write!(f, "1/32"),
+        }
+    }
+}
+
+impl NoteDivision {
+    /// Length of the division in quarter-note beats.
+    pub fn beats(&self) -> f32 {
+        match self {
+            NoteDivision::Whole => 4.0,
+            NoteDivision::Half => 2.0,
+            NoteDivision::Quarter => 1.0,
+            NoteDivision::QuarterTriplet => 2.0 / 3.0,
+            NoteDivision::DottedEighth => 0.75,
+            NoteDivision::Eighth => 0.5,
+            NoteDivision::EighthTriplet => 1.0 / 3.0,
+            NoteDivision::Sixteenth => 0.25,
+            NoteDivision::SixteenthTriplet => 1.0 / 6.0,
+            NoteDivision::ThirtySecond => 0.125,
+        }
+    }
+
+    /// Converts the division to a duration in seconds at the given BPM.
+    pub fn to_duration(&self, bpm: f32) -> f32 {
+        self.beats() * 60.0 / bpm
+    }
+}
 
 /// LFO rate representation.
 #[derive(Debug, Clone, Copy)]
@@ -32,15 +102,24 @@ pub enum LfoRate {
 
     /// Synchronized to tempo (beats per second)
     Sync(f32),
+
+    /// One cycle per note division at the host tempo
+    Synced(NoteDivision),
 }
 
 impl LfoRate {
-    /// Converts LfoRate to Hz.
+    /// Converts LfoRate to Hz, assuming [`DEFAULT_BPM`] for synced rates.
     pub fn to_hertz(&self) -> f32 {
+        self.to_hertz_at(DEFAULT_BPM)
+    }
+
+    /// Converts LfoRate to Hz at the given tempo.
+    pub fn to_hertz_at(&self, bpm: f32) -> f32 {
         match self {
             LfoRate::Hertz(hz) => *hz,
             LfoRate::MidiNote(note) => 440.0 * 2.0f32.powf((*note as f32 - 69.0) / 12.0),
             LfoRate::Sync(beats_per_sec) => *beats_per_sec,
+            LfoRate::Synced(division) => 1.0 / division.to_duration(bpm),
         }
     }
 }
@@ -122,6 +201,15 @@ pub struct Lfo {
 
     /// Sample rate for timing
     sample_rate: f32,
+
+    /// Whether `retrigger` restarts the LFO at its start phase
+    retrigger: bool,
+
+    /// Current rate, kept so synced rates can follow tempo changes
+    rate: LfoRate,
+
+    /// Host tempo in BPM for synced rates
+    bpm: f32,
 }
 
 impl Lfo {
@@ -151,13 +239,20 @@ impl Lfo {
             oversample_factor: OversampleFactor::None,
         };
 
+        let mut oscillator = Oscillator::new(osc_config);
+        // Free-running until retrigger is enabled
+        oscillator.set_phase_reset(false);
+
         Self {
-            oscillator: Oscillator::new(osc_config),
+            oscillator,
             depth: config.depth,
             delay_counter: 0,
             delay_samples: config.delay_samples,
             current_value: 0.0,
             sample_rate: config.sample_rate,
+            retrigger: false,
+            rate: config.rate,
+            bpm: DEFAULT_BPM,
         }
     }
 
@@ -207,13 +302,34 @@ impl Lfo {
     ///
     /// * `rate` - New LFO rate
     pub fn set_rate(&mut self, rate: LfoRate) {
-        let hz = rate.to_hertz();
-        self.oscillator.set_frequency(hz);
+        self.rate = rate;
+        self.oscillator.set_frequency(rate.to_hertz_at(self.bpm));
     }
 
     /// Sets the LFO rate in Hz.
     pub fn set_rate_hz(&mut self, hz: f32) {
-        self.oscillator.set_frequency(hz);
+        self.set_rate(LfoRate::Hertz(hz));
+    }
+
+    /// Sets the host tempo used by [`LfoRate::Synced`] rates.
+    ///
+    /// Only the rate changes; the phase carries on, so tempo changes
+    /// don't cause jumps in the modulation.
+    ///
+    /// # Arguments
+    ///
+    /// * `bpm` - Tempo in beats per minute (20.0 to 999.0)
+    pub fn set_bpm(&mut self, bpm: f32) {
+        self.bpm = bpm.clamp(20.0, 999.0);
+        if let LfoRate::Synced(_) = self.rate {
+            self.oscillator
+                .set_frequency(self.rate.to_hertz_at(self.bpm));
+        }
+    }
+
+    /// Gets the host tempo in BPM.
+    pub fn bpm(&self) -> f32 {
+        self.bpm
     }
 
     /// Sets the modulation depth.
@@ -234,6 +350,57 @@ impl Lfo {
         self.oscillator.set_waveform(waveform);
     }
 
+    /// Sets a fixed seed for the random waveforms (sample and hold,
+    /// smoothed random); `None` makes them non-deterministic again.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed value, or `None` for an entropy seed
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.oscillator.set_noise_seed(seed);
+    }
+
+    /// Sets the LFO start phase and moves the LFO to it.
+    ///
+    /// Two LFOs offset by 0.5 run in anti-phase, e.g. for stereo panning.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Start phase as a fraction of the cycle (0.0 to 1.0)
+    pub fn set_phase(&mut self, offset: f32) {
+        self.oscillator.set_start_phase(offset);
+        self.oscillator.set_phase(self.oscillator.start_phase());
+    }
+
+    /// Enables or disables restarting the LFO on every note-on.
+    ///
+    /// When disabled (the default) the LFO runs freely across notes.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether `retrigger` restarts the LFO
+    pub fn set_retrigger(&mut self, enabled: bool) {
+        self.retrigger = enabled;
+        self.oscillator.set_phase_reset(enabled);
+    }
+
+    /// Checks if retrigger on note-on is enabled.
+    pub fn retrigger_enabled(&self) -> bool {
+        self.retrigger
+    }
+
+    /// Restarts the LFO at its start phase, including the delay.
+    ///
+    /// Called on note-on; does nothing when retrigger is disabled.
+    pub fn retrigger(&mut self) {
+        if !self.retrigger {
+            return;
+        }
+
+        self.oscillator.retrigger();
+        self.delay_counter = 0;
+    }
+
     /// Resets the LFO phase to the beginning.
     pub fn reset(&mut self) {
         self.oscillator.reset_phase();
@@ -557,4 +724,170 @@ mod tests {
             assert_eq!(lfo.value(), processed);
         }
     }
+
+    // --- Retrigger: value at note-on matches the start phase ---
+    #[test]
+    fn test_retrigger_starts_at_phase() {
+        let mut lfo = Lfo::with_config(LfoConfig {
+            rate: LfoRate::Hertz(3.0),
+            waveform: Waveform::Sine,
+            depth: 0.8,
+            sample_rate: 1000.0,
+            ..Default::default()
+        });
+        lfo.set_phase(0.25);
+        lfo.set_retrigger(true);
+
+        lfo.process_block(137);
+        lfo.retrigger();
+        // A quarter cycle into a sine is its peak
+        assert!((lfo.process() - 0.8).abs() < 1e-5);
+    }
+
+    // --- Retrigger: disabled keeps the LFO free-running ---
+    #[test]
+    fn test_retrigger_disabled_free_runs() {
+        let config = LfoConfig {
+            rate: LfoRate::Hertz(3.0),
+            waveform: Waveform::Sine,
+            depth: 1.0,
+            sample_rate: 1000.0,
+            ..Default::default()
+        };
+        let mut retriggered = Lfo::with_config(config);
+        let mut free = Lfo::with_config(config);
+        assert!(!retriggered.retrigger_enabled());
+
+        retriggered.process_block(137);
+        free.process_block(137);
+        retriggered.retrigger();
+        assert_eq!(retriggered.process_block(50), free.process_block(50));
+    }
+
+    // --- Phase offset: half a cycle apart gives opposite values ---
+    #[test]
+    fn test_phase_offset_anti_phase() {
+        let config = LfoConfig {
+            rate: LfoRate::Hertz(2.0),
+            waveform: Waveform::Triangle,
+            depth: 1.0,
+            sample_rate: 1000.0,
+            ..Default::default()
+        };
+        let mut left = Lfo::with_config(config);
+        let mut right = Lfo::with_config(config);
+        left.set_phase(0.0);
+        right.set_phase(0.5);
+
+        for (l, r) in left.process_block(500).iter().zip(right.process_block(500)) {
+            assert!(
+                (l + r).abs() < 1e-4,
+                "Expected anti-phase, got {} and {}",
+                l,
+                r
+            );
+        }
+    }
+
+    // --- Tempo sync: one cycle per eighth note at 140 BPM ---
+    #[test]
+    fn test_synced_eighth_at_140_bpm() {
+        // An eighth at 140 BPM lasts 3/14 s, i.e. 3000 samples at 14 kHz
+        let mut lfo = Lfo::with_config(LfoConfig {
+            rate: LfoRate::Synced(NoteDivision::Eighth),
+            waveform: Waveform::Sawtooth,
+            depth: 1.0,
+            sample_rate: 14000.0,
+            ..Default::default()
+        });
+        lfo.set_bpm(140.0);
+
+        lfo.process_block(1500);
+        assert!((lfo.oscillator.phase() - 0.5).abs() < 1e-3);
+        lfo.process_block(1500);
+        let phase = lfo.oscillator.phase();
+        assert!(
+            !(1e-3..=1.0 - 1e-3).contains(&phase),
+            "Phase {} after one eighth",
+            phase
+        );
+    }
+
+    // --- Tempo sync: a tempo change keeps the phase ---
+    #[test]
+    fn test_bpm_change_keeps_phase() {
+        let mut lfo = Lfo::with_config(LfoConfig {
+            rate: LfoRate::Synced(NoteDivision::Quarter),
+            depth: 1.0,
+            sample_rate: 1000.0,
+            ..Default::default()
+        });
+        lfo.process_block(123);
+        let before = lfo.oscillator.phase();
+        lfo.set_bpm(90.0);
+        assert_eq!(lfo.oscillator.phase(), before);
+
+        // Quarter notes at 90 BPM = 1.5 Hz
+        lfo.process_block(100);
+        assert!((lfo.oscillator.phase() - before - 0.15).abs() < 1e-4);
+    }
+
+    // --- Note divisions ---
+    #[test]
+    fn test_note_division_durations() {
+        assert!((NoteDivision::Quarter.to_duration(120.0) - 0.5).abs() < 1e-6);
+        assert!((NoteDivision::EighthTriplet.to_duration(120.0) - 1.0 / 6.0).abs() < 1e-6);
+        assert!((NoteDivision::DottedEighth.to_duration(60.0) - 0.75).abs() < 1e-6);
+        assert_eq!(NoteDivision::SixteenthTriplet.to_string(), "1/16T");
+    }
+
+    // --- Random shapes: sample and hold steps once per cycle ---
+    #[test]
+    fn test_sample_and_hold_holds_for_cycle() {
+        let mut lfo = Lfo::with_config(LfoConfig {
+            rate: LfoRate::Hertz(10.0),
+            waveform: Waveform::SampleAndHold,
+            depth: 1.0,
+            sample_rate: 1000.0,
+            ..Default::default()
+        });
+        lfo.set_seed(Some(42));
+
+        // 100 samples per cycle; the wrap may land a sample early or late
+        let samples = lfo.process_block(450);
+        let steps: Vec<usize> = (1..samples.len())
+            .filter(|&i| samples[i] != samples[i - 1])
+            .collect();
+        assert_eq!(steps.len(), 4, "Expected one step per cycle: {:?}", steps);
+        for pair in steps.windows(2) {
+            let held = pair[1] - pair[0];
+            assert!((99..=101).contains(&held), "Held for {} samples", held);
+        }
+    }
+
+    // --- Random shapes: seeded output is reproducible and smooth ---
+    #[test]
+    fn test_random_smooth_seeded_and_continuous() {
+        let config = LfoConfig {
+            rate: LfoRate::Hertz(5.0),
+            waveform: Waveform::RandomSmooth,
+            depth: 1.0,
+            sample_rate: 1000.0,
+            ..Default::default()
+        };
+        let mut a = Lfo::with_config(config);
+        let mut b = Lfo::with_config(config);
+        a.set_seed(Some(9));
+        b.set_seed(Some(9));
+
+        let samples = a.process_block(1000);
+        assert_eq!(samples, b.process_block(1000));
+        assert!(samples.iter().all(|v| v.abs() <= 1.0));
+        // No steps: the largest jump is far below a held step
+        let max_step = samples
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_step < 0.05, "Largest step {}", max_step);
+    }
 }
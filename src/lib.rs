@@ -29,8 +29,10 @@
 //! synth.note_on(60, 127); // MIDI note 60 (C4) with velocity 127
 //! ```
 
+pub mod analysis;
 pub mod arpeggiator;
 pub mod audio_analysis;
+pub mod automation;
 pub mod chord_generator;
 pub mod effects;
 pub mod envelope;
@@ -38,7 +40,7 @@ pub mod filter;
 #[cfg(feature = "gdext")]
 pub mod gdextension;
 pub mod lfo;
-pub use lfo::{Lfo, LfoRate};
+pub use lfo::{Lfo, LfoRate, NoteDivision};
 pub mod melody_generator;
 pub mod modulation;
 pub mod oscillator;
@@ -51,28 +53,32 @@ pub mod send_fx;
 pub mod song;
 pub mod step_sequencer;
 pub mod synth;
+pub mod tap_tempo;
 pub mod time_stretch;
 pub mod tracks;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
-pub use arpeggiator::{ArpConfig, ArpMode, ArpSpeed, Arpeggiator};
+pub use analysis::{dominant_frequency, magnitude_spectrum, WindowFn};
+pub use arpeggiator::{ArpConfig, ArpEvents, ArpMode, ArpSpeed, Arpeggiator};
 pub use audio_analysis::{
     measure_cross_correlation, measure_peak, measure_peak_db, measure_rms, measure_rms_db,
     measure_stereo_correlation, AudioAssertions, HarmonicDistortionAnalyzer, LatencyMeasurer,
     SpectrumAnalyzer,
 };
+pub use automation::{AutomationLane, AutomationPoint};
 pub use chord_generator::{
     Chord, ChordGenerator, ChordStyle, ChordType, Key, ProgressionPattern, Scale,
 };
 pub use effects::{
-    BitCrusher, BitCrusherConfig, Chorus, DecimationMode, Effect, EffectType, FilterBandConfig,
-    FilterBank, FilterBankConfig, FilterBankType, Flanger, FlangerConfig, Freeze, FreezeConfig,
-    FreezeType, Phaser, PhaserConfig, RingModulator, RingModulatorConfig, RingModulatorMode,
-    RingModulatorWave, Saturation, SimpleEq, StereoBitCrusher, StereoFlanger, StereoPhaser,
-    StereoRingModulator, StereoWarp, Warp, WarpConfig, WarpMode,
+    BitCrusher, BitCrusherConfig, Chorus, ConvolutionReverb, DecimationMode, DitherMode, Effect,
+    EffectType, FilterBandConfig, FilterBank, FilterBankConfig, FilterBankType, Flanger,
+    FlangerConfig, FormantVowel, Freeze, FreezeConfig, FreezeType, Gate, Limiter, Phaser,
+    PhaserConfig, RingModulator, RingModulatorConfig, RingModulatorMode, RingModulatorWave,
+    Saturation, SimpleEq, StereoBitCrusher, StereoChorus, StereoFlanger, StereoPhaser,
+    StereoRingModulator, StereoWarp, StereoWidener, Warp, WarpConfig, WarpMode,
 };
-pub use envelope::{Envelope, EnvelopeStage};
+pub use envelope::{EnvLoopMode, Envelope, EnvelopeStage};
 pub use filter::{Filter, FilterType, ZdfFilter, ZdfFilterMode};
 pub use melody_generator::{Melody, MelodyGenerator, MelodyNote, MelodyStyle};
 pub use modulation::{
@@ -83,12 +89,18 @@ pub use modulation::{
 
 #[cfg(feature = "midi_cc")]
 pub use modulation::midi_cc::{
-    cc_to_cutoff, cc_to_pitch, cc_to_resonance, cc_to_time, AssignableCC, CCParameterTarget,
-    MidiCCError, MidiCCManager, StandardCC, MAX_CC_COUNT,
+    cc_to_cutoff, cc_to_pitch, cc_to_resonance, cc_to_time, AssignableCC, CCCurve,
+    CCParameterTarget, MidiCCError, MidiCCManager, StandardCC, MAX_CC_COUNT,
+};
+pub use oscillator::{
+    AntiAliasMode, NoiseColor, Oscillator, OscillatorType, OversampleFactor, Waveform,
+};
+pub use piano_roll::{
+    EditMode, MidiImportError, NoteEvent, PianoRoll, PianoRollConfig, Resolution,
+};
+pub use presets::{
+    Preset, PresetCategory, PresetCollection, PresetFormatError, PresetManager, PresetParameters,
 };
-pub use oscillator::{Oscillator, OscillatorType, OversampleFactor, Waveform};
-pub use piano_roll::{EditMode, NoteEvent, PianoRoll, PianoRollConfig, Resolution};
-pub use presets::{Preset, PresetCategory, PresetCollection, PresetManager, PresetParameters};
 pub use project::{
     DrumPatternState, DrumTrackState, EffectSlotState, EnvelopeState, GlobalSettings,
     InsertEffectChain, LfoState, ModulationRouting, OscillatorState, PatternState, PianoRollNote,
@@ -99,8 +111,9 @@ pub use rhythm_generator::{
     Complexity, DrumNote, DrumPattern, DrumSound, RhythmGenerator, RhythmStyle,
 };
 pub use sampler::{
-    AutoSlicer, KeyZone, LoopInfo, LoopMode, MultiSampleInstrument, MultiSampler, Sample,
-    SampleFormat, SampleInfo, SampleLibrary, Sampler, SlicePoint, SlicingMode,
+    AutoSlicer, InterpMode, KeyZone, LoopInfo, LoopMode, MultiSampleInstrument, MultiSampler,
+    Sample, SampleError, SampleFormat, SampleInfo, SampleLibrary, Sampler, SlicePoint, SlicingMode,
+    StretchMode, VelocityLayer,
 };
 pub use send_fx::{
     PrePost, SendConnection, SendEffectSlot, SendFxError, SendFxManager,
@@ -114,7 +127,8 @@ pub use step_sequencer::{
     DrumStyle, ParamLocks, Scale as SeqScale, Step, StepSequencer, Track, NUM_STEPS,
     NUM_TRACKS as SEQ_NUM_TRACKS,
 };
-pub use synth::Synth;
+pub use synth::{AbSlot, ParameterDescriptor, ParameterError, Synth, VoiceStealPolicy};
+pub use tap_tempo::TapTempo;
 pub use time_stretch::{StretchAlgorithm, StretchAnalysis, TimeStretch, TimeStretchConfig};
 pub use tracks::{
     AudioTrack, BaseWidthFilter, BusTrack, EffectSlot, FxLfo, LfoMode, LfoWaveform, Machine,
@@ -132,5 +146,5 @@ pub use crate::filter::BiquadFilter;
 // Virtual Analog parameter IDs (for automation and UI)
 pub use synth::{
     PARAM_OVERSAMPLE, PARAM_SATURATION_DRIVE, PARAM_SATURATION_MIX, PARAM_ZDF_CUTOFF,
-    PARAM_ZDF_DRIVE, PARAM_ZDF_ENABLED, PARAM_ZDF_RES,
+    PARAM_ZDF_DRIVE, PARAM_ZDF_ENABLED, PARAM_ZDF_KEYTRACK, PARAM_ZDF_RES,
 };
@@ -204,6 +204,7 @@ pub struct Chord {
 /// * `length` - Number of measures to generate
 /// * `complexity` - How complex the melody is (0.0-1.0)
 /// * `randomness` - How random the melody is (0.0-1.0)
+/// * `motif_coherence` - How often later phrases develop the opening motif (0.0-1.0)
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct MelodyGenerator {
@@ -219,6 +220,8 @@ pub struct MelodyGenerator {
     complexity: f32,
     /// Melody randomness (0.0-1.0)
     randomness: f32,
+    /// Motif repetition amount (0.0 = through-composed, 1.0 = always repeat)
+    motif_coherence: f32,
 }
 
 /// Melody generator implementation.
@@ -253,6 +256,7 @@ impl MelodyGenerator {
             length,
             complexity: 0.5,
             randomness: 0.5,
+            motif_coherence: 0.0,
         }
     }
 
@@ -279,13 +283,16 @@ impl MelodyGenerator {
             length,
             complexity: complexity.clamp(0.0, 1.0),
             randomness: randomness.clamp(0.0, 1.0),
+            motif_coherence: 0.0,
         }
     }
 
     /// Generates a melody based on current parameters.
     ///
     /// Uses the configured key, tempo, length, complexity, and randomness
-    /// settings to generate a melody following music theory rules.
+    /// settings to generate a melody following music theory rules. The first
+    /// phrase is kept as a motif that later phrases may develop (see
+    /// [`MelodyGenerator::set_motif_coherence`]).
     ///
     /// # Returns
     ///
@@ -315,9 +322,30 @@ impl MelodyGenerator {
         let phrase_length = 4.0;
         let num_phrases = self.length / 4;
 
+        let mut motif: Vec<MelodyNote> = Vec::new();
+
         for phrase_idx in 0..num_phrases {
+            let phrase_start = current_beat;
             let phrase_end = (phrase_idx + 1) as f64 * phrase_length;
 
+            // Answer the opening phrase with a variation of its motif
+            if !motif.is_empty() && rng.gen::<f32>() < self.motif_coherence {
+                for note in
+                    self.develop_motif(&motif, &scale_notes, phrase_start, phrase_end, &mut rng)
+                {
+                    durations.push(note.duration);
+                    notes.push(note);
+                }
+                current_beat = phrase_end;
+
+                if let Some(end_note) = self.phrase_ending_note(&scale_notes, current_beat) {
+                    durations.push(end_note.duration);
+                    current_beat += end_note.duration;
+                    notes.push(end_note);
+                }
+                continue;
+            }
+
             while current_beat < phrase_end {
                 // Decide note duration based on complexity and randomness
                 let duration = self.generate_note_duration(&mut rng, phrase_end - current_beat);
@@ -348,17 +376,13 @@ impl MelodyGenerator {
 
                 // Handle phrase boundary - return to tonic
                 if (phrase_end - current_beat).abs() < 0.01 {
-                    let tonic_pitch = self.get_tonic_pitch(&scale_notes);
-                    if let Some(tonic) = tonic_pitch {
-                        let end_note = MelodyNote {
-                            pitch: tonic,
-                            velocity: 0.6,
-                            start_beat: current_beat,
-                            duration: 1.0,
-                        };
+                    if phrase_idx == 0 {
+                        motif = notes.clone();
+                    }
+                    if let Some(end_note) = self.phrase_ending_note(&scale_notes, current_beat) {
+                        durations.push(end_note.duration);
+                        current_beat += end_note.duration;
                         notes.push(end_note);
-                        durations.push(1.0);
-                        current_beat += 1.0;
                     }
                 }
             }
@@ -537,6 +561,24 @@ impl MelodyGenerator {
         // This method is deprecated - use Melody::quantize instead
         // Kept for backward compatibility
     }
+
+    /// Sets how strongly later phrases repeat the opening motif.
+    ///
+    /// Each phrase after the first is, with this probability, a variation of
+    /// the first phrase: transposed along the scale, and at lower coherence
+    /// also inverted or rhythmically varied.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - 0.0 = through-composed, 1.0 = every phrase repeats the motif
+    pub fn set_motif_coherence(&mut self, amount: f32) {
+        self.motif_coherence = amount.clamp(0.0, 1.0);
+    }
+
+    /// Gets the motif coherence amount.
+    pub fn motif_coherence(&self) -> f32 {
+        self.motif_coherence
+    }
 }
 
 impl Melody {
@@ -560,6 +602,9 @@ impl Melody {
 impl MelodyGenerator {
     /// Exports the melody as a MIDI file.
     ///
+    /// Generates a new melody and writes it as a single-track standard MIDI
+    /// file at the generator's tempo.
+    ///
     /// # Arguments
     ///
     /// * `path` - File path for the output MIDI file
@@ -567,79 +612,18 @@ impl MelodyGenerator {
     /// # Returns
     ///
     /// Ok(()) on success, or an error message on failure.
-    ///
-    /// # Note
-    ///
-    /// This requires the `mido` crate to be available. If not available,
-    /// the function will return an error.
-    pub fn export_midi(&mut self, _path: &str) -> Result<(), Box<dyn Error>> {
-        // Try to use mido if available, otherwise return helpful error
-        #[cfg(feature = "midi_cc")]
-        {
-            use std::fs::File;
-            use std::io::Write;
-
-            // Generate the melody first
-            let melody = self.generate();
-            let notes = &melody.notes;
-
-            let mut file = File::create(_path)?;
-
-            // Write MIDI header
-            // MThd + 6 bytes header length + format type + num tracks + ticks per beat
-            let track_count = 1u16;
-            let ticks_per_beat = 480u16;
-            let mut header = b"MThd\x00\x00\x00\x06\x00\x01".to_vec();
-            header.extend_from_slice(&track_count.to_be_bytes());
-            header.extend_from_slice(&ticks_per_beat.to_be_bytes());
-            file.write_all(&header)?;
-
-            // Write track chunk header
-            let track_size = (notes.len() * 10) as u32;
-            let mut track_header = b"MTrk\x00\x00\x00".to_vec();
-            track_header.extend_from_slice(&track_size.to_be_bytes());
-            file.write_all(&track_header)?;
-
-            // Write tempo
-            let tempo_us_per_beat = 60_000_000.0 / self.tempo;
-            let mut tempo_msg = vec![0x00, 0xFF, 0x51, 0x03];
-            let tempo_bytes = (tempo_us_per_beat as u32).to_be_bytes();
-            tempo_msg.extend_from_slice(&tempo_bytes[3..]); // Take last 3 bytes for tempo
-            file.write_all(&tempo_msg)?;
-
-            // Write note events
-            let mut previous_time = 0f64;
-            for note in notes {
-                let delta_time = ((note.start_beat - previous_time) * 480.0) as u32;
-                previous_time = note.start_beat;
-
-                // Note on: delta_time, 0x90, pitch, velocity
-                let mut on_event = delta_time.to_be_bytes().to_vec();
-                on_event.push(0x90);
-                on_event.push(note.pitch);
-                on_event.push((note.velocity * 127.0) as u8);
-                file.write_all(&on_event)?;
-
-                // Note off: duration_ticks, 0x80, pitch, 0x00
-                let duration_ticks = (note.duration * 480.0) as u32; // 480 ticks per beat
-                let mut off_event = duration_ticks.to_be_bytes().to_vec();
-                off_event.push(0x80);
-                off_event.push(note.pitch);
-                off_event.push(0x00);
-                file.write_all(&off_event)?;
-            }
-
-            // End of track: 0x00 0xFF 0x2F 0x00
-            let end_track = vec![0x00, 0xFF, 0x2F, 0x00];
-            file.write_all(&end_track)?;
-
-            Ok(())
-        }
-
-        #[cfg(not(feature = "midi_cc"))]
-        Err(Box::new(std::io::Error::other(
-            "MIDI export requires the 'midi' feature flag. Add `mido = \"0.5\"` to your Cargo.toml",
-        )))
+    pub fn export_midi(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let melody = self.generate();
+        let bytes = crate::piano_roll::single_track_midi(
+            melody.notes.iter().map(|note| {
+                let velocity = (note.velocity * 127.0).round() as u8;
+                (note.start_beat, note.duration, note.pitch, velocity)
+            }),
+            0,
+            self.tempo,
+        );
+        std::fs::write(path, bytes)?;
+        Ok(())
     }
 
     // ===== Private Helper Methods =====
@@ -786,6 +770,79 @@ impl MelodyGenerator {
         (base_velocity * emphasis as f32).clamp(0.0, 1.0)
     }
 
+    /// Renders a variation of the motif into the phrase `[start, end)`.
+    ///
+    /// The motif is transposed by up to a fourth along the scale (kept
+    /// inside the scale range so the contour is preserved). The less
+    /// coherent the melody, the more likely it is inverted or given a
+    /// dotted rhythm. Notes that don't fit the phrase are dropped.
+    fn develop_motif<R: Rng>(
+        &self,
+        motif: &[MelodyNote],
+        scale_notes: &[u8],
+        start: f64,
+        end: f64,
+        rng: &mut R,
+    ) -> Vec<MelodyNote> {
+        let variation = 1.0 - self.motif_coherence;
+        let mut degrees: Vec<i32> = motif
+            .iter()
+            .map(|n| scale_notes.binary_search(&n.pitch).unwrap_or(0) as i32)
+            .collect();
+
+        // Inversion: mirror intervals around the first note
+        if rng.gen::<f32>() < variation * 0.5 {
+            let axis = degrees[0];
+            degrees.iter_mut().for_each(|d| *d = 2 * axis - *d);
+        }
+
+        // Transposition, limited so every note stays in the scale range
+        let lowest = *degrees.iter().min().unwrap_or(&0);
+        let highest = *degrees.iter().max().unwrap_or(&0);
+        let top = scale_notes.len() as i32 - 1;
+        let shift = rng.gen_range(-3..=3).clamp(-lowest, top - highest);
+
+        // Rhythmic variation: turn an even pair into a dotted pair
+        let mut rhythm: Vec<(f64, f64)> =
+            motif.iter().map(|n| (n.start_beat, n.duration)).collect();
+        if rng.gen::<f32>() < variation {
+            let pairs: Vec<usize> = (1..rhythm.len())
+                .filter(|&i| (rhythm[i - 1].1 - rhythm[i].1).abs() < 1e-9)
+                .collect();
+            if !pairs.is_empty() {
+                let i = pairs[rng.gen_range(0..pairs.len())];
+                let total = rhythm[i - 1].1 + rhythm[i].1;
+                rhythm[i - 1].1 = total * 0.75;
+                rhythm[i] = (rhythm[i - 1].0 + total * 0.75, total * 0.25);
+            }
+        }
+
+        motif
+            .iter()
+            .zip(degrees)
+            .zip(rhythm)
+            .filter_map(|((note, degree), (offset, duration))| {
+                let start_beat = start + offset;
+                (start_beat < end - 1e-9).then(|| MelodyNote {
+                    pitch: scale_notes[(degree + shift).clamp(0, top) as usize],
+                    velocity: note.velocity,
+                    start_beat,
+                    duration: duration.min(end - start_beat),
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the tonic note that closes a phrase, if the root is in range.
+    fn phrase_ending_note(&self, scale_notes: &[u8], start_beat: f64) -> Option<MelodyNote> {
+        self.get_tonic_pitch(scale_notes).map(|tonic| MelodyNote {
+            pitch: tonic,
+            velocity: 0.6,
+            start_beat,
+            duration: 1.0,
+        })
+    }
+
     /// Gets the tonic (root) pitch of the current scale.
     fn get_tonic_pitch(&self, scale_notes: &[u8]) -> Option<u8> {
         scale_notes.iter().find(|&&n| n == self.key.root).copied()
@@ -1137,4 +1194,91 @@ mod tests {
             assert!(note.velocity >= 0.0 && note.velocity <= 1.0);
         }
     }
+
+    #[test]
+    fn test_export_midi_is_valid_smf() {
+        let path = std::env::temp_dir().join(format!("wavelet_melody_{}.mid", std::process::id()));
+        let mut generator = create_test_generator();
+        generator.export_midi(path.to_str().unwrap()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Header, then one track chunk whose length covers the rest of the file
+        assert_eq!(&bytes[..4], b"MThd");
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let track_len = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        assert_eq!(track_len, bytes.len() - 22);
+        assert_eq!(&bytes[bytes.len() - 4..], &[0x00, 0xFF, 0x2F, 0x00]);
+
+        let mut piano_roll = crate::piano_roll::PianoRoll::new();
+        piano_roll.import_midi(&bytes).unwrap();
+        assert!(piano_roll.note_count() > 0);
+    }
+
+    #[test]
+    fn test_motif_coherence_repeats_contour() {
+        let mut generator = MelodyGenerator::new(
+            Key {
+                root: 60,
+                scale: Scale::Major,
+            },
+            120.0,
+            8,
+        );
+        generator.set_motif_coherence(1.0);
+        let scale_notes = generator.scale_notes();
+        let degree = |pitch| scale_notes.binary_search(&pitch).unwrap() as i32;
+
+        for _ in 0..20 {
+            let melody = generator.generate();
+            // Phrase 1 covers beats 0-4, its tonic ending 4-5, phrase 2 beats 5-8
+            let first: Vec<_> = melody.notes.iter().filter(|n| n.start_beat < 4.0).collect();
+            let second: Vec<_> = melody
+                .notes
+                .iter()
+                .filter(|n| n.start_beat >= 5.0 && n.start_beat < 8.0)
+                .collect();
+            assert!(!second.is_empty());
+
+            // Transposed repetition: same rhythm and same steps along the scale
+            for (a, b) in first.iter().zip(&second) {
+                assert!((b.start_beat - a.start_beat - 5.0).abs() < 1e-9);
+            }
+            let steps = |notes: &[&MelodyNote]| -> Vec<i32> {
+                notes
+                    .windows(2)
+                    .map(|w| degree(w[1].pitch) - degree(w[0].pitch))
+                    .collect()
+            };
+            let n = first.len().min(second.len());
+            assert_eq!(steps(&first[..n]), steps(&second[..n]));
+        }
+    }
+
+    #[test]
+    fn test_motif_variations_stay_in_scale() {
+        // High root: transpositions must not leave the scale range
+        let mut generator = MelodyGenerator::new(
+            Key {
+                root: 96,
+                scale: Scale::Minor,
+            },
+            120.0,
+            16,
+        );
+        generator.set_motif_coherence(0.5);
+        assert_eq!(generator.motif_coherence(), 0.5);
+        let scale_notes = generator.scale_notes();
+
+        for _ in 0..20 {
+            let melody = generator.generate();
+            assert!(melody
+                .notes
+                .iter()
+                .all(|n| scale_notes.binary_search(&n.pitch).is_ok()));
+        }
+
+        generator.set_motif_coherence(3.0);
+        assert_eq!(generator.motif_coherence(), 1.0);
+    }
 }
@@ -10,7 +10,8 @@
 //! - Standard MIDI CC support (Mod Wheel, Pitch Bend, etc.)
 //! - Program Change (PC) support
 //! - Bank Change (MSB/LSB) support
-//! - CC learn functionality
+//! - CC learn functionality: bind the next incoming CC to any parameter target
+//! - Value curves (linear, exponential, toggle) per assignable CC
 //! - Real-time CC value monitoring
 //!
 //! # MIDI CC Specification (Tonverk Compatible)
@@ -40,6 +41,9 @@ pub const MAX_CC_COUNT: usize = 16;
 /// CC learn timeout in milliseconds
 pub const CC_LEARN_TIMEOUT_MS: u64 = 5000;
 
+/// Output ratio across the exponential curve (60 dB)
+const EXP_CURVE_RANGE: f64 = 1000.0;
+
 /// Standard MIDI CC numbers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StandardCC {
@@ -265,6 +269,8 @@ pub struct AssignableCC {
     pub bipolar: bool,
     /// Enable/disable this CC mapping
     pub enabled: bool,
+    /// Curve applied to the incoming CC value
+    pub curve: CCCurve,
 }
 
 impl Default for AssignableCC {
@@ -277,6 +283,7 @@ impl Default for AssignableCC {
             max_input: 1.0,
             bipolar: false,
             enabled: false,
+            curve: CCCurve::Linear,
         }
     }
 }
@@ -292,6 +299,7 @@ impl AssignableCC {
             max_input: 1.0,
             bipolar: false,
             enabled: true,
+            curve: CCCurve::Linear,
         }
     }
 
@@ -307,6 +315,41 @@ impl AssignableCC {
         self.bipolar = bipolar;
         self
     }
+
+    /// Set value curve
+    pub fn with_curve(mut self, curve: CCCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+}
+
+/// Curve mapping a normalized CC value (0.0 - 1.0) to a parameter value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CCCurve {
+    /// Value passes through unchanged
+    #[default]
+    Linear,
+    /// Slow start, fast end (for frequencies and times)
+    Exponential,
+    /// 0.0 below the midpoint, 1.0 from the midpoint up (for switches)
+    Toggle,
+}
+
+impl CCCurve {
+    /// Apply the curve to a normalized value
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            CCCurve::Linear => value,
+            CCCurve::Exponential => (EXP_CURVE_RANGE.powf(value) - 1.0) / (EXP_CURVE_RANGE - 1.0),
+            CCCurve::Toggle => {
+                if value >= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
 }
 
 /// CC parameter targets
@@ -411,6 +454,8 @@ pub struct MidiCCManager {
     cc_learn_mode: bool,
     /// CC learn target
     cc_learn_target: Option<usize>,
+    /// Parameter target and curve bound by `begin_learn`
+    cc_learn_binding: Option<(CCParameterTarget, CCCurve)>,
     /// Last CC learn time
     cc_learn_time: Option<u128>,
 }
@@ -446,6 +491,7 @@ impl Default for MidiCCManager {
             midi_channel: 0,
             cc_learn_mode: false,
             cc_learn_target: None,
+            cc_learn_binding: None,
             cc_learn_time: None,
         }
     }
@@ -475,11 +521,22 @@ impl MidiCCManager {
         // Check if in CC learn mode
         if self.cc_learn_mode {
             if let Some(target_index) = self.cc_learn_target {
+                if let Some((target, curve)) = self.cc_learn_binding {
+                    // The CC moves to the new target: drop its old binding
+                    for (index, cc) in self.assignable_ccs.iter_mut().enumerate() {
+                        if index != target_index && cc.cc_number == cc_number {
+                            *cc = AssignableCC::default();
+                        }
+                    }
+                    let cc = &mut self.assignable_ccs[target_index];
+                    cc.target = target;
+                    cc.curve = curve;
+                    cc.enabled = true;
+                    self.cc_values.insert(cc_number, value);
+                }
                 self.assignable_ccs[target_index].cc_number = cc_number;
                 self.assignable_ccs[target_index].name = format!("CC{:02}", cc_number);
-                self.cc_learn_mode = false;
-                self.cc_learn_target = None;
-                self.cc_learn_time = None;
+                self.disable_cc_learn();
                 return Some(&mut self.assignable_ccs[target_index]);
             }
         }
@@ -588,10 +645,67 @@ impl MidiCCManager {
         Ok(())
     }
 
+    /// Start MIDI learn for a parameter target with a linear curve
+    ///
+    /// See [`MidiCCManager::begin_learn_with_curve`].
+    pub fn begin_learn(&mut self, target: CCParameterTarget) -> Result<(), MidiCCError> {
+        self.begin_learn_with_curve(target, CCCurve::Linear)
+    }
+
+    /// Start MIDI learn for a parameter target
+    ///
+    /// The next incoming CC is bound to `target` and from then on drives it
+    /// through `curve`. A target that is already bound keeps its slot and
+    /// gets the new CC; otherwise the first slot without a target is used.
+    /// A CC that was bound to another target loses that binding.
+    ///
+    /// # Errors
+    ///
+    /// `MidiCCError::InvalidTarget` for `CCParameterTarget::None`, and
+    /// `MidiCCError::NoFreeSlot` when all `MAX_CC_COUNT` slots are bound to
+    /// other targets
+    pub fn begin_learn_with_curve(
+        &mut self,
+        target: CCParameterTarget,
+        curve: CCCurve,
+    ) -> Result<(), MidiCCError> {
+        if target == CCParameterTarget::None {
+            return Err(MidiCCError::InvalidTarget);
+        }
+
+        let index = self
+            .find_target(target)
+            .or_else(|| self.find_target(CCParameterTarget::None))
+            .ok_or(MidiCCError::NoFreeSlot)?;
+        self.enable_cc_learn(index)?;
+        self.cc_learn_binding = Some((target, curve));
+        Ok(())
+    }
+
+    /// Find the slot bound to a target
+    fn find_target(&self, target: CCParameterTarget) -> Option<usize> {
+        self.assignable_ccs
+            .iter()
+            .position(|cc| cc.target == target)
+    }
+
+    /// Get the current value of a parameter target, with its slot's range,
+    /// curve and polarity applied
+    ///
+    /// Returns `None` if no CC is bound to the target.
+    pub fn get_target_value(&self, target: CCParameterTarget) -> Option<f64> {
+        if target == CCParameterTarget::None {
+            return None;
+        }
+        self.find_target(target)
+            .map(|index| self.get_assignable_cc_normalized(index))
+    }
+
     /// Disable CC learn mode
     pub fn disable_cc_learn(&mut self) {
         self.cc_learn_mode = false;
         self.cc_learn_target = None;
+        self.cc_learn_binding = None;
         self.cc_learn_time = None;
     }
 
@@ -624,7 +738,9 @@ impl MidiCCManager {
                 return 0.0;
             }
 
-            let value = self.get_cc_value(cc.cc_number) as f64 / 127.0;
+            let value = cc
+                .curve
+                .apply(self.get_cc_value(cc.cc_number) as f64 / 127.0);
             let range = cc.max_input - cc.min_input;
 
             let normalized = cc.min_input + value * range;
@@ -683,6 +799,8 @@ pub enum MidiCCError {
     InvalidTarget,
     /// CC learn timeout
     CCLearnTimeout,
+    /// Every assignable CC slot is bound to a target
+    NoFreeSlot,
 }
 
 impl std::fmt::Display for MidiCCError {
@@ -696,6 +814,9 @@ impl std::fmt::Display for MidiCCError {
             }
             MidiCCError::InvalidTarget => write!(f, "Invalid target parameter"),
             MidiCCError::CCLearnTimeout => write!(f, "CC learn mode timed out"),
+            MidiCCError::NoFreeSlot => {
+                write!(f, "All {} assignable CC slots are in use", MAX_CC_COUNT)
+            }
         }
     }
 }
@@ -857,4 +978,92 @@ mod tests {
         assert_eq!(manager.current_bank(), 0);
         assert_eq!(manager.get_cc_value(7), 0);
     }
+
+    #[test]
+    fn test_begin_learn_binds_next_cc_to_target() {
+        let mut manager = MidiCCManager::new();
+        manager
+            .begin_learn(CCParameterTarget::FilterCutoff)
+            .unwrap();
+        assert!(manager.is_cc_learn_active());
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            None
+        );
+
+        // The learn message binds CC74
+        manager.process_cc(74, 0);
+        assert!(!manager.is_cc_learn_active());
+
+        // Later CC74 messages drive the target
+        manager.process_cc(74, 127);
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            Some(1.0)
+        );
+        manager.process_cc(74, 0);
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            Some(0.0)
+        );
+
+        // Re-learning the same target moves it to the new CC
+        manager
+            .begin_learn_with_curve(CCParameterTarget::FilterCutoff, CCCurve::Toggle)
+            .unwrap();
+        manager.process_cc(20, 10);
+        manager.process_cc(74, 127);
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            Some(0.0)
+        );
+        manager.process_cc(20, 100);
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            Some(1.0)
+        );
+
+        // Learning CC20 for another target drops its old binding
+        manager.begin_learn(CCParameterTarget::LFORate).unwrap();
+        manager.process_cc(20, 64);
+        assert_eq!(
+            manager.get_target_value(CCParameterTarget::FilterCutoff),
+            None
+        );
+        assert!(manager
+            .get_target_value(CCParameterTarget::LFORate)
+            .is_some());
+    }
+
+    #[test]
+    fn test_begin_learn_fails_when_slots_are_full() {
+        let mut manager = MidiCCManager::new();
+        for i in 0..MAX_CC_COUNT {
+            manager
+                .begin_learn(CCParameterTarget::Custom(i as u16))
+                .unwrap();
+            manager.process_cc(i as u8, 0);
+        }
+
+        let result = manager.begin_learn(CCParameterTarget::FilterCutoff);
+        assert!(matches!(result, Err(MidiCCError::NoFreeSlot)));
+        assert!(!manager.is_cc_learn_active());
+        assert!(matches!(
+            manager.begin_learn(CCParameterTarget::None),
+            Err(MidiCCError::InvalidTarget)
+        ));
+
+        // A bound target can still be re-learned
+        assert!(manager.begin_learn(CCParameterTarget::Custom(3)).is_ok());
+    }
+
+    #[test]
+    fn test_cc_curves() {
+        assert_eq!(CCCurve::Linear.apply(0.25), 0.25);
+        assert_eq!(CCCurve::Exponential.apply(0.0), 0.0);
+        assert!((CCCurve::Exponential.apply(1.0) - 1.0).abs() < 1e-12);
+        assert!(CCCurve::Exponential.apply(0.5) < 0.05);
+        assert_eq!(CCCurve::Toggle.apply(0.49), 0.0);
+        assert_eq!(CCCurve::Toggle.apply(0.5), 1.0);
+    }
 }
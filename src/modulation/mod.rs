@@ -35,6 +35,6 @@ pub mod midi_cc;
 
 #[cfg(feature = "midi_cc")]
 pub use midi_cc::{
-    cc_to_cutoff, cc_to_pitch, cc_to_resonance, cc_to_time, AssignableCC, CCParameterTarget,
-    MidiCCError, MidiCCManager, StandardCC, MAX_CC_COUNT,
+    cc_to_cutoff, cc_to_pitch, cc_to_resonance, cc_to_time, AssignableCC, CCCurve,
+    CCParameterTarget, MidiCCError, MidiCCManager, StandardCC, MAX_CC_COUNT,
 };
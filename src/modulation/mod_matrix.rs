@@ -14,6 +14,8 @@
 use std::collections::HashMap;
 use std::fmt;
 
+use crate::effects::{EffectParameterId, TrackEffects};
+
 /// Maximum number of modulation connections
 pub const MAX_CONNECTIONS: usize = 32;
 
@@ -125,6 +127,14 @@ pub enum ModulationTargetType {
     /// Pitch (fine)
     PitchFine,
 
+    /// Parameter of the effect in a per-track effect slot
+    EffectParam {
+        /// Effect slot index
+        slot: usize,
+        /// Parameter within the slot's effect
+        param: EffectParameterId,
+    },
+
     /// Custom parameter
     Custom(u8),
 }
@@ -147,6 +157,9 @@ impl fmt::Display for ModulationTargetType {
             ModulationTargetType::Volume => write!(f, "Volume"),
             ModulationTargetType::PitchCoarse => write!(f, "Pitch Coarse"),
             ModulationTargetType::PitchFine => write!(f, "Pitch Fine"),
+            ModulationTargetType::EffectParam { slot, param } => {
+                write!(f, "FX {} {}", slot + 1, param)
+            }
             ModulationTargetType::Custom(n) => write!(f, "Custom {}", n),
         }
     }
@@ -664,6 +677,31 @@ impl ModulationMatrix {
         output
     }
 
+    /// Processes modulation for a single sample and applies effect targets
+    ///
+    /// Works like [`process`](Self::process), then offsets every
+    /// `EffectParam` target whose target ID is the track of `effects` by its
+    /// summed value via [`TrackEffects::modulate_param`]: the parameter moves
+    /// from its unmodulated value by that fraction of its range. Targets
+    /// pointing at empty or out-of-range slots are skipped.
+    pub fn process_with_effects(
+        &mut self,
+        source_values: &HashMap<(ModulationSourceType, u8), f32>,
+        effects: &mut TrackEffects,
+    ) -> HashMap<(ModulationTargetType, u8), f32> {
+        let output = self.process(source_values);
+
+        for (&(target_type, target_id), &value) in &output {
+            if let ModulationTargetType::EffectParam { slot, param } = target_type {
+                if target_id == effects.track_id() {
+                    let _ = effects.modulate_param(slot, param, value);
+                }
+            }
+        }
+
+        output
+    }
+
     /// Resets all connections
     pub fn reset(&mut self) {
         for conn in &mut self.connections {
@@ -987,18 +1025,141 @@ mod tests {
 
     #[test]
     fn test_modulation_matrix_from_configs() {
-        let mut configs = Vec::new();
-
-        let mut config = ModulationConnectionConfig::default();
-        config.target = ModulationTargetType::FilterCutoff;
-        configs.push(config);
-
-        let mut config2 = ModulationConnectionConfig::default();
-        config2.target = ModulationTargetType::Volume;
-        configs.push(config2);
+        let configs = vec![
+            ModulationConnectionConfig {
+                target: ModulationTargetType::FilterCutoff,
+                ..Default::default()
+            },
+            ModulationConnectionConfig {
+                target: ModulationTargetType::Volume,
+                ..Default::default()
+            },
+        ];
 
         let matrix = ModulationMatrix::from_configs(0, &configs);
 
         assert_eq!(matrix.connection_count(), 2);
     }
+
+    #[test]
+    fn test_modulation_matrix_lfo_to_effect_cutoff() {
+        use crate::effects::EffectType;
+        use crate::lfo::Lfo;
+
+        let mut effects = TrackEffects::new(0, 44100.0);
+        effects.add_effect(0, EffectType::Filter).unwrap();
+        effects
+            .set_param(0, EffectParameterId::FilterCutoff, 0.5)
+            .unwrap();
+
+        let target = ModulationTargetType::EffectParam {
+            slot: 0,
+            param: EffectParameterId::FilterCutoff,
+        };
+        let mut matrix = ModulationMatrix::new(0);
+        matrix
+            .add_connection_from_config(ModulationConnectionConfig {
+                target,
+                depth: 0.45,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut lfo = Lfo::new();
+        lfo.set_rate_hz(10.0);
+        lfo.set_depth(1.0);
+
+        let mut source_values = HashMap::new();
+        let mut min_cutoff = f32::MAX;
+        let mut max_cutoff = f32::MIN;
+        for _ in 0..4410 {
+            let lfo_value = lfo.process();
+            source_values.insert((ModulationSourceType::LFO, 0), lfo_value);
+            matrix.process_with_effects(&source_values, &mut effects);
+
+            // Bipolar around the unmodulated cutoff
+            let expected = 0.5 + 0.45 * lfo_value;
+            let cutoff = effects
+                .get_param(0, EffectParameterId::FilterCutoff)
+                .unwrap()
+                .unwrap();
+            assert!(
+                (cutoff - expected).abs() < 0.01,
+                "cutoff {} should track LFO value {}",
+                cutoff,
+                expected
+            );
+            min_cutoff = min_cutoff.min(cutoff);
+            max_cutoff = max_cutoff.max(cutoff);
+        }
+
+        // One full LFO cycle should sweep most of the cutoff range
+        assert!(min_cutoff < 0.1 && max_cutoff > 0.9);
+    }
+
+    #[test]
+    fn test_modulation_matrix_effect_param_scales_to_range() {
+        use crate::effects::EffectType;
+
+        let threshold = EffectParameterId::CompressorThreshold;
+        let mut effects = TrackEffects::new(2, 44100.0);
+        effects.add_effect(1, EffectType::Compressor).unwrap();
+        effects.set_param(1, threshold, -20.0).unwrap();
+
+        let mut matrix = ModulationMatrix::new(2);
+        let config = ModulationConnectionConfig {
+            target: ModulationTargetType::EffectParam {
+                slot: 1,
+                param: threshold,
+            },
+            target_id: 2,
+            depth: 0.5,
+            ..Default::default()
+        };
+        matrix.add_connection_from_config(config.clone()).unwrap();
+
+        // A connection for another track leaves this track's effects alone
+        matrix
+            .add_connection_from_config(ModulationConnectionConfig {
+                target_id: 3,
+                depth: 1.0,
+                ..config
+            })
+            .unwrap();
+
+        let mut threshold_for = |effects: &mut TrackEffects, lfo_value: f32| {
+            let source_values = HashMap::from([((ModulationSourceType::LFO, 0), lfo_value)]);
+            matrix.process_with_effects(&source_values, effects);
+            effects.get_param(1, threshold).unwrap().unwrap()
+        };
+
+        // Half the 60 dB range either way of -20 dB, clamped at 0 dB
+        assert!((threshold_for(&mut effects, 0.0) + 20.0).abs() < 1e-4);
+        assert!((threshold_for(&mut effects, -0.5) + 35.0).abs() < 1e-4);
+        assert_eq!(threshold_for(&mut effects, 1.0), 0.0);
+
+        // The unmodulated value follows set_param
+        effects.set_param(1, threshold, -40.0).unwrap();
+        assert!((threshold_for(&mut effects, 0.5) + 25.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_modulation_matrix_effect_param_max_connections() {
+        let mut matrix = ModulationMatrix::new(0);
+        let config = ModulationConnectionConfig {
+            target: ModulationTargetType::EffectParam {
+                slot: 1,
+                param: EffectParameterId::Mix,
+            },
+            ..Default::default()
+        };
+
+        for _ in 0..MAX_CONNECTIONS {
+            matrix.add_connection_from_config(config.clone()).unwrap();
+        }
+        assert_eq!(
+            matrix.add_connection_from_config(config),
+            Err(ModulationMatrixError::MaxConnectionsReached)
+        );
+    }
 }
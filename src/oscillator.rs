@@ -19,7 +19,8 @@
 
 #![allow(dead_code)] // Reserve oversample fields for future features
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::f32::consts::PI;
 
 /// Enumeration of supported oscillator waveforms.
@@ -48,6 +49,14 @@ pub enum Waveform {
     /// Phase modulation waveform - for FM synthesis
     /// Generates carrier for phase modulation
     PM,
+
+    /// Sample and hold - a new random level every cycle
+    /// Mostly useful as an LFO shape (stepped random modulation)
+    SampleAndHold,
+
+    /// Smoothed random - glides between random levels once per cycle
+    /// A slewed sample and hold for organic LFO drift
+    RandomSmooth,
 }
 
 /// Oversampling factor for anti-aliasing.
@@ -74,6 +83,34 @@ impl OversampleFactor {
     }
 }
 
+/// Anti-aliasing strategy for waveforms with discontinuities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AntiAliasMode {
+    /// Naive waveform generation
+    #[default]
+    None,
+
+    /// Run at a higher internal rate (see [`OversampleFactor`])
+    Oversample,
+
+    /// Polynomial band-limited step correction on sawtooth and square edges
+    PolyBlep,
+}
+
+/// Spectral color of [`Waveform::Noise`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseColor {
+    /// Flat spectrum, equal power per Hz
+    #[default]
+    White,
+
+    /// -3 dB/octave, equal power per octave
+    Pink,
+
+    /// -6 dB/octave, integrated white noise
+    Brown,
+}
+
 /// Enumeration for quick oscillator type selection.
 /// Maps to Waveform variants for API convenience.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -197,7 +234,28 @@ pub struct Oscillator {
     sample_rate: f32,
 
     /// Random number generator for noise
-    rng: rand::rngs::ThreadRng,
+    rng: StdRng,
+
+    /// Spectral color of the noise waveform
+    noise_color: NoiseColor,
+
+    /// Fixed noise seed, if any
+    noise_seed: Option<u64>,
+
+    /// Pink noise filter state (Paul Kellett's refined method)
+    pink_state: [f32; 7],
+
+    /// Brown noise integrator state
+    brown_state: f32,
+
+    /// Random level held for the current cycle (sample and hold shapes)
+    random_level: f32,
+
+    /// Random level of the previous cycle (smoothed random start point)
+    previous_random_level: f32,
+
+    /// Anti-aliasing strategy
+    anti_alias_mode: AntiAliasMode,
 
     /// Oversampling factor
     oversample_factor: OversampleFactor,
@@ -207,6 +265,30 @@ pub struct Oscillator {
 
     /// Current position in oversample buffer
     oversample_pos: usize,
+
+    /// Phase of the hard sync master (0.0 to 1.0)
+    sync_phase: f32,
+
+    /// Phase increment of the hard sync master, if sync is enabled
+    sync_increment: Option<f32>,
+
+    /// Number of unison voices (1 = unison off)
+    unison_voices: u8,
+
+    /// Total unison detune in cents (outer voices sit at +/- this amount)
+    unison_detune_cents: f32,
+
+    /// Stereo spread of the unison voices (0.0 = mono, 1.0 = full width)
+    unison_spread: f32,
+
+    /// Phase of each unison voice (0.0 to 1.0)
+    unison_phases: Vec<f32>,
+
+    /// Whether `retrigger` resets the phase
+    phase_reset: bool,
+
+    /// Phase `retrigger` restarts from (0.0 to 1.0)
+    start_phase: f32,
 }
 
 impl Oscillator {
@@ -223,6 +305,8 @@ impl Oscillator {
         let phase_increment = config.frequency / config.sample_rate;
         let oversample_factor = config.oversample_factor;
         let oversample_count = oversample_factor.as_u32() as usize;
+        let mut rng = StdRng::from_entropy();
+        let random_level = rng.gen::<f32>() * 2.0 - 1.0;
 
         Self {
             phase: 0.0,
@@ -230,10 +314,29 @@ impl Oscillator {
             waveform: config.waveform,
             amplitude: config.amplitude,
             sample_rate: config.sample_rate,
-            rng: rand::thread_rng(),
+            rng,
+            noise_color: NoiseColor::White,
+            noise_seed: None,
+            pink_state: [0.0; 7],
+            brown_state: 0.0,
+            random_level,
+            previous_random_level: 0.0,
+            anti_alias_mode: if oversample_count > 1 {
+                AntiAliasMode::Oversample
+            } else {
+                AntiAliasMode::None
+            },
             oversample_factor,
             oversample_buffer: vec![0.0; oversample_count],
             oversample_pos: 0,
+            sync_phase: 0.0,
+            sync_increment: None,
+            unison_voices: 1,
+            unison_detune_cents: 0.0,
+            unison_spread: 0.0,
+            unison_phases: vec![0.0],
+            phase_reset: true,
+            start_phase: 0.0,
         }
     }
 }
@@ -269,6 +372,48 @@ impl Oscillator {
         self.waveform = waveform;
     }
 
+    /// Sets the spectral color of the noise waveform.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - White, pink or brown noise
+    pub fn set_noise_color(&mut self, color: NoiseColor) {
+        self.noise_color = color;
+        self.pink_state = [0.0; 7];
+        self.brown_state = 0.0;
+    }
+
+    /// Gets the noise color.
+    pub fn noise_color(&self) -> NoiseColor {
+        self.noise_color
+    }
+
+    /// Sets a fixed seed for the noise generator.
+    ///
+    /// With a seed the noise sequence is reproducible; `None` returns to
+    /// an entropy-seeded generator. Either way the generator restarts now.
+    /// The seed also drives the sample and hold and smoothed random shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed value, or `None` for non-deterministic noise
+    pub fn set_noise_seed(&mut self, seed: Option<u64>) {
+        self.noise_seed = seed;
+        self.rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        self.pink_state = [0.0; 7];
+        self.brown_state = 0.0;
+        self.previous_random_level = 0.0;
+        self.random_level = self.rng.gen::<f32>() * 2.0 - 1.0;
+    }
+
+    /// Gets the fixed noise seed, if any.
+    pub fn noise_seed(&self) -> Option<u64> {
+        self.noise_seed
+    }
+
     /// Sets the oscillator amplitude.
     ///
     /// # Arguments
@@ -287,10 +432,14 @@ impl Oscillator {
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         // Calculate current frequency from old sample rate
         let current_freq = self.phase_increment * self.sample_rate;
+        let sync_freq = self.sync_source();
         // Update sample rate
         self.sample_rate = sample_rate;
         // Recalculate phase increment with new sample rate
         self.phase_increment = current_freq / sample_rate;
+        if let Some(freq) = sync_freq {
+            self.set_sync_source(freq);
+        }
     }
 
     /// Sets the oversampling factor for anti-aliasing.
@@ -307,6 +456,9 @@ impl Oscillator {
         // Reallocate buffer if needed
         if new_factor > 1 {
             self.oversample_buffer.resize(new_factor, 0.0);
+            self.anti_alias_mode = AntiAliasMode::Oversample;
+        } else if self.anti_alias_mode == AntiAliasMode::Oversample {
+            self.anti_alias_mode = AntiAliasMode::None;
         }
 
         self.oversample_factor = factor;
@@ -321,9 +473,198 @@ impl Oscillator {
         self.oversample_factor
     }
 
+    /// Sets the anti-aliasing strategy.
+    ///
+    /// `Oversample` uses the current oversampling factor, or 4x if none is
+    /// set. `None` and `PolyBlep` disable oversampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Anti-aliasing mode
+    pub fn set_anti_alias_mode(&mut self, mode: AntiAliasMode) {
+        match mode {
+            AntiAliasMode::Oversample => {
+                if self.oversample_factor == OversampleFactor::None {
+                    self.set_oversample_factor(OversampleFactor::X4);
+                }
+            }
+            AntiAliasMode::None | AntiAliasMode::PolyBlep => {
+                self.oversample_factor = OversampleFactor::None;
+            }
+        }
+        self.anti_alias_mode = mode;
+    }
+
+    /// Gets the current anti-aliasing strategy.
+    ///
+    /// # Returns
+    ///
+    /// Current anti-aliasing mode
+    pub fn anti_alias_mode(&self) -> AntiAliasMode {
+        self.anti_alias_mode
+    }
+
     /// Resets the oscillator phase to the starting position.
     pub fn reset_phase(&mut self) {
         self.phase = 0.0;
+        self.reset_unison_phases();
+    }
+
+    /// Moves the oscillator to a specific phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - Phase as a fraction of the cycle (0.0 to 1.0)
+    pub fn set_phase(&mut self, phase: f32) {
+        self.phase = phase.rem_euclid(1.0);
+    }
+
+    /// Gets the current phase (0.0 to 1.0).
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    /// Enables or disables phase reset on `retrigger`.
+    ///
+    /// Resetting gives consistent note transients (bass); free running
+    /// gives analog-style variation between notes (pads).
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether retriggering resets the phase
+    pub fn set_phase_reset(&mut self, enabled: bool) {
+        self.phase_reset = enabled;
+    }
+
+    /// Checks if phase reset on retrigger is enabled.
+    pub fn phase_reset(&self) -> bool {
+        self.phase_reset
+    }
+
+    /// Sets the phase `retrigger` restarts from.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - Start phase as a fraction of the cycle (0.0 to 1.0)
+    pub fn set_start_phase(&mut self, phase: f32) {
+        self.start_phase = phase.clamp(0.0, 1.0).fract();
+    }
+
+    /// Gets the retrigger start phase.
+    pub fn start_phase(&self) -> f32 {
+        self.start_phase
+    }
+
+    /// Restarts the waveform for a new note.
+    ///
+    /// Moves the phase to the start phase when phase reset is enabled,
+    /// otherwise does nothing and the oscillator keeps running free.
+    pub fn retrigger(&mut self) {
+        if !self.phase_reset {
+            return;
+        }
+
+        self.phase = self.start_phase;
+        self.sync_phase = 0.0;
+        self.reset_unison_phases();
+    }
+
+    /// Enables unison: stacks detuned copies of the current waveform.
+    ///
+    /// Voices are detuned symmetrically around the base frequency and summed
+    /// with equal-power normalization, so the level stays consistent as the
+    /// voice count grows. 7 sawtooth voices at ~25 cents gives a supersaw.
+    /// Unison voices bypass oversampling; use `AntiAliasMode::PolyBlep`.
+    ///
+    /// # Arguments
+    ///
+    /// * `voices` - Number of voices (1 to 9, 1 disables unison)
+    /// * `detune_cents` - Detune of the outermost voices in cents (0 to 100)
+    /// * `spread` - Stereo width for `next_sample_stereo` (0.0 to 1.0)
+    pub fn set_unison(&mut self, voices: u8, detune_cents: f32, spread: f32) {
+        self.unison_voices = voices.clamp(1, 9);
+        self.unison_detune_cents = detune_cents.clamp(0.0, 100.0);
+        self.unison_spread = spread.clamp(0.0, 1.0);
+        self.reset_unison_phases();
+    }
+
+    /// Gets the number of unison voices.
+    ///
+    /// # Returns
+    ///
+    /// Voice count (1 when unison is off)
+    pub fn unison_voices(&self) -> u8 {
+        self.unison_voices
+    }
+
+    /// Spreads the unison voice start phases so they don't sum in phase.
+    fn reset_unison_phases(&mut self) {
+        const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_034;
+        // Refill in place: retrigger runs on every note-on
+        let phase = self.phase;
+        self.unison_phases.clear();
+        self.unison_phases.extend(
+            (0..self.unison_voices).map(|i| (phase + i as f32 * GOLDEN_RATIO_CONJUGATE).fract()),
+        );
+    }
+
+    /// Position of unison voice `index` from -1.0 (lowest) to 1.0 (highest).
+    fn unison_position(&self, index: usize) -> f32 {
+        let voices = self.unison_voices as usize;
+        if voices <= 1 {
+            0.0
+        } else {
+            2.0 * index as f32 / (voices - 1) as f32 - 1.0
+        }
+    }
+
+    /// Generates one sample from all unison voices as (mono, left, right).
+    fn next_unison_sample(&mut self) -> (f32, f32, f32) {
+        let voices = self.unison_voices as usize;
+        let (mut mono, mut left, mut right) = (0.0, 0.0, 0.0);
+
+        for i in 0..voices {
+            let position = self.unison_position(i);
+            let ratio = 2.0f32.powf(position * self.unison_detune_cents / 1200.0);
+            let increment = self.phase_increment * ratio;
+            let phase = self.unison_phases[i];
+
+            let mut sample = self.sample_waveform_at(phase);
+            if self.anti_alias_mode == AntiAliasMode::PolyBlep {
+                sample += self.poly_blep_correction_at(phase, increment);
+            }
+
+            // Equal-power pan across the stereo field, unity gain at centre
+            let angle = (position * self.unison_spread + 1.0) * PI / 4.0;
+            mono += sample;
+            left += sample * angle.cos() * std::f32::consts::SQRT_2;
+            right += sample * angle.sin() * std::f32::consts::SQRT_2;
+
+            self.unison_phases[i] = (phase + increment).fract();
+        }
+
+        self.advance_phase();
+
+        let norm = 1.0 / (voices as f32).sqrt();
+        (mono * norm, left * norm, right * norm)
+    }
+
+    /// Generates the next stereo sample.
+    ///
+    /// With unison enabled the voices are spread across the stereo field;
+    /// otherwise both channels carry the mono sample.
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (left, right) samples
+    pub fn next_sample_stereo(&mut self) -> (f32, f32) {
+        if self.unison_voices > 1 {
+            let (_, left, right) = self.next_unison_sample();
+            (left, right)
+        } else {
+            let sample = self.next_sample();
+            (sample, sample)
+        }
     }
 
     /// Synchronizes multiple oscillators to the same phase.
@@ -336,6 +677,35 @@ impl Oscillator {
         self.phase = source.phase;
     }
 
+    /// Enables hard sync to an internal master running at `master_freq`.
+    ///
+    /// Each time the master's phase wraps, this oscillator's phase restarts,
+    /// producing the classic sync-lead timbre as the slave frequency moves.
+    /// Hard sync aliases heavily, so combine it with oversampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `master_freq` - Master oscillator frequency in Hz
+    pub fn set_sync_source(&mut self, master_freq: f32) {
+        self.sync_increment = Some(master_freq.max(0.0) / self.sample_rate);
+    }
+
+    /// Disables hard sync.
+    pub fn clear_sync_source(&mut self) {
+        self.sync_increment = None;
+        self.sync_phase = 0.0;
+    }
+
+    /// Gets the hard sync master frequency, if sync is enabled.
+    ///
+    /// # Returns
+    ///
+    /// Master frequency in Hz, or None when free running
+    pub fn sync_source(&self) -> Option<f32> {
+        self.sync_increment
+            .map(|increment| increment * self.sample_rate)
+    }
+
     /// Generates the next audio sample from the oscillator.
     ///
     /// This method calculates the sample value based on current phase
@@ -351,9 +721,15 @@ impl Oscillator {
     pub fn next_sample(&mut self) -> f32 {
         let oversample_factor = self.oversample_factor.as_u32() as usize;
 
-        if oversample_factor <= 1 {
+        if self.unison_voices > 1 {
+            // Mono sum of the unison voices
+            self.next_unison_sample().0
+        } else if oversample_factor <= 1 {
             // No oversampling - generate sample directly
-            let sample = self.sample_waveform();
+            let mut sample = self.sample_waveform();
+            if self.anti_alias_mode == AntiAliasMode::PolyBlep {
+                sample += self.poly_blep_correction();
+            }
             self.advance_phase();
             sample
         } else {
@@ -364,10 +740,7 @@ impl Oscillator {
             // Generate oversampled samples
             for i in 0..oversample_factor {
                 self.oversample_buffer[i] = self.sample_waveform();
-                self.phase += oversample_phase_increment;
-                if self.phase >= 1.0 {
-                    self.phase -= 1.0;
-                }
+                self.advance_phase_by(oversample_phase_increment, oversample_factor as f32);
             }
 
             // Apply simple decimation (average the oversampled samples)
@@ -377,6 +750,26 @@ impl Oscillator {
         }
     }
 
+    /// Generates the next sample with its phase offset by `modulation`.
+    ///
+    /// This is the phase modulation form of FM: the waveform is read at
+    /// `phase + modulation` while the phase accumulator itself advances
+    /// normally, so the pitch does not drift. Oversampling, polyBLEP and
+    /// unison are bypassed on this path.
+    ///
+    /// # Arguments
+    ///
+    /// * `modulation` - Phase offset in cycles (1.0 = one full period)
+    ///
+    /// # Returns
+    ///
+    /// The next sample value in the range [-amplitude, amplitude]
+    pub fn next_sample_phase_modulated(&mut self, modulation: f32) -> f32 {
+        let sample = self.sample_waveform_at((self.phase + modulation).rem_euclid(1.0));
+        self.advance_phase();
+        sample
+    }
+
     /// Generates multiple samples for batch processing.
     ///
     /// # Arguments
@@ -392,50 +785,164 @@ impl Oscillator {
 
     /// Internal method to sample the current waveform at current phase.
     fn sample_waveform(&mut self) -> f32 {
+        self.sample_waveform_at(self.phase)
+    }
+
+    /// Internal method to sample the current waveform at `phase`.
+    fn sample_waveform_at(&mut self, phase: f32) -> f32 {
         // Convert phase from [0, 1) to [0, 2*PI) for trigonometric functions
-        let phase_2pi = self.phase * 2.0 * PI;
+        let phase_2pi = phase * 2.0 * PI;
 
         match self.waveform {
             Waveform::Sine => phase_2pi.sin() * self.amplitude,
 
             Waveform::Square => {
-                if self.phase < 0.5 {
+                if phase < 0.5 {
                     self.amplitude
                 } else {
                     -self.amplitude
                 }
             }
 
-            Waveform::Sawtooth => (2.0 * self.phase - 1.0) * self.amplitude,
+            Waveform::Sawtooth => (2.0 * phase - 1.0) * self.amplitude,
 
             Waveform::Triangle => {
-                let value = if self.phase < 0.5 {
-                    4.0 * self.phase - 1.0
+                let value = if phase < 0.5 {
+                    4.0 * phase - 1.0
                 } else {
-                    3.0 - 4.0 * self.phase
+                    3.0 - 4.0 * phase
                 };
                 value * self.amplitude
             }
 
-            Waveform::Noise => {
-                // White noise: random values in [-1, 1]
-                (self.rng.gen::<f32>() * 2.0 - 1.0) * self.amplitude
-            }
+            Waveform::Noise => self.next_noise() * self.amplitude,
 
             Waveform::PM => {
                 // Phase modulation carrier - sine wave for FM synthesis
                 phase_2pi.sin() * self.amplitude
             }
+
+            Waveform::SampleAndHold => self.random_level * self.amplitude,
+
+            Waveform::RandomSmooth => {
+                // Cosine glide from the previous cycle's level to this one's
+                let glide = 0.5 - 0.5 * (phase * PI).cos();
+                let level = self.previous_random_level
+                    + (self.random_level - self.previous_random_level) * glide;
+                level * self.amplitude
+            }
+        }
+    }
+
+    /// Draws the random level for a new cycle of the random shapes.
+    fn start_random_cycle(&mut self) {
+        if matches!(
+            self.waveform,
+            Waveform::SampleAndHold | Waveform::RandomSmooth
+        ) {
+            self.previous_random_level = self.random_level;
+            self.random_level = self.rng.gen::<f32>() * 2.0 - 1.0;
+        }
+    }
+
+    /// Generates one noise sample of the current color in [-1, 1].
+    fn next_noise(&mut self) -> f32 {
+        // White noise: random values in [-1, 1]
+        let white = self.rng.gen::<f32>() * 2.0 - 1.0;
+
+        match self.noise_color {
+            NoiseColor::White => white,
+
+            NoiseColor::Pink => {
+                let b = &mut self.pink_state;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.96900 * b[2] + white * 0.153852;
+                b[3] = 0.86650 * b[3] + white * 0.3104856;
+                b[4] = 0.55000 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.0168980;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+                b[6] = white * 0.115926;
+                (pink * 0.11).clamp(-1.0, 1.0)
+            }
+
+            NoiseColor::Brown => {
+                // Leaky integrator keeps the random walk from drifting off
+                self.brown_state = (self.brown_state + 0.02 * white) / 1.02;
+                (self.brown_state * 3.5).clamp(-1.0, 1.0)
+            }
+        }
+    }
+
+    /// PolyBLEP correction for the discontinuities of the current waveform.
+    fn poly_blep_correction(&self) -> f32 {
+        self.poly_blep_correction_at(self.phase, self.phase_increment)
+    }
+
+    /// PolyBLEP correction at `phase` for a waveform advancing by `dt` per sample.
+    fn poly_blep_correction_at(&self, phase: f32, dt: f32) -> f32 {
+        match self.waveform {
+            // Falling edge at the phase wrap
+            Waveform::Sawtooth => -poly_blep(phase, dt) * self.amplitude,
+
+            // Rising edge at 0.0, falling edge at 0.5
+            Waveform::Square => {
+                let falling = (phase + 0.5).fract();
+                (poly_blep(phase, dt) - poly_blep(falling, dt)) * self.amplitude
+            }
+
+            _ => 0.0,
         }
     }
 
     /// Internal method to advance the phase by one sample.
     fn advance_phase(&mut self) {
-        self.phase += self.phase_increment;
+        self.advance_phase_by(self.phase_increment, 1.0);
+    }
+
+    /// Advances the phase by `increment`, applying hard sync if enabled.
+    /// `steps_per_sample` is the oversampling factor the increment was divided by.
+    fn advance_phase_by(&mut self, increment: f32, steps_per_sample: f32) {
+        self.phase += increment;
         // Wrap phase around when it exceeds 1.0
         if self.phase >= 1.0 {
             self.phase -= 1.0;
+            self.start_random_cycle();
         }
+
+        if let Some(sync_increment) = self.sync_increment {
+            self.sync_phase += sync_increment / steps_per_sample;
+            if self.sync_phase >= 1.0 {
+                self.sync_phase -= 1.0;
+                // Restart the slave at the exact point the master wrapped
+                let elapsed = self.sync_phase / (sync_increment / steps_per_sample);
+                self.phase = (elapsed * increment).fract();
+                self.start_random_cycle();
+            }
+        }
+    }
+}
+
+/// Two-sample polynomial band-limited step residual.
+///
+/// Returns the correction to add around a unit upward step at phase 0.0
+/// (scaled by 2 for a -1 to +1 jump).
+///
+/// # Arguments
+///
+/// * `t` - Current phase (0.0 to 1.0)
+/// * `dt` - Phase increment per sample
+fn poly_blep(t: f32, dt: f32) -> f32 {
+    if dt <= 0.0 {
+        0.0
+    } else if t < dt {
+        let x = t / dt;
+        2.0 * x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + 2.0 * x + 1.0
+    } else {
+        0.0
     }
 }
 
@@ -798,4 +1305,361 @@ mod tests {
             assert!((a - b).abs() < 1e-6, "Mismatch at {}: {} vs {}", i, a, b);
         }
     }
+
+    // --- Hard sync: slave phase restarts at the master period ---
+    #[test]
+    fn test_hard_sync_resets_at_master_period() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Sawtooth,
+            frequency: 370.0,
+            amplitude: 1.0,
+            sample_rate: 48000.0,
+            ..Default::default()
+        });
+        // 100 Hz master = 480 samples per period
+        osc.set_sync_source(100.0);
+        assert!((osc.sync_source().unwrap() - 100.0).abs() < 1e-3);
+
+        let mut phases = Vec::new();
+        for _ in 0..1440 {
+            osc.next_sample();
+            phases.push(osc.phase);
+        }
+
+        // Phase restarts on every master wrap (allowing one sample of rounding)
+        for period in 1..3 {
+            let wrap = period * 480 - 1;
+            let restart = phases[wrap - 1..=wrap + 1]
+                .iter()
+                .cloned()
+                .fold(f32::INFINITY, f32::min);
+            assert!(
+                restart < osc.phase_increment,
+                "Phase should restart near sample {}, got {}",
+                wrap,
+                restart
+            );
+        }
+
+        // Without sync the slave runs free
+        osc.clear_sync_source();
+        assert_eq!(osc.sync_source(), None);
+        osc.reset_phase();
+        let free: Vec<f32> = (0..480)
+            .map(|_| {
+                osc.next_sample();
+                osc.phase
+            })
+            .collect();
+        assert!(free[479] > osc.phase_increment);
+    }
+
+    // --- Hard sync also runs inside the oversampling path ---
+    #[test]
+    fn test_hard_sync_with_oversampling() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Sawtooth,
+            frequency: 370.0,
+            amplitude: 1.0,
+            sample_rate: 48000.0,
+            oversample_factor: OversampleFactor::X4,
+            ..Default::default()
+        });
+        osc.set_sync_source(100.0);
+
+        // Skip the first sample, which may straddle the master wrap
+        let samples = osc.next_samples(960);
+        for i in 1..400 {
+            assert!((samples[i] - samples[i + 480]).abs() < 1e-2);
+        }
+    }
+
+    // --- Helper: power (dB) at the alias frequencies of a 1 kHz tone at 44.1 kHz ---
+    fn alias_power_db(osc: &mut Oscillator) -> f64 {
+        let sample_rate = 44100.0;
+        let samples: Vec<f32> = osc.next_samples(44100);
+
+        // Harmonics above Nyquist fold back between the harmonics
+        let mut alias_freqs = Vec::new();
+        for harmonic in 23..=80 {
+            let folded = (harmonic as f64 * 1000.0) % sample_rate;
+            let folded = folded.min(sample_rate - folded);
+            if folded < 10000.0 {
+                alias_freqs.push(folded);
+            }
+        }
+
+        let power: f64 = alias_freqs
+            .iter()
+            .map(|&freq| {
+                let (mut re, mut im) = (0.0f64, 0.0f64);
+                for (n, &s) in samples.iter().enumerate() {
+                    let angle = 2.0 * std::f64::consts::PI * freq * n as f64 / sample_rate;
+                    re += s as f64 * angle.cos();
+                    im -= s as f64 * angle.sin();
+                }
+                (re * re + im * im) / (samples.len() as f64).powi(2)
+            })
+            .sum();
+        10.0 * power.log10()
+    }
+
+    // --- PolyBLEP: lowers the aliasing floor versus naive generation ---
+    #[test]
+    fn test_polyblep_reduces_aliasing() {
+        for waveform in [Waveform::Sawtooth, Waveform::Square] {
+            let config = OscillatorConfig {
+                waveform,
+                frequency: 1000.0,
+                amplitude: 1.0,
+                sample_rate: 44100.0,
+                ..Default::default()
+            };
+
+            let mut naive = Oscillator::new(config.clone());
+            assert_eq!(naive.anti_alias_mode(), AntiAliasMode::None);
+            let mut blep = Oscillator::new(config);
+            blep.set_anti_alias_mode(AntiAliasMode::PolyBlep);
+
+            let naive_db = alias_power_db(&mut naive);
+            let blep_db = alias_power_db(&mut blep);
+            assert!(
+                naive_db - blep_db > 30.0,
+                "{:?}: polyBLEP should cut aliasing by 30 dB, naive {:.1} dB vs {:.1} dB",
+                waveform,
+                naive_db,
+                blep_db
+            );
+        }
+    }
+
+    // --- Anti-alias mode follows the oversampling factor ---
+    #[test]
+    fn test_anti_alias_mode_oversample() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            oversample_factor: OversampleFactor::X2,
+            ..Default::default()
+        });
+        assert_eq!(osc.anti_alias_mode(), AntiAliasMode::Oversample);
+
+        osc.set_anti_alias_mode(AntiAliasMode::PolyBlep);
+        assert_eq!(osc.oversample_factor(), OversampleFactor::None);
+
+        osc.set_anti_alias_mode(AntiAliasMode::Oversample);
+        assert_eq!(osc.oversample_factor(), OversampleFactor::X4);
+    }
+
+    // --- Helper: RMS of a sample buffer ---
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    // --- Unison: level stays consistent as voices are added ---
+    #[test]
+    fn test_unison_equal_power_level() {
+        let config = OscillatorConfig {
+            waveform: Waveform::Sawtooth,
+            frequency: 220.0,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        };
+
+        let mut single = Oscillator::new(config.clone());
+        let single_rms = rms(&single.next_samples(44100));
+
+        let mut supersaw = Oscillator::new(config);
+        supersaw.set_unison(7, 25.0, 1.0);
+        assert_eq!(supersaw.unison_voices(), 7);
+        let supersaw_rms = rms(&supersaw.next_samples(44100));
+
+        let ratio_db = 20.0 * (supersaw_rms / single_rms).log10();
+        assert!(
+            ratio_db.abs() < 3.0,
+            "Supersaw level should be within 3 dB of a single voice, got {} dB",
+            ratio_db
+        );
+    }
+
+    // --- Unison: spread widens the stereo image ---
+    #[test]
+    fn test_unison_stereo_spread() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Sawtooth,
+            frequency: 220.0,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        });
+
+        let side_to_mid = |osc: &mut Oscillator| {
+            let (mut mid, mut side) = (0.0, 0.0);
+            for _ in 0..44100 {
+                let (l, r) = osc.next_sample_stereo();
+                mid += (l + r) * (l + r);
+                side += (l - r) * (l - r);
+            }
+            side / mid
+        };
+
+        osc.set_unison(7, 25.0, 0.0);
+        assert!(side_to_mid(&mut osc) < 1e-6, "Zero spread should be mono");
+
+        osc.set_unison(7, 25.0, 1.0);
+        assert!(side_to_mid(&mut osc) > 0.05, "Full spread should be wide");
+
+        // Without unison both channels carry the mono signal
+        osc.set_unison(1, 0.0, 1.0);
+        let (l, r) = osc.next_sample_stereo();
+        assert_eq!(l, r);
+    }
+
+    // --- Retrigger: phase reset gives identical note starts ---
+    #[test]
+    fn test_retrigger_phase_reset() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Sine,
+            frequency: 110.0,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        });
+        osc.set_start_phase(0.25);
+
+        osc.retrigger();
+        let first = osc.next_sample();
+        osc.next_samples(123);
+        osc.retrigger();
+        let second = osc.next_sample();
+
+        assert_eq!(first, second);
+        assert!(
+            (first - 1.0).abs() < 1e-6,
+            "Quarter cycle of a sine is its peak"
+        );
+    }
+
+    // --- Retrigger: free-running mode keeps the phase ---
+    #[test]
+    fn test_retrigger_free_running() {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Sawtooth,
+            frequency: 110.0,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        });
+        osc.set_phase_reset(false);
+
+        osc.next_samples(123);
+        let phase = osc.phase;
+        osc.retrigger();
+        assert_eq!(osc.phase, phase);
+    }
+
+    /// Average power per FFT bin (dB) of `samples` between `low` and `high` Hz.
+    fn band_power_db(samples: &[f32], sample_rate: f64, low: f64, high: f64) -> f64 {
+        const BLOCK: usize = 512;
+        let bin_hz = sample_rate / BLOCK as f64;
+        let bins: Vec<usize> = (1..BLOCK / 2)
+            .filter(|&k| (low..high).contains(&(k as f64 * bin_hz)))
+            .collect();
+
+        let mut power = 0.0f64;
+        for block in samples.chunks_exact(BLOCK) {
+            for &k in &bins {
+                let (mut re, mut im) = (0.0f64, 0.0f64);
+                for (n, &s) in block.iter().enumerate() {
+                    let angle = 2.0 * std::f64::consts::PI * (k * n) as f64 / BLOCK as f64;
+                    re += s as f64 * angle.cos();
+                    im -= s as f64 * angle.sin();
+                }
+                power += re * re + im * im;
+            }
+        }
+        10.0 * (power / bins.len() as f64).log10()
+    }
+
+    fn noise_oscillator(color: NoiseColor) -> Oscillator {
+        let mut osc = Oscillator::new(OscillatorConfig {
+            waveform: Waveform::Noise,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        });
+        osc.set_noise_color(color);
+        osc.set_noise_seed(Some(7));
+        osc
+    }
+
+    // --- Noise: white noise has a flat spectrum ---
+    #[test]
+    fn test_white_noise_flat_spectrum() {
+        let mut osc = noise_oscillator(NoiseColor::White);
+        let samples = osc.next_samples(32768);
+
+        let low = band_power_db(&samples, 44100.0, 500.0, 1000.0);
+        let high = band_power_db(&samples, 44100.0, 8000.0, 16000.0);
+        assert!(
+            (low - high).abs() < 1.5,
+            "White noise bands differ by {} dB",
+            low - high
+        );
+    }
+
+    // --- Noise: pink and brown roll off at 3 and 6 dB per octave ---
+    #[test]
+    fn test_colored_noise_slopes() {
+        for (color, db_per_octave) in [(NoiseColor::Pink, 3.0), (NoiseColor::Brown, 6.0)] {
+            let mut osc = noise_oscillator(color);
+            let samples = osc.next_samples(32768);
+
+            // Two octaves apart
+            let low = band_power_db(&samples, 44100.0, 1000.0, 2000.0);
+            let high = band_power_db(&samples, 44100.0, 4000.0, 8000.0);
+            let slope = (low - high) / 2.0;
+            assert!(
+                (slope - db_per_octave).abs() < 1.5,
+                "{:?} noise rolls off {} dB/octave",
+                color,
+                slope
+            );
+        }
+    }
+
+    // --- Noise: a fixed seed makes the output reproducible ---
+    #[test]
+    fn test_noise_seed_deterministic() {
+        let mut a = noise_oscillator(NoiseColor::Pink);
+        let mut b = noise_oscillator(NoiseColor::Pink);
+        assert_eq!(a.next_samples(256), b.next_samples(256));
+        assert_eq!(a.noise_seed(), Some(7));
+        assert_eq!(a.noise_color(), NoiseColor::Pink);
+    }
+
+    // --- Phase modulation: zero offset matches the plain waveform ---
+    #[test]
+    fn test_phase_modulated_zero_offset() {
+        let config = OscillatorConfig {
+            waveform: Waveform::Sine,
+            frequency: 440.0,
+            amplitude: 1.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        };
+        let mut plain = Oscillator::new(config.clone());
+        let mut modulated = Oscillator::new(config);
+
+        for _ in 0..256 {
+            assert_eq!(
+                plain.next_sample(),
+                modulated.next_sample_phase_modulated(0.0)
+            );
+        }
+
+        // A half-cycle offset inverts the sine without moving the phase
+        let phase = modulated.phase;
+        let expected = -(phase * 2.0 * PI).sin();
+        assert!((modulated.next_sample_phase_modulated(0.5) - expected).abs() < 1e-5);
+    }
 }
@@ -9,9 +9,11 @@
 //! - Drag to create/resize/move notes
 //! - Velocity editing per note
 //! - Snap-to-grid quantization
+//! - Seeded humanize and swing
 //! - Multiple octaves support
 //! - Copy/paste patterns
 //! - Undo/redo support
+//! - Standard MIDI file import/export
 //!
 //! # Example
 //!
@@ -25,7 +27,24 @@
 //! pr.add_note(60, 0.0, 1.0, 100); // C4, beat 0-1, velocity 100
 //! ```
 
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Ticks per quarter note used when exporting MIDI
+const EXPORT_TICKS_PER_BEAT: u16 = 480;
+
+/// Largest humanize timing offset as a fraction of a grid step.
+///
+/// Kept under half a step so quantizing always snaps back to the original grid.
+const HUMANIZE_MAX_STEP_FRACTION: f64 = 0.49;
+
+/// Largest humanize velocity offset (at `velocity_amount` 1.0)
+const HUMANIZE_MAX_VELOCITY: f32 = 32.0;
+
+/// Strongest swing ratio (0.5 = straight)
+const MAX_SWING_RATIO: f32 = 0.7;
 
 /// Grid resolution for piano roll
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -91,6 +110,8 @@ pub struct PianoRoll {
     note_height: f64,
     /// Beat width in pixels
     beat_width: f64,
+    /// Seed for humanize, so the same seed always gives the same feel
+    humanize_seed: u64,
 }
 
 /// Edit mode for piano roll
@@ -179,6 +200,7 @@ impl Default for PianoRoll {
             grid_color: 0x333333,
             note_height: 12.0,
             beat_width: 40.0,
+            humanize_seed: 0,
         }
     }
 }
@@ -214,6 +236,7 @@ impl PianoRoll {
             grid_color: config.grid_color,
             note_height: config.note_height,
             beat_width: config.beat_width,
+            humanize_seed: 0,
         }
     }
 
@@ -441,6 +464,92 @@ impl PianoRoll {
         count
     }
 
+    /// Quantize all notes to the grid.
+    ///
+    /// Restores the grid after [`PianoRoll::humanize`] or
+    /// [`PianoRoll::apply_swing`], whose offsets stay within half a step.
+    pub fn quantize_all(&mut self) -> usize {
+        self.save_undo();
+        let step = self.grid_step();
+        for note in &mut self.notes {
+            note.start_beat = (note.start_beat / step).round() * step;
+        }
+        self.notes.len()
+    }
+
+    /// Randomly jitter note start times and velocities.
+    ///
+    /// Offsets come from the humanize seed (see
+    /// [`PianoRoll::set_humanize_seed`]), so the same seed and notes always
+    /// give the same result. Notes never move before beat 0 and durations
+    /// are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `timing_amount` - Largest start offset in beats, capped just under
+    ///   half a grid step so [`PianoRoll::quantize_all`] can undo it
+    /// * `velocity_amount` - Velocity jitter (0.0 - 1.0, 1.0 = up to ±32)
+    ///
+    /// # Returns
+    ///
+    /// Number of notes humanized
+    pub fn humanize(&mut self, timing_amount: f32, velocity_amount: f32) -> usize {
+        self.save_undo();
+        let max_offset =
+            (timing_amount.max(0.0) as f64).min(self.grid_step() * HUMANIZE_MAX_STEP_FRACTION);
+        let max_velocity = velocity_amount.clamp(0.0, 1.0) * HUMANIZE_MAX_VELOCITY;
+        let mut rng = StdRng::seed_from_u64(self.humanize_seed);
+
+        for note in &mut self.notes {
+            let timing: f64 = rng.gen_range(-1.0..=1.0);
+            let velocity: f32 = rng.gen_range(-1.0..=1.0);
+
+            note.start_beat = (note.start_beat + timing * max_offset).max(0.0);
+            let jittered = note.velocity as f32 + (velocity * max_velocity).round();
+            note.velocity = jittered.clamp(1.0, 127.0) as u8;
+        }
+        self.notes.len()
+    }
+
+    /// Set the seed used by [`PianoRoll::humanize`].
+    pub fn set_humanize_seed(&mut self, seed: u64) {
+        self.humanize_seed = seed;
+    }
+
+    /// Get the humanize seed.
+    pub fn humanize_seed(&self) -> u64 {
+        self.humanize_seed
+    }
+
+    /// Delay the off-beat subdivisions for a swing feel.
+    ///
+    /// Grid steps are taken in pairs; a note on the second step of a pair is
+    /// pushed so the pair splits `ratio : 1 - ratio`. Any humanize offset a
+    /// note already has is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - Swing ratio (0.5 = straight, 0.66 = triplet feel, max 0.7)
+    ///
+    /// # Returns
+    ///
+    /// Number of notes moved
+    pub fn apply_swing(&mut self, ratio: f32) -> usize {
+        self.save_undo();
+        let step = self.grid_step();
+        let delay = (ratio.clamp(0.5, MAX_SWING_RATIO) as f64 - 0.5) * 2.0 * step;
+        let mut count = 0;
+
+        for note in &mut self.notes {
+            let index = (note.start_beat / step).round() as i64;
+            if index % 2 == 1 {
+                note.start_beat += delay;
+                count += 1;
+            }
+        }
+        count
+    }
+
     /// Set resolution
     pub fn set_resolution(&mut self, resolution: Resolution) {
         self.resolution = resolution;
@@ -757,6 +866,70 @@ impl PianoRoll {
         }
     }
 
+    /// Import notes from a standard MIDI file (format 0 or 1).
+    ///
+    /// Note-on/note-off pairs become [`NoteEvent`]s with start and duration in
+    /// beats, derived from the file's ticks-per-beat. Because the grid is in
+    /// beats, tempo meta-events do not move notes: a note on beat 3 stays on
+    /// beat 3 whatever tempo the file plays at. Overlapping notes on the same
+    /// pitch are paired first-in first-out, and notes left hanging at the end
+    /// of a track are closed there. In format 1 files, each track that
+    /// contains notes gets its own piano roll track.
+    ///
+    /// Existing notes are replaced (undoable). On error the piano roll is
+    /// left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Contents of a `.mid` file
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success, or the reason the file could not be read
+    pub fn import_midi(&mut self, bytes: &[u8]) -> Result<(), MidiImportError> {
+        let notes = parse_midi_file(bytes)?;
+        self.save_undo();
+        self.notes = notes;
+        self.selected.clear();
+        Ok(())
+    }
+
+    /// Export the notes as a standard MIDI file (format 1, 480 ticks per beat).
+    ///
+    /// Each piano roll track is written as its own MIDI track, in track order.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of a `.mid` file
+    pub fn export_midi(&self) -> Vec<u8> {
+        let mut tracks: Vec<u8> = self.notes.iter().map(|n| n.track).collect();
+        tracks.sort_unstable();
+        tracks.dedup();
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"MThd");
+        bytes.extend_from_slice(&6u32.to_be_bytes());
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&(tracks.len().max(1) as u16).to_be_bytes());
+        bytes.extend_from_slice(&EXPORT_TICKS_PER_BEAT.to_be_bytes());
+
+        if tracks.is_empty() {
+            write_track_chunk(&mut bytes, &[], 0, None);
+        }
+
+        for track in tracks {
+            let events = note_events(
+                self.notes
+                    .iter()
+                    .filter(|n| n.track == track)
+                    .map(|n| (n.start_beat, n.duration, n.note, n.velocity)),
+            );
+            write_track_chunk(&mut bytes, &events, 0, None);
+        }
+
+        bytes
+    }
+
     // ==================== Pattern Operations ====================
 
     /// Transpose selected notes
@@ -867,6 +1040,327 @@ impl PianoRoll {
     }
 }
 
+/// Errors from [`PianoRoll::import_midi`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MidiImportError {
+    /// The data does not start with an `MThd` header
+    NotMidiFile,
+    /// Only formats 0 and 1 are supported
+    UnsupportedFormat(u16),
+    /// SMPTE time division is not supported (only ticks per beat)
+    SmpteTimeDivision,
+    /// The file ended in the middle of a chunk or event
+    UnexpectedEndOfData,
+    /// A data byte appeared with no running status to apply it to
+    MissingRunningStatus,
+}
+
+impl fmt::Display for MidiImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiImportError::NotMidiFile => write!(f, "Not a standard MIDI file"),
+            MidiImportError::UnsupportedFormat(format) => {
+                write!(f, "Unsupported MIDI file format: {}", format)
+            }
+            MidiImportError::SmpteTimeDivision => {
+                write!(f, "SMPTE time division is not supported")
+            }
+            MidiImportError::UnexpectedEndOfData => write!(f, "Unexpected end of MIDI data"),
+            MidiImportError::MissingRunningStatus => {
+                write!(f, "Data byte without a running status")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MidiImportError {}
+
+/// Byte cursor over MIDI file data
+struct MidiReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MidiReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], MidiImportError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(MidiImportError::UnexpectedEndOfData)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, MidiImportError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, MidiImportError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, MidiImportError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Reads a variable-length quantity (at most 4 bytes).
+    fn vlq(&mut self) -> Result<u32, MidiImportError> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Parses a standard MIDI file into note events.
+fn parse_midi_file(bytes: &[u8]) -> Result<Vec<NoteEvent>, MidiImportError> {
+    let mut reader = MidiReader::new(bytes);
+    if reader.bytes(4).map_err(|_| MidiImportError::NotMidiFile)? != b"MThd" {
+        return Err(MidiImportError::NotMidiFile);
+    }
+
+    let header_len = reader.u32()? as usize;
+    let mut header = MidiReader::new(reader.bytes(header_len)?);
+    let format = header.u16()?;
+    let track_count = header.u16()?;
+    let division = header.u16()?;
+
+    if format > 1 {
+        return Err(MidiImportError::UnsupportedFormat(format));
+    }
+    if division & 0x8000 != 0 {
+        return Err(MidiImportError::SmpteTimeDivision);
+    }
+    let ticks_per_beat = division.max(1) as f64;
+
+    let mut notes = Vec::new();
+    let mut note_track = 0u8;
+    let mut tracks_read = 0;
+    while tracks_read < track_count && !reader.is_empty() {
+        let chunk_id = reader.bytes(4)?;
+        let chunk_len = reader.u32()? as usize;
+        let chunk = reader.bytes(chunk_len)?;
+
+        // Skip unknown chunk types, as the spec requires
+        if chunk_id != b"MTrk" {
+            continue;
+        }
+        tracks_read += 1;
+
+        let track_notes = parse_track(chunk, ticks_per_beat, note_track)?;
+        if !track_notes.is_empty() {
+            notes.extend(track_notes);
+            note_track = note_track.saturating_add(1);
+        }
+    }
+
+    notes.sort_by(|a, b| a.start_beat.total_cmp(&b.start_beat));
+    Ok(notes)
+}
+
+/// Parses one `MTrk` chunk, pairing note-ons with note-offs.
+fn parse_track(
+    chunk: &[u8],
+    ticks_per_beat: f64,
+    track: u8,
+) -> Result<Vec<NoteEvent>, MidiImportError> {
+    let mut reader = MidiReader::new(chunk);
+    let mut notes = Vec::new();
+    // Open notes per (channel, note): (start tick, velocity), oldest first
+    let mut open: HashMap<(u8, u8), VecDeque<(u32, u8)>> = HashMap::new();
+    let mut tick = 0u32;
+    let mut running_status: Option<u8> = None;
+
+    let close = |notes: &mut Vec<NoteEvent>, note: u8, start: u32, end: u32, velocity: u8| {
+        notes.push(NoteEvent {
+            note,
+            start_beat: start as f64 / ticks_per_beat,
+            duration: end.saturating_sub(start) as f64 / ticks_per_beat,
+            velocity,
+            track,
+        });
+    };
+
+    while !reader.is_empty() {
+        tick = tick.saturating_add(reader.vlq()?);
+
+        let mut status = reader.u8()?;
+        match status {
+            0xFF => {
+                // Meta event: type, length, data (tempo, end of track, ...)
+                let meta_type = reader.u8()?;
+                let len = reader.vlq()? as usize;
+                reader.bytes(len)?;
+                if meta_type == 0x2F {
+                    break;
+                }
+                continue;
+            }
+            0xF0 | 0xF7 => {
+                // SysEx: length-prefixed, ignored
+                let len = reader.vlq()? as usize;
+                reader.bytes(len)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        // Running status: a data byte reuses the previous status byte
+        let first_data = if status < 0x80 {
+            let data = status;
+            status = running_status.ok_or(MidiImportError::MissingRunningStatus)?;
+            data
+        } else {
+            running_status = Some(status);
+            reader.u8()?
+        };
+
+        let channel = status & 0x0F;
+        match status & 0xF0 {
+            0x80 | 0x90 => {
+                let note = first_data & 0x7F;
+                let velocity = reader.u8()? & 0x7F;
+                let key = (channel, note);
+                if status & 0xF0 == 0x90 && velocity > 0 {
+                    open.entry(key).or_default().push_back((tick, velocity));
+                } else if let Some((start, velocity)) =
+                    open.get_mut(&key).and_then(|queue| queue.pop_front())
+                {
+                    close(&mut notes, note, start, tick, velocity);
+                }
+            }
+            0xA0 | 0xB0 | 0xE0 => {
+                reader.u8()?;
+            }
+            // Program change and channel pressure carry a single data byte
+            _ => {}
+        }
+    }
+
+    // Close notes still held when the track ends
+    for ((_, note), queue) in open {
+        for (start, velocity) in queue {
+            close(&mut notes, note, start, tick, velocity);
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Largest value a MIDI variable-length quantity can hold (4 bytes)
+const MAX_VLQ: u32 = 0x0FFF_FFFF;
+
+/// Writes a variable-length quantity.
+///
+/// Values above [`MAX_VLQ`] are clamped, since readers stop after 4 bytes.
+fn write_vlq(bytes: &mut Vec<u8>, value: u32) {
+    let mut value = value.min(MAX_VLQ);
+    let mut buffer = [0u8; 4];
+    let mut len = 0;
+    loop {
+        buffer[len] = (value & 0x7F) as u8;
+        len += 1;
+        value >>= 7;
+        if value == 0 {
+            break;
+        }
+    }
+    for i in (0..len).rev() {
+        let continuation = if i > 0 { 0x80 } else { 0x00 };
+        bytes.push(buffer[i] | continuation);
+    }
+}
+
+/// Converts (start beat, duration, note, velocity) notes into sorted
+/// (tick, is_note_on, note, velocity) events; offs sort before ons on the same tick.
+fn note_events(notes: impl IntoIterator<Item = (f64, f64, u8, u8)>) -> Vec<(u32, bool, u8, u8)> {
+    let ticks_per_beat = EXPORT_TICKS_PER_BEAT as f64;
+    let mut events = Vec::new();
+    for (start_beat, duration, note, velocity) in notes {
+        let start = (start_beat.max(0.0) * ticks_per_beat).round() as u32;
+        let end = ((start_beat + duration).max(0.0) * ticks_per_beat).round() as u32;
+        let note = note.min(127);
+        events.push((start, true, note, velocity.clamp(1, 127)));
+        events.push((end.max(start + 1), false, note, 0));
+    }
+    events.sort_by_key(|&(tick, is_on, _, _)| (tick, is_on));
+    events
+}
+
+/// Writes an `MTrk` chunk of note events, sorted by tick.
+///
+/// The chunk is assembled first so its length covers the variable-length
+/// delta times exactly.
+fn write_track_chunk(
+    bytes: &mut Vec<u8>,
+    events: &[(u32, bool, u8, u8)],
+    channel: u8,
+    tempo_bpm: Option<f64>,
+) {
+    let mut track = Vec::new();
+    if let Some(bpm) = tempo_bpm {
+        // Set tempo: microseconds per quarter note, 3 bytes
+        let us_per_beat = (60_000_000.0 / bpm.max(1.0)).round() as u32;
+        track.extend_from_slice(&[0x00, 0xFF, 0x51, 0x03]);
+        track.extend_from_slice(&us_per_beat.min(0xFF_FFFF).to_be_bytes()[1..]);
+    }
+
+    let mut last_tick = 0;
+    for &(tick, is_on, note, velocity) in events {
+        write_vlq(&mut track, tick - last_tick);
+        last_tick = tick;
+        track.push(if is_on { 0x90 } else { 0x80 } | (channel & 0x0F));
+        track.push(note);
+        track.push(velocity);
+    }
+    // End of track
+    track.extend_from_slice(&[0x00, 0xFF, 0x2F, 0x00]);
+
+    bytes.extend_from_slice(b"MTrk");
+    bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&track);
+}
+
+/// Builds a single-track standard MIDI file (format 1, 480 ticks per beat)
+/// with a tempo event, for the melody and rhythm generators' file export.
+///
+/// # Arguments
+///
+/// * `notes` - (start beat, duration in beats, note, velocity 1-127)
+/// * `channel` - MIDI channel (0-15, 9 = General MIDI drums)
+/// * `tempo_bpm` - Tempo in beats per minute
+pub(crate) fn single_track_midi(
+    notes: impl IntoIterator<Item = (f64, f64, u8, u8)>,
+    channel: u8,
+    tempo_bpm: f64,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"MThd");
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&EXPORT_TICKS_PER_BEAT.to_be_bytes());
+    write_track_chunk(&mut bytes, &note_events(notes), channel, Some(tempo_bpm));
+    bytes
+}
+
 /// Simple random function (替代 rand crate)
 fn rand() -> u32 {
     static mut STATE: u64 = 1;
@@ -984,4 +1478,251 @@ mod tests {
         pr.quantize_selected();
         assert!((pr.notes[0].start_beat - 0.0625).abs() < 0.001);
     }
+
+    // --- MIDI file import/export ---
+
+    /// Builds a format-0 file with the given ticks per beat and track data.
+    fn midi_file(ticks_per_beat: u16, track: &[u8]) -> Vec<u8> {
+        let mut bytes = b"MThd\x00\x00\x00\x06\x00\x00\x00\x01".to_vec();
+        bytes.extend_from_slice(&ticks_per_beat.to_be_bytes());
+        bytes.extend_from_slice(b"MTrk");
+        bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(track);
+        bytes
+    }
+
+    #[test]
+    fn test_write_vlq() {
+        let encode = |value| {
+            let mut bytes = Vec::new();
+            write_vlq(&mut bytes, value);
+            bytes
+        };
+        assert_eq!(encode(0), [0x00]);
+        assert_eq!(encode(0x7F), [0x7F]);
+        assert_eq!(encode(0x80), [0x81, 0x00]);
+        assert_eq!(encode(480), [0x83, 0x60]);
+        assert_eq!(encode(0x0FFF_FFFF), [0xFF, 0xFF, 0xFF, 0x7F]);
+        // Too large for 4 bytes: clamped rather than emitting a 5th byte
+        assert_eq!(encode(0x1000_0000), [0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(encode(u32::MAX), [0xFF, 0xFF, 0xFF, 0x7F]);
+
+        for value in [0, 1, 0x3FFF, 0x4000, 0x1F_FFFF, 0x20_0000, MAX_VLQ] {
+            let bytes = encode(value);
+            assert_eq!(MidiReader::new(&bytes).vlq().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_single_track_midi_round_trip() {
+        // Simultaneous notes (delta 0) and a long gap (multi-byte delta)
+        let notes = [
+            (0.0, 0.25, 36, 127),
+            (0.0, 0.25, 42, 64),
+            (1.5, 0.5, 38, 100),
+            (300.0, 1.0, 49, 90),
+        ];
+        let bytes = single_track_midi(notes, 9, 96.0);
+
+        let track_len = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        assert_eq!(track_len, bytes.len() - 22);
+
+        let parsed = parse_midi_file(&bytes).unwrap();
+        assert_eq!(parsed.len(), notes.len());
+        for (start_beat, duration, note, velocity) in notes {
+            let found = parsed
+                .iter()
+                .find(|n| n.note == note)
+                .expect("note lost in round trip");
+            assert!((found.start_beat - start_beat).abs() < 1e-3);
+            assert!((found.duration - duration).abs() < 1e-3);
+            assert_eq!(found.velocity, velocity);
+        }
+    }
+
+    #[test]
+    fn test_midi_round_trip() {
+        let mut pr = PianoRoll::new();
+        pr.add_note(60, 0.0, 1.0, 100);
+        pr.add_note(64, 1.0, 0.5, 90);
+        pr.add_note(67, 1.5, 2.25, 70);
+        pr.add_note_to_track(36, 0.0, 0.25, 127, 1);
+
+        let bytes = pr.export_midi();
+        let mut imported = PianoRoll::new();
+        imported.import_midi(&bytes).unwrap();
+
+        assert_eq!(imported.note_count(), pr.note_count());
+        for original in pr.get_all_notes() {
+            let found = imported
+                .get_all_notes()
+                .iter()
+                .find(|n| n.note == original.note)
+                .expect("note lost in round trip");
+            assert!((found.start_beat - original.start_beat).abs() < 1e-3);
+            assert!((found.duration - original.duration).abs() < 1e-3);
+            assert_eq!(found.velocity, original.velocity);
+            assert_eq!(found.track, original.track);
+        }
+    }
+
+    #[test]
+    fn test_midi_import_running_status_and_tempo() {
+        // 96 ticks per beat, tempo change mid-track, running status note-offs
+        let track = [
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // tempo 120 BPM
+            0x00, 0x90, 60, 100, // C4 on at beat 0
+            0x60, 60, 0, // running status: note-on vel 0 = off at beat 1
+            0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, // tempo 60 BPM
+            0x00, 64, 80, // running status: E4 on at beat 1
+            0x81, 0x40, 0x80, 64, 0, // E4 off after 192 ticks (beat 3)
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let mut pr = PianoRoll::new();
+        pr.import_midi(&midi_file(96, &track)).unwrap();
+
+        let notes = pr.get_all_notes();
+        assert_eq!(notes.len(), 2);
+        assert_eq!((notes[0].note, notes[0].velocity), (60, 100));
+        assert!((notes[0].start_beat - 0.0).abs() < 1e-9);
+        assert!((notes[0].duration - 1.0).abs() < 1e-9);
+        // The tempo change does not move the note off its beat
+        assert_eq!((notes[1].note, notes[1].velocity), (64, 80));
+        assert!((notes[1].start_beat - 1.0).abs() < 1e-9);
+        assert!((notes[1].duration - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_midi_import_overlapping_same_pitch() {
+        // Two overlapping C4s: offs pair with ons first-in first-out
+        let track = [
+            0x00, 0x90, 60, 100, // on at 0
+            0x30, 0x90, 60, 50, // on at 0.5
+            0x30, 0x80, 60, 0, // off at 1.0 closes the first
+            0x30, 0x80, 60, 0, // off at 1.5 closes the second
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let mut pr = PianoRoll::new();
+        pr.import_midi(&midi_file(96, &track)).unwrap();
+
+        let notes = pr.get_all_notes();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].velocity, 100);
+        assert!((notes[0].duration - 1.0).abs() < 1e-9);
+        assert_eq!(notes[1].velocity, 50);
+        assert!((notes[1].start_beat - 0.5).abs() < 1e-9);
+        assert!((notes[1].duration - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_midi_import_errors_leave_roll_unchanged() {
+        let mut pr = PianoRoll::new();
+        pr.add_note(60, 0.0, 1.0, 100);
+
+        assert_eq!(
+            pr.import_midi(b"RIFF1234"),
+            Err(MidiImportError::NotMidiFile)
+        );
+
+        let mut format2 = midi_file(96, &[0x00, 0xFF, 0x2F, 0x00]);
+        format2[9] = 2;
+        assert_eq!(
+            pr.import_midi(&format2),
+            Err(MidiImportError::UnsupportedFormat(2))
+        );
+
+        let truncated = midi_file(96, &[0x00, 0x90, 60]);
+        assert_eq!(
+            pr.import_midi(&truncated),
+            Err(MidiImportError::UnexpectedEndOfData)
+        );
+
+        let no_status = midi_file(96, &[0x00, 60, 100]);
+        assert_eq!(
+            pr.import_midi(&no_status),
+            Err(MidiImportError::MissingRunningStatus)
+        );
+
+        assert_eq!(pr.note_count(), 1);
+    }
+
+    // --- Humanize and swing ---
+
+    fn grid_roll() -> PianoRoll {
+        let mut pr = PianoRoll::new();
+        pr.set_resolution(Resolution::Quarter);
+        for i in 0..16 {
+            pr.add_note(60 + (i % 12) as u8, i as f64 * 0.25, 0.25, 100);
+        }
+        pr
+    }
+
+    #[test]
+    fn test_humanize_is_deterministic_and_bounded() {
+        let mut a = grid_roll();
+        let mut b = grid_roll();
+        a.set_humanize_seed(42);
+        b.set_humanize_seed(42);
+
+        a.humanize(0.05, 0.5);
+        b.humanize(0.05, 0.5);
+        assert_eq!(a.get_all_notes(), b.get_all_notes());
+
+        let original = grid_roll();
+        let mut moved = 0;
+        for (h, o) in a.get_all_notes().iter().zip(original.get_all_notes()) {
+            let offset = h.start_beat - o.start_beat;
+            assert!(offset.abs() <= 0.05 + 1e-9, "offset {offset}");
+            assert!(h.start_beat >= 0.0);
+            assert_eq!(h.duration, o.duration);
+            assert!((h.velocity as i32 - o.velocity as i32).abs() <= 16);
+            if offset != 0.0 {
+                moved += 1;
+            }
+        }
+        assert!(moved > 8);
+
+        // A different seed gives a different feel
+        let mut c = grid_roll();
+        c.set_humanize_seed(7);
+        c.humanize(0.05, 0.5);
+        assert_ne!(a.get_all_notes(), c.get_all_notes());
+    }
+
+    #[test]
+    fn test_humanize_then_quantize_restores_grid() {
+        let mut pr = grid_roll();
+        // Far more than half a step: capped so quantize still works
+        pr.humanize(10.0, 0.0);
+        assert!(pr.get_all_notes().iter().all(|n| n.start_beat >= 0.0));
+
+        pr.quantize_all();
+        assert_eq!(pr.get_all_notes(), grid_roll().get_all_notes());
+    }
+
+    #[test]
+    fn test_apply_swing_delays_off_beats() {
+        let mut pr = grid_roll();
+        let moved = pr.apply_swing(2.0 / 3.0);
+        assert_eq!(moved, 8);
+
+        let step = pr.grid_step();
+        for (i, note) in pr.get_all_notes().iter().enumerate() {
+            let straight = i as f64 * step;
+            if i % 2 == 0 {
+                assert_eq!(note.start_beat, straight);
+            } else {
+                // Pair splits 2:1, so the off-beat lands a third of a step late
+                assert!((note.start_beat - straight - step / 3.0).abs() < 1e-6);
+            }
+        }
+
+        pr.quantize_all();
+        assert_eq!(pr.get_all_notes(), grid_roll().get_all_notes());
+
+        // Straight swing leaves the notes alone
+        let mut straight = grid_roll();
+        straight.apply_swing(0.5);
+        assert_eq!(straight.get_all_notes(), grid_roll().get_all_notes());
+    }
 }
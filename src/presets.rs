@@ -12,6 +12,48 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
+
+/// 二进制预设文件头
+const BINARY_MAGIC: &[u8; 4] = b"WVPR";
+
+/// 当前二进制格式版本
+const BINARY_VERSION: u16 = 1;
+
+/// 二进制预设解析错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetFormatError {
+    /// Missing "WVPR" header
+    NotPresetFile,
+    /// Written by a newer (or unknown) format version
+    UnsupportedVersion(u16),
+    /// Data ends in the middle of a field
+    UnexpectedEndOfData,
+    /// A string field is not valid UTF-8
+    InvalidString,
+    /// Unknown category index
+    InvalidCategory(u8),
+}
+
+impl fmt::Display for PresetFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetFormatError::NotPresetFile => write!(f, "not a binary preset file"),
+            PresetFormatError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported preset format version {} (newest supported is {})",
+                version, BINARY_VERSION
+            ),
+            PresetFormatError::UnexpectedEndOfData => write!(f, "unexpected end of preset data"),
+            PresetFormatError::InvalidString => write!(f, "preset string is not valid UTF-8"),
+            PresetFormatError::InvalidCategory(index) => {
+                write!(f, "unknown preset category {}", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PresetFormatError {}
 
 /// 预设分类
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -88,6 +130,9 @@ pub struct PresetParameters {
     pub filter_resonance: f32,
     /// 起音时间 (Attack)
     pub attack: f32,
+    /// 保持时间 (Hold)
+    #[serde(default)]
+    pub hold: f32,
     /// 释音时间 (Release)
     pub release: f32,
     /// 波形类型
@@ -104,6 +149,7 @@ impl Default for PresetParameters {
             filter_cutoff: 2000.0,
             filter_resonance: 1.0,
             attack: 0.01,
+            hold: 0.0,
             release: 0.5,
             waveform: "sawtooth".to_string(),
             extra: HashMap::new(),
@@ -111,6 +157,39 @@ impl Default for PresetParameters {
     }
 }
 
+impl PresetParameters {
+    /// 在两个预设参数之间插值 (morph)
+    ///
+    /// Continuous values are interpolated linearly by `t` (clamped to
+    /// 0.0 - 1.0); discrete values such as the waveform, and extra
+    /// parameters only one side has, come from the closer preset
+    /// (`t < 0.5` → self).
+    pub fn morph(&self, other: &PresetParameters, t: f32) -> PresetParameters {
+        let t = t.clamp(0.0, 1.0);
+        // Exact at both ends
+        let lerp = |a: f32, b: f32| a * (1.0 - t) + b * t;
+        let nearer = if t < 0.5 { self } else { other };
+
+        let mut extra = nearer.extra.clone();
+        for (key, &a) in &self.extra {
+            if let Some(&b) = other.extra.get(key) {
+                extra.insert(key.clone(), lerp(a, b));
+            }
+        }
+
+        PresetParameters {
+            volume: lerp(self.volume, other.volume),
+            filter_cutoff: lerp(self.filter_cutoff, other.filter_cutoff),
+            filter_resonance: lerp(self.filter_resonance, other.filter_resonance),
+            attack: lerp(self.attack, other.attack),
+            hold: lerp(self.hold, other.hold),
+            release: lerp(self.release, other.release),
+            waveform: nearer.waveform.clone(),
+            extra,
+        }
+    }
+}
+
 /// 单个预设
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Preset {
@@ -147,6 +226,170 @@ impl Default for Preset {
     }
 }
 
+impl Preset {
+    /// 序列化为紧凑的二进制格式
+    ///
+    /// Layout: "WVPR", format version (u16), then the fields in declaration
+    /// order. Numbers are little-endian, floats are stored as their exact
+    /// bits, strings and lists are prefixed with a u32 length. JSON stays the
+    /// interchange format; this is for fast local loading.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(128);
+        out.extend_from_slice(BINARY_MAGIC);
+        out.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+
+        write_str(&mut out, &self.name);
+        let category = PresetCategory::all_categories()
+            .iter()
+            .position(|c| *c == self.category)
+            .unwrap_or(0);
+        out.push(category as u8);
+        write_str(&mut out, &self.description);
+
+        let params = &self.parameters;
+        for value in [
+            params.volume,
+            params.filter_cutoff,
+            params.filter_resonance,
+            params.attack,
+            params.hold,
+            params.release,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        write_str(&mut out, &params.waveform);
+        // Sorted so the same preset always gives the same bytes
+        let mut extra: Vec<_> = params.extra.iter().collect();
+        extra.sort_by(|a, b| a.0.cmp(b.0));
+        out.extend_from_slice(&(extra.len() as u32).to_le_bytes());
+        for (key, value) in extra {
+            write_str(&mut out, key);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        out.extend_from_slice(&(self.tags.len() as u32).to_le_bytes());
+        for tag in &self.tags {
+            write_str(&mut out, tag);
+        }
+        out.push(self.is_favorite as u8);
+        out.extend_from_slice(&self.usage_count.to_le_bytes());
+        out
+    }
+
+    /// 从二进制格式反序列化
+    ///
+    /// Accepts every format version up to the current one.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, PresetFormatError> {
+        let mut reader = ByteReader { data, pos: 0 };
+        if reader.take(4).ok() != Some(BINARY_MAGIC.as_slice()) {
+            return Err(PresetFormatError::NotPresetFile);
+        }
+        match reader.u16()? {
+            1 => Self::read_v1(&mut reader),
+            version => Err(PresetFormatError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// 读取版本 1 的字段
+    fn read_v1(reader: &mut ByteReader) -> Result<Self, PresetFormatError> {
+        let name = reader.string()?;
+        let index = reader.u8()?;
+        let category = PresetCategory::all_categories()
+            .get(index as usize)
+            .cloned()
+            .ok_or(PresetFormatError::InvalidCategory(index))?;
+        let description = reader.string()?;
+
+        let volume = reader.f32()?;
+        let filter_cutoff = reader.f32()?;
+        let filter_resonance = reader.f32()?;
+        let attack = reader.f32()?;
+        let hold = reader.f32()?;
+        let release = reader.f32()?;
+        let waveform = reader.string()?;
+        let mut extra = HashMap::new();
+        for _ in 0..reader.u32()? {
+            let key = reader.string()?;
+            extra.insert(key, reader.f32()?);
+        }
+
+        let mut tags = Vec::new();
+        for _ in 0..reader.u32()? {
+            tags.push(reader.string()?);
+        }
+        let is_favorite = reader.u8()? != 0;
+        let usage_count = reader.u32()?;
+
+        Ok(Self {
+            name,
+            category,
+            description,
+            parameters: PresetParameters {
+                volume,
+                filter_cutoff,
+                filter_resonance,
+                attack,
+                hold,
+                release,
+                waveform,
+                extra,
+            },
+            tags,
+            is_favorite,
+            usage_count,
+        })
+    }
+}
+
+/// 写入带长度前缀的字符串
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// 二进制预设读取器
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PresetFormatError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(PresetFormatError::UnexpectedEndOfData)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, PresetFormatError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, PresetFormatError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, PresetFormatError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32, PresetFormatError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn string(&mut self) -> Result<String, PresetFormatError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| PresetFormatError::InvalidString)
+    }
+}
+
 /// 预设集合
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PresetCollection {
@@ -415,6 +658,32 @@ impl PresetManager {
         }
         stats
     }
+
+    /// 按分类和名称筛选预设
+    ///
+    /// `None` (or an empty name) matches everything; names are matched
+    /// case-insensitively.
+    pub fn filter(
+        &self,
+        category: Option<PresetCategory>,
+        name_contains: Option<&str>,
+    ) -> Vec<&Preset> {
+        let query = name_contains.unwrap_or("").to_lowercase();
+        self.collection
+            .presets
+            .iter()
+            .filter(|p| category.as_ref().is_none_or(|c| p.category == *c))
+            .filter(|p| p.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// 获取已有预设的分类 (按分类顺序)
+    pub fn categories(&self) -> Vec<PresetCategory> {
+        PresetCategory::all_categories()
+            .into_iter()
+            .filter(|c| self.collection.presets.iter().any(|p| p.category == *c))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -503,6 +772,139 @@ mod tests {
         assert_eq!(collection.get_favorites().len(), 1);
     }
 
+    #[test]
+    fn test_preset_binary_round_trip() {
+        let mut extra = HashMap::new();
+        extra.insert("lfo_rate".to_string(), 3.3);
+        extra.insert("detune".to_string(), -0.1);
+        let preset = Preset {
+            name: "Glass Pad 音色".to_string(),
+            category: PresetCategory::Ambient,
+            description: "Slow shimmering pad".to_string(),
+            parameters: PresetParameters {
+                volume: 0.1 + 0.2,
+                filter_cutoff: 1234.567,
+                filter_resonance: f32::MIN_POSITIVE,
+                attack: 1.0 / 3.0,
+                hold: 0.05,
+                release: 7.25,
+                waveform: "triangle".to_string(),
+                extra,
+            },
+            tags: vec!["pad".to_string(), "shimmer".to_string()],
+            is_favorite: true,
+            usage_count: 42,
+        };
+
+        let bytes = preset.to_bytes();
+        assert_eq!(&bytes[..4], b"WVPR");
+        assert_eq!(Preset::from_bytes(&bytes).unwrap(), preset);
+        // Deterministic despite the HashMap
+        assert_eq!(preset.clone().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_preset_binary_errors() {
+        let bytes = Preset::default().to_bytes();
+
+        let mut newer = bytes.clone();
+        newer[4] = 99;
+        assert_eq!(
+            Preset::from_bytes(&newer),
+            Err(PresetFormatError::UnsupportedVersion(99))
+        );
+        assert_eq!(
+            Preset::from_bytes(b"{\"name\":1}"),
+            Err(PresetFormatError::NotPresetFile)
+        );
+        assert_eq!(
+            Preset::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(PresetFormatError::UnexpectedEndOfData)
+        );
+    }
+
+    #[test]
+    fn test_preset_parameters_hold_defaults_in_json() {
+        let json = r#"{"volume":0.7,"filter_cutoff":2000.0,"filter_resonance":1.0,
+            "attack":0.01,"release":0.5,"waveform":"sine"}"#;
+        let params: PresetParameters = serde_json::from_str(json).unwrap();
+        assert_eq!(params.hold, 0.0);
+    }
+
+    #[test]
+    fn test_preset_parameters_morph() {
+        let a = PresetParameters {
+            filter_cutoff: 400.0,
+            waveform: "sine".to_string(),
+            extra: HashMap::from([("mix".to_string(), 0.0), ("drive".to_string(), 0.5)]),
+            ..PresetParameters::default()
+        };
+        let b = PresetParameters {
+            filter_cutoff: 4000.0,
+            attack: 0.3,
+            hold: 0.1,
+            waveform: "square".to_string(),
+            extra: HashMap::from([("mix".to_string(), 1.0)]),
+            ..PresetParameters::default()
+        };
+
+        let mid = a.morph(&b, 0.5);
+        assert!((mid.filter_cutoff - 2200.0).abs() < 1e-3);
+        assert!((mid.attack - 0.155).abs() < 1e-6);
+        assert!((mid.hold - 0.05).abs() < 1e-6);
+        assert!((mid.extra["mix"] - 0.5).abs() < 1e-6);
+        // Discrete values snap to the nearer preset
+        assert_eq!(mid.waveform, "square");
+        assert_eq!(a.morph(&b, 0.49).waveform, "sine");
+        assert_eq!(a.morph(&b, 0.49).extra.get("drive"), Some(&0.5));
+        assert_eq!(mid.extra.get("drive"), None);
+
+        assert_eq!(a.morph(&b, 0.0), a);
+        assert_eq!(a.morph(&b, 1.0), b);
+        assert_eq!(a.morph(&b, -2.0), a);
+        assert_eq!(a.morph(&b, 3.0), b);
+    }
+
+    #[test]
+    fn test_preset_manager_filter() {
+        let mut manager = PresetManager::new();
+        for (name, category) in [
+            ("Deep Bass", PresetCategory::Bass),
+            ("Acid BASS", PresetCategory::Bass),
+            ("Bass Pad", PresetCategory::Pad),
+            ("Glass Pad", PresetCategory::Pad),
+        ] {
+            manager.add_preset(Preset {
+                name: name.to_string(),
+                category,
+                ..Preset::default()
+            });
+        }
+
+        let names = |presets: Vec<&Preset>| -> Vec<String> {
+            presets.iter().map(|p| p.name.clone()).collect()
+        };
+        assert_eq!(
+            names(manager.filter(Some(PresetCategory::Bass), None)),
+            vec!["Deep Bass", "Acid BASS"]
+        );
+        assert_eq!(
+            names(manager.filter(None, Some("bass"))),
+            vec!["Deep Bass", "Acid BASS", "Bass Pad"]
+        );
+        assert_eq!(
+            names(manager.filter(Some(PresetCategory::Pad), Some("BASS"))),
+            vec!["Bass Pad"]
+        );
+        assert_eq!(manager.filter(Some(PresetCategory::Pad), Some("")).len(), 2);
+        assert!(manager.filter(Some(PresetCategory::Lead), None).is_empty());
+
+        assert_eq!(
+            manager.categories(),
+            vec![PresetCategory::Bass, PresetCategory::Pad]
+        );
+    }
+
     #[test]
     fn test_preset_manager() {
         let mut manager = PresetManager::new();
@@ -170,6 +170,9 @@ pub struct EnvelopeState {
     pub name: String,
     /// Attack time (seconds)
     pub attack: f64,
+    /// Hold time at peak (seconds)
+    #[serde(default)]
+    pub hold: f64,
     /// Decay time (seconds)
     pub decay: f64,
     /// Sustain level (0.0 to 1.0)
@@ -191,6 +194,7 @@ impl Default for EnvelopeState {
         Self {
             name: String::from("Default"),
             attack: 0.01,
+            hold: 0.0,
             decay: 0.2,
             sustain: 0.7,
             release: 0.3,
@@ -922,6 +926,7 @@ mod tests {
         let env = EnvelopeState::default();
         assert_eq!(env.name, "Default");
         assert_eq!(env.attack, 0.01);
+        assert_eq!(env.hold, 0.0);
         assert_eq!(env.decay, 0.2);
         assert_eq!(env.sustain, 0.7);
         assert_eq!(env.release, 0.3);
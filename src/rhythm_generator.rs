@@ -23,7 +23,14 @@
 //! let pattern = generator.generate_preset(RhythmStyle::EDM);
 //! ```
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Largest velocity offset applied by `humanize` at full amount
+const HUMANIZE_MAX_VELOCITY: f32 = 0.15;
+
+/// Longest loop, in measures, `generate_layered` extends a pattern to
+const MAX_LAYERED_PERIOD_MEASURES: usize = 64;
 
 /// Drum sound types enumeration.
 ///
@@ -193,6 +200,21 @@ pub struct RhythmGenerator {
     accent_strength: f32,
     /// Fill density (0.0-1.0)
     fill_density: f32,
+    /// Style whose groove the basic beat follows
+    style: RhythmStyle,
+    /// Euclidean layers for `generate_layered`
+    layers: Vec<EuclideanLayer>,
+}
+
+/// One voice of a layered Euclidean pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EuclideanLayer {
+    /// Drum sound of the layer
+    sound: DrumSound,
+    /// Hits per cycle
+    pulses: u8,
+    /// Cycle length in 16th notes
+    steps: u8,
 }
 
 /// Rhythm generator implementation.
@@ -224,6 +246,8 @@ impl RhythmGenerator {
             complexity: Complexity::Medium,
             accent_strength: 0.7,
             fill_density: 0.3,
+            style: RhythmStyle::Custom,
+            layers: Vec::new(),
         }
     }
 
@@ -251,6 +275,8 @@ impl RhythmGenerator {
             complexity,
             accent_strength: 0.7,
             fill_density: 0.3,
+            style: RhythmStyle::Custom,
+            layers: Vec::new(),
         }
     }
 
@@ -304,7 +330,7 @@ impl RhythmGenerator {
             tempo: self.tempo,
             time_signature: self.time_signature,
             length: self.length,
-            style: RhythmStyle::Custom,
+            style: self.style,
             swing: self.swing,
         }
     }
@@ -328,6 +354,8 @@ impl RhythmGenerator {
     /// let edm_pattern = generator.generate_preset(RhythmStyle::EDM);
     /// ```
     pub fn generate_preset(&mut self, style: RhythmStyle) -> DrumPattern {
+        self.style = style;
+
         // Configure parameters based on style
         match style {
             RhythmStyle::Pop => {
@@ -419,15 +447,14 @@ impl RhythmGenerator {
             }
         }
 
-        let mut pattern = self.generate();
-        pattern.style = style;
-        pattern
+        self.generate()
     }
 
     /// Generates a basic beat for one measure.
     ///
-    /// Creates the fundamental kick/snare/hi-hat pattern for a measure
-    /// based on the configured style and complexity.
+    /// Creates the fundamental kick/snare/hi-hat pattern for a measure.
+    /// Styles with a signature groove use their own generator; the others
+    /// (and Custom) use the generic pattern for the configured complexity.
     ///
     /// # Arguments
     ///
@@ -438,9 +465,19 @@ impl RhythmGenerator {
     ///
     /// Vector of DrumNotes for the basic beat.
     fn generate_basic_beat(&self, measure_start: f64, rng: &mut impl Rng) -> Vec<DrumNote> {
+        match self.style {
+            RhythmStyle::House | RhythmStyle::Techno | RhythmStyle::EDM => {
+                return self.generate_four_on_the_floor(measure_start);
+            }
+            RhythmStyle::Reggae => return self.generate_reggae_pattern(measure_start),
+            RhythmStyle::HipHop => return self.generate_boom_bap_pattern(measure_start, rng),
+            RhythmStyle::Jazz => return self.generate_jazz_pattern(measure_start, rng),
+            _ => {}
+        }
+
         let mut notes = Vec::new();
 
-        // Determine pattern type based on style and complexity
+        // Determine pattern type based on complexity
         match self.complexity {
             Complexity::Simple => {
                 notes.extend(self.generate_simple_pattern(measure_start, rng));
@@ -456,6 +493,201 @@ impl RhythmGenerator {
         notes
     }
 
+    /// Four-on-the-floor pattern for House, Techno and EDM.
+    ///
+    /// Kick on every beat, clap on the backbeats and an open hi-hat on each
+    /// off-beat. Techno adds driving 16th-note closed hats.
+    fn generate_four_on_the_floor(&self, measure_start: f64) -> Vec<DrumNote> {
+        let mut notes = Vec::new();
+
+        for beat in 0..self.time_signature {
+            let beat_start = measure_start + beat as f64;
+            notes.push(DrumNote {
+                sound: DrumSound::Kick,
+                start_beat: beat_start,
+                velocity: self.accent_strength,
+                duration: 0.1,
+            });
+
+            if beat % 2 == 1 {
+                notes.push(DrumNote {
+                    sound: DrumSound::Clap,
+                    start_beat: beat_start,
+                    velocity: self.accent_strength * 0.85,
+                    duration: 0.08,
+                });
+            }
+
+            notes.push(DrumNote {
+                sound: DrumSound::HiHatOpen,
+                start_beat: beat_start + 0.5,
+                velocity: 0.55,
+                duration: 0.2,
+            });
+
+            if self.style == RhythmStyle::Techno {
+                for sixteenth in [0.25, 0.75] {
+                    notes.push(DrumNote {
+                        sound: DrumSound::HiHatClosed,
+                        start_beat: beat_start + sixteenth,
+                        velocity: 0.4,
+                        duration: 0.05,
+                    });
+                }
+            }
+        }
+
+        notes
+    }
+
+    /// Reggae pattern - kick and rimshot on 2 and 4, hi-hat skank on the off-beats.
+    fn generate_reggae_pattern(&self, measure_start: f64) -> Vec<DrumNote> {
+        let mut notes = Vec::new();
+
+        for beat in 0..self.time_signature {
+            let beat_start = measure_start + beat as f64;
+
+            if beat % 2 == 1 {
+                notes.push(DrumNote {
+                    sound: DrumSound::Kick,
+                    start_beat: beat_start,
+                    velocity: self.accent_strength.max(0.8),
+                    duration: 0.1,
+                });
+                notes.push(DrumNote {
+                    sound: DrumSound::SnareAcoustic,
+                    start_beat: beat_start,
+                    velocity: self.accent_strength.max(0.75),
+                    duration: 0.08,
+                });
+            } else {
+                // Beats 1 and 3 are left almost empty
+                notes.push(DrumNote {
+                    sound: DrumSound::HiHatPedal,
+                    start_beat: beat_start,
+                    velocity: 0.3,
+                    duration: 0.05,
+                });
+            }
+
+            notes.push(DrumNote {
+                sound: DrumSound::HiHatClosed,
+                start_beat: beat_start + 0.5,
+                velocity: 0.6,
+                duration: 0.05,
+            });
+        }
+
+        notes
+    }
+
+    /// Boom-bap pattern for Hip Hop - syncopated kick, hard snare on 2 and 4.
+    fn generate_boom_bap_pattern(&self, measure_start: f64, rng: &mut impl Rng) -> Vec<DrumNote> {
+        let mut notes = Vec::new();
+
+        // "Boom" on 1 and the and of 3, with an occasional pickup before beat 3
+        let mut kicks = vec![
+            (0.0, self.accent_strength),
+            (2.5, self.accent_strength * 0.85),
+        ];
+        if rng.gen::<f32>() < 0.5 {
+            kicks.push((1.75, self.accent_strength * 0.6));
+        }
+        for (offset, velocity) in kicks {
+            if offset < self.time_signature as f64 {
+                notes.push(DrumNote {
+                    sound: DrumSound::Kick,
+                    start_beat: measure_start + offset,
+                    velocity,
+                    duration: 0.1,
+                });
+            }
+        }
+
+        for beat in 0..self.time_signature {
+            let beat_start = measure_start + beat as f64;
+
+            // "Bap" on the backbeats
+            if beat % 2 == 1 {
+                notes.push(DrumNote {
+                    sound: DrumSound::Snare,
+                    start_beat: beat_start,
+                    velocity: (self.accent_strength * 1.1).min(1.0),
+                    duration: 0.08,
+                });
+            }
+
+            // Eighth-note hats, softer on the off-beats
+            notes.push(DrumNote {
+                sound: DrumSound::HiHatClosed,
+                start_beat: beat_start,
+                velocity: 0.55,
+                duration: 0.05,
+            });
+            notes.push(DrumNote {
+                sound: DrumSound::HiHatClosed,
+                start_beat: beat_start + 0.5,
+                velocity: 0.35,
+                duration: 0.05,
+            });
+        }
+
+        notes
+    }
+
+    /// Jazz pattern - ride "spang-a-lang", hi-hat foot on 2 and 4, feathered kick.
+    fn generate_jazz_pattern(&self, measure_start: f64, rng: &mut impl Rng) -> Vec<DrumNote> {
+        let mut notes = Vec::new();
+
+        for beat in 0..self.time_signature {
+            let beat_start = measure_start + beat as f64;
+            let backbeat = beat % 2 == 1;
+
+            notes.push(DrumNote {
+                sound: DrumSound::Ride,
+                start_beat: beat_start,
+                velocity: if backbeat { 0.7 } else { 0.6 },
+                duration: 0.3,
+            });
+
+            if backbeat {
+                // Triplet skip note leading into the next beat
+                notes.push(DrumNote {
+                    sound: DrumSound::Ride,
+                    start_beat: beat_start + 2.0 / 3.0,
+                    velocity: 0.45,
+                    duration: 0.2,
+                });
+                notes.push(DrumNote {
+                    sound: DrumSound::HiHatPedal,
+                    start_beat: beat_start,
+                    velocity: 0.5,
+                    duration: 0.05,
+                });
+            }
+
+            // Feathered kick, felt more than heard
+            notes.push(DrumNote {
+                sound: DrumSound::Kick,
+                start_beat: beat_start,
+                velocity: 0.25,
+                duration: 0.1,
+            });
+
+            // Sparse snare comping
+            if rng.gen::<f32>() < 0.25 {
+                notes.push(DrumNote {
+                    sound: DrumSound::Snare,
+                    start_beat: beat_start + 2.0 / 3.0,
+                    velocity: 0.3,
+                    duration: 0.08,
+                });
+            }
+        }
+
+        notes
+    }
+
     /// Simple pattern generation - basic 4/4 beat.
     fn generate_simple_pattern(&self, measure_start: f64, _rng: &mut impl Rng) -> Vec<DrumNote> {
         let mut notes = Vec::new();
@@ -865,6 +1097,197 @@ impl RhythmGenerator {
             .collect()
     }
 
+    /// Generates a Euclidean rhythm for one drum sound.
+    ///
+    /// Distributes `pulses` hits as evenly as possible over a cycle of
+    /// `steps` 16th notes (Bjorklund's algorithm), rotated left by
+    /// `rotation` steps. The cycle repeats for the generator's length in
+    /// measures, so cycles that don't divide the measure drift across bar
+    /// lines. E(3,8) is the tresillo `x..x..x.`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pulses` - Number of hits per cycle (clamped to `steps`)
+    /// * `steps` - Cycle length in 16th notes (must be at least 1)
+    /// * `rotation` - Steps to rotate the cycle left
+    /// * `sound` - Drum sound to place on the hits
+    ///
+    /// # Returns
+    ///
+    /// The pattern, or an error message if `steps` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wavelet::rhythm_generator::{DrumSound, RhythmGenerator};
+    ///
+    /// let generator = RhythmGenerator::new(120.0, 1);
+    /// let tresillo = generator.generate_euclidean(3, 8, 0, DrumSound::Kick).unwrap();
+    /// assert_eq!(tresillo.notes.len(), 6);
+    /// ```
+    pub fn generate_euclidean(
+        &self,
+        pulses: u8,
+        steps: u8,
+        rotation: u8,
+        sound: DrumSound,
+    ) -> Result<DrumPattern, String> {
+        if steps == 0 {
+            return Err("Euclidean rhythm needs at least one step".to_string());
+        }
+
+        let mut cycle = bjorklund(pulses.min(steps) as usize, steps as usize);
+        cycle.rotate_left(rotation as usize % steps as usize);
+
+        let step_beats = 0.25;
+        let total_steps = self.length * self.time_signature as usize * 4;
+        let notes = (0..total_steps)
+            .filter(|&step| cycle[step % cycle.len()])
+            .map(|step| DrumNote {
+                sound,
+                start_beat: step as f64 * step_beats,
+                // Accent the start of each cycle
+                velocity: if step % cycle.len() == 0 {
+                    0.6 + 0.4 * self.accent_strength
+                } else {
+                    0.6
+                },
+                duration: step_beats,
+            })
+            .collect();
+
+        Ok(DrumPattern {
+            notes,
+            tempo: self.tempo,
+            time_signature: self.time_signature,
+            length: self.length,
+            style: RhythmStyle::Custom,
+            swing: 0.0,
+        })
+    }
+
+    /// Adds a Euclidean layer for polyrhythmic patterns.
+    ///
+    /// Each layer cycles over its own number of 16th-note steps, so layers
+    /// with different step counts phase against each other (e.g. a kick
+    /// every 4 steps against hats every 3). Layers with 0 steps are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `sound` - Drum sound of the layer
+    /// * `pulses` - Hits per cycle (clamped to `steps`)
+    /// * `steps` - Cycle length in 16th notes
+    pub fn add_euclidean_layer(&mut self, sound: DrumSound, pulses: u8, steps: u8) {
+        if steps == 0 {
+            return;
+        }
+        self.layers.push(EuclideanLayer {
+            sound,
+            pulses: pulses.min(steps),
+            steps,
+        });
+    }
+
+    /// Removes all Euclidean layers.
+    pub fn clear_layers(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Renders all Euclidean layers onto one timeline.
+    ///
+    /// The combined pattern only repeats once every layer completes a whole
+    /// number of cycles (the least common multiple of the step counts), so
+    /// the length is rounded up to whole measures of that period and the
+    /// pattern loops seamlessly. Periods longer than 64 measures are not
+    /// resolved; the pattern then keeps the generator's length. Each sound
+    /// gets its default velocity.
+    ///
+    /// # Returns
+    ///
+    /// A DrumPattern with the hits of all layers, sorted by time.
+    pub fn generate_layered(&self) -> DrumPattern {
+        let steps_per_measure = self.time_signature as usize * 4;
+        let period = self
+            .layers
+            .iter()
+            .try_fold(steps_per_measure, |period, layer| {
+                lcm(period, layer.steps as usize)
+            });
+        let length = match period.map(|period| period / steps_per_measure) {
+            Some(measures) if measures <= MAX_LAYERED_PERIOD_MEASURES => {
+                self.length.max(1).div_ceil(measures) * measures
+            }
+            _ => self.length.max(1),
+        };
+
+        let step_beats = 0.25;
+        let total_steps = length * steps_per_measure;
+        let mut notes = Vec::new();
+        for layer in &self.layers {
+            let cycle = bjorklund(layer.pulses as usize, layer.steps as usize);
+            notes.extend(
+                (0..total_steps)
+                    .filter(|&step| cycle[step % cycle.len()])
+                    .map(|step| DrumNote {
+                        sound: layer.sound,
+                        start_beat: step as f64 * step_beats,
+                        velocity: default_velocity(layer.sound),
+                        duration: step_beats,
+                    }),
+            );
+        }
+        notes.sort_by(|a, b| a.start_beat.total_cmp(&b.start_beat));
+
+        DrumPattern {
+            notes,
+            tempo: self.tempo,
+            time_signature: self.time_signature,
+            length,
+            style: RhythmStyle::Custom,
+            swing: 0.0,
+        }
+    }
+
+    /// Randomly jitters note timing and velocity so a pattern feels played.
+    ///
+    /// The same seed and pattern always give the same result. Notes never
+    /// move before beat 0, and velocity offsets are additive so accents stay
+    /// louder than ghost notes.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Pattern to humanize
+    /// * `timing_ms` - Largest start offset in milliseconds (at the pattern tempo)
+    /// * `velocity_amount` - Velocity jitter (0.0 - 1.0, 1.0 = up to ±0.15)
+    /// * `seed` - Random seed
+    ///
+    /// # Returns
+    ///
+    /// The humanized pattern, with notes re-sorted by time.
+    pub fn humanize(
+        &self,
+        mut pattern: DrumPattern,
+        timing_ms: f32,
+        velocity_amount: f32,
+        seed: u64,
+    ) -> DrumPattern {
+        let max_offset = timing_ms.max(0.0) as f64 / 1000.0 * pattern.tempo / 60.0;
+        let max_velocity = velocity_amount.clamp(0.0, 1.0) * HUMANIZE_MAX_VELOCITY;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for note in &mut pattern.notes {
+            let timing: f64 = rng.gen_range(-1.0..=1.0);
+            let velocity: f32 = rng.gen_range(-1.0..=1.0);
+
+            note.start_beat = (note.start_beat + timing * max_offset).max(0.0);
+            note.velocity = (note.velocity + velocity * max_velocity).clamp(0.0, 1.0);
+        }
+        pattern
+            .notes
+            .sort_by(|a, b| a.start_beat.total_cmp(&b.start_beat));
+        pattern
+    }
+
     /// Sets the swing/shuffle percentage.
     ///
     /// # Arguments
@@ -885,6 +1308,9 @@ impl RhythmGenerator {
 
     /// Exports the drum pattern as MIDI.
     ///
+    /// Generates a new pattern and writes it as a single-track standard MIDI
+    /// file on channel 10 using the General MIDI drum map.
+    ///
     /// # Arguments
     ///
     /// * `path` - File path for the output MIDI file
@@ -892,101 +1318,97 @@ impl RhythmGenerator {
     /// # Returns
     ///
     /// Ok(()) on success, or an error message on failure.
-    pub fn export_midi(&mut self, _path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        #[allow(unused_variables)]
-        #[cfg(feature = "midi_cc")]
-        {
-            use std::fs::File;
-            use std::io::Write;
-
-            // Generate the pattern first
-            let pattern = self.generate();
-            let notes = &pattern.notes;
-
-            let mut file = File::create(_path)?;
-
-            // MIDI drum map - standard General MIDI drum notes
-            let drum_map: [(DrumSound, u8); 17] = [
-                (DrumSound::Kick, 36),
-                (DrumSound::Snare, 38),
-                (DrumSound::SnareAcoustic, 38),
-                (DrumSound::HiHatClosed, 42),
-                (DrumSound::HiHatOpen, 46),
-                (DrumSound::HiHatPedal, 44),
-                (DrumSound::FloorTom, 41),
-                (DrumSound::RackTom, 48),
-                (DrumSound::Clap, 39),
-                (DrumSound::Crash, 49),
-                (DrumSound::Ride, 51),
-                (DrumSound::Splash, 55),
-                (DrumSound::Tambourine, 54),
-                (DrumSound::Shaker, 68),
-                (DrumSound::Cowbell, 56),
-                (DrumSound::Congas, 64),
-                (DrumSound::Bongos, 67),
-            ];
-
-            // Write MIDI header
-            let track_count = 1u16;
-            let ticks_per_beat = 480u16;
-            let mut header = b"MThd\x00\x00\x00\x06\x00\x01".to_vec();
-            header.extend_from_slice(&track_count.to_be_bytes());
-            header.extend_from_slice(&ticks_per_beat.to_be_bytes());
-            file.write_all(&header)?;
-
-            // Write track chunk header
-            let track_size = (notes.len() * 12) as u32;
-            let mut track_header = b"MTrk\x00\x00\x00".to_vec();
-            track_header.extend_from_slice(&track_size.to_be_bytes());
-            file.write_all(&track_header)?;
-
-            // Write tempo
-            let tempo_us_per_beat = 60_000_000.0 / self.tempo;
-            let mut tempo_msg = vec![0x00, 0xFF, 0x51, 0x03];
-            let tempo_bytes = (tempo_us_per_beat as u32).to_be_bytes();
-            tempo_msg.extend_from_slice(&tempo_bytes[3..]);
-            file.write_all(&tempo_msg)?;
-
-            // Write drum events - sort by start time
-            let mut sorted_notes: Vec<_> = notes.iter().collect();
-            sorted_notes.sort_by(|a, b| a.start_beat.partial_cmp(&b.start_beat).unwrap());
-
-            for note in sorted_notes {
-                let delta_time = (note.start_beat * 480.0) as u32; // 480 ticks per beat
-                let midi_note = drum_map
-                    .iter()
-                    .find(|(sound, _)| *sound == note.sound)
-                    .map(|(_, m)| *m)
-                    .unwrap_or(36); // Default to kick
-
-                // Note on
-                let mut on_event = delta_time.to_be_bytes().to_vec();
-                on_event.push(0x99); // Channel 10 (drums)
-                on_event.push(midi_note);
-                on_event.push((note.velocity * 127.0) as u8);
-                file.write_all(&on_event)?;
-
-                // Note off
-                let duration_ticks = (note.duration * 480.0) as u32;
-                let mut off_event = duration_ticks.to_be_bytes().to_vec();
-                off_event.push(0x89);
-                off_event.push(midi_note);
-                off_event.push(0x00);
-                file.write_all(&off_event)?;
-            }
+    pub fn export_midi(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let pattern = self.generate();
+        let bytes = crate::piano_roll::single_track_midi(
+            pattern.notes.iter().map(|note| {
+                let velocity = (note.velocity * 127.0).round() as u8;
+                (
+                    note.start_beat,
+                    note.duration,
+                    gm_drum_note(note.sound),
+                    velocity,
+                )
+            }),
+            9,
+            self.tempo,
+        );
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
 
-            // End of track
-            let end_track = vec![0x00, 0xFF, 0x2F, 0x00];
-            file.write_all(&end_track)?;
+/// General MIDI drum map note for a drum sound.
+fn gm_drum_note(sound: DrumSound) -> u8 {
+    match sound {
+        DrumSound::Kick => 36,
+        DrumSound::Snare | DrumSound::SnareAcoustic => 38,
+        DrumSound::HiHatClosed => 42,
+        DrumSound::HiHatOpen => 46,
+        DrumSound::HiHatPedal => 44,
+        DrumSound::FloorTom => 41,
+        DrumSound::RackTom => 48,
+        DrumSound::Clap => 39,
+        DrumSound::Crash => 49,
+        DrumSound::Ride => 51,
+        DrumSound::Splash => 55,
+        DrumSound::Tambourine => 54,
+        DrumSound::Shaker => 68,
+        DrumSound::Cowbell => 56,
+        DrumSound::Congas => 64,
+        DrumSound::Bongos => 67,
+    }
+}
 
-            Ok(())
-        }
+/// Typical playing velocity of a drum sound in generated layers.
+fn default_velocity(sound: DrumSound) -> f32 {
+    match sound {
+        DrumSound::Kick => 0.9,
+        DrumSound::Snare | DrumSound::SnareAcoustic | DrumSound::Clap => 0.85,
+        DrumSound::Crash | DrumSound::Splash => 0.8,
+        DrumSound::FloorTom | DrumSound::RackTom => 0.75,
+        DrumSound::HiHatOpen | DrumSound::Ride | DrumSound::Cowbell => 0.65,
+        DrumSound::Congas | DrumSound::Bongos => 0.7,
+        DrumSound::HiHatClosed | DrumSound::Tambourine => 0.6,
+        DrumSound::HiHatPedal | DrumSound::Shaker => 0.5,
+    }
+}
 
-        #[cfg(not(feature = "midi_cc"))]
-        Err(Box::new(std::io::Error::other(
-            "MIDI export requires the 'midi' feature flag",
-        )))
+/// Least common multiple of two step counts, or None on overflow.
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
     }
+    (a / x).checked_mul(b)
+}
+
+/// Bjorklund's algorithm: spreads `pulses` onsets evenly over `steps`.
+///
+/// Repeatedly pairs the onset groups with the remainder groups until at
+/// most one remainder group is left; the result starts on an onset.
+fn bjorklund(pulses: usize, steps: usize) -> Vec<bool> {
+    if pulses == 0 {
+        return vec![false; steps];
+    }
+
+    let mut groups: Vec<Vec<bool>> = vec![vec![true]; pulses];
+    let mut remainder: Vec<Vec<bool>> = vec![vec![false]; steps - pulses];
+
+    while remainder.len() > 1 {
+        let paired = groups.len().min(remainder.len());
+        let leftover = if groups.len() > paired {
+            groups.split_off(paired)
+        } else {
+            remainder.split_off(paired)
+        };
+        for (group, tail) in groups.iter_mut().zip(remainder) {
+            group.extend(tail);
+        }
+        remainder = leftover;
+    }
+
+    groups.into_iter().chain(remainder).flatten().collect()
 }
 
 // ===== Unit Tests =====
@@ -1179,4 +1601,229 @@ mod tests {
         let fast_gen = RhythmGenerator::new(400.0, 1);
         assert_eq!(fast_gen.tempo, 300.0); // Clamped to maximum
     }
+
+    /// Renders one Euclidean cycle as `x`/`.` text.
+    fn euclidean_text(pulses: u8, steps: u8, rotation: u8) -> String {
+        let pattern = RhythmGenerator::new(120.0, 1)
+            .generate_euclidean(pulses, steps, rotation, DrumSound::Kick)
+            .unwrap();
+        (0..steps as usize)
+            .map(|step| {
+                let beat = step as f64 * 0.25;
+                if pattern.notes.iter().any(|n| n.start_beat == beat) {
+                    'x'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_euclidean_known_patterns() {
+        assert_eq!(euclidean_text(5, 8, 0), "x.xx.xx.");
+        assert_eq!(euclidean_text(3, 8, 0), "x..x..x.");
+        assert_eq!(euclidean_text(4, 16, 0), "x...x...x...x...");
+        assert_eq!(euclidean_text(3, 8, 1), "..x..x.x");
+    }
+
+    #[test]
+    fn test_euclidean_edge_cases() {
+        let generator = RhythmGenerator::new(120.0, 2);
+
+        let empty = generator
+            .generate_euclidean(0, 8, 0, DrumSound::Kick)
+            .unwrap();
+        assert!(empty.notes.is_empty());
+
+        // Too many pulses clamps to every step: 32 16ths in two measures
+        let full = generator
+            .generate_euclidean(20, 8, 3, DrumSound::HiHatClosed)
+            .unwrap();
+        assert_eq!(full.notes.len(), 32);
+        assert!(full.notes.iter().all(|n| n.sound == DrumSound::HiHatClosed));
+
+        assert!(generator
+            .generate_euclidean(3, 0, 0, DrumSound::Kick)
+            .is_err());
+    }
+
+    #[test]
+    fn test_layered_three_against_four() {
+        // Three 4/4 measures = 12 beats
+        let mut generator = RhythmGenerator::new(120.0, 3);
+        generator.add_euclidean_layer(DrumSound::Kick, 1, 4);
+        generator.add_euclidean_layer(DrumSound::HiHatClosed, 1, 3);
+        let pattern = generator.generate_layered();
+
+        assert_eq!(pattern.length, 3);
+        let count = |sound| pattern.notes.iter().filter(|n| n.sound == sound).count();
+        assert_eq!(count(DrumSound::Kick), 12);
+        assert_eq!(count(DrumSound::HiHatClosed), 16);
+
+        // Sorted on one timeline; the voices coincide every 3 beats
+        assert!(pattern
+            .notes
+            .windows(2)
+            .all(|w| w[0].start_beat <= w[1].start_beat));
+        let together = pattern
+            .notes
+            .iter()
+            .filter(|n| n.sound == DrumSound::Kick)
+            .filter(|k| {
+                pattern
+                    .notes
+                    .iter()
+                    .any(|h| h.sound == DrumSound::HiHatClosed && h.start_beat == k.start_beat)
+            })
+            .count();
+        assert_eq!(together, 4);
+        assert_eq!(pattern.notes[0].velocity, default_velocity(DrumSound::Kick));
+    }
+
+    #[test]
+    fn test_layered_length_resolves_period() {
+        // A 5-step layer against the 16-step measure repeats every 5 measures
+        let mut generator = RhythmGenerator::new(120.0, 2);
+        generator.add_euclidean_layer(DrumSound::Cowbell, 2, 5);
+        generator.add_euclidean_layer(DrumSound::Kick, 4, 0);
+        let pattern = generator.generate_layered();
+        assert_eq!(pattern.length, 5);
+        assert_eq!(pattern.notes.len(), 80 / 5 * 2);
+
+        generator.clear_layers();
+        assert!(generator.generate_layered().notes.is_empty());
+    }
+
+    #[test]
+    fn test_layered_length_caps_long_period() {
+        // 13 and 17 steps only realign after 221 measures
+        let mut generator = RhythmGenerator::new(120.0, 2);
+        generator.add_euclidean_layer(DrumSound::Kick, 3, 13);
+        generator.add_euclidean_layer(DrumSound::HiHatClosed, 5, 17);
+        assert_eq!(generator.generate_layered().length, 2);
+
+        // Coprime step counts whose period overflows usize
+        for steps in [251, 241, 239, 233, 229, 227, 223, 211, 199, 197] {
+            generator.add_euclidean_layer(DrumSound::Shaker, 1, steps);
+        }
+        let pattern = generator.generate_layered();
+        assert_eq!(pattern.length, 2);
+        assert!(pattern.notes.iter().all(|n| n.start_beat < 8.0));
+    }
+
+    #[test]
+    fn test_humanize_deterministic_and_keeps_accents() {
+        let generator = RhythmGenerator::new(120.0, 1);
+        let note = |start_beat, velocity| DrumNote {
+            sound: DrumSound::Snare,
+            start_beat,
+            velocity,
+            duration: 0.25,
+        };
+        let pattern = DrumPattern {
+            notes: (0..16)
+                .map(|i| note(i as f64 * 0.25, if i % 4 == 0 { 1.0 } else { 0.3 }))
+                .collect(),
+            tempo: 120.0,
+            time_signature: 4,
+            length: 1,
+            style: RhythmStyle::Custom,
+            swing: 0.0,
+        };
+
+        let a = generator.humanize(pattern.clone(), 10.0, 1.0, 42);
+        let b = generator.humanize(pattern.clone(), 10.0, 1.0, 42);
+        assert_eq!(a.notes, b.notes);
+        assert_ne!(a.notes, pattern.notes);
+
+        // 10 ms at 120 BPM is 0.02 beats
+        for (orig, human) in pattern.notes.iter().zip(&a.notes) {
+            assert!(human.start_beat >= 0.0);
+            assert!((human.start_beat - orig.start_beat).abs() <= 0.02 + 1e-9);
+            assert!((0.0..=1.0).contains(&human.velocity));
+        }
+        let quietest_accent = a
+            .notes
+            .iter()
+            .filter(|n| n.velocity > 0.65)
+            .fold(1.0f32, |m, n| m.min(n.velocity));
+        let loudest_ghost = a
+            .notes
+            .iter()
+            .filter(|n| n.velocity <= 0.65)
+            .fold(0.0f32, |m, n| m.max(n.velocity));
+        assert_eq!(a.notes.iter().filter(|n| n.velocity > 0.65).count(), 4);
+        assert!(quietest_accent > loudest_ghost);
+    }
+
+    #[test]
+    fn test_house_kick_on_every_beat() {
+        let pattern = RhythmGenerator::new(125.0, 2).generate_preset(RhythmStyle::House);
+        for beat in 0..8 {
+            assert!(
+                pattern
+                    .notes
+                    .iter()
+                    .any(|n| n.sound == DrumSound::Kick && n.start_beat == beat as f64),
+                "missing kick on beat {}",
+                beat
+            );
+        }
+    }
+
+    #[test]
+    fn test_reggae_emphasizes_two_and_four() {
+        let pattern = RhythmGenerator::new(80.0, 2).generate_preset(RhythmStyle::Reggae);
+        // First three beats of the first measure (the fill starts on beat 4)
+        let loudness = |beat: f64| -> f32 {
+            pattern
+                .notes
+                .iter()
+                .filter(|n| n.start_beat == beat)
+                .map(|n| n.velocity)
+                .sum()
+        };
+        assert!(loudness(1.0) > loudness(0.0) * 2.0);
+        assert!(loudness(1.0) > loudness(2.0) * 2.0);
+        assert!(pattern
+            .notes
+            .iter()
+            .filter(|n| n.sound == DrumSound::Kick && n.start_beat < 3.0)
+            .all(|n| n.start_beat == 1.0));
+
+        // Reggae and House no longer share a groove
+        let house = RhythmGenerator::new(80.0, 2).generate_preset(RhythmStyle::House);
+        assert!(house
+            .notes
+            .iter()
+            .any(|n| n.sound == DrumSound::Kick && n.start_beat == 0.0));
+        assert_eq!(pattern.style, RhythmStyle::Reggae);
+    }
+
+    #[test]
+    fn test_export_midi_is_valid_smf() {
+        let path = std::env::temp_dir().join(format!("wavelet_rhythm_{}.mid", std::process::id()));
+        let mut generator = RhythmGenerator::new(120.0, 2);
+        generator.export_midi(path.to_str().unwrap()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(&bytes[..4], b"MThd");
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let track_len = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        assert_eq!(track_len, bytes.len() - 22);
+
+        // Tempo event first, then drum notes on channel 10
+        assert_eq!(&bytes[22..26], &[0x00, 0xFF, 0x51, 0x03]);
+        assert_eq!(bytes[30], 0x99);
+
+        let mut piano_roll = crate::piano_roll::PianoRoll::new();
+        piano_roll.import_midi(&bytes).unwrap();
+        assert!(piano_roll.note_count() > 0);
+        assert!(piano_roll
+            .get_all_notes()
+            .iter()
+            .all(|n| (35..=81).contains(&n.note)));
+    }
 }
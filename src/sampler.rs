@@ -12,6 +12,7 @@
 #![allow(dead_code)] // Reserve sampler fields for future sample editing features
 
 use std::collections::HashMap;
+use std::fmt;
 
 /// Sample format
 #[derive(Debug, Clone, PartialEq)]
@@ -87,6 +88,210 @@ pub enum LoopMode {
     OneShot,
 }
 
+/// Error decoding a sample file
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleError {
+    /// Missing RIFF/WAVE header
+    NotWavFile,
+    /// Missing FORM/AIFF (or AIFC) header
+    NotAiffFile,
+    /// No format chunk (`fmt ` or `COMM`) before the audio data
+    MissingFormatChunk,
+    /// No audio data chunk (`data` or `SSND`)
+    MissingDataChunk,
+    /// A chunk runs past the end of the file
+    UnexpectedEndOfData,
+    /// Encoding other than PCM16, PCM24 or Float32
+    UnsupportedFormat {
+        /// WAVE format tag (1 = PCM, 3 = IEEE float)
+        format_tag: u16,
+        /// Bits per sample
+        bits_per_sample: u16,
+    },
+    /// AIFF sample size other than 16 or 24 bits (32 for float)
+    UnsupportedBitDepth(u16),
+    /// AIFF-C compression type other than uncompressed PCM or float
+    UnsupportedCompression(String),
+    /// Channel count other than mono or stereo
+    UnsupportedChannelCount(u16),
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleError::NotWavFile => write!(f, "not a RIFF/WAVE file"),
+            SampleError::NotAiffFile => write!(f, "not a FORM/AIFF file"),
+            SampleError::MissingFormatChunk => write!(f, "sample file has no format chunk"),
+            SampleError::MissingDataChunk => write!(f, "sample file has no audio data"),
+            SampleError::UnexpectedEndOfData => write!(f, "sample file is truncated"),
+            SampleError::UnsupportedFormat {
+                format_tag,
+                bits_per_sample,
+            } => write!(
+                f,
+                "unsupported WAV encoding (format tag {}, {} bits)",
+                format_tag, bits_per_sample
+            ),
+            SampleError::UnsupportedBitDepth(bits) => {
+                write!(f, "unsupported sample size {} bits", bits)
+            }
+            SampleError::UnsupportedCompression(kind) => {
+                write!(f, "unsupported AIFF-C compression '{}'", kind)
+            }
+            SampleError::UnsupportedChannelCount(channels) => {
+                write!(f, "unsupported channel count {}", channels)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SampleError {}
+
+/// WAVE format tag for integer PCM
+const WAVE_FORMAT_PCM: u16 = 1;
+/// WAVE format tag for IEEE float
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+/// WAVE format tag whose real format sits in the extension sub-format GUID
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Layout of interleaved sample data from a WAV or AIFF file
+struct PcmLayout {
+    format: SampleFormat,
+    channels: u16,
+    sample_rate: u32,
+    big_endian: bool,
+}
+
+/// Reads a little-endian u16 at `pos`
+fn read_u16_le(bytes: &[u8], pos: usize) -> Option<u16> {
+    bytes
+        .get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+/// Reads a little-endian u32 at `pos`
+fn read_u32_le(bytes: &[u8], pos: usize) -> Option<u32> {
+    bytes
+        .get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Reads a big-endian u16 at `pos`
+fn read_u16_be(bytes: &[u8], pos: usize) -> Option<u16> {
+    bytes
+        .get(pos..pos + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Reads a big-endian u32 at `pos`
+fn read_u32_be(bytes: &[u8], pos: usize) -> Option<u32> {
+    bytes
+        .get(pos..pos + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Converts an 80-bit IEEE 754 extended float (as used for the AIFF
+/// sample rate) to f64
+fn extended_to_f64(bytes: &[u8; 10]) -> f64 {
+    let sign = if bytes[0] & 0x80 != 0 { -1.0 } else { 1.0 };
+    let exponent = (u16::from_be_bytes([bytes[0], bytes[1]]) & 0x7FFF) as i32;
+    let mut mantissa_bytes = [0u8; 8];
+    mantissa_bytes.copy_from_slice(&bytes[2..10]);
+    let mantissa = u64::from_be_bytes(mantissa_bytes);
+
+    if exponent == 0 && mantissa == 0 {
+        return 0.0;
+    }
+    // The mantissa has an explicit integer bit, so scale by 2^63 as well
+    sign * mantissa as f64 * 2f64.powi(exponent - 16383 - 63)
+}
+
+/// Parses an AIFF `COMM` chunk body
+///
+/// Returns the sample layout and the frame count it declares.
+fn parse_aiff_comm(chunk: &[u8], aifc: bool) -> Result<(PcmLayout, usize), SampleError> {
+    let channels = read_u16_be(chunk, 0).ok_or(SampleError::UnexpectedEndOfData)?;
+    let frames = read_u32_be(chunk, 2).ok_or(SampleError::UnexpectedEndOfData)? as usize;
+    let bits = read_u16_be(chunk, 6).ok_or(SampleError::UnexpectedEndOfData)?;
+    let rate: &[u8; 10] = chunk
+        .get(8..18)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(SampleError::UnexpectedEndOfData)?;
+
+    let compression: &[u8] = if aifc {
+        chunk.get(18..22).ok_or(SampleError::UnexpectedEndOfData)?
+    } else {
+        b"NONE"
+    };
+
+    let (float, big_endian) = match compression {
+        b"NONE" | b"twos" => (false, true),
+        b"sowt" => (false, false),
+        b"fl32" | b"FL32" => (true, true),
+        other => {
+            return Err(SampleError::UnsupportedCompression(
+                String::from_utf8_lossy(other).into_owned(),
+            ))
+        }
+    };
+
+    let format = match (float, bits) {
+        (false, 16) => SampleFormat::Pcm16,
+        (false, 24) => SampleFormat::Pcm24,
+        (true, 32) => SampleFormat::Float32,
+        _ => return Err(SampleError::UnsupportedBitDepth(bits)),
+    };
+
+    if channels == 0 || channels > 2 {
+        return Err(SampleError::UnsupportedChannelCount(channels));
+    }
+
+    let layout = PcmLayout {
+        format,
+        channels,
+        sample_rate: extended_to_f64(rate).round() as u32,
+        big_endian,
+    };
+    Ok((layout, frames))
+}
+
+/// Parses a `fmt ` chunk body
+fn parse_wav_format(chunk: &[u8]) -> Result<PcmLayout, SampleError> {
+    let field = |pos| read_u16_le(chunk, pos).ok_or(SampleError::UnexpectedEndOfData);
+    let mut format_tag = field(0)?;
+    let channels = field(2)?;
+    let sample_rate = read_u32_le(chunk, 4).ok_or(SampleError::UnexpectedEndOfData)?;
+    let bits_per_sample = field(14)?;
+
+    if format_tag == WAVE_FORMAT_EXTENSIBLE {
+        // cbSize, valid bits and channel mask precede the sub-format GUID
+        format_tag = field(24)?;
+    }
+
+    let format = match (format_tag, bits_per_sample) {
+        (WAVE_FORMAT_PCM, 16) => SampleFormat::Pcm16,
+        (WAVE_FORMAT_PCM, 24) => SampleFormat::Pcm24,
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => SampleFormat::Float32,
+        _ => {
+            return Err(SampleError::UnsupportedFormat {
+                format_tag,
+                bits_per_sample,
+            })
+        }
+    };
+
+    if channels == 0 || channels > 2 {
+        return Err(SampleError::UnsupportedChannelCount(channels));
+    }
+
+    Ok(PcmLayout {
+        format,
+        channels,
+        sample_rate,
+        big_endian: false,
+    })
+}
+
 /// Single sample
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sample {
@@ -164,6 +369,154 @@ impl Sample {
         }
     }
 
+    /// Decode a sample from the bytes of a WAV file
+    ///
+    /// Supports 16-bit and 24-bit PCM and 32-bit float, mono or stereo.
+    /// Chunks other than `fmt ` and `data` (LIST, cue, ...) are skipped.
+    /// Samples are normalized to -1.0..1.0; stereo files fill `data` with
+    /// the left channel and `data_stereo` with the right.
+    pub fn from_wav_bytes(bytes: &[u8]) -> Result<Sample, SampleError> {
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(SampleError::NotWavFile);
+        }
+
+        let mut format = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size =
+                read_u32_le(bytes, pos + 4).ok_or(SampleError::UnexpectedEndOfData)? as usize;
+            let body_start = pos + 8;
+
+            match id {
+                b"fmt " => {
+                    let body = bytes
+                        .get(body_start..body_start + size)
+                        .ok_or(SampleError::UnexpectedEndOfData)?;
+                    format = Some(parse_wav_format(body)?);
+                }
+                b"data" => {
+                    let format = format.ok_or(SampleError::MissingFormatChunk)?;
+                    // Tolerate writers that leave the size of a streamed file too large
+                    let end = (body_start + size).min(bytes.len());
+                    return Ok(Self::from_interleaved(&bytes[body_start..end], &format));
+                }
+                _ => {}
+            }
+
+            // Chunks are padded to an even length
+            pos = body_start + size + (size & 1);
+        }
+
+        Err(if format.is_some() {
+            SampleError::MissingDataChunk
+        } else {
+            SampleError::MissingFormatChunk
+        })
+    }
+
+    /// Decode a sample from the bytes of an AIFF or AIFF-C file
+    ///
+    /// Supports big-endian 16-bit and 24-bit PCM, plus the AIFF-C `sowt`
+    /// (little-endian PCM) and `fl32` (32-bit float) types; compressed
+    /// AIFF-C files are rejected. Samples are normalized like WAV files.
+    pub fn from_aiff_bytes(bytes: &[u8]) -> Result<Sample, SampleError> {
+        if bytes.len() < 12 || &bytes[0..4] != b"FORM" {
+            return Err(SampleError::NotAiffFile);
+        }
+        let aifc = match &bytes[8..12] {
+            b"AIFF" => false,
+            b"AIFC" => true,
+            _ => return Err(SampleError::NotAiffFile),
+        };
+
+        // COMM and SSND may come in either order
+        let mut comm = None;
+        let mut sound = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size =
+                read_u32_be(bytes, pos + 4).ok_or(SampleError::UnexpectedEndOfData)? as usize;
+            let body_start = pos + 8;
+
+            match id {
+                b"COMM" => {
+                    let body = bytes
+                        .get(body_start..body_start + size)
+                        .ok_or(SampleError::UnexpectedEndOfData)?;
+                    comm = Some(parse_aiff_comm(body, aifc)?);
+                }
+                b"SSND" => {
+                    // Skip the offset/block size header and any leading offset
+                    let offset =
+                        read_u32_be(bytes, body_start).ok_or(SampleError::UnexpectedEndOfData)?;
+                    let start = body_start + 8 + offset as usize;
+                    let end = (body_start + size).min(bytes.len());
+                    sound = Some(bytes.get(start..end.max(start)).unwrap_or(&[]));
+                }
+                _ => {}
+            }
+
+            // Chunks are padded to an even length
+            pos = body_start + size + (size & 1);
+        }
+
+        let (layout, frames) = comm.ok_or(SampleError::MissingFormatChunk)?;
+        let sound = sound.ok_or(SampleError::MissingDataChunk)?;
+
+        // Ignore trailing block padding beyond the declared frame count
+        let frame_bytes = Self::bytes_per_sample(&layout.format) * layout.channels as usize;
+        let len = sound.len().min(frames * frame_bytes);
+        Ok(Self::from_interleaved(&sound[..len], &layout))
+    }
+
+    /// Bytes used by one sample of a format
+    fn bytes_per_sample(format: &SampleFormat) -> usize {
+        match format {
+            SampleFormat::Pcm16 => 2,
+            SampleFormat::Pcm24 => 3,
+            SampleFormat::Float32 => 4,
+        }
+    }
+
+    /// Build a sample from interleaved WAV or AIFF data
+    fn from_interleaved(data: &[u8], layout: &PcmLayout) -> Sample {
+        let decoded: Vec<f32> = data
+            .chunks_exact(Self::bytes_per_sample(&layout.format))
+            .map(|bytes| {
+                // Normalize to little-endian
+                let mut b = [0u8; 4];
+                b[..bytes.len()].copy_from_slice(bytes);
+                if layout.big_endian {
+                    b[..bytes.len()].reverse();
+                }
+                b
+            })
+            .map(|b| match layout.format {
+                SampleFormat::Pcm16 => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+                SampleFormat::Pcm24 => {
+                    // Sign-extend by placing the 24 bits at the top of an i32
+                    (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0
+                }
+                SampleFormat::Float32 => {
+                    f32::from_le_bytes([b[0], b[1], b[2], b[3]]).clamp(-1.0, 1.0)
+                }
+            })
+            .collect();
+
+        let mut sample = if layout.channels == 2 {
+            let frames = decoded.chunks_exact(2);
+            let left = frames.clone().map(|f| f[0]).collect();
+            let right = frames.map(|f| f[1]).collect();
+            Sample::new_stereo("", left, right, layout.sample_rate)
+        } else {
+            Sample::new("", decoded, layout.sample_rate)
+        };
+        sample.info.format = layout.format.clone();
+        sample
+    }
+
     /// Check if stereo
     pub fn is_stereo(&self) -> bool {
         self.data_stereo.is_some()
@@ -218,6 +571,62 @@ pub enum SlicingMode {
     Manual,
 }
 
+/// Interpolation between stored samples during pitched playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpMode {
+    /// Nearest sample (no interpolation)
+    Nearest,
+    /// Linear interpolation between neighbouring samples
+    #[default]
+    Linear,
+    /// 4-point Catmull-Rom cubic interpolation
+    Cubic,
+}
+
+/// How `Sampler::set_time_stretch` changes the playback duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StretchMode {
+    /// Resample: duration and pitch change together
+    #[default]
+    Varispeed,
+    /// Waveform-similarity overlap-add: duration changes, pitch is kept
+    Wsola,
+}
+
+/// WSOLA output hop (half the grain length, samples)
+const WSOLA_HOP: usize = 512;
+
+/// WSOLA similarity search range either side of the nominal position (samples)
+const WSOLA_SEARCH: usize = 256;
+
+/// Decimation of the WSOLA waveform comparison, to keep the search cheap
+const WSOLA_COMPARE_STRIDE: usize = 4;
+
+/// Read `data` at a fractional position
+///
+/// Neighbours outside the buffer are clamped to the first/last sample.
+fn read_interpolated(data: &[f32], position: f64, mode: InterpMode) -> f32 {
+    let last = data.len() as isize - 1;
+    let index = position.floor() as isize;
+    let frac = (position - position.floor()) as f32;
+    let at = |i: isize| data[i.clamp(0, last) as usize];
+
+    match mode {
+        InterpMode::Nearest => at(index),
+        InterpMode::Linear => {
+            let (a, b) = (at(index), at(index + 1));
+            a + (b - a) * frac
+        }
+        InterpMode::Cubic => {
+            let (y0, y1, y2, y3) = (at(index - 1), at(index), at(index + 1), at(index + 2));
+            let c1 = 0.5 * (y2 - y0);
+            let c2 = y0 - 2.5 * y1 + 2.0 * y2 - 0.5 * y3;
+            let c3 = 0.5 * (y3 - y0) + 1.5 * (y1 - y2);
+            ((c3 * frac + c2) * frac + c1) * frac + y1
+        }
+    }
+}
+
 /// Sample player
 #[derive(Debug, Clone)]
 pub struct Sampler {
@@ -242,6 +651,9 @@ pub struct Sampler {
     /// Time stretch (1.0 = normal)
     time_stretch: f32,
 
+    /// Tempo sync (original BPM, project BPM)
+    tempo_sync: Option<(f32, f32)>,
+
     /// Loop mode
     loop_mode: LoopMode,
 
@@ -260,6 +672,39 @@ pub struct Sampler {
 
     /// Crossfade length (samples)
     crossfade: usize,
+
+    /// Interpolation used between samples
+    interpolation: InterpMode,
+
+    /// Distance played since the last trigger (samples, drives the fade-in)
+    travelled: f64,
+
+    /// Position of the outgoing playhead during a loop crossfade
+    loop_xfade_position: f64,
+
+    /// Direction of the outgoing playhead (true = reverse)
+    loop_xfade_reverse: bool,
+
+    /// Samples left in the current loop crossfade
+    loop_xfade_remaining: usize,
+
+    /// Length of the current loop crossfade (samples)
+    loop_xfade_length: usize,
+
+    /// How time stretch is applied
+    stretch_mode: StretchMode,
+
+    /// Start of the WSOLA grain fading in
+    wsola_grain_start: f64,
+
+    /// Start of the WSOLA grain fading out
+    wsola_prev_start: f64,
+
+    /// Sample index within the current WSOLA hop
+    wsola_phase: usize,
+
+    /// Whether a WSOLA grain has been started since the last trigger
+    wsola_primed: bool,
 }
 
 impl Default for Sampler {
@@ -272,6 +717,7 @@ impl Default for Sampler {
             volume: 1.0,
             pitch_offset: 0,
             time_stretch: 1.0,
+            tempo_sync: None,
             loop_mode: LoopMode::NoLoop,
             loop_start: 0,
             loop_end: 0,
@@ -279,6 +725,17 @@ impl Default for Sampler {
             trigger_position: 0.0,
             fade_gain: 1.0,
             crossfade: 64,
+            interpolation: InterpMode::default(),
+            travelled: 0.0,
+            loop_xfade_position: 0.0,
+            loop_xfade_reverse: false,
+            loop_xfade_remaining: 0,
+            loop_xfade_length: 0,
+            stretch_mode: StretchMode::default(),
+            wsola_grain_start: 0.0,
+            wsola_prev_start: 0.0,
+            wsola_phase: 0,
+            wsola_primed: false,
         }
     }
 }
@@ -317,10 +774,63 @@ impl Sampler {
     }
 
     /// 设置时间拉伸
+    ///
+    /// The factor scales the playback rate: 0.5 plays twice as long. Whether
+    /// pitch follows depends on the stretch mode.
     pub fn set_time_stretch(&mut self, stretch: f32) {
         self.time_stretch = stretch.clamp(0.25, 4.0);
     }
 
+    /// Sync playback to the project tempo
+    ///
+    /// The playback rate is scaled by `project_bpm / original_bpm`, weighted
+    /// by the sample's `tempo_sensitivity` (0 = ignore tempo, 1 = follow it,
+    /// 2 = follow it twice as strongly). In varispeed mode the pitch follows
+    /// the tempo; in WSOLA mode it is kept. Non-positive tempos disable sync.
+    pub fn set_sync(&mut self, original_bpm: f32, project_bpm: f32) {
+        self.tempo_sync =
+            (original_bpm > 0.0 && project_bpm > 0.0).then_some((original_bpm, project_bpm));
+    }
+
+    /// Disable tempo sync
+    pub fn clear_sync(&mut self) {
+        self.tempo_sync = None;
+    }
+
+    /// Playback rate factor applied by tempo sync (1.0 = no change)
+    pub fn tempo_ratio(&self) -> f32 {
+        let (Some((original_bpm, project_bpm)), Some(sample)) = (self.tempo_sync, &self.sample)
+        else {
+            return 1.0;
+        };
+        let sensitivity = sample.info.tempo_sensitivity.clamp(0.0, 2.0);
+        (project_bpm / original_bpm)
+            .powf(sensitivity)
+            .clamp(0.25, 4.0)
+    }
+
+    /// Set time stretch mode (varispeed or pitch-preserving WSOLA)
+    pub fn set_time_stretch_mode(&mut self, mode: StretchMode) {
+        self.stretch_mode = mode;
+        self.wsola_phase = 0;
+        self.wsola_primed = false;
+    }
+
+    /// Get time stretch mode
+    pub fn time_stretch_mode(&self) -> StretchMode {
+        self.stretch_mode
+    }
+
+    /// Set interpolation mode
+    pub fn set_interpolation(&mut self, mode: InterpMode) {
+        self.interpolation = mode;
+    }
+
+    /// Get interpolation mode
+    pub fn interpolation(&self) -> InterpMode {
+        self.interpolation
+    }
+
     /// 设置音量
     pub fn set_volume(&mut self, volume: f32) {
         self.volume = volume.clamp(0.0, 1.0);
@@ -348,6 +858,10 @@ impl Sampler {
         self.playing = true;
         self.trigger_position = self.position;
         self.fade_gain = 0.0;
+        self.travelled = 0.0;
+        self.loop_xfade_remaining = 0;
+        self.wsola_phase = 0;
+        self.wsola_primed = false;
     }
 
     /// 停止播放
@@ -361,6 +875,10 @@ impl Sampler {
         self.playing = false;
         self.trigger_position = 0.0;
         self.fade_gain = 1.0;
+        self.travelled = 0.0;
+        self.loop_xfade_remaining = 0;
+        self.wsola_phase = 0;
+        self.wsola_primed = false;
     }
 
     /// 跳转到位置 (0.0 - 1.0)
@@ -390,13 +908,17 @@ impl Sampler {
         if let Some(ref sample) = self.sample {
             // 计算播放位置
             let pitch_factor = 2.0f32.powf(self.pitch_offset as f32 / 12.0);
-            let effective_speed = self.speed * pitch_factor * self.time_stretch;
+            let effective_speed =
+                self.speed * pitch_factor * self.time_stretch * self.tempo_ratio();
+            // Rate at which WSOLA grains are read (sets the pitch)
+            let read_step = (self.speed * pitch_factor) as f64;
 
             if self.reverse {
                 self.position -= effective_speed as f64;
             } else {
                 self.position += effective_speed as f64;
             }
+            self.travelled += effective_speed as f64;
 
             // 处理循环
             let length = sample.info.length as f64;
@@ -416,29 +938,74 @@ impl Sampler {
                     }
                 }
                 LoopMode::Loop | LoopMode::PingPong => {
-                    if self.position < self.loop_start as f64 {
-                        self.position = self.loop_end as f64;
-                    } else if self.position >= self.loop_end as f64 {
-                        match self.loop_mode {
-                            LoopMode::PingPong => {
-                                // 反向循环
-                                self.reverse = !self.reverse;
-                                self.position = self.loop_end as f64;
-                            }
-                            _ => {
-                                self.position = self.loop_start as f64;
-                            }
+                    // Unset or invalid loop points loop the whole sample
+                    let loop_end =
+                        if self.loop_end > self.loop_start && self.loop_end <= sample.info.length {
+                            self.loop_end as f64
+                        } else {
+                            length
+                        };
+                    let loop_start = (self.loop_start as f64).min(loop_end - 1.0);
+                    let loop_len = loop_end - loop_start;
+
+                    if self.position < loop_start || self.position >= loop_end {
+                        // The outgoing playhead runs on past the boundary while fading out
+                        // WSOLA grains already overlap across the wrap
+                        let xfade = match self.stretch_mode {
+                            StretchMode::Varispeed => self.crossfade.min(loop_len as usize / 2),
+                            StretchMode::Wsola => 0,
+                        };
+                        self.loop_xfade_position = self.position;
+                        self.loop_xfade_reverse = self.reverse;
+                        self.loop_xfade_remaining = xfade;
+                        self.loop_xfade_length = xfade;
+
+                        let past_end = self.position >= loop_end;
+                        self.position = match (self.loop_mode, past_end) {
+                            // 反向循环
+                            (LoopMode::PingPong, true) => 2.0 * loop_end - self.position,
+                            (LoopMode::PingPong, false) => 2.0 * loop_start - self.position,
+                            (_, true) => self.position - loop_len,
+                            (_, false) => self.position + loop_len,
+                        }
+                        .clamp(loop_start, loop_end - 1e-9);
+                        if self.loop_mode == LoopMode::PingPong {
+                            self.reverse = !self.reverse;
                         }
                     }
                 }
             }
 
             // 获取采样值
-            let (left, right) = self.get_sample_at(self.position as usize);
+            let (mut left, mut right) = match self.stretch_mode {
+                StretchMode::Varispeed => self.get_sample_at(self.position),
+                StretchMode::Wsola => {
+                    let step = if self.reverse { -read_step } else { read_step };
+                    self.wsola_sample(step)
+                }
+            };
+
+            // Equal-power crossfade from the outgoing playhead after a loop boundary
+            if self.loop_xfade_remaining > 0 {
+                let outgoing = self.loop_xfade_position.clamp(0.0, length - 1.0);
+                let (out_left, out_right) = self.get_sample_at(outgoing);
+                let progress =
+                    1.0 - self.loop_xfade_remaining as f32 / (self.loop_xfade_length + 1) as f32;
+                let angle = progress * std::f32::consts::FRAC_PI_2;
+                left = left * angle.sin() + out_left * angle.cos();
+                right = right * angle.sin() + out_right * angle.cos();
+
+                if self.loop_xfade_reverse {
+                    self.loop_xfade_position -= effective_speed as f64;
+                } else {
+                    self.loop_xfade_position += effective_speed as f64;
+                }
+                self.loop_xfade_remaining -= 1;
+            }
 
             // 计算淡入淡出增益
             let fade_samples = self.crossfade as f64;
-            let trigger_diff = (self.position - self.trigger_position).abs();
+            let trigger_diff = self.travelled;
 
             if trigger_diff < fade_samples {
                 // 淡入
@@ -457,15 +1024,98 @@ impl Sampler {
         }
     }
 
-    /// 获取指定位置的采样值
-    fn get_sample_at(&self, index: usize) -> (f32, f32) {
-        if let Some(ref sample) = self.sample {
-            let idx = index.min(sample.info.length - 1);
-            let left = sample.data[idx];
-            let right = sample.data_stereo.as_ref().map(|s| s[idx]).unwrap_or(left);
-            (left, right)
-        } else {
-            (0.0, 0.0)
+    /// Produce one WSOLA output sample
+    ///
+    /// Two Hann-windowed grains overlap by half. Each new grain starts near
+    /// the playback position, shifted to where its waveform best matches
+    /// the natural continuation of the previous grain, so the overlap adds
+    /// up in phase.
+    fn wsola_sample(&mut self, step: f64) -> (f32, f32) {
+        if self.wsola_phase == 0 {
+            let nominal = self.position;
+            if self.wsola_primed {
+                self.wsola_prev_start = self.wsola_grain_start;
+                let natural = self.wsola_prev_start + WSOLA_HOP as f64 * step;
+                self.wsola_grain_start = self.wsola_best_start(nominal, natural, step);
+            } else {
+                // The first grain continues an imaginary predecessor
+                self.wsola_grain_start = nominal;
+                self.wsola_prev_start = nominal - WSOLA_HOP as f64 * step;
+                self.wsola_primed = true;
+            }
+        }
+
+        let k = self.wsola_phase;
+        let window =
+            |n: usize| 0.5 - 0.5 * (std::f32::consts::PI * n as f32 / WSOLA_HOP as f32).cos();
+        let (in_left, in_right) = self.get_sample_at(self.wsola_grain_start + k as f64 * step);
+        let (out_left, out_right) =
+            self.get_sample_at(self.wsola_prev_start + (k + WSOLA_HOP) as f64 * step);
+        let (w_in, w_out) = (window(k), window(k + WSOLA_HOP));
+
+        self.wsola_phase = (k + 1) % WSOLA_HOP;
+        (
+            in_left * w_in + out_left * w_out,
+            in_right * w_in + out_right * w_out,
+        )
+    }
+
+    /// Find the grain start near `nominal` most similar to `natural`
+    ///
+    /// Compares one hop of (left channel) waveform by normalized
+    /// cross-correlation; offsets are tried outward from zero so an exact
+    /// match at the nominal position wins ties.
+    fn wsola_best_start(&self, nominal: f64, natural: f64, step: f64) -> f64 {
+        let Some(ref sample) = self.sample else {
+            return nominal;
+        };
+        let data = &sample.data;
+        let read = |pos: f64| read_interpolated(data, pos, InterpMode::Linear);
+
+        let mut points = [0.0f32; WSOLA_HOP / WSOLA_COMPARE_STRIDE];
+        for (i, point) in points.iter_mut().enumerate() {
+            *point = read(natural + (i * WSOLA_COMPARE_STRIDE) as f64 * step);
+        }
+        let target_energy: f32 = points.iter().map(|x| x * x).sum();
+        if target_energy <= f32::EPSILON {
+            return nominal;
+        }
+
+        let mut best = (nominal, f32::MIN);
+        for distance in 0..=WSOLA_SEARCH as isize {
+            for offset in [distance, -distance] {
+                let start = nominal + offset as f64;
+                let (mut dot, mut energy) = (0.0f32, 0.0f32);
+                for (i, &target) in points.iter().enumerate() {
+                    let x = read(start + (i * WSOLA_COMPARE_STRIDE) as f64 * step);
+                    dot += x * target;
+                    energy += x * x;
+                }
+                let score = dot / (energy * target_energy).sqrt().max(f32::EPSILON);
+                if score > best.1 + 1e-6 {
+                    best = (start, score);
+                }
+                if distance == 0 {
+                    break;
+                }
+            }
+        }
+        best.0
+    }
+
+    /// 获取指定位置的采样值 (interpolated at the fractional position)
+    fn get_sample_at(&self, position: f64) -> (f32, f32) {
+        match self.sample {
+            Some(ref sample) if !sample.data.is_empty() => {
+                let left = read_interpolated(&sample.data, position, self.interpolation);
+                let right = sample
+                    .data_stereo
+                    .as_ref()
+                    .map(|s| read_interpolated(s, position, self.interpolation))
+                    .unwrap_or(left);
+                (left, right)
+            }
+            _ => (0.0, 0.0),
         }
     }
 
@@ -558,13 +1208,38 @@ impl SampleLibrary {
     }
 }
 
+/// Energy window of the onset detector (samples)
+const ONSET_WINDOW: usize = 512;
+
+/// Hop between onset detector windows (samples)
+const ONSET_HOP: usize = 64;
+
+/// Energy added before taking ratios, keeps silence from dividing by zero
+const ONSET_ENERGY_FLOOR: f64 = 1e-10;
+
+/// Peak level below which a sample counts as silent
+const SILENCE_LEVEL: f32 = 1e-6;
+
 /// 自动切片器
+///
+/// Onsets are found by energy flux: the energy of a short window after each
+/// candidate point is compared with the window before it, and a rise of
+/// more than the sensitivity-dependent threshold marks a transient.
 pub struct AutoSlicer {
-    /// 瞬态检测阈值
+    /// 瞬态检测阈值 (minimum peak level of an onset, after normalization)
     threshold: f32,
 
     /// 最小切片间隔 (samples)
     min_slice_distance: usize,
+
+    /// Onset sensitivity (0.0 = only strong hits, 1.0 = subtle onsets)
+    sensitivity: f32,
+
+    /// Peak-normalize the sample before detection
+    normalize: bool,
+
+    /// Maximum number of slices (0 = unlimited)
+    max_slices: usize,
 }
 
 impl AutoSlicer {
@@ -573,44 +1248,122 @@ impl AutoSlicer {
         Self {
             threshold,
             min_slice_distance,
+            sensitivity: 0.5,
+            normalize: true,
+            max_slices: 0,
         }
     }
 
+    /// Set onset sensitivity (0.0 - 1.0)
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.sensitivity = sensitivity.clamp(0.0, 1.0);
+    }
+
+    /// Get onset sensitivity
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
+    /// Enable peak normalization before detection
+    ///
+    /// With normalization the threshold and sensitivity behave the same
+    /// for quiet and loud recordings.
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Check if peak normalization is enabled
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// Cap the number of slices (0 = unlimited); the strongest onsets win
+    pub fn set_max_slices(&mut self, max_slices: usize) {
+        self.max_slices = max_slices;
+    }
+
+    /// Get the slice cap (0 = unlimited)
+    pub fn max_slices(&self) -> usize {
+        self.max_slices
+    }
+
+    /// Energy rise in dB that counts as an onset (24 dB down to 3 dB)
+    fn flux_threshold_db(&self) -> f64 {
+        3.0 + (1.0 - self.sensitivity as f64) * 21.0
+    }
+
     /// 检测瞬态点
     pub fn detect_transients(&self, samples: &[f32]) -> Vec<usize> {
-        let mut transients = Vec::new();
-        let mut last_slice = 0;
-
-        for (i, &sample) in samples.iter().enumerate().skip(1) {
-            let diff = (sample - samples[i - 1]).abs();
+        let peak = samples.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        if peak < SILENCE_LEVEL || samples.len() < 2 * ONSET_WINDOW {
+            return Vec::new();
+        }
+        let gain = if self.normalize { 1.0 / peak } else { 1.0 };
+
+        // Prefix sums of squared samples give each window's energy in O(1)
+        let mut energy_sum = Vec::with_capacity(samples.len() + 1);
+        energy_sum.push(0.0f64);
+        for &x in samples {
+            let x = (x * gain) as f64;
+            energy_sum.push(energy_sum[energy_sum.len() - 1] + x * x);
+        }
+        let energy = |start: usize| {
+            (energy_sum[start + ONSET_WINDOW] - energy_sum[start]) / ONSET_WINDOW as f64
+        };
 
-            if diff > self.threshold && i - last_slice >= self.min_slice_distance {
-                // 检查是否是真正的瞬态(需要连续多个高值)
-                if self.is_transient(samples, i) {
-                    transients.push(i);
-                    last_slice = i;
+        // Group consecutive hops above the threshold and keep each group's peak
+        let threshold_db = self.flux_threshold_db();
+        let mut onsets: Vec<(usize, f64)> = Vec::new();
+        let mut group: Option<(usize, f64)> = None;
+        let mut boundary = ONSET_WINDOW;
+        while boundary + ONSET_WINDOW <= samples.len() {
+            let before = energy(boundary - ONSET_WINDOW) + ONSET_ENERGY_FLOOR;
+            let after = energy(boundary) + ONSET_ENERGY_FLOOR;
+            let flux = 10.0 * (after / before).log10();
+
+            if flux > threshold_db {
+                if group.is_none_or(|(_, strongest)| flux > strongest) {
+                    group = Some((boundary, flux));
                 }
+            } else if let Some(found) = group.take() {
+                onsets.push(found);
             }
+            boundary += ONSET_HOP;
         }
+        onsets.extend(group);
 
-        transients
-    }
-
-    /// 检查是否是真正的瞬态
-    fn is_transient(&self, samples: &[f32], index: usize) -> bool {
-        let window = 5;
-        let start = index.saturating_sub(window);
-        let end = (index + window).min(samples.len());
-
-        let mut max_diff = 0.0;
-        for i in start..end {
-            let diff = (samples[i] - samples.get(i + 1).unwrap_or(&0.0)).abs();
-            if diff > max_diff {
-                max_diff = diff;
+        // Place each onset on the first sample reaching half the window peak
+        let mut transients: Vec<(usize, f64)> = Vec::new();
+        let mut last_slice = 0;
+        for (boundary, flux) in onsets {
+            let window = &samples[boundary..boundary + ONSET_WINDOW];
+            let window_peak = window.iter().fold(0.0f32, |m, x| m.max(x.abs())) * gain;
+            if window_peak < self.threshold {
+                continue;
+            }
+            let offset = window
+                .iter()
+                .position(|x| x.abs() * gain >= window_peak * 0.5)
+                .unwrap_or(0);
+            let position = boundary + offset;
+
+            if position - last_slice >= self.min_slice_distance {
+                transients.push((position, flux));
+                last_slice = position;
             }
         }
 
-        max_diff > self.threshold * 2.0
+        // Keep the strongest onsets when capped
+        if self.max_slices > 0 && transients.len() >= self.max_slices {
+            transients.sort_by(|a, b| b.1.total_cmp(&a.1));
+            transients.truncate(self.max_slices - 1);
+            transients.sort_by_key(|&(position, _)| position);
+        }
+
+        transients
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect()
     }
 
     /// 自动切片采样
@@ -765,6 +1518,179 @@ mod tests {
         assert_eq!(sampler.loop_mode, LoopMode::PingPong);
     }
 
+    /// Largest sample-to-sample jump of a looped DC-offset ramp
+    fn loop_max_jump(mode: LoopMode, loop_start: usize, loop_end: usize) -> f32 {
+        // DC offset plus a ramp, so the loop end and start don't match
+        let data: Vec<f32> = (0..1000).map(|i| 0.5 + 0.4 * i as f32 / 1000.0).collect();
+        let mut sampler = Sampler::new();
+        sampler.load(Sample::new("Ramp", data, 44100));
+        sampler.set_loop_mode(mode);
+        sampler.set_loop(loop_start, loop_end);
+        sampler.play();
+
+        let output: Vec<f32> = (0..5000).map(|_| sampler.process().0).collect();
+        assert!(sampler.is_playing());
+        // Skip the trigger fade-in
+        output[100..]
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_sampler_loop_crossfade_has_no_spike() {
+        // Without crossfading the wrap jumps by 0.16 (and 0.16 for start 0)
+        assert!(loop_max_jump(LoopMode::Loop, 200, 600) < 0.02);
+        assert!(loop_max_jump(LoopMode::Loop, 0, 400) < 0.02);
+        assert!(loop_max_jump(LoopMode::PingPong, 200, 600) < 0.02);
+    }
+
+    #[test]
+    fn test_sampler_short_loop_shrinks_crossfade() {
+        let mut sampler = Sampler::new();
+        sampler.load(Sample::new("DC", vec![0.5; 1000], 44100));
+        sampler.set_loop_mode(LoopMode::Loop);
+        sampler.set_loop(300, 340);
+        sampler.play();
+
+        for _ in 0..400 {
+            sampler.process();
+        }
+        assert_eq!(sampler.loop_xfade_length, 20);
+        assert!(sampler.position >= 300.0 && sampler.position < 340.0);
+    }
+
+    #[test]
+    fn test_sampler_ping_pong_turns_at_both_ends() {
+        let data: Vec<f32> = (0..1000).map(|i| i as f32 / 1000.0).collect();
+        let mut sampler = Sampler::new();
+        sampler.load(Sample::new("Ramp", data, 44100));
+        sampler.set_loop_mode(LoopMode::PingPong);
+        sampler.set_loop(200, 600);
+        sampler.play();
+
+        for _ in 0..5000 {
+            sampler.process();
+            assert!(sampler.position >= 200.0 && sampler.position < 600.0);
+        }
+
+        sampler.reset();
+        assert_eq!(sampler.loop_xfade_remaining, 0);
+    }
+
+    /// Frequency of a signal from its rising zero crossings
+    fn zero_crossing_frequency(signal: &[f32], sample_rate: f32) -> f32 {
+        let crossings: Vec<usize> = signal
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] < 0.0 && w[1] >= 0.0)
+            .map(|(i, _)| i)
+            .collect();
+        let cycles = (crossings.len() - 1) as f32;
+        let span = (crossings[crossings.len() - 1] - crossings[0]) as f32;
+        cycles * sample_rate / span
+    }
+
+    /// Plays a 441 Hz sine to the end and returns the output
+    fn play_stretched_sine(mode: StretchMode, stretch: f32) -> Vec<f32> {
+        let data: Vec<f32> = (0..44100)
+            .map(|i| (2.0 * std::f32::consts::PI * 441.0 * i as f32 / 44100.0).sin())
+            .collect();
+        let mut sampler = Sampler::new();
+        sampler.load(Sample::new("Sine", data, 44100));
+        sampler.set_time_stretch_mode(mode);
+        sampler.set_time_stretch(stretch);
+        sampler.play();
+
+        let mut output = Vec::new();
+        while sampler.is_playing() {
+            output.push(sampler.process().0);
+        }
+        output
+    }
+
+    #[test]
+    fn test_sampler_wsola_keeps_pitch() {
+        let stretched = play_stretched_sine(StretchMode::Wsola, 0.5);
+        assert!(
+            (stretched.len() as i64 - 88200).abs() < 10,
+            "{}",
+            stretched.len()
+        );
+        let freq = zero_crossing_frequency(&stretched[4410..80000], 44100.0);
+        assert!(
+            (freq - 441.0).abs() < 441.0 * 0.01,
+            "WSOLA changed pitch: {freq}"
+        );
+
+        // Varispeed drops an octave for the same stretch
+        let varispeed = play_stretched_sine(StretchMode::Varispeed, 0.5);
+        assert_eq!(varispeed.len(), stretched.len());
+        let freq = zero_crossing_frequency(&varispeed[4410..80000], 44100.0);
+        assert!((freq - 220.5).abs() < 220.5 * 0.01, "{freq}");
+    }
+
+    #[test]
+    fn test_sampler_wsola_unity_is_transparent() {
+        let wsola = play_stretched_sine(StretchMode::Wsola, 1.0);
+        let varispeed = play_stretched_sine(StretchMode::Varispeed, 1.0);
+        assert_eq!(wsola.len(), varispeed.len());
+        // After the first hop, whose predecessor grain is imaginary
+        for (a, b) in wsola.iter().zip(&varispeed).skip(WSOLA_HOP) {
+            assert!((a - b).abs() < 1e-4, "{a} vs {b}");
+        }
+        assert_eq!(Sampler::new().time_stretch_mode(), StretchMode::Varispeed);
+    }
+
+    #[test]
+    fn test_sampler_tempo_sync_rate() {
+        let mut sample = Sample::new("Loop", vec![0.0; 44100], 44100);
+        sample.info.tempo_sensitivity = 1.0;
+        let mut sampler = Sampler::new();
+        sampler.load(sample);
+        sampler.set_sync(120.0, 150.0);
+        assert!((sampler.tempo_ratio() - 1.25).abs() < 1e-6);
+
+        sampler.play();
+        for _ in 0..1000 {
+            sampler.process();
+        }
+        assert!(
+            (sampler.position - 1250.0).abs() < 1e-3,
+            "{}",
+            sampler.position
+        );
+
+        // Pitch-preserving sync advances at the same rate
+        sampler.set_time_stretch_mode(StretchMode::Wsola);
+        sampler.reset();
+        sampler.play();
+        for _ in 0..1000 {
+            sampler.process();
+        }
+        assert!((sampler.position - 1250.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_sampler_tempo_sync_sensitivity() {
+        let mut sample = Sample::new("Loop", vec![0.0; 100], 44100);
+        sample.info.tempo_sensitivity = 0.0;
+        let mut sampler = Sampler::new();
+        sampler.load(sample.clone());
+        sampler.set_sync(120.0, 90.0);
+        assert_eq!(sampler.tempo_ratio(), 1.0);
+
+        sample.info.tempo_sensitivity = 2.0;
+        sampler.load(sample);
+        assert!((sampler.tempo_ratio() - 0.5625).abs() < 1e-6);
+
+        sampler.set_sync(120.0, 120.5);
+        assert!((sampler.tempo_ratio() - 1.0).abs() < 0.01);
+
+        sampler.set_sync(0.0, 120.0);
+        assert_eq!(sampler.tempo_ratio(), 1.0);
+    }
+
     #[test]
     fn test_sample_library() {
         let mut library = SampleLibrary::new();
@@ -831,6 +1757,74 @@ mod tests {
         );
     }
 
+    /// Clicks with a decaying tail over a quiet steady tone
+    fn click_train(positions: &[usize], level: f32, len: usize) -> Vec<f32> {
+        let mut data: Vec<f32> = (0..len)
+            .map(|i| level * 0.05 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+            .collect();
+        for &position in positions {
+            for (k, x) in data[position..].iter_mut().take(400).enumerate() {
+                *x += level * (-(k as f32) / 60.0).exp();
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_auto_slicer_recovers_click_train() {
+        let clicks = [3000, 6000, 9500, 13000, 17000];
+        let slicer = AutoSlicer::new(0.1, 500);
+        for level in [0.9, 0.005] {
+            // Peak normalization makes the quiet train slice the same
+            let transients = slicer.detect_transients(&click_train(&clicks, level, 20000));
+            assert_eq!(
+                transients.len(),
+                clicks.len(),
+                "level {level}: {transients:?}"
+            );
+            for (found, expected) in transients.iter().zip(clicks) {
+                assert!(found.abs_diff(expected) <= 4, "{found} vs {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_auto_slicer_max_slices_keeps_strongest() {
+        let mut data = click_train(&[3000, 9000], 0.9, 14000);
+        // A weaker click in between, only found at higher sensitivity
+        for (k, x) in data[6000..].iter_mut().take(400).enumerate() {
+            *x += 0.4 * (-(k as f32) / 60.0).exp();
+        }
+
+        let mut slicer = AutoSlicer::new(0.1, 500);
+        assert_eq!(slicer.detect_transients(&data).len(), 2);
+        slicer.set_sensitivity(0.8);
+        assert_eq!(slicer.detect_transients(&data).len(), 3);
+
+        slicer.set_max_slices(3);
+        let transients = slicer.detect_transients(&data);
+        assert_eq!(transients.len(), 2);
+        assert!(transients[0].abs_diff(3000) <= 4 && transients[1].abs_diff(9000) <= 4);
+
+        let sample = Sample::new("Loop", data, 44100);
+        assert_eq!(slicer.slice_sample(&sample).len(), 3);
+    }
+
+    #[test]
+    fn test_auto_slicer_silence_and_steady_tone() {
+        let slicer = AutoSlicer::new(0.1, 100);
+
+        let silent = Sample::new("Silence", vec![0.0; 10000], 44100);
+        let slices = slicer.slice_sample(&silent);
+        assert_eq!(slices.len(), 1);
+        assert_eq!((slices[0].start, slices[0].end), (0, 10000));
+
+        let tone: Vec<f32> = (0..20000)
+            .map(|i| 0.8 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+            .collect();
+        assert!(slicer.detect_transients(&tone).is_empty());
+    }
+
     #[test]
     fn test_sample_duration() {
         let sample = Sample::new("Test", vec![0.5; 44100], 44100);
@@ -982,6 +1976,124 @@ mod tests {
         assert!(result);
     }
 
+    #[test]
+    fn test_multi_sampler_pitch_ratio() {
+        let mut sampler = MultiSampler::new();
+        let sample = Sample::new("Test", vec![0.5; 44100], 44100);
+        let mut instrument = MultiSampleInstrument::new("Test");
+        instrument.add_zone(KeyZone::new(sample, 0, 127, 60));
+        sampler.load_instrument(instrument);
+
+        // The root note plays at original pitch
+        sampler.note_on(60, 100);
+        assert_eq!(sampler.active_samplers[0].speed, 1.0);
+        assert_eq!(sampler.active_samplers[0].pitch_offset, 0);
+
+        // An octave up doubles the speed, a fifth up is ~1.498
+        sampler.note_on(72, 100);
+        assert!((sampler.active_samplers[1].speed - 2.0).abs() < 1e-6);
+        sampler.note_on(67, 100);
+        assert!((sampler.active_samplers[2].speed - 1.4983).abs() < 1e-3);
+        sampler.note_on(48, 100);
+        assert!((sampler.active_samplers[3].speed - 0.5).abs() < 1e-6);
+    }
+
+    /// Name of the sample loaded in an active sampler
+    fn playing_name(sampler: &MultiSampler, index: usize) -> &str {
+        &sampler.active_samplers[index]
+            .sample
+            .as_ref()
+            .unwrap()
+            .info
+            .name
+    }
+
+    #[test]
+    fn test_multi_sampler_round_robin_cycles() {
+        let named = |name: &str| Sample::new(name, vec![0.5; 44100], 44100);
+        let mut zone = KeyZone::new(named("Default"), 0, 127, 60);
+        zone.add_layer(VelocityLayer::new(0, 63, vec![named("Soft")]));
+        zone.add_layer(VelocityLayer::new(
+            64,
+            127,
+            vec![named("Hard A"), named("Hard B"), named("Hard C")],
+        ));
+
+        let mut instrument = MultiSampleInstrument::new("Drums");
+        instrument.add_zone(zone);
+        let mut sampler = MultiSampler::new();
+        sampler.load_instrument(instrument);
+
+        for _ in 0..4 {
+            sampler.note_on(60, 120);
+        }
+        sampler.note_on(60, 20);
+
+        assert_eq!(playing_name(&sampler, 0), "Hard A");
+        assert_eq!(playing_name(&sampler, 1), "Hard B");
+        assert_eq!(playing_name(&sampler, 2), "Hard C");
+        assert_eq!(playing_name(&sampler, 3), "Hard A");
+        assert_eq!(playing_name(&sampler, 4), "Soft");
+    }
+
+    #[test]
+    fn test_key_zone_velocity_gap_uses_nearest_layer() {
+        let named = |name: &str| Sample::new(name, vec![0.5; 16], 44100);
+        let mut zone = KeyZone::new(named("Default"), 0, 127, 60);
+        assert_eq!(zone.layer_index(100), None);
+
+        zone.add_layer(VelocityLayer::new(100, 127, vec![named("Loud")]));
+        zone.add_layer(VelocityLayer::new(0, 40, vec![named("Quiet")]));
+        // Empty layers are ignored
+        zone.add_layer(VelocityLayer::new(41, 99, Vec::new()));
+        assert_eq!(zone.layers.len(), 2);
+
+        assert_eq!(zone.layer_index(10), Some(0));
+        assert_eq!(zone.layer_index(50), Some(0));
+        assert_eq!(zone.layer_index(90), Some(1));
+        assert_eq!(zone.layer_index(127), Some(1));
+    }
+
+    #[test]
+    fn test_multi_sampler_key_crossfade() {
+        let named = |name: &str| Sample::new(name, vec![0.5; 44100], 44100);
+        let mut instrument = MultiSampleInstrument::new("Piano");
+        instrument.add_zone(KeyZone::new(named("Low"), 0, 64, 48));
+        instrument.add_zone(KeyZone::new(named("High"), 60, 127, 72));
+
+        // Gains move across the overlap and always sum to unity
+        let mut previous_high = 0.0;
+        for note in 60..=64 {
+            let zones = instrument.crossfade_zones(note);
+            assert_eq!(zones.len(), 2);
+            assert!((zones[0].1 + zones[1].1 - 1.0).abs() < 1e-6);
+            assert!(zones[1].1 > previous_high);
+            previous_high = zones[1].1;
+        }
+        assert_eq!(instrument.crossfade_zones(40), vec![(0, 1.0)]);
+        assert_eq!(instrument.crossfade_zones(90), vec![(1, 1.0)]);
+
+        let mut sampler = MultiSampler::new();
+        sampler.load_instrument(instrument);
+
+        // A note in the overlap plays both zones
+        sampler.note_on(62, 127);
+        assert_eq!(sampler.active_samplers.len(), 2);
+        assert_eq!(playing_name(&sampler, 0), "Low");
+        assert_eq!(playing_name(&sampler, 1), "High");
+        let total = sampler.active_samplers[0].volume + sampler.active_samplers[1].volume;
+        assert!((total - 1.0).abs() < 1e-6);
+
+        // Outside the overlap a single zone plays as before
+        sampler.note_on(40, 127);
+        assert_eq!(sampler.active_samplers.len(), 3);
+        assert_eq!(sampler.active_samplers[2].volume, 1.0);
+
+        // Releasing the note releases both voices
+        assert!(sampler.note_off(62));
+        assert!(!sampler.active_notes.contains_key(&62));
+    }
+
     #[test]
     fn test_multi_sampler_max_polyphony() {
         let mut sampler = MultiSampler::new();
@@ -1033,6 +2145,290 @@ mod tests {
         assert!(RecordLength::Max.to_samples(44100) > RecordLength::One.to_samples(44100));
     }
 
+    /// Builds a WAV file from a `fmt ` body, extra chunks and sample data
+    fn build_wav(fmt: &[u8], extra: &[(&[u8; 4], &[u8])], data: &[u8]) -> Vec<u8> {
+        let mut chunks = Vec::new();
+        let mut push_chunk = |id: &[u8; 4], body: &[u8]| {
+            chunks.extend_from_slice(id);
+            chunks.extend_from_slice(&(body.len() as u32).to_le_bytes());
+            chunks.extend_from_slice(body);
+            if body.len() % 2 == 1 {
+                chunks.push(0);
+            }
+        };
+        push_chunk(b"fmt ", fmt);
+        for (id, body) in extra {
+            push_chunk(id, body);
+        }
+        push_chunk(b"data", data);
+
+        let mut wav = b"RIFF".to_vec();
+        wav.extend_from_slice(&(chunks.len() as u32 + 4).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(&chunks);
+        wav
+    }
+
+    /// A plain `fmt ` chunk body
+    fn fmt_chunk(format_tag: u16, channels: u16, sample_rate: u32, bits: u16) -> Vec<u8> {
+        let block_align = channels * bits / 8;
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&format_tag.to_le_bytes());
+        fmt.extend_from_slice(&channels.to_le_bytes());
+        fmt.extend_from_slice(&sample_rate.to_le_bytes());
+        fmt.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        fmt.extend_from_slice(&block_align.to_le_bytes());
+        fmt.extend_from_slice(&bits.to_le_bytes());
+        fmt
+    }
+
+    #[test]
+    fn test_wav_decode_pcm16_mono() {
+        let values: [i16; 5] = [0, 16384, -16384, 32767, -32768];
+        let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        // An odd-sized LIST chunk before the data exercises pad-byte handling
+        let wav = build_wav(&fmt_chunk(1, 1, 22050, 16), &[(b"LIST", b"INFOabc")], &data);
+
+        let sample = Sample::from_wav_bytes(&wav).unwrap();
+        assert_eq!(sample.info.sample_rate, 22050);
+        assert_eq!(sample.info.channels, 1);
+        assert_eq!(sample.info.length, 5);
+        assert_eq!(sample.info.format, SampleFormat::Pcm16);
+        assert!(!sample.is_stereo());
+
+        let expected = [0.0, 0.5, -0.5, 32767.0 / 32768.0, -1.0];
+        for (got, want) in sample.data.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-6, "{got} != {want}");
+        }
+    }
+
+    #[test]
+    fn test_wav_decode_pcm24_stereo() {
+        // Frames: (0.5, -0.25), (-1.0, 0.0)
+        let values: [i32; 4] = [4_194_304, -2_097_152, -8_388_608, 0];
+        let data: Vec<u8> = values
+            .iter()
+            .flat_map(|v| v.to_le_bytes()[..3].to_vec())
+            .collect();
+        let wav = build_wav(&fmt_chunk(1, 2, 48000, 24), &[], &data);
+
+        let sample = Sample::from_wav_bytes(&wav).unwrap();
+        assert_eq!(sample.info.channels, 2);
+        assert_eq!(sample.info.length, 2);
+        assert_eq!(sample.info.format, SampleFormat::Pcm24);
+        assert_eq!(sample.data, vec![0.5, -1.0]);
+        assert_eq!(sample.data_stereo, Some(vec![-0.25, 0.0]));
+    }
+
+    #[test]
+    fn test_wav_decode_float32() {
+        let data: Vec<u8> = [0.25f32, -0.75, 2.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let wav = build_wav(&fmt_chunk(3, 1, 44100, 32), &[(b"cue ", &[0; 4])], &data);
+
+        let sample = Sample::from_wav_bytes(&wav).unwrap();
+        assert_eq!(sample.info.format, SampleFormat::Float32);
+        // Out-of-range floats are clamped
+        assert_eq!(sample.data, vec![0.25, -0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_wav_decode_errors() {
+        assert_eq!(
+            Sample::from_wav_bytes(b"not a wav file"),
+            Err(SampleError::NotWavFile)
+        );
+
+        let pcm8 = build_wav(&fmt_chunk(1, 1, 44100, 8), &[], &[128, 128]);
+        assert_eq!(
+            Sample::from_wav_bytes(&pcm8),
+            Err(SampleError::UnsupportedFormat {
+                format_tag: 1,
+                bits_per_sample: 8
+            })
+        );
+
+        let surround = build_wav(&fmt_chunk(1, 6, 44100, 16), &[], &[0; 12]);
+        assert_eq!(
+            Sample::from_wav_bytes(&surround),
+            Err(SampleError::UnsupportedChannelCount(6))
+        );
+
+        let mut no_data = build_wav(&fmt_chunk(1, 1, 44100, 16), &[], &[]);
+        no_data.truncate(no_data.len() - 8);
+        assert_eq!(
+            Sample::from_wav_bytes(&no_data),
+            Err(SampleError::MissingDataChunk)
+        );
+    }
+
+    /// Encodes a sample rate as an 80-bit extended float
+    fn extended(rate: u32) -> [u8; 10] {
+        let shift = rate.leading_zeros();
+        let exponent = (16383 + 31 - shift) as u16;
+        let mantissa = ((rate as u64) << (32 + shift)).to_be_bytes();
+        let mut bytes = [0u8; 10];
+        bytes[..2].copy_from_slice(&exponent.to_be_bytes());
+        bytes[2..].copy_from_slice(&mantissa);
+        bytes
+    }
+
+    /// Builds an AIFF (or AIFF-C with `compression`) file
+    fn build_aiff(
+        channels: u16,
+        bits: u16,
+        rate: u32,
+        compression: Option<&[u8; 4]>,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let frames = data.len() / (channels as usize * bits as usize / 8);
+        let mut comm = Vec::new();
+        comm.extend_from_slice(&channels.to_be_bytes());
+        comm.extend_from_slice(&(frames as u32).to_be_bytes());
+        comm.extend_from_slice(&bits.to_be_bytes());
+        comm.extend_from_slice(&extended(rate));
+        if let Some(kind) = compression {
+            comm.extend_from_slice(kind);
+            comm.extend_from_slice(&[0, 0]); // empty pascal-string name
+        }
+
+        let mut ssnd = vec![0u8; 8]; // offset and block size
+        ssnd.extend_from_slice(data);
+
+        let mut chunks = Vec::new();
+        // SSND first: chunk order is not fixed in AIFF
+        for (id, body) in [(b"SSND", &ssnd), (b"COMM", &comm)] {
+            chunks.extend_from_slice(id);
+            chunks.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            chunks.extend_from_slice(body);
+            if body.len() % 2 == 1 {
+                chunks.push(0);
+            }
+        }
+
+        let mut aiff = b"FORM".to_vec();
+        aiff.extend_from_slice(&(chunks.len() as u32 + 4).to_be_bytes());
+        aiff.extend_from_slice(if compression.is_some() {
+            b"AIFC"
+        } else {
+            b"AIFF"
+        });
+        aiff.extend_from_slice(&chunks);
+        aiff
+    }
+
+    #[test]
+    fn test_aiff_decode_pcm16() {
+        let values: [i16; 4] = [0, 16384, -16384, -32768];
+        let data: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+        let aiff = build_aiff(1, 16, 44100, None, &data);
+
+        let sample = Sample::from_aiff_bytes(&aiff).unwrap();
+        assert_eq!(sample.info.sample_rate, 44100);
+        assert_eq!(sample.info.length, 4);
+        assert_eq!(sample.info.format, SampleFormat::Pcm16);
+        assert_eq!(sample.data, vec![0.0, 0.5, -0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_aiff_decode_pcm24_stereo_odd_rate() {
+        // 3 bytes per sample, 1 frame: an odd SSND size exercises padding
+        let data = [0x40, 0x00, 0x00, 0xE0, 0x00, 0x00];
+        let aiff = build_aiff(2, 24, 22050, None, &data[..]);
+
+        let sample = Sample::from_aiff_bytes(&aiff).unwrap();
+        assert_eq!(sample.info.sample_rate, 22050);
+        assert_eq!(sample.info.length, 1);
+        assert_eq!(sample.data, vec![0.5]);
+        assert_eq!(sample.data_stereo, Some(vec![-0.25]));
+    }
+
+    #[test]
+    fn test_aifc_variants() {
+        let data: Vec<u8> = [0.5f32, -0.25]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let float = build_aiff(1, 32, 48000, Some(b"fl32"), &data);
+        let sample = Sample::from_aiff_bytes(&float).unwrap();
+        assert_eq!(sample.info.sample_rate, 48000);
+        assert_eq!(sample.data, vec![0.5, -0.25]);
+
+        let little: Vec<u8> = 16384i16.to_le_bytes().to_vec();
+        let sowt = build_aiff(1, 16, 48000, Some(b"sowt"), &little);
+        assert_eq!(Sample::from_aiff_bytes(&sowt).unwrap().data, vec![0.5]);
+
+        let ima = build_aiff(1, 16, 48000, Some(b"ima4"), &[0; 34]);
+        assert_eq!(
+            Sample::from_aiff_bytes(&ima),
+            Err(SampleError::UnsupportedCompression("ima4".to_string()))
+        );
+        assert_eq!(
+            Sample::from_aiff_bytes(b"RIFF\0\0\0\0WAVE"),
+            Err(SampleError::NotAiffFile)
+        );
+    }
+
+    /// RMS error of pitched playback of a sine against the ideal sine
+    fn pitched_sine_error(mode: InterpMode) -> f32 {
+        let period = 32.0;
+        let data: Vec<f32> = (0..4096)
+            .map(|i| (2.0 * std::f32::consts::PI * i as f32 / period).sin())
+            .collect();
+        let right: Vec<f32> = data.iter().map(|x| -x).collect();
+
+        let mut sampler = Sampler::new();
+        sampler.load(Sample::new_stereo("Sine", data, right, 44100));
+        sampler.set_interpolation(mode);
+        sampler.set_speed(1.37);
+        sampler.play();
+
+        let mut error = 0.0;
+        let mut count = 0;
+        // Skip the fade-in
+        for _ in 0..100 {
+            sampler.process();
+        }
+        for _ in 0..2000 {
+            let (left, right) = sampler.process();
+            let ideal = (2.0 * std::f64::consts::PI * sampler.position / period as f64).sin();
+            assert!((left + right).abs() < 1e-6, "channels interpolate alike");
+            error += (left as f64 - ideal).powi(2);
+            count += 1;
+        }
+        (error / count as f64).sqrt() as f32
+    }
+
+    #[test]
+    fn test_sampler_interpolation_reduces_distortion() {
+        let nearest = pitched_sine_error(InterpMode::Nearest);
+        let linear = pitched_sine_error(InterpMode::Linear);
+        let cubic = pitched_sine_error(InterpMode::Cubic);
+
+        assert!(
+            linear < nearest * 0.2,
+            "linear {linear} vs nearest {nearest}"
+        );
+        assert!(cubic < linear * 0.5, "cubic {cubic} vs linear {linear}");
+        assert_eq!(Sampler::new().interpolation(), InterpMode::Linear);
+    }
+
+    #[test]
+    fn test_sampler_interpolation_clamps_at_end() {
+        let data = [0.0, 1.0, 0.5];
+        for mode in [InterpMode::Nearest, InterpMode::Linear, InterpMode::Cubic] {
+            assert_eq!(read_interpolated(&data, 2.0, mode), 0.5);
+            assert_eq!(read_interpolated(&data, 1.0, mode), 1.0);
+            // Lookahead past the end (and behind the start) is clamped
+            assert!(read_interpolated(&data, 2.9, mode).abs() <= 1.0);
+            assert!(read_interpolated(&data, 0.1, mode).abs() <= 1.0);
+        }
+        assert_eq!(read_interpolated(&data, 2.5, InterpMode::Linear), 0.5);
+        assert_eq!(read_interpolated(&data, 0.5, InterpMode::Linear), 0.5);
+    }
+
     #[test]
     fn test_auto_sampler_config() {
         let config = AutoSamplerConfig::default();
@@ -1067,6 +2463,47 @@ pub struct KeyZone {
 
     /// 音量补偿 (dB)
     pub volume补偿: f32,
+
+    /// Velocity layers (empty = always play `sample`)
+    pub layers: Vec<VelocityLayer>,
+}
+
+/// Velocity layer of a key zone with its round-robin samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityLayer {
+    /// Lowest velocity of the layer
+    pub low_velocity: u8,
+
+    /// Highest velocity of the layer
+    pub high_velocity: u8,
+
+    /// Samples played in turn on consecutive hits
+    pub samples: Vec<Sample>,
+}
+
+impl VelocityLayer {
+    /// Create a velocity layer
+    pub fn new(low_velocity: u8, high_velocity: u8, samples: Vec<Sample>) -> Self {
+        Self {
+            low_velocity: low_velocity.min(high_velocity),
+            high_velocity: high_velocity.max(low_velocity),
+            samples,
+        }
+    }
+
+    /// Check if a velocity falls in this layer
+    pub fn contains(&self, velocity: u8) -> bool {
+        velocity >= self.low_velocity && velocity <= self.high_velocity
+    }
+
+    /// Distance from a velocity to this layer (0 inside)
+    fn distance(&self, velocity: u8) -> u8 {
+        if velocity < self.low_velocity {
+            self.low_velocity - velocity
+        } else {
+            velocity.saturating_sub(self.high_velocity)
+        }
+    }
 }
 
 impl KeyZone {
@@ -1079,9 +2516,29 @@ impl KeyZone {
             root_note,
             crossfade_samples: 64,
             volume补偿: 0.0,
+            layers: Vec::new(),
         }
     }
 
+    /// Add a velocity layer with one or more round-robin samples
+    pub fn add_layer(&mut self, layer: VelocityLayer) {
+        if layer.samples.is_empty() {
+            return;
+        }
+        self.layers.push(layer);
+        self.layers.sort_by_key(|l| l.low_velocity);
+    }
+
+    /// Find the layer for a velocity
+    ///
+    /// Velocities in a gap between layers use the nearest layer.
+    pub fn layer_index(&self, velocity: u8) -> Option<usize> {
+        self.layers
+            .iter()
+            .position(|l| l.contains(velocity))
+            .or_else(|| (0..self.layers.len()).min_by_key(|&i| self.layers[i].distance(velocity)))
+    }
+
     /// 检查音符是否在此键区内
     pub fn contains(&self, note: u8) -> bool {
         note >= self.low_note && note <= self.high_note
@@ -1091,6 +2548,11 @@ impl KeyZone {
     pub fn note_offset(&self, note: u8) -> i8 {
         note as i8 - self.root_note as i8
     }
+
+    /// Playback speed ratio for a note (1.0 at the root, 2.0 an octave up)
+    pub fn pitch_ratio(&self, note: u8) -> f32 {
+        2.0f32.powf(self.note_offset(note) as f32 / 12.0)
+    }
 }
 
 /// 多采样乐器 - 管理多个键区
@@ -1151,24 +2613,49 @@ impl MultiSampleInstrument {
 
     /// 根据音符查找合适的键区
     pub fn find_zone(&self, note: u8) -> Option<&KeyZone> {
+        self.find_zone_index(note).map(|i| &self.zones[i])
+    }
+
+    /// 根据音符查找合适的键区索引
+    pub fn find_zone_index(&self, note: u8) -> Option<usize> {
         // 首先查找精确匹配的键区
-        for zone in &self.zones {
-            if zone.contains(note) {
-                return Some(zone);
-            }
+        if let Some(index) = self.zones.iter().position(|z| z.contains(note)) {
+            return Some(index);
         }
         // 如果没有精确匹配，返回最近的键区
-        if let Some(closest) = self.zones.iter().min_by_key(|z| {
+        (0..self.zones.len()).min_by_key(|&i| {
+            let z = &self.zones[i];
             let in_range = z.low_note <= note;
             if in_range {
                 z.high_note.saturating_sub(note)
             } else {
                 note.saturating_sub(z.low_note)
             }
-        }) {
-            return Some(closest);
+        })
+    }
+
+    /// Zones to play for a note, with their crossfade gains
+    ///
+    /// A note inside the overlap of two adjacent zones plays both. Across
+    /// the overlap the gain moves linearly from the lower zone to the upper
+    /// one, and the two gains always sum to 1.0. Elsewhere a single zone
+    /// plays at full gain.
+    pub fn crossfade_zones(&self, note: u8) -> Vec<(usize, f32)> {
+        let Some(index) = self.find_zone_index(note) else {
+            return Vec::new();
+        };
+        let lower = &self.zones[index];
+
+        match self.zones.get(index + 1) {
+            Some(upper) if lower.contains(note) && upper.contains(note) => {
+                let start = upper.low_note;
+                let end = lower.high_note.min(upper.high_note);
+                // Both zones stay audible at the overlap edges
+                let t = (note - start + 1) as f32 / (end - start + 2) as f32;
+                vec![(index, 1.0 - t), (index + 1, t)]
+            }
+            _ => vec![(index, 1.0)],
         }
-        None
     }
 
     /// 获取键区数量
@@ -1199,6 +2686,7 @@ impl MultiSampleInstrument {
                 if zone.sample.info.tempo_sensitivity > current.sample.info.tempo_sensitivity {
                     current.sample = zone.sample.clone();
                     current.root_note = zone.root_note;
+                    current.layers = zone.layers.clone();
                 }
             } else {
                 // 不重叠（zone.low_note > current.high_note），保存当前键区
@@ -1221,10 +2709,13 @@ pub struct MultiSampler {
     active_samplers: Vec<Sampler>,
 
     /// 当前触发音符
-    active_notes: HashMap<u8, usize>, // note -> sampler index
+    active_notes: HashMap<u8, Vec<usize>>, // note -> sampler indices
 
     /// 最大复音数
     max_polyphony: u8,
+
+    /// Next round-robin sample per (zone, velocity layer)
+    round_robin: HashMap<(usize, usize), usize>,
 }
 
 impl Default for MultiSampler {
@@ -1234,6 +2725,7 @@ impl Default for MultiSampler {
             active_samplers: Vec::new(),
             active_notes: HashMap::new(),
             max_polyphony: 16,
+            round_robin: HashMap::new(),
         }
     }
 }
@@ -1257,75 +2749,99 @@ impl MultiSampler {
     }
 
     /// 触发音符
+    ///
+    /// A note in the overlap of two adjacent zones starts a voice for each,
+    /// crossfaded by `MultiSampleInstrument::crossfade_zones`.
     pub fn note_on(&mut self, note: u8, velocity: u8) -> bool {
-        // 先处理复音限制（如果需要）
-        let zone_sample = {
-            let instrument = match &self.instrument {
-                Some(instr) => instr,
-                None => return false,
-            };
-
-            match instrument.find_zone(note) {
-                Some(z) => z.sample.clone(),
-                None => return false,
-            }
+        let zones = match &self.instrument {
+            Some(instr) => instr.crossfade_zones(note),
+            None => return false,
         };
+        if zones.is_empty() {
+            return false;
+        }
 
         // 检查复音限制 - 立即移除最早的采样器
         if self.active_samplers.len() >= self.max_polyphony as usize {
-            if let Some((&oldest_note, _)) = self.active_notes.iter().next() {
+            if let Some(&oldest_note) = self.active_notes.keys().next() {
                 // 找到对应的sampler并停止
-                if let Some(index) = self.active_notes.get(&oldest_note) {
-                    if *index < self.active_samplers.len() {
-                        self.active_samplers[*index].stop();
+                if let Some(indices) = self.active_notes.remove(&oldest_note) {
+                    for index in indices {
+                        if let Some(sampler) = self.active_samplers.get_mut(index) {
+                            sampler.stop();
+                        }
                     }
                 }
-                self.active_notes.remove(&oldest_note);
             }
         }
 
-        // 获取乐器参数（第二次借用）
-        let instrument = self.instrument.as_ref().unwrap();
-        let zone = instrument.find_zone(note).unwrap();
+        let indices = zones
+            .into_iter()
+            .map(|(zone_index, gain)| self.start_zone_voice(zone_index, note, velocity, gain))
+            .collect();
+        self.active_notes.insert(note, indices);
+
+        true
+    }
+
+    /// Start a sampler for one zone of a note
+    ///
+    /// # Returns
+    ///
+    /// Index of the new sampler in `active_samplers`
+    fn start_zone_voice(&mut self, zone_index: usize, note: u8, velocity: u8, gain: f32) -> usize {
+        let Some(instrument) = self.instrument.as_ref() else {
+            return self.active_samplers.len();
+        };
+        let zone = &instrument.zones[zone_index];
+
+        // Pick the velocity layer, then rotate through its round-robins
+        let zone_sample = match zone.layer_index(velocity) {
+            Some(layer_index) => {
+                let samples = &zone.layers[layer_index].samples;
+                let next = self
+                    .round_robin
+                    .entry((zone_index, layer_index))
+                    .or_insert(0);
+                let sample = samples[*next % samples.len()].clone();
+                *next = (*next + 1) % samples.len();
+                sample
+            }
+            None => zone.sample.clone(),
+        };
 
         // 创建新的采样播放器
         let mut sampler = Sampler::new();
         sampler.load(zone_sample);
 
-        // 设置参数
-        let pitch_offset = zone.root_note as i8 + instrument.global_pitch_offset;
-        sampler.set_pitch_offset(pitch_offset);
-        sampler.set_volume(velocity as f32 / 127.0);
+        // 设置参数 (global transpose only; the zone pitch goes into the speed)
+        sampler.set_pitch_offset(instrument.global_pitch_offset);
+        sampler.set_volume(velocity as f32 / 127.0 * gain);
         sampler.set_loop_mode(instrument.loop_mode);
 
         // 计算音高偏移以匹配目标音符
-        let note_offset = zone.note_offset(note);
-        sampler.set_speed(
-            2.0f32.powi(note_offset as i32)
-                / 2.0f32.powi((zone.root_note as i8 - note as i8) as i32),
-        );
+        sampler.set_speed(zone.pitch_ratio(note));
 
         // 开始播放
         sampler.play();
 
-        let sampler_index = self.active_samplers.len();
         self.active_samplers.push(sampler);
-        self.active_notes.insert(note, sampler_index);
-
-        true
+        self.active_samplers.len() - 1
     }
 
     /// 释放音符
     pub fn note_off(&mut self, note: u8) -> bool {
-        if let Some(sampler_index) = self.active_notes.remove(&note) {
-            if sampler_index < self.active_samplers.len() {
-                // 停止采样器（淡出）
-                let sampler = &mut self.active_samplers[sampler_index];
-                sampler.set_loop_mode(LoopMode::NoLoop);
-                return true;
+        let mut released = false;
+        if let Some(indices) = self.active_notes.remove(&note) {
+            for sampler_index in indices {
+                if let Some(sampler) = self.active_samplers.get_mut(sampler_index) {
+                    // 停止采样器（淡出）
+                    sampler.set_loop_mode(LoopMode::NoLoop);
+                    released = true;
+                }
             }
         }
-        false
+        released
     }
 
     /// 处理所有采样器的音频
@@ -1348,6 +2864,7 @@ impl MultiSampler {
     pub fn reset(&mut self) {
         self.active_samplers.clear();
         self.active_notes.clear();
+        self.round_robin.clear();
     }
 
     /// 检查是否有采样器在播放
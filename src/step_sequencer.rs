@@ -11,6 +11,9 @@
 
 #![allow(dead_code)] // Reserve sequencer fields for future MIDI sync features
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 /// Number of tracks in the sequencer
 pub const NUM_TRACKS: usize = 8;
 /// Number of steps per track
@@ -23,6 +26,20 @@ pub enum TrigCondition {
     Probability, // Probability trigger
     Mute,        // Muted
     Solo,        // Solo
+    Fill,        // Only while fill is active
+    NotFill,     // Only while fill is not active
+    First,       // Only on the first pass of the pattern
+    NotFirst,    // On every pass but the first
+    /// A:B - fires on pass `a` of every `b` passes (1:4 = 1st, 5th, 9th...)
+    Ratio {
+        a: u8,
+        b: u8,
+    },
+    /// Inverse of A:B - fires on every pass except pass `a` of every `b`
+    NotRatio {
+        a: u8,
+        b: u8,
+    },
 }
 
 /// A single step in the sequencer
@@ -44,6 +61,56 @@ pub struct Step {
     pub param_locks: ParamLocks,
     /// Swing offset for this step (-1.0 to 1.0)
     pub swing: f64,
+    /// Micro-timing offset as a fraction of a step (-1.0 to 1.0)
+    pub nudge: f64,
+}
+
+impl Step {
+    /// Set the micro-timing offset
+    ///
+    /// Negative values play the step early, positive values late, by a
+    /// fraction of a step (±1.0 = a whole step, so a nudged step may cross
+    /// its neighbours).
+    pub fn set_nudge(&mut self, amount: f32) {
+        self.nudge = amount.clamp(-1.0, 1.0) as f64;
+    }
+
+    /// Get the micro-timing offset
+    pub fn nudge(&self) -> f32 {
+        self.nudge as f32
+    }
+
+    /// Evaluate the trigger condition for one pass of the pattern
+    ///
+    /// `repeat_count` is the number of completed passes of the track's
+    /// pattern (0 on the first pass). `Solo` steps always pass here; the
+    /// sequencer gates them on the track's solo state.
+    ///
+    /// # Arguments
+    ///
+    /// * `repeat_count` - Pattern pass counter, starting at 0
+    /// * `fill_active` - Whether fill mode is held
+    /// * `rng` - Random source for probability trigs
+    pub fn should_trigger(&self, repeat_count: u32, fill_active: bool, rng: &mut impl Rng) -> bool {
+        match self.condition {
+            TrigCondition::Normal | TrigCondition::Solo => true,
+            TrigCondition::Probability => rng.gen::<f64>() < self.probability,
+            TrigCondition::Mute => false,
+            TrigCondition::Fill => fill_active,
+            TrigCondition::NotFill => !fill_active,
+            TrigCondition::First => repeat_count == 0,
+            TrigCondition::NotFirst => repeat_count > 0,
+            TrigCondition::Ratio { a, b } => Self::ratio_matches(repeat_count, a, b),
+            TrigCondition::NotRatio { a, b } => !Self::ratio_matches(repeat_count, a, b),
+        }
+    }
+
+    /// Whether pass `repeat_count` is pass `a` of every `b`
+    fn ratio_matches(repeat_count: u32, a: u8, b: u8) -> bool {
+        let b = b.max(1) as u32;
+        let a = (a as u32).clamp(1, b);
+        repeat_count % b == a - 1
+    }
 }
 
 impl Default for Step {
@@ -57,6 +124,7 @@ impl Default for Step {
             condition: TrigCondition::Normal,
             param_locks: ParamLocks::default(),
             swing: 0.0,
+            nudge: 0.0,
         }
     }
 }
@@ -89,6 +157,8 @@ pub struct ParamLocks {
     pub reverb_send: Option<f64>,
     pub delay_send: Option<f64>,
     pub distortion_amount: Option<f64>,
+    /// Slide the continuous locks into the next step's values
+    pub slide: bool,
 }
 
 impl ParamLocks {
@@ -109,6 +179,35 @@ impl ParamLocks {
             && self.distortion_amount.is_none()
     }
 
+    /// Interpolate the continuous locks towards `next`
+    ///
+    /// `t` runs from 0.0 (this step) to 1.0 (the next step). A lock that
+    /// `next` doesn't set holds its value; discrete locks (waveform, pitch)
+    /// never slide.
+    pub fn slide_to(&self, next: &ParamLocks, t: f64) -> ParamLocks {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |from: Option<f64>, to: Option<f64>| match (from, to) {
+            (Some(from), Some(to)) => Some(from + (to - from) * t),
+            (from, _) => from,
+        };
+        ParamLocks {
+            filter_cutoff: lerp(self.filter_cutoff, next.filter_cutoff),
+            filter_resonance: lerp(self.filter_resonance, next.filter_resonance),
+            waveform: self.waveform,
+            pitch_offset: self.pitch_offset,
+            lfo_rate: lerp(self.lfo_rate, next.lfo_rate),
+            lfo_depth: lerp(self.lfo_depth, next.lfo_depth),
+            attack: lerp(self.attack, next.attack),
+            decay: lerp(self.decay, next.decay),
+            sustain: lerp(self.sustain, next.sustain),
+            release: lerp(self.release, next.release),
+            reverb_send: lerp(self.reverb_send, next.reverb_send),
+            delay_send: lerp(self.delay_send, next.delay_send),
+            distortion_amount: lerp(self.distortion_amount, next.distortion_amount),
+            slide: self.slide,
+        }
+    }
+
     /// Clear all parameter locks
     pub fn clear(&mut self) {
         self.filter_cutoff = None;
@@ -124,6 +223,7 @@ impl ParamLocks {
         self.reverb_send = None;
         self.delay_send = None;
         self.distortion_amount = None;
+        self.slide = false;
     }
 }
 
@@ -309,12 +409,18 @@ pub struct StepSequencer {
     pub record_track: Option<usize>,
     /// Swing enabled
     pub swing_enabled: bool,
-    /// Master swing amount (0.0 - 1.0)
+    /// Master swing amount (0.0 - 1.0, where 0.5 is no swing)
     pub swing_amount: f64,
     /// Swing interval (every N steps)
     pub swing_interval: usize,
     /// Random state for deterministic generation
     random_state: u64,
+    /// Random source for probability trigs
+    rng: StdRng,
+    /// Fill mode held (for `Fill`/`NotFill` trigs)
+    fill_active: bool,
+    /// Transport position in 16th-note steps (advanced by `tick`)
+    step_position: f64,
 }
 
 impl Default for StepSequencer {
@@ -335,6 +441,9 @@ impl Default for StepSequencer {
             swing_amount: 0.5,
             swing_interval: 2,
             random_state: 12345,
+            rng: StdRng::seed_from_u64(12345),
+            fill_active: false,
+            step_position: 0.0,
         }
     }
 }
@@ -390,6 +499,7 @@ impl StepSequencer {
     pub fn stop(&mut self) {
         self.playing = false;
         self.beat_position = 0.0;
+        self.step_position = 0.0;
         for track in &mut self.tracks {
             track.reset();
         }
@@ -407,11 +517,45 @@ impl StepSequencer {
     /// Reset to the beginning
     pub fn reset(&mut self) {
         self.beat_position = 0.0;
+        self.step_position = 0.0;
         for track in &mut self.tracks {
             track.reset();
         }
     }
 
+    /// Set the master swing
+    ///
+    /// Delays every off-beat 16th (the 2nd, 4th, ... step) by `amount` of a
+    /// step; 0.0 is straight. Adds to each track's own swing and to step
+    /// nudges.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Delay as a fraction of the step time (0.0 - 0.75)
+    pub fn set_swing(&mut self, amount: f32) {
+        self.swing_amount = 0.5 + amount.clamp(0.0, 0.75) as f64 * 0.5;
+    }
+
+    /// Get the master swing as a fraction of the step time
+    pub fn swing(&self) -> f32 {
+        ((self.swing_amount - 0.5) * 2.0) as f32
+    }
+
+    /// Hold or release fill mode
+    pub fn set_fill(&mut self, active: bool) {
+        self.fill_active = active;
+    }
+
+    /// Whether fill mode is held
+    pub fn fill_active(&self) -> bool {
+        self.fill_active
+    }
+
+    /// Reseed the random source used by probability trigs
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
     /// Set BPM
     pub fn set_bpm(&mut self, bpm: f64) {
         self.bpm = bpm.clamp(20.0, 300.0);
@@ -428,7 +572,7 @@ impl StepSequencer {
             .random_state
             .wrapping_mul(6364136223846793005)
             .wrapping_add(1);
-        ((self.random_state >> 33) as u32) as f64 / u32::MAX as f64
+        ((self.random_state >> 32) as u32) as f64 / u32::MAX as f64
     }
 
     /// Process one audio sample
@@ -443,17 +587,12 @@ impl StepSequencer {
 
         self.beat_position += 1.0 / samples_per_step;
 
-        // Pre-calculate random values for this buffer
-        let mut rng_values: Vec<f64> = Vec::with_capacity(self.tracks.len());
-        for _ in 0..self.tracks.len() {
-            rng_values.push(self.random());
-        }
-
         let mut triggers = Vec::new();
 
         for (track_idx, track) in self.tracks.iter_mut().enumerate() {
             let current_beat = self.beat_position * 4.0; // Convert to 16th notes
             let step_float = current_beat.floor() as usize % track.length;
+            let repeat_count = (current_beat.floor() as usize / track.length) as u32;
 
             if step_float != track.current_step {
                 track.current_step = step_float;
@@ -464,10 +603,8 @@ impl StepSequencer {
                     false
                 } else {
                     match step.condition {
-                        TrigCondition::Normal => true,
-                        TrigCondition::Probability => rng_values[track_idx] < step.probability,
-                        TrigCondition::Mute => false,
                         TrigCondition::Solo => false,
+                        _ => step.should_trigger(repeat_count, self.fill_active, &mut self.rng),
                     }
                 };
 
@@ -480,6 +617,138 @@ impl StepSequencer {
         triggers
     }
 
+    /// Advance the transport by one audio block
+    ///
+    /// Returns the trigs that fall inside the next `frames` samples as
+    /// (track index, step, sample offset within the block). The returned
+    /// step carries its note (scale-quantized if the track has a scale),
+    /// velocity, gate length and parameter locks.
+    ///
+    /// Each track loops over its own length, so a block may span several
+    /// pattern loop points. Swing delays the off-beat of every
+    /// `swing_interval` steps, each step's `swing` nudges it by up to half
+    /// a step, each step's `nudge` shifts it by up to a whole step (never
+    /// before the transport start), and each step's `TrigCondition` is evaluated with the
+    /// track's pattern pass count (see [`Step::should_trigger`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - Block length in samples
+    /// * `bpm` - Tempo in beats per minute (a step is a 16th note)
+    /// * `sample_rate` - Sample rate in Hz
+    pub fn tick(&mut self, frames: usize, bpm: f32, sample_rate: f32) -> Vec<(usize, Step, u64)> {
+        if !self.playing || frames == 0 || bpm <= 0.0 || sample_rate <= 0.0 {
+            return Vec::new();
+        }
+
+        let samples_per_step = 60.0 / bpm as f64 / 4.0 * sample_rate as f64;
+        let block_start = self.step_position;
+        let block_end = block_start + frames as f64 / samples_per_step;
+        self.step_position = block_end;
+        self.beat_position = block_end / 4.0;
+
+        let active = self.active_tracks();
+        let mut events = Vec::new();
+
+        for track_idx in active {
+            // Trig times may be shifted up to three steps by swing and nudge
+            let first = (block_start.floor() as i64 - 3).max(0);
+            let last = block_end.ceil() as i64 + 3;
+
+            for n in first..=last {
+                let n = n as usize;
+                // First sample at or after the trig time (the epsilon keeps
+                // accumulated rounding from firing a trig in two blocks)
+                let time = (n as f64 + self.trig_delay(track_idx, n)).max(0.0);
+                let offset = ((time - block_start) * samples_per_step - 1e-6).ceil();
+                if offset < 0.0 || offset >= frames as f64 {
+                    continue;
+                }
+
+                let track = &self.tracks[track_idx];
+                let step_idx = n % track.length;
+                let step = &track.steps[step_idx];
+                if !step.active {
+                    continue;
+                }
+
+                let fires = match step.condition {
+                    TrigCondition::Solo => track.solo,
+                    _ => {
+                        let repeat_count = (n / track.length) as u32;
+                        step.should_trigger(repeat_count, self.fill_active, &mut self.rng)
+                    }
+                };
+
+                let track = &mut self.tracks[track_idx];
+                track.current_step = step_idx;
+                if !fires {
+                    continue;
+                }
+
+                let mut step = track.steps[step_idx].clone();
+                if let Some(scale) = track.scale_quantization {
+                    step.note = quantize_to_scale(step.note, 0, scale);
+                }
+                events.push((track_idx, step, offset as u64));
+            }
+        }
+
+        events.sort_by_key(|&(track_idx, _, offset)| (offset, track_idx));
+        events
+    }
+
+    /// Parameter locks of a track at the current transport position
+    ///
+    /// Returns the locks of the step the transport is in. If that step's
+    /// locks slide, each continuous lock moves towards the next step's value
+    /// over the step duration; the last step slides into the first (the
+    /// loop point). Steps without a trig carry no locks.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_idx` - Track index
+    ///
+    /// # Returns
+    ///
+    /// The (possibly interpolated) locks, or None for an invalid track
+    pub fn param_locks_at(&self, track_idx: usize) -> Option<ParamLocks> {
+        let track = self.tracks.get(track_idx)?;
+        let position = self.step_position.max(0.0);
+        let index = position.floor() as usize % track.length;
+        let step = &track.steps[index];
+        if !step.active {
+            return Some(ParamLocks::default());
+        }
+        if !step.param_locks.slide {
+            return Some(step.param_locks.clone());
+        }
+
+        let next = &track.steps[(index + 1) % track.length];
+        let next_locks = if next.active {
+            next.param_locks.clone()
+        } else {
+            ParamLocks::default()
+        };
+        Some(step.param_locks.slide_to(&next_locks, position.fract()))
+    }
+
+    /// Timing offset of global step `n` on a track, in steps
+    fn trig_delay(&self, track_idx: usize, n: usize) -> f64 {
+        let track = &self.tracks[track_idx];
+        let interval = track.swing_interval.max(2);
+
+        let mut delay = 0.0;
+        if self.swing_enabled && n % interval == interval / 2 {
+            // 0.5 is straight; the master amount adds to the track's
+            let swing = (track.swing + self.swing_amount - 0.5).clamp(0.0, 0.9);
+            delay += (2.0 * swing - 1.0) * (interval / 2) as f64;
+        }
+        let step = &track.steps[n % track.length];
+        delay += step.swing.clamp(-1.0, 1.0) * 0.5 + step.nudge.clamp(-1.0, 1.0);
+        delay.clamp(-3.0, 3.0)
+    }
+
     /// Get the next note to play
     /// Returns (note, velocity, gate_length, track_index) or None
     pub fn get_next_note(&mut self, sample_rate: f64) -> Option<(u8, u8, f64, usize, ParamLocks)> {
@@ -941,6 +1210,349 @@ mod tests {
         assert_eq!(seq.bpm, 300.0, "BPM should clamp to maximum 300");
     }
 
+    /// Runs one bar at 120 BPM in 512-sample blocks
+    /// and returns (track, step index, absolute sample) per trig.
+    fn run_bar(seq: &mut StepSequencer) -> Vec<(usize, usize, u64)> {
+        let bar = 88200;
+        let mut trigs = Vec::new();
+        let mut clock = 0;
+        while clock < bar {
+            let frames = 512.min(bar - clock);
+            for (track, step, offset) in seq.tick(frames, 120.0, 44100.0) {
+                let index = seq.tracks[track]
+                    .steps
+                    .iter()
+                    .position(|s| s.note == step.note)
+                    .unwrap();
+                trigs.push((track, index, clock as u64 + offset));
+            }
+            clock += frames;
+        }
+        trigs
+    }
+
+    #[test]
+    fn test_tick_one_bar_fires_expected_trigs() {
+        let mut seq = StepSequencer::new();
+        for step in 0..NUM_STEPS {
+            seq.tracks[0].steps[step].note = step as u8;
+        }
+        seq.generate_drum_pattern(1, DrumStyle::FourOnTheFloor);
+        for (i, step) in [0, 4, 8, 12].into_iter().enumerate() {
+            seq.tracks[0].steps[step].active = true;
+            seq.tracks[1].steps[step].note = 36 + i as u8;
+        }
+        seq.play();
+
+        let trigs = run_bar(&mut seq);
+        assert_eq!(trigs.len(), 8);
+        // 120 BPM: a 16th note is 5512.5 samples
+        let track0: Vec<_> = trigs.iter().filter(|t| t.0 == 0).collect();
+        for (&&(_, step, sample), expected) in track0.iter().zip([0, 4, 8, 12]) {
+            assert_eq!(step, expected);
+            assert_eq!(sample, (expected as f64 * 5512.5).ceil() as u64);
+        }
+
+        // The pattern loops into the next bar
+        assert_eq!(run_bar(&mut seq).len(), 8);
+    }
+
+    #[test]
+    fn test_tick_short_track_spans_loop_point() {
+        let mut seq = StepSequencer::new();
+        seq.tracks[0].set_length(3);
+        seq.tracks[0].steps[0].active = true;
+        seq.play();
+
+        // One block covering the whole bar: steps 0, 3, 6, 9, 12, 15
+        let trigs = seq.tick(88200, 120.0, 44100.0);
+        let offsets: Vec<u64> = trigs.iter().map(|t| t.2).collect();
+        let expected: Vec<u64> = [0, 3, 6, 9, 12, 15]
+            .iter()
+            .map(|&n| (n as f64 * 5512.5).ceil() as u64)
+            .collect();
+        assert_eq!(offsets, expected);
+        assert_eq!(trigs[0].1.param_locks.filter_cutoff, None);
+    }
+
+    #[test]
+    fn test_tick_swing_delays_off_beats() {
+        let mut seq = StepSequencer::new();
+        for step in 0..2 {
+            seq.tracks[0].steps[step].active = true;
+            seq.tracks[0].steps[step].note = step as u8;
+        }
+        seq.tracks[0].steps[1].param_locks.filter_cutoff = Some(0.25);
+        seq.tracks[0].swing = 0.75;
+        seq.play();
+
+        let trigs = run_bar(&mut seq);
+        assert_eq!(trigs[0].2, 0);
+        // 75% swing pushes the second 16th half a step late
+        assert_eq!(trigs[1].2, (1.5 * 5512.5f64).ceil() as u64);
+
+        let trigs = StepSequencer::new().tick(512, 120.0, 44100.0);
+        assert!(trigs.is_empty());
+    }
+
+    #[test]
+    fn test_tick_probability_and_locks() {
+        let mut seq = StepSequencer::new();
+        for step in &mut seq.tracks[0].steps {
+            step.active = true;
+            step.condition = TrigCondition::Probability;
+            step.probability = 0.5;
+            step.param_locks.filter_cutoff = Some(0.3);
+        }
+        seq.play();
+
+        let mut fired = 0;
+        for _ in 0..20 {
+            for (_, step, _) in seq.tick(88200, 120.0, 44100.0) {
+                assert_eq!(step.param_locks.filter_cutoff, Some(0.3));
+                fired += 1;
+            }
+        }
+        // 320 steps at 50%
+        assert!(fired > 120 && fired < 200, "fired {fired}");
+    }
+
+    #[test]
+    fn test_trig_condition_ratio_fires_every_fourth_repeat() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let step = Step {
+            active: true,
+            condition: TrigCondition::Ratio { a: 1, b: 4 },
+            ..Step::default()
+        };
+        let fired: Vec<u32> = (0..12)
+            .filter(|&repeat| step.should_trigger(repeat, false, &mut rng))
+            .collect();
+        assert_eq!(fired, vec![0, 4, 8]);
+
+        let step = Step {
+            condition: TrigCondition::NotRatio { a: 2, b: 2 },
+            ..step
+        };
+        assert!(step.should_trigger(0, false, &mut rng));
+        assert!(!step.should_trigger(1, false, &mut rng));
+    }
+
+    #[test]
+    fn test_trig_condition_fill_and_first() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let with = |condition| Step {
+            condition,
+            ..Step::default()
+        };
+
+        assert!(with(TrigCondition::Fill).should_trigger(3, true, &mut rng));
+        assert!(!with(TrigCondition::Fill).should_trigger(3, false, &mut rng));
+        assert!(with(TrigCondition::NotFill).should_trigger(3, false, &mut rng));
+        assert!(with(TrigCondition::First).should_trigger(0, false, &mut rng));
+        assert!(!with(TrigCondition::First).should_trigger(1, false, &mut rng));
+        assert!(with(TrigCondition::NotFirst).should_trigger(1, false, &mut rng));
+        assert!(!with(TrigCondition::Mute).should_trigger(0, false, &mut rng));
+    }
+
+    #[test]
+    fn test_trig_condition_probability_is_seeded() {
+        let step = Step {
+            condition: TrigCondition::Probability,
+            probability: 0.3,
+            ..Step::default()
+        };
+        let run = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..1000)
+                .map(|_| step.should_trigger(0, false, &mut rng))
+                .collect::<Vec<bool>>()
+        };
+        assert_eq!(run(7), run(7));
+        let fired = run(7).iter().filter(|&&f| f).count();
+        assert!(fired > 250 && fired < 350, "fired {fired}");
+    }
+
+    #[test]
+    fn test_tick_ratio_tracks_pattern_repeats() {
+        let mut seq = StepSequencer::new();
+        seq.tracks[0].set_length(4);
+        seq.tracks[0].steps[0].active = true;
+        seq.tracks[0].steps[0].condition = TrigCondition::Ratio { a: 1, b: 4 };
+        seq.tracks[1].steps[0].active = true;
+        seq.tracks[1].steps[0].condition = TrigCondition::Fill;
+        seq.play();
+
+        // 16 passes of the 4-step track over four bars
+        let mut fired = Vec::new();
+        for bar in 0..4 {
+            for (track, _, offset) in seq.tick(88200, 120.0, 44100.0) {
+                assert_eq!(track, 0);
+                fired.push(bar * 88200 + offset);
+            }
+        }
+        // Every fourth pass, i.e. once per bar
+        assert_eq!(fired, vec![0, 88200, 176400, 264600]);
+
+        seq.set_fill(true);
+        let trigs = seq.tick(88200, 120.0, 44100.0);
+        assert!(trigs.iter().any(|t| t.0 == 1));
+    }
+
+    #[test]
+    fn test_tick_nudge_shifts_event_offset() {
+        let mut seq = StepSequencer::new();
+        seq.tracks[0].steps[4].active = true;
+        seq.tracks[0].steps[4].set_nudge(0.25);
+        seq.play();
+
+        let trigs = seq.tick(88200, 120.0, 44100.0);
+        // A quarter of a 5512.5-sample step
+        assert_eq!(trigs[0].2, (4.25f64 * 5512.5).ceil() as u64);
+        assert_eq!(trigs[0].2 - 22050, 1379);
+
+        seq.tracks[0].steps[4].set_nudge(5.0);
+        assert_eq!(seq.tracks[0].steps[4].nudge(), 1.0);
+    }
+
+    #[test]
+    fn test_tick_nudge_crosses_neighbours_and_pattern_start() {
+        let mut seq = StepSequencer::new();
+        for (step, nudge) in [(0, -0.5), (4, 0.6), (5, -0.6)] {
+            seq.tracks[0].steps[step].active = true;
+            seq.tracks[0].steps[step].note = step as u8;
+            seq.tracks[0].steps[step].set_nudge(nudge);
+        }
+        seq.play();
+
+        // Step 5 pulled ahead of step 4; step 0 clamps to the start
+        let notes: Vec<(u8, u64)> = seq
+            .tick(88200, 120.0, 44100.0)
+            .into_iter()
+            .map(|(_, step, offset)| (step.note, offset))
+            .collect();
+        assert_eq!(notes[0], (0, 0));
+        assert_eq!(notes[1].0, 5);
+        assert_eq!(notes[2].0, 4);
+        // The second pass of step 0 lands half a step before the bar line
+        assert_eq!(notes[3], (0, (15.5f64 * 5512.5).ceil() as u64));
+        assert_eq!(notes.len(), 4);
+    }
+
+    #[test]
+    fn test_param_lock_slide_interpolates() {
+        let mut seq = StepSequencer::new();
+        seq.tracks[0].steps[0].active = true;
+        seq.tracks[0].steps[0].param_locks.filter_cutoff = Some(0.2);
+        seq.tracks[0].steps[0].param_locks.slide = true;
+        seq.tracks[0].steps[1].active = true;
+        seq.tracks[0].steps[1].param_locks.filter_cutoff = Some(0.8);
+        seq.play();
+
+        // Walk through step 0 in small blocks
+        let mut values = Vec::new();
+        for _ in 0..10 {
+            seq.tick(512, 120.0, 44100.0);
+            values.push(seq.param_locks_at(0).unwrap().filter_cutoff.unwrap());
+        }
+        assert!(values.windows(2).all(|w| w[1] > w[0]), "{values:?}");
+        assert!(values.iter().all(|&v| v > 0.2 && v < 0.8));
+        // 5120 of 5512.5 samples in
+        assert!((values[9] - (0.2 + 0.6 * 5120.0 / 5512.5)).abs() < 1e-6);
+
+        // Step 1 doesn't slide: it holds its own value
+        seq.tick(1024, 120.0, 44100.0);
+        assert_eq!(seq.param_locks_at(0).unwrap().filter_cutoff, Some(0.8));
+    }
+
+    #[test]
+    fn test_param_lock_slide_holds_and_wraps() {
+        let mut seq = StepSequencer::new();
+        seq.tracks[0].set_length(2);
+        seq.tracks[0].steps[0].active = true;
+        seq.tracks[0].steps[0].param_locks.filter_cutoff = Some(0.5);
+        seq.tracks[0].steps[0].param_locks.filter_resonance = Some(0.1);
+        seq.tracks[0].steps[1].active = true;
+        seq.tracks[0].steps[1].param_locks.filter_cutoff = Some(1.0);
+        seq.tracks[0].steps[1].param_locks.slide = true;
+        seq.play();
+
+        // Half way through step 0 (no slide)
+        seq.tick(2756, 120.0, 44100.0);
+        assert_eq!(seq.param_locks_at(0).unwrap().filter_cutoff, Some(0.5));
+
+        // Half way through step 1: sliding back towards the first step
+        seq.tick(5512, 120.0, 44100.0);
+        let locks = seq.param_locks_at(0).unwrap();
+        assert!((locks.filter_cutoff.unwrap() - 0.75).abs() < 1e-3);
+        // Step 1 has no resonance lock to slide from
+        assert_eq!(locks.filter_resonance, None);
+
+        // A slide into a step without the lock holds
+        let held = seq.tracks[0].steps[0]
+            .param_locks
+            .slide_to(&ParamLocks::default(), 0.5);
+        assert_eq!(held.filter_cutoff, Some(0.5));
+        assert!(seq.param_locks_at(NUM_TRACKS).is_none());
+    }
+
+    #[test]
+    fn test_set_swing_delays_off_beats() {
+        let mut seq = StepSequencer::new();
+        for step in 0..4 {
+            seq.tracks[0].steps[step].active = true;
+            seq.tracks[0].steps[step].note = step as u8;
+        }
+        seq.set_swing(0.5);
+        assert_eq!(seq.swing(), 0.5);
+        seq.play();
+
+        let offsets: Vec<u64> = seq
+            .tick(22050, 120.0, 44100.0)
+            .iter()
+            .map(|t| t.2)
+            .collect();
+        // Off-beats late by half of a 5512.5-sample step
+        assert_eq!(offsets, vec![0, 8269, 11025, 19294]);
+
+        // Nudge adds to the swing delay
+        seq.reset();
+        seq.tracks[0].steps[1].set_nudge(-0.25);
+        let trigs = seq.tick(22050, 120.0, 44100.0);
+        assert_eq!(trigs[1].2, (1.25f64 * 5512.5).ceil() as u64);
+    }
+
+    #[test]
+    fn test_set_swing_zero_is_straight() {
+        let mut seq = StepSequencer::new();
+        for step in &mut seq.tracks[0].steps {
+            step.active = true;
+        }
+        seq.set_swing(0.0);
+        seq.play();
+
+        let trigs = seq.tick(88200, 120.0, 44100.0);
+        for (n, trig) in trigs.iter().enumerate() {
+            assert_eq!(trig.2, (n as f64 * 5512.5).ceil() as u64);
+        }
+        seq.set_swing(2.0);
+        assert_eq!(seq.swing(), 0.75);
+    }
+
+    #[test]
+    fn test_random_covers_unit_range() {
+        let mut seq = StepSequencer::new();
+        let values: Vec<f64> = (0..1000).map(|_| seq.random()).collect();
+        assert!(values.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert!(values.iter().any(|&v| v > 0.9));
+
+        // randomize() activates steps with a coin flip
+        seq.randomize();
+        let steps = seq.tracks.iter().flat_map(|t| &t.steps);
+        let active = steps.filter(|s| s.active).count();
+        assert!(active > 0);
+    }
+
     #[test]
     fn test_not_playing_returns_no_triggers() {
         let mut seq = StepSequencer::new();
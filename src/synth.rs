@@ -6,73 +6,488 @@
 
 #![allow(dead_code)] // Reserve VA parameters for future automation features
 
-use crate::effects::{Effect, EffectProcessor, EffectType, Saturation};
+use crate::automation::AutomationLane;
+use crate::effects::{Effect, EffectProcessor, EffectType, Limiter, RingModulator, Saturation};
 use crate::envelope::{AdsrEnvelope, EnvelopeConfig};
-use crate::filter::{Filter, FilterType, ZdfFilter, ZdfFilterConfig, ZdfFilterMode};
+use crate::filter::{
+    Filter, FilterType, ZdfFilter, ZdfFilterConfig, ZdfFilterMode, ZDF_MAX_RESONANCE,
+};
 use crate::lfo::{Lfo, LfoConfig, LfoRate};
 use crate::oscillator::{
-    midi_to_frequency, Oscillator, OscillatorConfig, OversampleFactor, Waveform,
+    midi_to_frequency, Oscillator, OscillatorConfig, OscillatorType, OversampleFactor, Waveform,
 };
 use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::fmt;
+
+/// Upper limit for `Synth::set_max_voices`.
+pub const MAX_VOICES: usize = 64;
+
+/// Default number of simultaneous voices (polyphony).
+pub const DEFAULT_MAX_VOICES: usize = 16;
+
+/// Number of oscillators (FM operators) per voice.
+pub const OSCILLATORS_PER_VOICE: usize = 2;
+
+/// Maximum FM modulation index in radians.
+pub const MAX_FM_INDEX: f32 = 10.0;
+
+/// Number of note events `Synth::render_block` can queue between blocks.
+pub const EVENT_QUEUE_CAPACITY: usize = 256;
+
+/// Number of distinct MIDI notes (sizes the note tracking so it never grows).
+const MIDI_NOTE_COUNT: usize = 128;
+
+/// Maximum portamento glide time in seconds.
+pub const MAX_GLIDE_TIME: f32 = 10.0;
+
+/// Semitones from middle C at which key pan reaches a hard side (spread 1.0).
+const KEY_PAN_RANGE: f32 = 48.0;
+
+/// Which voice to take over when a note arrives and every voice is in use.
+///
+/// Voices that are already releasing are always stolen first (the quietest
+/// of them); the policy decides between voices whose notes are still held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VoiceStealPolicy {
+    /// Steal the voice that was triggered longest ago
+    #[default]
+    Oldest,
+
+    /// Steal the voice with the lowest current output level
+    Quietest,
+
+    /// Steal the voice played with the lowest velocity (ties: oldest)
+    LowestPriority,
+}
+
+/// A/B comparison slot for flipping between two sound variations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbSlot {
+    /// Slot A
+    A,
+
+    /// Slot B
+    B,
+}
+
+impl AbSlot {
+    /// Gets the other slot.
+    pub fn other(self) -> Self {
+        match self {
+            AbSlot::A => AbSlot::B,
+            AbSlot::B => AbSlot::A,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Sound parameters captured by an A/B slot.
+#[derive(Debug, Clone)]
+struct SoundSnapshot {
+    /// Voice filter settings (with cleared audio state)
+    voice_filter: VoiceFilter,
+    /// Saturation/effect settings (with cleared audio state)
+    chain: OutputChain,
+    zdf_enabled: bool,
+    lfos: Vec<Lfo>,
+    key_pan_spread: f32,
+    master_volume: f32,
+    oversample_factor: OversampleFactor,
+    oscillator_waveforms: [Waveform; OSCILLATORS_PER_VOICE],
+    oscillator_levels: [f32; OSCILLATORS_PER_VOICE],
+    fm: Option<FmRouting>,
+    mono_mode: bool,
+    glide_time: f32,
+    envelope_hold: f32,
+    max_voices: usize,
+    steal_policy: VoiceStealPolicy,
+}
+
+/// Default ramp time of smoothed parameters in milliseconds.
+pub const DEFAULT_PARAM_SMOOTHING_MS: f32 = 5.0;
+
+/// Upper limit for `Synth::set_param_smoothing` in milliseconds.
+pub const MAX_PARAM_SMOOTHING_MS: f32 = 1000.0;
+
+/// Continuous parameters that ramp instead of jumping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SmoothedParam {
+    MasterVolume,
+    ZdfCutoff,
+    ZdfResonance,
+    ZdfDrive,
+    SaturationDrive,
+    SaturationMix,
+}
+
+impl SmoothedParam {
+    /// Every smoothed parameter, in index order.
+    const ALL: [SmoothedParam; 6] = [
+        SmoothedParam::MasterVolume,
+        SmoothedParam::ZdfCutoff,
+        SmoothedParam::ZdfResonance,
+        SmoothedParam::ZdfDrive,
+        SmoothedParam::SaturationDrive,
+        SmoothedParam::SaturationMix,
+    ];
+}
 
-/// Maximum number of simultaneous voices (polyphony).
-const MAX_VOICES: usize = 16;
+/// Linear ramp of one parameter that lands exactly on its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ParamRamp {
+    /// Value at the current sample
+    current: f32,
+
+    /// Value the ramp ends on
+    target: f32,
+
+    /// Change per sample
+    step: f32,
+
+    /// Samples until the target is reached
+    remaining: u32,
+}
+
+impl ParamRamp {
+    /// Creates a settled ramp.
+    fn new(value: f32) -> Self {
+        Self {
+            current: value,
+            target: value,
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+
+    /// Starts ramping from `from` to `target` over `samples` samples.
+    fn start(&mut self, from: f32, target: f32, samples: u32) {
+        self.current = if samples == 0 { target } else { from };
+        self.target = target;
+        self.remaining = samples;
+        self.step = if samples == 0 {
+            0.0
+        } else {
+            (target - from) / samples as f32
+        };
+    }
+
+    /// Advances one sample and returns the new value.
+    fn next(&mut self) -> f32 {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            // Land on the target itself rather than accumulated steps
+            self.current = if self.remaining == 0 {
+                self.target
+            } else {
+                self.current + self.step
+            };
+        }
+        self.current
+    }
+
+    /// Checks if the ramp has reached its target.
+    fn is_settled(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Note event waiting for the next `Synth::render_block` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueuedEvent {
+    /// Note on with (note, velocity)
+    NoteOn(u8, u8),
+
+    /// Note off for a specific note
+    NoteOff(u8),
+}
+
+/// Two-operator FM routing between voice oscillators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FmRouting {
+    /// Oscillator whose phase is modulated
+    carrier: usize,
+
+    /// Oscillator providing the modulation (may equal `carrier` for feedback)
+    modulator: usize,
+
+    /// Modulation index in radians of peak phase deviation
+    index: f32,
+}
 
 /// Virtual Analog (VA) parameter IDs for control and automation.
 ///
 /// These constants define the parameter IDs used for controlling
-/// the Virtual Analog features of the synthesizer.
+/// the Virtual Analog features of the synthesizer through
+/// `Synth::set_parameter` and `Synth::get_parameter`. Values are in the
+/// parameter's own unit; out-of-range values are clamped.
+///
+/// ZDF filter on/off switch (normalized: 0.0 = off, 1.0 = on, on from 0.5)
 pub const PARAM_ZDF_ENABLED: i32 = 50;
+/// ZDF filter cutoff in Hz (20.0 to 20000.0, not normalized)
 pub const PARAM_ZDF_CUTOFF: i32 = 51;
+/// ZDF filter resonance (0.0 to 4.5, self-oscillates from 4.0; not normalized)
 pub const PARAM_ZDF_RES: i32 = 52;
+/// ZDF filter drive (0.0 to 10.0, not normalized)
 pub const PARAM_ZDF_DRIVE: i32 = 53;
+/// Saturation drive (0.0 to 10.0, not normalized)
 pub const PARAM_SATURATION_DRIVE: i32 = 54;
+/// Saturation wet/dry mix (normalized: 0.0 = dry, 1.0 = wet)
 pub const PARAM_SATURATION_MIX: i32 = 55;
+/// Oscillator oversampling factor (1.0, 2.0, 4.0 or 8.0, rounded down to one of them)
 pub const PARAM_OVERSAMPLE: i32 = 56;
+/// ZDF filter keyboard tracking (0.0 to 2.0, 1.0 = cutoff follows pitch; not normalized)
+pub const PARAM_ZDF_KEYTRACK: i32 = 57;
 
 /// AI Melody Generation parameter IDs.
 ///
 /// These constants define the parameter IDs used for controlling
-/// the AI melody generation features.
+/// the AI melody generation features. They belong to the melody
+/// generator, so `Synth::set_parameter` rejects them.
 pub const PARAM_MELODY_STYLE: i32 = 60;
 pub const PARAM_MELODY_GENERATE: i32 = 61;
 pub const PARAM_MELODY_LENGTH: i32 = 62;
 
+/// Metadata of one synth parameter, for building UIs and validating
+/// automation.
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterDescriptor {
+    /// Parameter ID (`PARAM_*`)
+    pub id: i32,
+
+    /// Display name
+    pub name: &'static str,
+
+    /// Unit label (empty for unitless values)
+    pub unit: &'static str,
+
+    /// Lowest value
+    pub min: f32,
+
+    /// Highest value
+    pub max: f32,
+
+    /// Value on a new synth
+    pub default: f32,
+
+    /// Whether a control should use a logarithmic scale
+    pub logarithmic: bool,
+
+    /// Whether the value switches between steps (toggle or selector)
+    pub stepped: bool,
+
+    /// Writes a clamped value to the synth
+    set: fn(&mut Synth, f32),
+
+    /// Reads the value from the synth
+    get: fn(&Synth) -> f32,
+
+    /// Ramp of smoothed parameters
+    smoothed: Option<SmoothedParam>,
+}
+
+/// Every parameter `Synth::set_parameter` accepts.
+///
+/// Setting, reading and automating parameters all dispatch through this
+/// table, so a parameter only has to be added here.
+static PARAMETER_DESCRIPTORS: [ParameterDescriptor; 8] = [
+    ParameterDescriptor {
+        id: PARAM_ZDF_ENABLED,
+        name: "ZDF Filter",
+        unit: "",
+        min: 0.0,
+        max: 1.0,
+        default: 1.0,
+        logarithmic: false,
+        stepped: true,
+        set: |synth, value| synth.set_zdf_enabled(value >= 0.5),
+        get: |synth| f32::from(u8::from(synth.zdf_enabled)),
+        smoothed: None,
+    },
+    ParameterDescriptor {
+        id: PARAM_ZDF_CUTOFF,
+        name: "ZDF Cutoff",
+        unit: "Hz",
+        min: 20.0,
+        max: 20000.0,
+        default: 1000.0,
+        logarithmic: true,
+        stepped: false,
+        set: Synth::set_zdf_cutoff,
+        get: |synth| synth.param_target(SmoothedParam::ZdfCutoff),
+        smoothed: Some(SmoothedParam::ZdfCutoff),
+    },
+    ParameterDescriptor {
+        id: PARAM_ZDF_RES,
+        name: "ZDF Resonance",
+        unit: "",
+        min: 0.0,
+        max: ZDF_MAX_RESONANCE,
+        default: 1.0,
+        logarithmic: false,
+        stepped: false,
+        set: Synth::set_zdf_resonance,
+        get: |synth| synth.param_target(SmoothedParam::ZdfResonance),
+        smoothed: Some(SmoothedParam::ZdfResonance),
+    },
+    ParameterDescriptor {
+        id: PARAM_ZDF_DRIVE,
+        name: "ZDF Drive",
+        unit: "",
+        min: 0.0,
+        max: 10.0,
+        default: 0.0,
+        logarithmic: false,
+        stepped: false,
+        set: Synth::set_zdf_drive,
+        get: |synth| synth.param_target(SmoothedParam::ZdfDrive),
+        smoothed: Some(SmoothedParam::ZdfDrive),
+    },
+    ParameterDescriptor {
+        id: PARAM_SATURATION_DRIVE,
+        name: "Saturation Drive",
+        unit: "",
+        min: 0.0,
+        max: 10.0,
+        default: 0.5,
+        logarithmic: false,
+        stepped: false,
+        set: Synth::set_saturation_drive,
+        get: |synth| synth.param_target(SmoothedParam::SaturationDrive),
+        smoothed: Some(SmoothedParam::SaturationDrive),
+    },
+    ParameterDescriptor {
+        id: PARAM_SATURATION_MIX,
+        name: "Saturation Mix",
+        unit: "",
+        min: 0.0,
+        max: 1.0,
+        default: 0.5,
+        logarithmic: false,
+        stepped: false,
+        set: Synth::set_saturation_mix,
+        get: |synth| synth.param_target(SmoothedParam::SaturationMix),
+        smoothed: Some(SmoothedParam::SaturationMix),
+    },
+    ParameterDescriptor {
+        id: PARAM_OVERSAMPLE,
+        name: "Oversampling",
+        unit: "x",
+        min: 1.0,
+        max: 8.0,
+        default: 1.0,
+        logarithmic: true,
+        stepped: true,
+        set: |synth, value| {
+            synth.set_oversample_factor(match value.round() as u32 {
+                0..=1 => OversampleFactor::None,
+                2..=3 => OversampleFactor::X2,
+                4..=7 => OversampleFactor::X4,
+                _ => OversampleFactor::X8,
+            })
+        },
+        get: |synth| synth.oversample_factor.as_u32() as f32,
+        smoothed: None,
+    },
+    ParameterDescriptor {
+        id: PARAM_ZDF_KEYTRACK,
+        name: "ZDF Keytrack",
+        unit: "",
+        min: 0.0,
+        max: 2.0,
+        default: 0.0,
+        logarithmic: false,
+        stepped: false,
+        set: Synth::set_zdf_keytrack,
+        get: |synth| synth.voice_filter.zdf_filter.keytrack(),
+        smoothed: None,
+    },
+];
+
+/// Error from `Synth::set_parameter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterError {
+    /// The ID is not a synth parameter
+    UnknownParameter(i32),
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::UnknownParameter(id) => write!(f, "Unknown parameter ID {}", id),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
 /// Voice structure representing one playing note.
 #[derive(Debug, Clone)]
 struct Voice {
-    /// Oscillator for this voice
-    oscillator: Oscillator,
+    /// Oscillators for this voice
+    oscillators: [Oscillator; OSCILLATORS_PER_VOICE],
+
+    /// Smoothed previous carrier output for FM self-feedback
+    fm_feedback: f32,
 
     /// Amplitude envelope
     amplitude_envelope: AdsrEnvelope,
 
+    /// Filters keyed to this voice's note
+    filter: VoiceFilter,
+
     /// Current MIDI note
     note: u8,
 
+    /// Sounding pitch in (fractional) MIDI notes, moves toward `glide_target`
+    pitch: f32,
+
+    /// Pitch the voice is gliding to
+    glide_target: f32,
+
+    /// Glide speed in semitones per sample
+    glide_step: f32,
+
     /// Current velocity (0-127)
     velocity: u8,
 
+    /// Stereo position (-1.0 = left, 0.0 = center, 1.0 = right)
+    pan: f32,
+
     /// Whether this voice is active
     active: bool,
 
+    /// Whether the note is still held (false once released)
+    held: bool,
+
     /// Voice age counter for voice stealing (higher = older)
     age: u64,
 }
 
 impl Voice {
     /// Creates a new voice for a specific note.
-    fn new(note: u8, velocity: u8, sample_rate: f32, age: u64) -> Self {
+    fn new(
+        note: u8,
+        velocity: u8,
+        sample_rate: f32,
+        age: u64,
+        waveforms: &[Waveform; OSCILLATORS_PER_VOICE],
+    ) -> Self {
         let freq = midi_to_frequency(note);
 
-        let osc_config = OscillatorConfig {
-            waveform: Waveform::Sawtooth,
-            frequency: freq,
-            amplitude: velocity as f32 / 127.0,
-            phase_offset: 0.0,
-            sample_rate,
-            oversample_factor: OversampleFactor::None,
-        };
+        let oscillators = waveforms.map(|waveform| {
+            Oscillator::new(OscillatorConfig {
+                waveform,
+                frequency: freq,
+                amplitude: velocity as f32 / 127.0,
+                phase_offset: 0.0,
+                sample_rate,
+                oversample_factor: OversampleFactor::None,
+            })
+        });
 
         let env_config = EnvelopeConfig {
             attack: 0.01,
@@ -84,37 +499,190 @@ impl Voice {
         };
 
         Self {
-            oscillator: Oscillator::new(osc_config),
+            oscillators,
+            fm_feedback: 0.0,
             amplitude_envelope: AdsrEnvelope::with_config(env_config),
+            filter: VoiceFilter::new(sample_rate),
             note,
+            pitch: note as f32,
+            glide_target: note as f32,
+            glide_step: 0.0,
             velocity,
+            pan: 0.0,
             active: true,
+            held: false,
             age,
         }
     }
 
+    /// Re-keys the voice for a new note, reusing its oscillators.
+    ///
+    /// Leaves the voice as `Voice::new` would, with the filter settings of
+    /// `filter`, without allocating.
+    fn restart(
+        &mut self,
+        note: u8,
+        velocity: u8,
+        age: u64,
+        waveforms: &[Waveform; OSCILLATORS_PER_VOICE],
+        filter: &VoiceFilter,
+    ) {
+        let freq = midi_to_frequency(note);
+        for (oscillator, &waveform) in self.oscillators.iter_mut().zip(waveforms) {
+            oscillator.set_waveform(waveform);
+            oscillator.set_frequency(freq);
+            oscillator.set_amplitude(velocity as f32 / 127.0);
+            oscillator.retrigger();
+        }
+
+        self.fm_feedback = 0.0;
+        self.amplitude_envelope.reset();
+        self.note = note;
+        self.pitch = note as f32;
+        self.glide_target = note as f32;
+        self.glide_step = 0.0;
+        self.velocity = velocity;
+        self.pan = 0.0;
+        self.active = true;
+        self.held = false;
+        self.age = age;
+        self.load_filter(filter);
+    }
+
+    /// Takes over the settings of `filter`, keyed to the voice's note.
+    ///
+    /// The filter state is cleared.
+    fn load_filter(&mut self, filter: &VoiceFilter) {
+        self.filter.clone_from(filter);
+        self.filter.reset();
+        self.filter.zdf_filter.set_note(self.note);
+    }
+
     /// Processes one sample from this voice.
-    fn process(&mut self) -> f32 {
+    ///
+    /// Oscillators are mixed by `levels`; with an FM routing the modulator
+    /// runs first so the carrier can read its output in the same sample.
+    /// The mix is enveloped, then runs through the voice's own filters.
+    fn process(
+        &mut self,
+        levels: &[f32; OSCILLATORS_PER_VOICE],
+        fm: Option<FmRouting>,
+        zdf_enabled: bool,
+    ) -> f32 {
         if !self.active {
             return 0.0;
         }
 
         let env_level = self.amplitude_envelope.process();
-        let osc_sample = self.oscillator.next_sample();
 
-        osc_sample * env_level
+        if self.pitch != self.glide_target {
+            self.advance_glide();
+        }
+
+        let mut outputs = [0.0f32; OSCILLATORS_PER_VOICE];
+        let fm = fm.filter(|routing| routing.index != 0.0);
+
+        if let Some(routing) = fm {
+            if routing.modulator != routing.carrier {
+                outputs[routing.modulator] = self.oscillators[routing.modulator].next_sample();
+            }
+        }
+
+        for (i, oscillator) in self.oscillators.iter_mut().enumerate() {
+            match fm {
+                Some(routing) if i == routing.carrier => {
+                    let modulation = if routing.modulator == routing.carrier {
+                        self.fm_feedback
+                    } else {
+                        outputs[routing.modulator]
+                    };
+                    let sample = oscillator
+                        .next_sample_phase_modulated(routing.index * modulation / (2.0 * PI));
+
+                    // Averaging the last two outputs tames feedback into noise
+                    self.fm_feedback = 0.5 * (self.fm_feedback + sample);
+                    outputs[i] = sample;
+                }
+                Some(routing) if i == routing.modulator => {}
+                _ => outputs[i] = oscillator.next_sample(),
+            }
+        }
+
+        let osc_sample: f32 = outputs
+            .iter()
+            .zip(levels)
+            .map(|(out, level)| out * level)
+            .sum();
+
+        self.filter.process(osc_sample * env_level, zdf_enabled)
     }
 
     /// Triggers the voice (note on).
     fn trigger(&mut self) {
+        for oscillator in &mut self.oscillators {
+            oscillator.retrigger();
+        }
+        self.fm_feedback = 0.0;
+        self.held = true;
         self.amplitude_envelope.note_on();
     }
 
+    /// Slides the voice to a new note without retriggering.
+    ///
+    /// The glide is linear in pitch, so every interval takes the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - Target MIDI note
+    /// * `glide_samples` - Glide duration in samples (below 1 jumps instantly)
+    fn glide_to(&mut self, note: u8, glide_samples: f32) {
+        self.note = note;
+        self.glide_target = note as f32;
+        self.filter.zdf_filter.set_note(note);
+
+        if glide_samples < 1.0 {
+            self.pitch = self.glide_target;
+            self.apply_pitch();
+        } else {
+            self.glide_step = (self.glide_target - self.pitch).abs() / glide_samples;
+        }
+    }
+
+    /// Moves the pitch one sample toward the glide target.
+    fn advance_glide(&mut self) {
+        let remaining = self.glide_target - self.pitch;
+        if remaining.abs() <= self.glide_step {
+            self.pitch = self.glide_target;
+        } else {
+            self.pitch += self.glide_step.copysign(remaining);
+        }
+        self.apply_pitch();
+    }
+
+    /// Sets every oscillator to the current (fractional) pitch.
+    fn apply_pitch(&mut self) {
+        let frequency = 440.0 * 2.0f32.powf((self.pitch - 69.0) / 12.0);
+        for oscillator in &mut self.oscillators {
+            oscillator.set_frequency(frequency);
+        }
+    }
+
+    /// Gets the current oscillator frequency in Hz.
+    fn frequency(&self) -> f32 {
+        440.0 * 2.0f32.powf((self.pitch - 69.0) / 12.0)
+    }
+
     /// Releases the voice (note off).
     fn release(&mut self) {
+        self.held = false;
         self.amplitude_envelope.note_off();
     }
 
+    /// Gets the current output level (envelope scaled by velocity).
+    fn level(&self) -> f32 {
+        self.amplitude_envelope.level() * self.velocity as f32 / 127.0
+    }
+
     /// Checks if the voice is still active.
     fn is_active(&self) -> bool {
         self.active && self.amplitude_envelope.is_active()
@@ -126,6 +694,128 @@ impl Voice {
     }
 }
 
+/// Filters of one voice.
+#[derive(Debug, Clone)]
+struct VoiceFilter {
+    /// Biquad filter (original filter)
+    filter: Filter,
+
+    /// ZDF (Zero-Delay Feedback) ladder filter for VA character
+    zdf_filter: ZdfFilter,
+}
+
+impl VoiceFilter {
+    /// Creates the default ZDF and biquad filters.
+    fn new(sample_rate: f32) -> Self {
+        let zdf_config = ZdfFilterConfig {
+            mode: ZdfFilterMode::LowPass4,
+            cutoff_frequency: 1000.0,
+            resonance: 1.0,
+            drive: 0.0,
+            sample_rate,
+        };
+
+        Self {
+            filter: Filter::new(FilterType::LowPass, 2000.0, 1.0, sample_rate),
+            zdf_filter: ZdfFilter::with_config(zdf_config),
+        }
+    }
+
+    /// Processes one sample through the filters.
+    fn process(&mut self, input: f32, zdf_enabled: bool) -> f32 {
+        let mut output = input;
+
+        // Process through ZDF filter if enabled
+        if zdf_enabled {
+            output = self.zdf_filter.process_sample(output);
+        }
+
+        // Process through biquad filter (original filter)
+        self.filter.process(output)
+    }
+
+    /// Clears the filter state.
+    fn reset(&mut self) {
+        self.filter.reset();
+        self.zdf_filter.reset();
+    }
+}
+
+/// Post-voice saturation, ring modulation and effects.
+#[derive(Debug, Clone)]
+struct OutputChain {
+    /// Saturation effect for analog-style saturation
+    saturation: Saturation,
+
+    /// Right-channel twin of `saturation` for stereo rendering
+    saturation_right: Saturation,
+
+    /// Ring modulator, optionally tracking the played note
+    ring_modulator: RingModulator,
+
+    /// Whether the ring modulator is active
+    ring_mod_enabled: bool,
+
+    /// Global effect processor
+    effects: EffectProcessor,
+}
+
+impl OutputChain {
+    /// Creates the default saturation and effect chain.
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            saturation: Saturation::new(),
+            saturation_right: Saturation::new(),
+            ring_modulator: RingModulator::new_with_sample_rate(sample_rate),
+            ring_mod_enabled: false,
+            effects: EffectProcessor::new(sample_rate),
+        }
+    }
+
+    /// Processes one sample through the chain.
+    fn process(&mut self, input: f32) -> f32 {
+        // Process through saturation
+        let mut saturated = self.saturation.process_sample(input);
+
+        // Process through ring modulator if enabled
+        if self.ring_mod_enabled {
+            saturated = self.ring_modulator.process(saturated);
+        }
+
+        // Process through effects
+        self.effects.process(saturated)
+    }
+
+    /// Processes one stereo sample pair through the chain.
+    ///
+    /// Both channels share the ring modulator carrier and end in the
+    /// stereo path of the effect processor.
+    fn process_stereo(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let mut left = self.saturation.process_sample(left);
+        let mut right = self.saturation_right.process_sample(right);
+
+        if self.ring_mod_enabled {
+            (left, right) = self.ring_modulator.process_stereo(left, right);
+        }
+
+        self.effects.process_stereo(left, right)
+    }
+
+    /// Applies a saturation setting to both channels.
+    fn update_saturation(&mut self, update: impl Fn(&mut Saturation)) {
+        update(&mut self.saturation);
+        update(&mut self.saturation_right);
+    }
+
+    /// Clears the saturation and effect state.
+    fn reset(&mut self) {
+        self.saturation.reset();
+        self.saturation_right.reset();
+        self.ring_modulator.reset();
+        self.effects.reset();
+    }
+}
+
 /// Main synthesizer structure.
 ///
 /// The Synth combines oscillators, filters, envelopes, LFOs, and effects
@@ -149,30 +839,36 @@ impl Voice {
 /// ```
 #[derive(Debug, Clone)]
 pub struct Synth {
-    /// Active voices for polyphony
+    /// Voice pool for polyphony (held, releasing and finished voices)
     voices: Vec<Voice>,
 
-    /// Global biquad filter (original filter)
-    filter: Filter,
+    /// Maximum number of simultaneous voices
+    max_voices: usize,
 
-    /// ZDF (Zero-Delay Feedback) ladder filter for VA character
-    zdf_filter: ZdfFilter,
+    /// Which voice to steal when the pool is full
+    steal_policy: VoiceStealPolicy,
+
+    /// Filter settings every new voice starts from
+    voice_filter: VoiceFilter,
+
+    /// Saturation, ring modulator and effect chain
+    chain: OutputChain,
 
     /// Whether ZDF filter is active
     zdf_enabled: bool,
 
-    /// Saturation effect for analog-style saturation
-    saturation: Saturation,
-
     /// Global LFOs for modulation
     lfos: Vec<Lfo>,
 
-    /// Global effect processor
-    effects: EffectProcessor,
+    /// How far voices are panned by note number (0.0 = centered)
+    key_pan_spread: f32,
 
     /// Master volume
     master_volume: f32,
 
+    /// Brickwall limiter on the master output (off by default)
+    limiter: Limiter,
+
     /// Sample rate
     sample_rate: f32,
 
@@ -184,6 +880,54 @@ pub struct Synth {
 
     /// Voice age counter for voice stealing
     voice_age_counter: u64,
+
+    /// Waveform of each voice oscillator
+    oscillator_waveforms: [Waveform; OSCILLATORS_PER_VOICE],
+
+    /// Mix level of each voice oscillator
+    oscillator_levels: [f32; OSCILLATORS_PER_VOICE],
+
+    /// FM routing between voice oscillators, if enabled
+    fm: Option<FmRouting>,
+
+    /// Monophonic legato mode
+    mono_mode: bool,
+
+    /// Portamento time in seconds for legato notes in mono mode
+    glide_time: f32,
+
+    /// Time voices hold their envelope peak before decaying, in seconds
+    envelope_hold: f32,
+
+    /// Notes held in mono mode, most recent last
+    held_notes: Vec<u8>,
+
+    /// Stored A/B comparison states
+    ab_slots: [Option<SoundSnapshot>; 2],
+
+    /// Slot the current sound belongs to
+    ab_active: AbSlot,
+
+    /// Note events applied at the start of the next `render_block`
+    event_queue: Vec<QueuedEvent>,
+
+    /// Ramp time of continuous parameters in milliseconds
+    param_smoothing_ms: f32,
+
+    /// Running ramps, indexed by `SmoothedParam`
+    param_ramps: [ParamRamp; SmoothedParam::ALL.len()],
+
+    /// Samples rendered since creation, timestamps recorded automation
+    sample_position: u64,
+
+    /// Lane receiving `set_param` writes while recording
+    automation_recording: Option<AutomationLane>,
+
+    /// Sample position where the current recording started
+    recording_start: u64,
+
+    /// Unused voices taken when the pool grows, so note-ons never allocate
+    spare_voices: Vec<Voice>,
 }
 
 impl Synth {
@@ -204,29 +948,47 @@ impl Synth {
             sample_rate,
             ..Default::default()
         };
-
-        let zdf_config = ZdfFilterConfig {
-            mode: ZdfFilterMode::LowPass4,
-            cutoff_frequency: 1000.0,
-            resonance: 1.0,
-            drive: 0.0,
-            sample_rate,
-        };
-
-        Self {
+        let mut limiter = Limiter::new(sample_rate);
+        limiter.set_enabled(false);
+        let oscillator_waveforms = [Waveform::Sawtooth, Waveform::Sine];
+        let spare_voices = (0..MAX_VOICES)
+            .map(|_| Voice::new(0, 0, sample_rate, 0, &oscillator_waveforms))
+            .collect();
+
+        let mut synth = Self {
             voices: Vec::with_capacity(MAX_VOICES),
-            filter: Filter::new(FilterType::LowPass, 2000.0, 1.0, sample_rate),
-            zdf_filter: ZdfFilter::with_config(zdf_config),
+            max_voices: DEFAULT_MAX_VOICES,
+            steal_policy: VoiceStealPolicy::default(),
+            voice_filter: VoiceFilter::new(sample_rate),
+            chain: OutputChain::new(sample_rate),
             zdf_enabled: true,
-            saturation: Saturation::new(),
             lfos: vec![Lfo::with_config(lfo_config)],
-            effects: EffectProcessor::new(sample_rate),
+            key_pan_spread: 0.0,
             master_volume: 0.7,
+            limiter,
             sample_rate,
-            active_notes: HashMap::new(),
+            active_notes: HashMap::with_capacity(MIDI_NOTE_COUNT),
             oversample_factor: OversampleFactor::None,
             voice_age_counter: 0,
-        }
+            oscillator_waveforms,
+            oscillator_levels: [1.0, 0.0],
+            fm: None,
+            mono_mode: false,
+            glide_time: 0.0,
+            envelope_hold: 0.0,
+            held_notes: Vec::with_capacity(MIDI_NOTE_COUNT),
+            ab_slots: [None, None],
+            ab_active: AbSlot::A,
+            event_queue: Vec::with_capacity(EVENT_QUEUE_CAPACITY),
+            spare_voices,
+            sample_position: 0,
+            automation_recording: None,
+            recording_start: 0,
+            param_smoothing_ms: DEFAULT_PARAM_SMOOTHING_MS,
+            param_ramps: [ParamRamp::new(0.0); SmoothedParam::ALL.len()],
+        };
+        synth.settle_param_ramps();
+        synth
     }
 
     /// Creates a new synthesizer with default sample rate (44100 Hz).
@@ -240,8 +1002,8 @@ impl Synth {
     ///
     /// Tuple of (left, right) audio samples
     pub fn process_stereo(&mut self) -> (f32, f32) {
-        let sample = self.process_mono();
-        (sample * self.master_volume, sample * self.master_volume)
+        let sample = self.process_mono() * self.smoothed_master_volume();
+        self.limit_master(sample, sample)
     }
 
     /// Processes one mono sample.
@@ -250,43 +1012,150 @@ impl Synth {
     ///
     /// Mono audio sample
     pub fn process_mono(&mut self) -> f32 {
-        // Sum all active voices
-        let mut output = 0.0f32;
-
-        // Collect indices of voices to process (avoid borrowing issues)
-        let voice_indices: Vec<usize> = self.active_notes.values().copied().collect();
+        self.sample_position += 1;
+        self.advance_param_ramps();
+        let (output, _) = self.sum_voices(false);
+        self.chain.process(output)
+    }
 
-        // Process voices and track which notes to remove
-        let mut notes_to_remove = Vec::new();
+    /// Renders a block of stereo audio with per-voice key panning.
+    ///
+    /// The two voice sums run through the stereo path of the saturation,
+    /// ring modulator and effect chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `out_l` - Left output buffer
+    /// * `out_r` - Right output buffer (the shorter buffer sets the length)
+    pub fn render_stereo(&mut self, out_l: &mut [f32], out_r: &mut [f32]) {
+        for (left, right) in out_l.iter_mut().zip(out_r.iter_mut()) {
+            self.sample_position += 1;
+            self.advance_param_ramps();
+            let volume = self.smoothed_master_volume();
+            let (sum_l, sum_r) = self.sum_voices(true);
+            let (wet_l, wet_r) = self.chain.process_stereo(sum_l, sum_r);
+            (*left, *right) = self.limit_master(wet_l * volume, wet_r * volume);
+        }
+    }
 
-        for &voice_idx in &voice_indices {
-            if let Some(voice) = self.voices.get_mut(voice_idx) {
-                if voice.is_active() {
-                    output += voice.process();
-                } else {
-                    notes_to_remove.push(voice.note);
-                }
-            }
+    /// Runs a master output pair through the limiter when it is enabled.
+    fn limit_master(&mut self, left: f32, right: f32) -> (f32, f32) {
+        if self.limiter.is_enabled() {
+            self.limiter.process_stereo(left, right)
+        } else {
+            (left, right)
         }
+    }
 
-        // Remove finished voices
-        for note in notes_to_remove {
-            self.active_notes.remove(&note);
+    /// Renders a block of mono audio in real-time safe fashion.
+    ///
+    /// Note events queued with `queue_note_on` / `queue_note_off` are
+    /// applied first, then the whole buffer is filled with the same signal
+    /// as the left channel of `process_stereo`. Splitting a render into
+    /// several calls produces the same audio as one long call.
+    ///
+    /// # Real-time safety
+    ///
+    /// This method never allocates, locks or panics: voices, note tracking
+    /// and the event queue are sized at construction, so it can be called
+    /// directly from an audio callback. A zero-length buffer only applies
+    /// the queued events.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Output buffer to fill
+    pub fn render_block(&mut self, out: &mut [f32]) {
+        self.apply_queued_events();
+
+        for sample in out.iter_mut() {
+            let master = self.process_mono() * self.smoothed_master_volume();
+            *sample = self.limit_master(master, master).0;
         }
+    }
 
-        // Process through ZDF filter if enabled
-        if self.zdf_enabled {
-            output = self.zdf_filter.process_sample(output);
+    /// Queues a note on for the next `render_block` call.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - MIDI note number (0-127)
+    /// * `velocity` - Note velocity (0-127, 0 releases the note)
+    ///
+    /// # Returns
+    ///
+    /// `false` if the queue is full and the event was dropped
+    pub fn queue_note_on(&mut self, note: u8, velocity: u8) -> bool {
+        self.queue_event(QueuedEvent::NoteOn(note, velocity))
+    }
+
+    /// Queues a note off for the next `render_block` call.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - MIDI note number (0-127)
+    ///
+    /// # Returns
+    ///
+    /// `false` if the queue is full and the event was dropped
+    pub fn queue_note_off(&mut self, note: u8) -> bool {
+        self.queue_event(QueuedEvent::NoteOff(note))
+    }
+
+    /// Gets the number of note events waiting for the next block.
+    pub fn queued_events(&self) -> usize {
+        self.event_queue.len()
+    }
+
+    /// Adds an event to the queue unless that would grow it.
+    fn queue_event(&mut self, event: QueuedEvent) -> bool {
+        if self.event_queue.len() >= EVENT_QUEUE_CAPACITY {
+            return false;
         }
+        self.event_queue.push(event);
+        true
+    }
 
-        // Process through biquad filter (original filter)
-        let filtered = self.filter.process(output);
+    /// Applies every queued note event in arrival order.
+    fn apply_queued_events(&mut self) {
+        for i in 0..self.event_queue.len() {
+            match self.event_queue[i] {
+                QueuedEvent::NoteOn(note, velocity) => self.note_on(note, velocity),
+                QueuedEvent::NoteOff(note) => self.note_off_specific(note),
+            }
+        }
+        self.event_queue.clear();
+    }
 
-        // Process through saturation
-        let saturated = self.saturation.process_sample(filtered);
+    /// Sums one sample of all sounding voices, including release tails.
+    ///
+    /// # Arguments
+    ///
+    /// * `panned` - Apply each voice's pan (otherwise both sums are equal)
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (left, right) voice sums
+    fn sum_voices(&mut self, panned: bool) -> (f32, f32) {
+        let mut left = 0.0f32;
+        let mut right = 0.0f32;
+
+        for (voice_idx, voice) in self.voices.iter_mut().enumerate() {
+            if voice.is_active() {
+                let sample = voice.process(&self.oscillator_levels, self.fm, self.zdf_enabled);
+                if panned {
+                    // Balance law: the center stays at unity on both sides
+                    left += sample * (1.0 - voice.pan).min(1.0);
+                    right += sample * (1.0 + voice.pan).min(1.0);
+                } else {
+                    left += sample;
+                    right += sample;
+                }
+            } else if self.active_notes.get(&voice.note) == Some(&voice_idx) {
+                // Forget held notes whose voice finished
+                self.active_notes.remove(&voice.note);
+            }
+        }
 
-        // Process through effects
-        self.effects.process(saturated)
+        (left, right)
     }
 
     /// Processes a block of stereo samples.
@@ -327,47 +1196,52 @@ impl Synth {
             return;
         }
 
-        // Check if note is already playing
-        if self.active_notes.contains_key(&note) {
-            self.note_off_specific(note);
+        if self.mono_mode {
+            if self.mono_legato_note_on(note) {
+                return;
+            }
+            // First note of a phrase: start fresh with a single voice
+            self.note_off();
+            self.held_notes.push(note);
         }
 
         // Increment voice age counter
         self.voice_age_counter += 1;
 
-        // Find available voice (oldest first for voice stealing)
-        let voice_idx = if self.voices.len() < MAX_VOICES {
-            self.voices.push(Voice::new(
-                note,
-                velocity,
-                self.sample_rate,
-                self.voice_age_counter,
-            ));
-            self.voices.len() - 1
-        } else {
-            // Voice stealing: find the oldest voice by age
-            let oldest_voice_idx = self
-                .active_notes
-                .iter()
-                .filter_map(|(n, &idx)| self.voices.get(idx).map(|v| (*n, idx, v.age)))
-                .min_by_key(|(_, _, age)| *age)
-                .map(|(n, idx, _)| (n, idx));
-
-            if let Some((old_note, voice_idx)) = oldest_voice_idx {
-                // Reuse this voice
-                self.active_notes.remove(&old_note);
-
-                // Reinitialize voice with new age
-                self.voices[voice_idx] =
-                    Voice::new(note, velocity, self.sample_rate, self.voice_age_counter);
-                voice_idx
-            } else {
-                return;
-            }
-        };
+        let voice_idx = self.allocate_voice(note);
+        if voice_idx == self.voices.len() {
+            let voice = self.spare_voices.pop().unwrap_or_else(|| {
+                Voice::new(
+                    note,
+                    velocity,
+                    self.sample_rate,
+                    0,
+                    &self.oscillator_waveforms,
+                )
+            });
+            self.voices.push(voice);
+        }
+        let pan = self.key_pan(note);
+        let voice = &mut self.voices[voice_idx];
+        voice.restart(
+            note,
+            velocity,
+            self.voice_age_counter,
+            &self.oscillator_waveforms,
+            &self.voice_filter,
+        );
+        voice.amplitude_envelope.set_hold(self.envelope_hold);
+        voice.pan = pan;
 
         self.active_notes.insert(note, voice_idx);
 
+        for lfo in &mut self.lfos {
+            lfo.retrigger();
+        }
+        self.chain
+            .ring_modulator
+            .set_note_frequency(midi_to_frequency(note));
+
         // Trigger the voice
         if let Some(voice) = self.voices.get_mut(voice_idx) {
             voice.trigger();
@@ -387,6 +1261,7 @@ impl Synth {
             }
         }
         self.active_notes.clear();
+        self.held_notes.clear();
     }
 
     /// Releases a specific note.
@@ -395,6 +1270,17 @@ impl Synth {
     ///
     /// * `note` - MIDI note number (0-127)
     pub fn note_off_specific(&mut self, note: u8) {
+        if self.mono_mode {
+            self.held_notes.retain(|&n| n != note);
+            // Glide back to the most recent note still held
+            if let Some(&previous) = self.held_notes.last() {
+                if self.active_notes.contains_key(&note) {
+                    self.mono_glide_to(previous);
+                }
+                return;
+            }
+        }
+
         if let Some(&voice_idx) = self.active_notes.get(&note) {
             if let Some(voice) = self.voices.get_mut(voice_idx) {
                 voice.release();
@@ -403,6 +1289,214 @@ impl Synth {
         }
     }
 
+    /// Picks the voice slot for a new note.
+    ///
+    /// A note that is still sounding reuses its own voice. Otherwise a
+    /// finished voice is recycled, the pool grows up to `max_voices`, and
+    /// finally a voice is stolen: the quietest releasing voice if there is
+    /// one, else the held voice chosen by the steal policy.
+    ///
+    /// # Returns
+    ///
+    /// Index into `voices` (equal to `voices.len()` for a new slot)
+    fn allocate_voice(&mut self, note: u8) -> usize {
+        if let Some(voice_idx) = self.active_notes.remove(&note) {
+            return voice_idx;
+        }
+
+        let same_note = self
+            .voices
+            .iter()
+            .position(|v| v.is_active() && v.note == note);
+        if let Some(voice_idx) = same_note {
+            return voice_idx;
+        }
+
+        if let Some(voice_idx) = self.voices.iter().position(|v| !v.is_active()) {
+            return voice_idx;
+        }
+
+        if self.voices.len() < self.max_voices {
+            return self.voices.len();
+        }
+
+        let releasing = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.held)
+            .min_by(|(_, a), (_, b)| a.level().total_cmp(&b.level()))
+            .map(|(idx, _)| idx);
+
+        let voice_idx = releasing.unwrap_or_else(|| {
+            let held = self.voices.iter().enumerate();
+            let chosen = match self.steal_policy {
+                VoiceStealPolicy::Oldest => held.min_by_key(|(_, v)| v.age),
+                VoiceStealPolicy::Quietest => {
+                    held.min_by(|(_, a), (_, b)| a.level().total_cmp(&b.level()))
+                }
+                VoiceStealPolicy::LowestPriority => held.min_by_key(|(_, v)| (v.velocity, v.age)),
+            };
+            chosen.map_or(0, |(idx, _)| idx)
+        });
+
+        let stolen_note = self.voices[voice_idx].note;
+        if self.active_notes.get(&stolen_note) == Some(&voice_idx) {
+            self.active_notes.remove(&stolen_note);
+        }
+        voice_idx
+    }
+
+    /// Sets how far voices are panned by their note number.
+    ///
+    /// Notes below middle C move left and notes above move right; at 1.0 a
+    /// note four octaves from middle C is panned hard. The pan is fixed at
+    /// note-on and only heard through `render_stereo`.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Spread amount (0.0 = all centered, 1.0 = full spread)
+    pub fn set_key_pan_spread(&mut self, amount: f32) {
+        self.key_pan_spread = amount.clamp(0.0, 1.0);
+    }
+
+    /// Gets the key pan spread amount.
+    pub fn key_pan_spread(&self) -> f32 {
+        self.key_pan_spread
+    }
+
+    /// Calculates the pan position for a note from the key pan spread.
+    fn key_pan(&self, note: u8) -> f32 {
+        ((note as f32 - 60.0) / KEY_PAN_RANGE * self.key_pan_spread).clamp(-1.0, 1.0)
+    }
+
+    /// Sets the maximum number of simultaneous voices.
+    ///
+    /// Shrinking the pool cuts off the voices above the new limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_voices` - Voice count (1 to `MAX_VOICES`)
+    pub fn set_max_voices(&mut self, max_voices: usize) {
+        self.max_voices = max_voices.clamp(1, MAX_VOICES);
+        if self.voices.len() > self.max_voices {
+            for mut voice in self.voices.drain(self.max_voices..) {
+                voice.stop();
+                self.spare_voices.push(voice);
+            }
+            let limit = self.max_voices;
+            self.active_notes.retain(|_, idx| *idx < limit);
+        }
+    }
+
+    /// Gets the maximum number of simultaneous voices.
+    pub fn max_voices(&self) -> usize {
+        self.max_voices
+    }
+
+    /// Sets which voice is stolen when all voices are in use.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Voice stealing policy
+    pub fn set_steal_policy(&mut self, policy: VoiceStealPolicy) {
+        self.steal_policy = policy;
+    }
+
+    /// Gets the voice stealing policy.
+    pub fn steal_policy(&self) -> VoiceStealPolicy {
+        self.steal_policy
+    }
+
+    /// Handles a mono-mode note-on while other notes are held.
+    ///
+    /// # Returns
+    ///
+    /// True if the sounding voice glided to `note` (no new voice needed)
+    fn mono_legato_note_on(&mut self, note: u8) -> bool {
+        self.held_notes.retain(|&n| n != note);
+        if self.held_notes.is_empty() || self.active_notes.is_empty() {
+            return false;
+        }
+
+        self.held_notes.push(note);
+        self.mono_glide_to(note);
+        true
+    }
+
+    /// Glides the single mono voice to `note` and re-keys it.
+    fn mono_glide_to(&mut self, note: u8) {
+        let Some(voice_idx) = self.active_notes.values().next().copied() else {
+            return;
+        };
+
+        self.active_notes.clear();
+        self.active_notes.insert(note, voice_idx);
+        self.chain
+            .ring_modulator
+            .set_note_frequency(midi_to_frequency(note));
+
+        let glide_samples = self.glide_time * self.sample_rate;
+        if let Some(voice) = self.voices.get_mut(voice_idx) {
+            voice.glide_to(note, glide_samples);
+        }
+    }
+
+    /// Enables or disables monophonic legato mode.
+    ///
+    /// In mono mode only one voice sounds. A note played while another is
+    /// held glides to the new pitch without retriggering the envelope;
+    /// releasing it glides back to the previous held note.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether mono mode is active
+    pub fn set_mono_mode(&mut self, enabled: bool) {
+        if enabled != self.mono_mode {
+            self.note_off();
+        }
+        self.mono_mode = enabled;
+    }
+
+    /// Checks whether monophonic legato mode is active.
+    pub fn mono_mode(&self) -> bool {
+        self.mono_mode
+    }
+
+    /// Sets the portamento glide time used in mono mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds` - Glide time (0.0 = instant jump, up to `MAX_GLIDE_TIME`)
+    pub fn set_glide_time(&mut self, seconds: f32) {
+        self.glide_time = seconds.clamp(0.0, MAX_GLIDE_TIME);
+    }
+
+    /// Gets the portamento glide time in seconds.
+    pub fn glide_time(&self) -> f32 {
+        self.glide_time
+    }
+
+    /// Sets the hold time of the voice amplitude envelopes.
+    ///
+    /// After the attack each voice stays at its peak for this long before
+    /// decaying. Sounding voices pick up the change immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds` - Hold time (0.0 = straight from attack to decay)
+    pub fn set_envelope_hold(&mut self, seconds: f32) {
+        self.envelope_hold = seconds.max(0.0);
+        for voice in &mut self.voices {
+            voice.amplitude_envelope.set_hold(self.envelope_hold);
+        }
+    }
+
+    /// Gets the envelope hold time in seconds.
+    pub fn envelope_hold(&self) -> f32 {
+        self.envelope_hold
+    }
+
     /// Sets the master volume.
     ///
     /// # Arguments
@@ -410,33 +1504,233 @@ impl Synth {
     /// * `volume` - Volume level (0.0 to 1.0)
     pub fn set_master_volume(&mut self, volume: f32) {
         self.master_volume = volume.clamp(0.0, 1.0);
+        self.smooth_param(SmoothedParam::MasterVolume, self.master_volume);
     }
 
-    /// Sets the global filter cutoff frequency.
+    /// Sets the smoothing time of continuous parameters.
+    ///
+    /// Master volume, ZDF cutoff, resonance and drive, and saturation drive
+    /// and mix ramp linearly to new values over this time instead of
+    /// jumping, which avoids zipper noise. Switches and waveforms still
+    /// change immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_ms` - Ramp time in milliseconds (0.0 = jump immediately)
+    pub fn set_param_smoothing(&mut self, time_ms: f32) {
+        self.param_smoothing_ms = time_ms.clamp(0.0, MAX_PARAM_SMOOTHING_MS);
+    }
+
+    /// Gets the smoothing time of continuous parameters in milliseconds.
+    pub fn param_smoothing(&self) -> f32 {
+        self.param_smoothing_ms
+    }
+
+    /// Gets the master volume currently applied, partway through a ramp.
+    pub fn smoothed_master_volume(&self) -> f32 {
+        self.param_ramps[SmoothedParam::MasterVolume as usize].current
+    }
+
+    /// Ramps a continuous parameter from its current value to `target`.
+    fn smooth_param(&mut self, param: SmoothedParam, target: f32) {
+        let samples = (self.param_smoothing_ms * 0.001 * self.sample_rate) as u32;
+        self.ramp_param(param, target, samples);
+    }
+
+    /// Ramps a continuous parameter over `samples` samples (0 = jump).
+    fn ramp_param(&mut self, param: SmoothedParam, target: f32, samples: u32) {
+        let current = self.param_value(param);
+        self.param_ramps[param as usize].start(current, target, samples);
+        if samples == 0 {
+            self.write_param(param, target);
+        }
+    }
+
+    /// Cancels running ramps, settling each at the value in use.
+    fn settle_param_ramps(&mut self) {
+        for param in SmoothedParam::ALL {
+            let value = match param {
+                SmoothedParam::MasterVolume => self.master_volume,
+                _ => self.param_value(param),
+            };
+            self.param_ramps[param as usize] = ParamRamp::new(value);
+        }
+    }
+
+    /// Ends running ramps by jumping each parameter to its target.
+    fn finish_param_ramps(&mut self) {
+        for param in SmoothedParam::ALL {
+            let ramp = self.param_ramps[param as usize];
+            if !ramp.is_settled() {
+                self.ramp_param(param, ramp.target, 0);
+            }
+        }
+    }
+
+    /// Moves every running parameter ramp one sample forward.
+    fn advance_param_ramps(&mut self) {
+        for param in SmoothedParam::ALL {
+            let ramp = &mut self.param_ramps[param as usize];
+            if !ramp.is_settled() {
+                let value = ramp.next();
+                self.write_param(param, value);
+            }
+        }
+    }
+
+    /// Gets the value a smoothed parameter is running at.
+    fn param_value(&self, param: SmoothedParam) -> f32 {
+        let zdf_filter = &self.voice_filter.zdf_filter;
+        let chain = &self.chain;
+        match param {
+            SmoothedParam::MasterVolume => self.param_ramps[param as usize].current,
+            SmoothedParam::ZdfCutoff => zdf_filter.cutoff(),
+            SmoothedParam::ZdfResonance => zdf_filter.resonance(),
+            SmoothedParam::ZdfDrive => zdf_filter.drive(),
+            SmoothedParam::SaturationDrive => chain.saturation.drive(),
+            SmoothedParam::SaturationMix => chain.saturation.mix(),
+        }
+    }
+
+    /// Writes a smoothed parameter value to the sound engine.
+    fn write_param(&mut self, param: SmoothedParam, value: f32) {
+        match param {
+            SmoothedParam::MasterVolume => self.param_ramps[param as usize].current = value,
+            SmoothedParam::ZdfCutoff => {
+                self.update_voice_filters(|filter| filter.zdf_filter.set_cutoff(value))
+            }
+            SmoothedParam::ZdfResonance => {
+                self.update_voice_filters(|filter| filter.zdf_filter.set_resonance(value))
+            }
+            SmoothedParam::ZdfDrive => {
+                self.update_voice_filters(|filter| filter.zdf_filter.set_drive(value))
+            }
+            SmoothedParam::SaturationDrive => {
+                self.chain.update_saturation(|sat| sat.set_drive(value))
+            }
+            SmoothedParam::SaturationMix => self.chain.update_saturation(|sat| sat.set_mix(value)),
+        }
+    }
+
+    /// Applies a filter setting to new voices and every allocated voice.
+    fn update_voice_filters(&mut self, update: impl Fn(&mut VoiceFilter)) {
+        update(&mut self.voice_filter);
+        for voice in &mut self.voices {
+            update(&mut voice.filter);
+        }
+    }
+
+    /// Configures the brickwall limiter on the master output.
+    ///
+    /// The limiter looks ahead 5ms (adding that much latency) so the
+    /// output never exceeds the ceiling. While disabled the output is
+    /// untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the limiter runs
+    /// * `ceiling_db` - Highest output level in dBFS (-30.0 to 0.0, default -0.3)
+    pub fn set_master_limiter(&mut self, enabled: bool, ceiling_db: f32) {
+        // Don't replay stale audio from the lookahead buffer
+        if enabled && !self.limiter.is_enabled() {
+            self.limiter.reset();
+        }
+        self.limiter.set_ceiling(ceiling_db);
+        self.limiter.set_enabled(enabled);
+    }
+
+    /// Checks if the master limiter is enabled.
+    pub fn master_limiter_enabled(&self) -> bool {
+        self.limiter.is_enabled()
+    }
+
+    /// Gets the master limiter ceiling in dBFS.
+    pub fn master_limiter_ceiling(&self) -> f32 {
+        self.limiter.ceiling()
+    }
+
+    /// Sets the waveform of one voice oscillator.
+    ///
+    /// Oscillator 0 is the main sawtooth; oscillator 1 is a sine that is
+    /// silent by default and mainly serves as an FM modulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Oscillator index (0 to `OSCILLATORS_PER_VOICE - 1`)
+    /// * `osc_type` - New oscillator type
+    pub fn set_oscillator_type(&mut self, index: usize, osc_type: OscillatorType) {
+        if index >= OSCILLATORS_PER_VOICE {
+            return;
+        }
+
+        let waveform = Waveform::from(osc_type);
+        self.oscillator_waveforms[index] = waveform;
+        for voice in &mut self.voices {
+            voice.oscillators[index].set_waveform(waveform);
+        }
+    }
+
+    /// Sets the mix level of one voice oscillator.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Oscillator index (0 to `OSCILLATORS_PER_VOICE - 1`)
+    /// * `level` - Mix level (0.0 to 1.0)
+    pub fn set_oscillator_level(&mut self, index: usize, level: f32) {
+        if let Some(slot) = self.oscillator_levels.get_mut(index) {
+            *slot = level.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Sets two-operator FM between voice oscillators.
+    ///
+    /// The modulator output offsets the carrier phase by up to `index`
+    /// radians at audio rate. Using the same oscillator as carrier and
+    /// modulator gives self-feedback FM. An index of 0 disables FM.
+    ///
+    /// # Arguments
+    ///
+    /// * `carrier` - Index of the oscillator being modulated
+    /// * `modulator` - Index of the modulating oscillator
+    /// * `index` - Modulation index (0.0 to `MAX_FM_INDEX`)
+    pub fn set_fm(&mut self, carrier: usize, modulator: usize, index: f32) {
+        if carrier >= OSCILLATORS_PER_VOICE || modulator >= OSCILLATORS_PER_VOICE {
+            return;
+        }
+
+        let index = index.clamp(0.0, MAX_FM_INDEX);
+        self.fm = (index > 0.0).then_some(FmRouting {
+            carrier,
+            modulator,
+            index,
+        });
+    }
+
+    /// Sets the filter cutoff frequency of every voice.
     ///
     /// # Arguments
     ///
     /// * `cutoff` - Cutoff frequency in Hz
     pub fn set_filter_cutoff(&mut self, cutoff: f32) {
-        self.filter.set_cutoff(cutoff);
+        self.update_voice_filters(|filter| filter.filter.set_cutoff(cutoff));
     }
 
-    /// Sets the global filter resonance.
+    /// Sets the filter resonance of every voice.
     ///
     /// # Arguments
     ///
     /// * `resonance` - Q value
     pub fn set_filter_resonance(&mut self, resonance: f32) {
-        self.filter.set_resonance(resonance);
+        self.update_voice_filters(|filter| filter.filter.set_resonance(resonance));
     }
 
-    /// Sets the global filter type.
+    /// Sets the filter type of every voice.
     ///
     /// # Arguments
     ///
     /// * `filter_type` - Type of filter
     pub fn set_filter_type(&mut self, filter_type: FilterType) {
-        self.filter.set_type(filter_type);
+        self.update_voice_filters(|filter| filter.filter.set_type(filter_type));
     }
 
     /// Sets the active effect type.
@@ -445,7 +1739,7 @@ impl Synth {
     ///
     /// * `effect_type` - Type of effect
     pub fn set_effect_type(&mut self, effect_type: EffectType) {
-        self.effects.set_effect_type(effect_type);
+        self.chain.effects.set_effect_type(effect_type);
     }
 
     /// Sets the effect mix.
@@ -454,7 +1748,49 @@ impl Synth {
     ///
     /// * `mix` - Wet/dry mix (0.0 to 1.0)
     pub fn set_effect_mix(&mut self, mix: f32) {
-        self.effects.set_mix(mix);
+        self.chain.effects.set_mix(mix);
+    }
+
+    /// Enables or disables the ring modulator.
+    ///
+    /// The ring modulator sits after the saturation and before the effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to enable the ring modulator
+    pub fn set_ring_mod_enabled(&mut self, enabled: bool) {
+        self.chain.ring_mod_enabled = enabled;
+    }
+
+    /// Checks if the ring modulator is enabled.
+    pub fn ring_mod_enabled(&self) -> bool {
+        self.chain.ring_mod_enabled
+    }
+
+    /// Sets the fixed ring modulator carrier frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq` - Carrier frequency in Hz (20 to 2000), used while tracking is off
+    pub fn set_ring_mod_frequency(&mut self, freq: f32) {
+        self.chain.ring_modulator.set_osc_frequency(freq);
+    }
+
+    /// Sets the ring modulator carrier tracking.
+    ///
+    /// While tracking, the carrier runs at the last played note's frequency
+    /// times `ratio`, which gives harmonic rather than clangorous sidebands.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - Carrier to note frequency ratio (0.0 = off, up to 8.0)
+    pub fn set_ring_mod_carrier_tracking(&mut self, ratio: f32) {
+        self.chain.ring_modulator.set_carrier_tracking(ratio);
+    }
+
+    /// Gets the ring modulator carrier frequency in Hz.
+    pub fn ring_mod_carrier_frequency(&self) -> f32 {
+        self.chain.ring_modulator.carrier_frequency()
     }
 
     // ===== Virtual Analog Feature Controls =====
@@ -477,16 +1813,41 @@ impl Synth {
     ///
     /// * `cutoff` - Cutoff frequency in Hz (20 to 20000)
     pub fn set_zdf_cutoff(&mut self, cutoff: f32) {
-        self.zdf_filter.set_cutoff(cutoff);
+        self.smooth_param(SmoothedParam::ZdfCutoff, cutoff);
+    }
+
+    /// Gets the ZDF filter cutoff frequency in Hz.
+    pub fn zdf_cutoff(&self) -> f32 {
+        self.voice_filter.zdf_filter.cutoff()
+    }
+
+    /// Sets the ZDF filter keyboard tracking amount.
+    ///
+    /// Each voice's cutoff tracks the note that voice plays.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Keytrack amount (0.0 = off, 1.0 = cutoff follows pitch 1:1)
+    pub fn set_zdf_keytrack(&mut self, amount: f32) {
+        self.update_voice_filters(|filter| filter.zdf_filter.set_keytrack(amount));
     }
 
     /// Sets the ZDF filter resonance.
     ///
     /// # Arguments
     ///
-    /// * `resonance` - Resonance value (0.0 to ~4.0)
+    /// * `resonance` - Resonance value (0.0 to 4.5; self-oscillates from 4.0)
     pub fn set_zdf_resonance(&mut self, resonance: f32) {
-        self.zdf_filter.set_resonance(resonance);
+        self.smooth_param(SmoothedParam::ZdfResonance, resonance);
+    }
+
+    /// Enables or disables ZDF filter resonance gain compensation.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to keep the passband level as resonance rises
+    pub fn set_zdf_gain_comp(&mut self, enabled: bool) {
+        self.update_voice_filters(|filter| filter.zdf_filter.set_gain_comp(enabled));
     }
 
     /// Sets the ZDF filter drive amount.
@@ -495,7 +1856,7 @@ impl Synth {
     ///
     /// * `drive` - Drive amount (0.0 to ~10.0)
     pub fn set_zdf_drive(&mut self, drive: f32) {
-        self.zdf_filter.set_drive(drive);
+        self.smooth_param(SmoothedParam::ZdfDrive, drive);
     }
 
     /// Sets the saturation drive amount.
@@ -504,7 +1865,7 @@ impl Synth {
     ///
     /// * `drive` - Drive amount (0.0 to ~10.0)
     pub fn set_saturation_drive(&mut self, drive: f32) {
-        self.saturation.set_drive(drive);
+        self.smooth_param(SmoothedParam::SaturationDrive, drive);
     }
 
     /// Sets the saturation mix.
@@ -513,7 +1874,121 @@ impl Synth {
     ///
     /// * `mix` - Wet/dry mix (0.0 = dry, 1.0 = fully saturated)
     pub fn set_saturation_mix(&mut self, mix: f32) {
-        self.saturation.set_mix(mix);
+        self.smooth_param(SmoothedParam::SaturationMix, mix);
+    }
+
+    /// Gets the descriptors of every parameter `set_parameter` accepts.
+    pub fn parameter_descriptors() -> &'static [ParameterDescriptor] {
+        &PARAMETER_DESCRIPTORS
+    }
+
+    /// Gets the descriptor of one parameter ID.
+    ///
+    /// # Returns
+    ///
+    /// The descriptor, or `None` if the ID is not a synth parameter
+    pub fn parameter_descriptor(param_id: i32) -> Option<&'static ParameterDescriptor> {
+        PARAMETER_DESCRIPTORS.iter().find(|d| d.id == param_id)
+    }
+
+    /// Sets a parameter by ID, recording it while automation records.
+    ///
+    /// # Arguments
+    ///
+    /// * `param_id` - One of the `parameter_descriptors` IDs (`PARAM_ZDF_CUTOFF`, ...)
+    /// * `value` - Value in the parameter's range (clamped)
+    ///
+    /// # Errors
+    ///
+    /// `ParameterError::UnknownParameter` if the ID is not a synth parameter
+    pub fn set_parameter(&mut self, param_id: i32, value: f32) -> Result<(), ParameterError> {
+        let descriptor = Self::parameter_descriptor(param_id)
+            .ok_or(ParameterError::UnknownParameter(param_id))?;
+        let value = value.clamp(descriptor.min, descriptor.max);
+        (descriptor.set)(self, value);
+
+        if let Some(lane) = &mut self.automation_recording {
+            lane.add_point(param_id, self.sample_position - self.recording_start, value);
+        }
+        Ok(())
+    }
+
+    /// Gets a parameter by ID.
+    ///
+    /// Smoothed parameters report the value they are heading to.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the ID is not a synth parameter
+    pub fn get_parameter(&self, param_id: i32) -> Option<f32> {
+        Self::parameter_descriptor(param_id).map(|descriptor| (descriptor.get)(self))
+    }
+
+    /// Gets the value a smoothed parameter is heading to.
+    fn param_target(&self, param: SmoothedParam) -> f32 {
+        self.param_ramps[param as usize].target
+    }
+
+    /// Starts recording `set_parameter` writes into a new automation lane.
+    ///
+    /// Positions are counted in rendered samples from this call. Starting
+    /// again discards the lane being recorded.
+    pub fn start_automation_recording(&mut self) {
+        self.recording_start = self.sample_position;
+        self.automation_recording = Some(AutomationLane::new());
+    }
+
+    /// Stops recording and returns the recorded lane.
+    ///
+    /// # Returns
+    ///
+    /// The recorded lane (empty if recording was not running)
+    pub fn stop_automation_recording(&mut self) -> AutomationLane {
+        self.automation_recording.take().unwrap_or_default()
+    }
+
+    /// Checks if parameter changes are being recorded.
+    pub fn is_recording_automation(&self) -> bool {
+        self.automation_recording.is_some()
+    }
+
+    /// Applies every parameter of a lane at a playback position.
+    ///
+    /// Continuous parameters are interpolated between recorded points and
+    /// applied without further smoothing, stepped ones hold their last
+    /// value. Parameters without points in the lane are left alone, so
+    /// lanes for different parameters can be applied one after another.
+    /// Playback is not recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `lane` - Recorded automation
+    /// * `sample_position` - Playback position in samples from the lane start
+    pub fn apply_automation(&mut self, lane: &AutomationLane, sample_position: u64) {
+        for param_id in lane.params() {
+            let Some(descriptor) = Self::parameter_descriptor(param_id) else {
+                continue;
+            };
+            let value = if descriptor.stepped {
+                lane.step_value_at(param_id, sample_position)
+            } else {
+                lane.value_at(param_id, sample_position)
+            };
+            let Some(value) = value else {
+                continue;
+            };
+
+            // The lane is already interpolated, so smoothed values jump
+            match descriptor.smoothed {
+                Some(param) => self.ramp_param(param, value, 0),
+                None => (descriptor.set)(self, value),
+            }
+        }
+    }
+
+    /// Gets the number of samples rendered since creation.
+    pub fn sample_position(&self) -> u64 {
+        self.sample_position
     }
 
     /// Sets the oscillator oversampling factor.
@@ -536,17 +2011,116 @@ impl Synth {
         self.oversample_factor
     }
 
+    // ===== A/B Comparison =====
+
+    /// Stores the current sound in an A/B slot.
+    ///
+    /// Everything a preset would hold is captured: oscillators, filters,
+    /// saturation, effects, LFOs, voice and glide settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - Slot to store into (becomes the active slot)
+    pub fn store_ab_slot(&mut self, slot: AbSlot) {
+        // Store the values running ramps are heading for
+        self.finish_param_ramps();
+        let mut voice_filter = self.voice_filter.clone();
+        voice_filter.reset();
+        let mut chain = self.chain.clone();
+        chain.reset();
+
+        self.ab_slots[slot.index()] = Some(SoundSnapshot {
+            voice_filter,
+            chain,
+            zdf_enabled: self.zdf_enabled,
+            lfos: self.lfos.clone(),
+            key_pan_spread: self.key_pan_spread,
+            master_volume: self.master_volume,
+            oversample_factor: self.oversample_factor,
+            oscillator_waveforms: self.oscillator_waveforms,
+            oscillator_levels: self.oscillator_levels,
+            fm: self.fm,
+            mono_mode: self.mono_mode,
+            glide_time: self.glide_time,
+            envelope_hold: self.envelope_hold,
+            max_voices: self.max_voices,
+            steal_policy: self.steal_policy,
+        });
+        self.ab_active = slot;
+    }
+
+    /// Restores the sound stored in an A/B slot.
+    ///
+    /// Effect tails are cleared. Recalling an empty slot does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - Slot to recall (becomes the active slot)
+    pub fn recall_ab_slot(&mut self, slot: AbSlot) {
+        let Some(snapshot) = self.ab_slots[slot.index()].clone() else {
+            return;
+        };
+
+        self.voice_filter = snapshot.voice_filter;
+        for voice in &mut self.voices {
+            voice.load_filter(&self.voice_filter);
+        }
+        self.chain = snapshot.chain;
+        self.zdf_enabled = snapshot.zdf_enabled;
+        self.lfos = snapshot.lfos;
+        self.key_pan_spread = snapshot.key_pan_spread;
+        self.master_volume = snapshot.master_volume;
+        // The recalled chain already holds its values
+        self.settle_param_ramps();
+        self.oversample_factor = snapshot.oversample_factor;
+        self.oscillator_waveforms = snapshot.oscillator_waveforms;
+        for voice in &mut self.voices {
+            for (osc, &waveform) in voice.oscillators.iter_mut().zip(&self.oscillator_waveforms) {
+                osc.set_waveform(waveform);
+            }
+        }
+        self.oscillator_levels = snapshot.oscillator_levels;
+        self.fm = snapshot.fm;
+        self.set_mono_mode(snapshot.mono_mode);
+        self.glide_time = snapshot.glide_time;
+        self.set_envelope_hold(snapshot.envelope_hold);
+        self.set_max_voices(snapshot.max_voices);
+        self.steal_policy = snapshot.steal_policy;
+        self.ab_active = slot;
+    }
+
+    /// Flips between the A and B sounds.
+    ///
+    /// The current sound is kept in the active slot first, so tweaks made
+    /// since the last store are not lost. If the other slot is empty it
+    /// receives a copy of the current sound.
+    pub fn swap_ab(&mut self) {
+        let current = self.ab_active;
+        let other = current.other();
+        if self.ab_slots[other.index()].is_none() {
+            self.store_ab_slot(other);
+            return;
+        }
+
+        self.store_ab_slot(current);
+        self.recall_ab_slot(other);
+    }
+
+    /// Gets the A/B slot the current sound belongs to.
+    pub fn active_ab_slot(&self) -> AbSlot {
+        self.ab_active
+    }
+
     /// Resets the synthesizer state.
     pub fn reset(&mut self) {
         for voice in &mut self.voices {
             voice.stop();
         }
-        self.voices.clear();
+        self.spare_voices.append(&mut self.voices);
         self.active_notes.clear();
-        self.filter.reset();
-        self.zdf_filter.reset();
-        self.saturation.reset();
-        self.effects.reset();
+        self.held_notes.clear();
+        self.chain.reset();
+        self.limiter.reset();
     }
 
     /// Gets the number of active voices.
@@ -554,6 +2128,11 @@ impl Synth {
         self.active_notes.len()
     }
 
+    /// Gets the number of sounding voices, including release tails.
+    pub fn sounding_voice_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.is_active()).count()
+    }
+
     // ===== AI Melody Generation Methods =====
 
     /// Generates a new melody based on the specified style.
@@ -892,4 +2471,787 @@ mod tests {
             level
         );
     }
+
+    // --- FM: index 0 is identical to no FM ---
+    #[test]
+    fn test_fm_zero_index_matches_plain() {
+        let mut plain = Synth::new(48000.0);
+        let mut fm = Synth::new(48000.0);
+        fm.set_fm(0, 1, 0.0);
+
+        plain.note_on(60, 100);
+        fm.note_on(60, 100);
+        assert_eq!(process_n(&mut plain, 2048), process_n(&mut fm, 2048));
+    }
+
+    // --- FM: a sine modulator changes the carrier timbre ---
+    #[test]
+    fn test_fm_changes_output() {
+        let mut plain = Synth::new(48000.0);
+        let mut fm = Synth::new(48000.0);
+        for synth in [&mut plain, &mut fm] {
+            synth.set_oscillator_type(0, OscillatorType::Sine);
+        }
+        fm.set_fm(0, 1, 3.0);
+
+        plain.note_on(60, 100);
+        fm.note_on(60, 100);
+        let a = process_n(&mut plain, 2048);
+        let b = process_n(&mut fm, 2048);
+        let diff: Vec<f32> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+        assert!(
+            rms(&diff) > 0.01,
+            "FM should audibly change the output, diff RMS={}",
+            rms(&diff)
+        );
+    }
+
+    // --- FM: self-feedback at max index stays bounded ---
+    #[test]
+    fn test_fm_feedback_bounded() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_oscillator_type(0, OscillatorType::Sine);
+        synth.set_fm(0, 0, MAX_FM_INDEX);
+        synth.note_on(60, 127);
+
+        let samples = process_n(&mut synth, 48000);
+        assert!(samples.iter().all(|s| s.is_finite() && s.abs() <= 1.5));
+        assert!(
+            rms(&samples) > 0.001,
+            "Feedback FM should still produce audio"
+        );
+    }
+
+    // --- ZDF keytracking follows the played note ---
+    #[test]
+    fn test_zdf_keytrack_follows_note() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_zdf_keytrack(1.0);
+
+        synth.note_on(48, 100);
+        synth.note_on(60, 100);
+
+        // Both held notes keep a cutoff keyed to their own pitch
+        let cutoff = |note| {
+            let voice = &synth.voices[synth.active_notes[&note]];
+            voice.filter.zdf_filter.effective_cutoff()
+        };
+        assert!((cutoff(60) / cutoff(48) - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_filter_settings_reach_sounding_voices() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_param_smoothing(0.0);
+        synth.note_on(60, 100);
+        synth.set_zdf_cutoff(500.0);
+        synth.note_on(64, 100);
+
+        for note in [60, 64] {
+            let voice = &synth.voices[synth.active_notes[&note]];
+            assert_eq!(voice.filter.zdf_filter.cutoff(), 500.0);
+        }
+    }
+
+    // --- Mono legato glide ---
+
+    /// Frequency of the single sounding voice in mono mode.
+    fn mono_frequency(synth: &Synth) -> f32 {
+        let voice_idx = *synth.active_notes.values().next().unwrap();
+        synth.voices[voice_idx].frequency()
+    }
+
+    #[test]
+    fn test_mono_glide_interpolates_frequency() {
+        let mut synth = Synth::new(1000.0);
+        synth.set_mono_mode(true);
+        synth.set_glide_time(0.1);
+
+        synth.note_on(48, 100);
+        // Past the 10ms attack
+        process_n(&mut synth, 50);
+
+        synth.note_on(60, 100);
+        assert_eq!(synth.active_voice_count(), 1);
+
+        // Halfway through the 100-sample glide: halfway in pitch
+        process_n(&mut synth, 50);
+        let halfway = mono_frequency(&synth);
+        let expected = midi_to_frequency(54);
+        assert!(
+            (halfway - expected).abs() / expected < 0.01,
+            "expected ~{expected} Hz, got {halfway}"
+        );
+
+        process_n(&mut synth, 60);
+        assert!((mono_frequency(&synth) - midi_to_frequency(60)).abs() < 1e-2);
+
+        // Legato: the envelope was not retriggered
+        assert_eq!(synth.voices.len(), 1);
+        assert_ne!(
+            synth.voices[0].amplitude_envelope.stage(),
+            crate::envelope::EnvelopeStage::Attack
+        );
+    }
+
+    #[test]
+    fn test_envelope_hold_delays_decay() {
+        let mut synth = Synth::new(1000.0);
+        synth.set_envelope_hold(0.1);
+        synth.note_on(60, 100);
+
+        // 10ms attack, then 100ms at the peak
+        process_n(&mut synth, 50);
+        assert_eq!(
+            synth.voices[0].amplitude_envelope.stage(),
+            crate::envelope::EnvelopeStage::Hold
+        );
+        process_n(&mut synth, 70);
+        assert_eq!(
+            synth.voices[0].amplitude_envelope.stage(),
+            crate::envelope::EnvelopeStage::Decay
+        );
+    }
+
+    #[test]
+    fn test_mono_release_glides_back() {
+        let mut synth = Synth::new(1000.0);
+        synth.set_mono_mode(true);
+        synth.set_glide_time(0.1);
+
+        synth.note_on(48, 100);
+        synth.note_on(60, 100);
+        process_n(&mut synth, 200);
+
+        synth.note_off_specific(60);
+        assert_eq!(synth.active_voice_count(), 1);
+        process_n(&mut synth, 50);
+        let halfway = mono_frequency(&synth);
+        assert!(halfway < midi_to_frequency(60) && halfway > midi_to_frequency(48));
+
+        process_n(&mut synth, 60);
+        assert!((mono_frequency(&synth) - midi_to_frequency(48)).abs() < 1e-2);
+
+        // Releasing the last held note releases the voice
+        synth.note_off_specific(48);
+        assert_eq!(synth.active_voice_count(), 0);
+    }
+
+    #[test]
+    fn test_mono_zero_glide_jumps() {
+        let mut synth = Synth::new(1000.0);
+        synth.set_mono_mode(true);
+        synth.set_glide_time(0.0);
+
+        synth.note_on(48, 100);
+        synth.note_on(60, 100);
+        assert_eq!(mono_frequency(&synth), midi_to_frequency(60));
+    }
+
+    // --- Voice allocation and stealing policies ---
+
+    /// Plays three notes with the given velocities into a 3-voice synth.
+    fn full_pool(policy: VoiceStealPolicy, velocities: [u8; 3]) -> Synth {
+        let mut synth = Synth::new(1000.0);
+        synth.set_max_voices(3);
+        synth.set_steal_policy(policy);
+        for (note, velocity) in [60, 64, 67].into_iter().zip(velocities) {
+            synth.note_on(note, velocity);
+            process_n(&mut synth, 10);
+        }
+        // Let every envelope settle at sustain
+        process_n(&mut synth, 500);
+        synth
+    }
+
+    #[test]
+    fn test_steal_policy_oldest() {
+        let mut synth = full_pool(VoiceStealPolicy::Oldest, [100, 40, 100]);
+        synth.note_on(72, 100);
+
+        assert_eq!(synth.voices.len(), 3);
+        assert!(!synth.active_notes.contains_key(&60));
+        assert!(synth.active_notes.contains_key(&64));
+        assert!(synth.active_notes.contains_key(&72));
+    }
+
+    #[test]
+    fn test_steal_policy_quietest() {
+        let mut synth = full_pool(VoiceStealPolicy::Quietest, [100, 40, 100]);
+        synth.note_on(72, 100);
+
+        assert!(synth.active_notes.contains_key(&60));
+        assert!(!synth.active_notes.contains_key(&64));
+        assert!(synth.active_notes.contains_key(&72));
+    }
+
+    #[test]
+    fn test_steal_policy_lowest_priority() {
+        let mut synth = full_pool(VoiceStealPolicy::LowestPriority, [100, 127, 30]);
+        synth.note_on(72, 100);
+
+        assert!(synth.active_notes.contains_key(&64));
+        assert!(!synth.active_notes.contains_key(&67));
+        assert_eq!(synth.active_voice_count(), 3);
+    }
+
+    #[test]
+    fn test_releasing_voice_stolen_before_held() {
+        let mut synth = full_pool(VoiceStealPolicy::Oldest, [100, 100, 100]);
+        synth.note_off_specific(67);
+        process_n(&mut synth, 10);
+        synth.note_on(72, 100);
+
+        // The oldest held note survives; the release tail was taken
+        assert!(synth.active_notes.contains_key(&60));
+        assert!(synth.active_notes.contains_key(&64));
+        assert_eq!(synth.sounding_voice_count(), 3);
+    }
+
+    #[test]
+    fn test_released_voice_freed_after_envelope() {
+        let mut synth = Synth::new(48000.0);
+        synth.note_on(60, 100);
+        process_n(&mut synth, 4800);
+        synth.note_off_specific(60);
+
+        // Release tail keeps sounding
+        assert_eq!(synth.active_voice_count(), 0);
+        assert_eq!(synth.sounding_voice_count(), 1);
+        let tail = process_n(&mut synth, 2400);
+        assert!(rms(&tail) > 0.001, "release tail should be audible");
+
+        // 0.3s release (from sustain; longer from mid-decay)
+        process_n(&mut synth, 24000);
+        assert_eq!(synth.sounding_voice_count(), 0);
+
+        // The finished voice is recycled instead of growing the pool
+        synth.note_on(64, 100);
+        assert_eq!(synth.voices.len(), 1);
+    }
+
+    #[test]
+    fn test_retrigger_reuses_own_voice() {
+        let mut synth = Synth::new(1000.0);
+        synth.note_on(60, 100);
+        process_n(&mut synth, 100);
+        synth.note_on(60, 100);
+        assert_eq!(synth.voices.len(), 1);
+
+        // Also while the note is releasing
+        synth.note_off_specific(60);
+        process_n(&mut synth, 10);
+        synth.note_on(60, 100);
+        assert_eq!(synth.voices.len(), 1);
+        assert_eq!(synth.sounding_voice_count(), 1);
+    }
+
+    #[test]
+    fn test_set_max_voices_limits() {
+        let mut synth = Synth::new(1000.0);
+        assert_eq!(synth.max_voices(), DEFAULT_MAX_VOICES);
+        synth.set_max_voices(0);
+        assert_eq!(synth.max_voices(), 1);
+        synth.set_max_voices(1000);
+        assert_eq!(synth.max_voices(), MAX_VOICES);
+
+        for note in 60..70 {
+            synth.note_on(note, 100);
+        }
+        synth.set_max_voices(4);
+        assert_eq!(synth.voices.len(), 4);
+        assert_eq!(synth.active_voice_count(), 4);
+    }
+
+    // --- Key pan spread ---
+
+    /// Renders a note alone and returns the (left, right) RMS.
+    fn stereo_rms(spread: f32, note: u8) -> (f32, f32) {
+        let mut synth = Synth::new(48000.0);
+        synth.set_key_pan_spread(spread);
+        synth.note_on(note, 100);
+
+        let mut left = vec![0.0; 4800];
+        let mut right = vec![0.0; 4800];
+        synth.render_stereo(&mut left, &mut right);
+        (rms(&left), rms(&right))
+    }
+
+    #[test]
+    fn test_key_pan_spread_places_notes_on_opposite_sides() {
+        let (low_l, low_r) = stereo_rms(1.0, 24);
+        let (high_l, high_r) = stereo_rms(1.0, 96);
+
+        assert!(
+            low_l > low_r * 2.0,
+            "low note should be left: {low_l} / {low_r}"
+        );
+        assert!(
+            high_r > high_l * 2.0,
+            "high note should be right: {high_l} / {high_r}"
+        );
+    }
+
+    #[test]
+    fn test_key_pan_spread_zero_is_centered() {
+        let mut synth = Synth::new(48000.0);
+        synth.note_on(36, 100);
+        synth.note_on(84, 100);
+
+        let mut left = vec![0.0; 4800];
+        let mut right = vec![0.0; 4800];
+        synth.render_stereo(&mut left, &mut right);
+        assert!(rms(&left) > 0.001);
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn test_render_stereo_runs_one_chain() {
+        let setup = |synth: &mut Synth| {
+            synth.set_effect_type(EffectType::Delay);
+            synth.set_ring_mod_enabled(true);
+            synth.set_ring_mod_frequency(300.0);
+            synth.note_on(60, 100);
+        };
+        let mut stereo = Synth::new(48000.0);
+        let mut mono = Synth::new(48000.0);
+        setup(&mut stereo);
+        setup(&mut mono);
+
+        let mut left = vec![0.0; 4800];
+        let mut right = vec![0.0; 4800];
+        stereo.render_stereo(&mut left, &mut right);
+        let mut expected = vec![0.0; 4800];
+        mono.render_block(&mut expected);
+
+        // The stereo path advances the ring carrier and delay once per frame
+        assert_eq!(left, right);
+        assert_eq!(left, expected);
+    }
+
+    #[test]
+    fn test_key_pan_fixed_at_note_on() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_key_pan_spread(0.5);
+        assert_eq!(synth.key_pan_spread(), 0.5);
+        synth.note_on(84, 100);
+        let idx = synth.active_notes[&84];
+        assert!((synth.voices[idx].pan - 0.25).abs() < 1e-6);
+
+        synth.set_key_pan_spread(0.0);
+        assert!((synth.voices[idx].pan - 0.25).abs() < 1e-6);
+        synth.set_key_pan_spread(3.0);
+        assert_eq!(synth.key_pan_spread(), 1.0);
+    }
+
+    // --- A/B comparison ---
+
+    /// Debug text of the sound-defining state, for comparing A/B recalls.
+    fn sound_fingerprint(synth: &Synth) -> String {
+        format!(
+            "{:?} {:?} {:?} {:?} {}",
+            synth.voice_filter.filter,
+            synth.oscillator_waveforms,
+            synth.oscillator_levels,
+            synth.master_volume,
+            synth.glide_time
+        )
+    }
+
+    #[test]
+    fn test_ab_store_and_swap() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_filter_cutoff(800.0);
+        synth.set_master_volume(0.4);
+        synth.store_ab_slot(AbSlot::A);
+        let sound_a = sound_fingerprint(&synth);
+
+        synth.set_filter_cutoff(5000.0);
+        synth.set_oscillator_type(0, OscillatorType::Square);
+        synth.set_oscillator_level(1, 0.5);
+        synth.set_glide_time(0.2);
+        synth.store_ab_slot(AbSlot::B);
+        let sound_b = sound_fingerprint(&synth);
+        assert_ne!(sound_a, sound_b);
+
+        synth.swap_ab();
+        assert_eq!(synth.active_ab_slot(), AbSlot::A);
+        assert_eq!(sound_fingerprint(&synth), sound_a);
+        assert_eq!(synth.master_volume, 0.4);
+
+        synth.swap_ab();
+        assert_eq!(synth.active_ab_slot(), AbSlot::B);
+        assert_eq!(sound_fingerprint(&synth), sound_b);
+        assert_eq!(synth.oscillator_waveforms[0], Waveform::Square);
+    }
+
+    #[test]
+    fn test_ab_store_mid_ramp_keeps_targets() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_param_smoothing(100.0);
+        synth.set_zdf_cutoff(4000.0);
+        synth.set_saturation_drive(3.0);
+        process_n(&mut synth, 100);
+        synth.store_ab_slot(AbSlot::A);
+
+        synth.set_zdf_cutoff(200.0);
+        synth.set_saturation_drive(0.5);
+        synth.recall_ab_slot(AbSlot::A);
+        assert_eq!(synth.zdf_cutoff(), 4000.0);
+        assert_eq!(synth.param_value(SmoothedParam::SaturationDrive), 3.0);
+    }
+
+    #[test]
+    fn test_ab_swap_keeps_tweaks_and_fills_empty_slot() {
+        let mut synth = Synth::new(48000.0);
+
+        // Empty slot: recall does nothing
+        synth.set_master_volume(0.3);
+        synth.recall_ab_slot(AbSlot::B);
+        assert_eq!(synth.master_volume, 0.3);
+
+        // Only A stored: swap copies the current sound into B
+        synth.store_ab_slot(AbSlot::A);
+        synth.swap_ab();
+        assert_eq!(synth.active_ab_slot(), AbSlot::B);
+        assert_eq!(synth.master_volume, 0.3);
+
+        // Tweaks to B survive a round trip through A
+        synth.set_master_volume(0.9);
+        synth.swap_ab();
+        assert_eq!(synth.master_volume, 0.3);
+        synth.swap_ab();
+        assert_eq!(synth.master_volume, 0.9);
+    }
+
+    #[test]
+    fn test_render_block_is_continuous_across_calls() {
+        let mut whole = Synth::new(48000.0);
+        let mut split = Synth::new(48000.0);
+        whole.queue_note_on(60, 100);
+        split.queue_note_on(60, 100);
+
+        let mut expected = vec![0.0; 1024];
+        whole.render_block(&mut expected);
+
+        let mut rendered = vec![0.0; 1024];
+        for block in rendered.chunks_mut(100) {
+            split.render_block(block);
+        }
+
+        assert_eq!(rendered, expected);
+        assert!(rendered.iter().any(|s| s.abs() > 0.01));
+    }
+
+    #[test]
+    fn test_render_block_applies_events_at_block_start() {
+        let mut synth = Synth::new(48000.0);
+        assert!(synth.queue_note_on(60, 100));
+        assert_eq!(synth.queued_events(), 1);
+        assert!(synth.active_notes.is_empty());
+
+        // A zero-length block still applies the queue
+        synth.render_block(&mut []);
+        assert_eq!(synth.queued_events(), 0);
+        assert!(synth.active_notes.contains_key(&60));
+
+        synth.queue_note_off(60);
+        let mut block = [0.0; 64];
+        synth.render_block(&mut block);
+        assert!(synth.active_notes.is_empty());
+    }
+
+    #[test]
+    fn test_event_queue_drops_events_when_full() {
+        let mut synth = Synth::new(48000.0);
+        for i in 0..EVENT_QUEUE_CAPACITY {
+            assert!(synth.queue_note_off((i % 128) as u8));
+        }
+        assert!(!synth.queue_note_on(60, 100));
+        assert_eq!(synth.queued_events(), EVENT_QUEUE_CAPACITY);
+        assert_eq!(synth.event_queue.capacity(), EVENT_QUEUE_CAPACITY);
+    }
+
+    #[test]
+    fn test_master_limiter_caps_hot_output() {
+        let stack = |synth: &mut Synth| {
+            synth.set_master_volume(1.0);
+            synth.set_zdf_enabled(false);
+            for note in [36, 40, 43, 48, 52, 55, 60, 64, 67, 72, 76, 79] {
+                synth.note_on(note, 127);
+            }
+            let mut left = vec![0.0; 9600];
+            let mut right = vec![0.0; 9600];
+            synth.render_stereo(&mut left, &mut right);
+            left.iter()
+                .chain(&right)
+                .fold(0.0f32, |m, x| m.max(x.abs()))
+        };
+
+        let mut unlimited = Synth::new(48000.0);
+        assert!(stack(&mut unlimited) > 1.0);
+
+        let mut limited = Synth::new(48000.0);
+        limited.set_master_limiter(true, -1.0);
+        let ceiling = 10.0f32.powf(-1.0 / 20.0);
+        let peak = stack(&mut limited);
+        assert!(peak <= ceiling, "peak {peak}");
+        assert!(peak > ceiling * 0.9);
+    }
+
+    #[test]
+    fn test_master_limiter_disabled_is_transparent() {
+        let mut plain = Synth::new(48000.0);
+        let mut toggled = Synth::new(48000.0);
+        assert!(!toggled.master_limiter_enabled());
+        assert_eq!(toggled.master_limiter_ceiling(), -0.3);
+
+        toggled.set_master_limiter(true, -6.0);
+        toggled.set_master_limiter(false, -6.0);
+        plain.note_on(60, 127);
+        toggled.note_on(60, 127);
+
+        let mut expected = vec![0.0; 2048];
+        let mut rendered = vec![0.0; 2048];
+        plain.render_block(&mut expected);
+        toggled.render_block(&mut rendered);
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_automation_records_and_plays_back_cutoff_sweep() {
+        let mut synth = Synth::new(48000.0);
+        synth.note_on(60, 100);
+        let mut block = [0.0; 100];
+        synth.render_block(&mut block);
+
+        // Sweep 200 Hz -> 2000 Hz in 100-sample steps, resonance once
+        synth.start_automation_recording();
+        assert!(synth.is_recording_automation());
+        for step in 0..10 {
+            assert!(synth
+                .set_parameter(PARAM_ZDF_CUTOFF, 200.0 + step as f32 * 200.0)
+                .is_ok());
+            if step == 5 {
+                synth.set_parameter(PARAM_ZDF_RES, 2.0).unwrap();
+            }
+            synth.render_block(&mut block);
+        }
+        let lane = synth.stop_automation_recording();
+        assert!(!synth.is_recording_automation());
+
+        assert_eq!(lane.points(PARAM_ZDF_CUTOFF).len(), 10);
+        assert_eq!(lane.points(PARAM_ZDF_CUTOFF)[3].sample_position, 300);
+        assert_eq!(lane.points(PARAM_ZDF_RES)[0].sample_position, 500);
+        assert_eq!(lane.length(), 900);
+
+        // Not recorded any more
+        synth.set_parameter(PARAM_ZDF_CUTOFF, 5000.0).unwrap();
+        assert_eq!(lane.points(PARAM_ZDF_CUTOFF).len(), 10);
+
+        let mut playback = Synth::new(48000.0);
+        for (position, expected) in [(0, 200.0), (300, 800.0), (350, 900.0), (2000, 2000.0)] {
+            playback.apply_automation(&lane, position);
+            assert!(
+                (playback.zdf_cutoff() - expected).abs() < 1e-3,
+                "at {position}: {} != {expected}",
+                playback.zdf_cutoff()
+            );
+        }
+    }
+
+    #[test]
+    fn test_automation_lanes_for_different_params_overlap() {
+        let mut synth = Synth::new(48000.0);
+        synth.start_automation_recording();
+        synth.set_parameter(PARAM_ZDF_CUTOFF, 500.0).unwrap();
+        let cutoff_lane = synth.stop_automation_recording();
+
+        synth.start_automation_recording();
+        synth.set_parameter(PARAM_ZDF_ENABLED, 0.0).unwrap();
+        synth.process_block_mono(100);
+        synth.set_parameter(PARAM_ZDF_ENABLED, 1.0).unwrap();
+        let switch_lane = synth.stop_automation_recording();
+        assert!(synth.set_parameter(PARAM_MELODY_STYLE, 1.0).is_err());
+
+        let mut playback = Synth::new(48000.0);
+        playback.apply_automation(&cutoff_lane, 60);
+        playback.apply_automation(&switch_lane, 60);
+        assert_eq!(playback.zdf_cutoff(), 500.0);
+        // Switches don't interpolate
+        assert!(!playback.zdf_enabled);
+        playback.apply_automation(&switch_lane, 100);
+        assert!(playback.zdf_enabled);
+        assert_eq!(playback.zdf_cutoff(), 500.0);
+    }
+
+    #[test]
+    fn test_param_smoothing_ramps_volume_over_window() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_param_smoothing(10.0);
+        synth.set_master_volume(0.2);
+
+        // 10 ms at 48 kHz: 480 samples from 0.7 down to 0.2
+        let mut volumes = Vec::new();
+        for _ in 0..600 {
+            synth.process_stereo();
+            volumes.push(synth.smoothed_master_volume());
+        }
+
+        assert!((volumes[239] - 0.45).abs() < 1e-4, "{}", volumes[239]);
+        assert!(volumes.windows(2).take(479).all(|w| w[1] < w[0]));
+        // Exactly on target at the end of the window, and it stays there
+        assert_eq!(volumes[479], 0.2);
+        assert!(volumes[479..].iter().all(|&v| v == 0.2));
+        assert_eq!(synth.master_volume, 0.2);
+    }
+
+    #[test]
+    fn test_param_smoothing_ramps_cutoff_and_can_be_disabled() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_param_smoothing(1.0);
+        synth.set_zdf_cutoff(2000.0);
+        assert_eq!(synth.zdf_cutoff(), 1000.0);
+
+        let mut block = [0.0; 24];
+        synth.render_block(&mut block);
+        assert!((synth.zdf_cutoff() - 1500.0).abs() < 0.1);
+        synth.render_block(&mut block);
+        assert_eq!(synth.zdf_cutoff(), 2000.0);
+
+        // A new target mid-ramp starts from the value in use
+        synth.set_zdf_cutoff(1000.0);
+        synth.render_block(&mut block);
+        synth.set_zdf_cutoff(3000.0);
+        assert!((synth.zdf_cutoff() - 1500.0).abs() < 0.1);
+
+        synth.set_param_smoothing(0.0);
+        synth.set_zdf_cutoff(400.0);
+        assert_eq!(synth.zdf_cutoff(), 400.0);
+        synth.render_block(&mut block);
+        assert_eq!(synth.zdf_cutoff(), 400.0);
+    }
+
+    #[test]
+    fn test_parameters_round_trip_by_id() {
+        let mut synth = Synth::new(48000.0);
+        let values = [0.0, 440.0, 2.5, 3.0, 7.5, 0.25, 4.0, 1.5];
+
+        for (descriptor, &value) in Synth::parameter_descriptors().iter().zip(&values) {
+            let id = descriptor.id;
+            assert!(value >= descriptor.min && value <= descriptor.max);
+            synth.set_parameter(id, value).unwrap();
+            assert_eq!(synth.get_parameter(id), Some(value), "param {id}");
+        }
+
+        // Smoothed parameters reach the same value in the engine
+        synth.process_block_mono(1000);
+        assert_eq!(synth.zdf_cutoff(), 440.0);
+        assert_eq!(synth.oversample_factor(), OversampleFactor::X4);
+        assert!(!synth.zdf_enabled);
+    }
+
+    #[test]
+    fn test_parameters_clamp_and_reject_unknown_ids() {
+        let mut synth = Synth::new(48000.0);
+
+        synth.set_parameter(PARAM_ZDF_CUTOFF, 1e6).unwrap();
+        assert_eq!(synth.get_parameter(PARAM_ZDF_CUTOFF), Some(20000.0));
+        synth.set_parameter(PARAM_SATURATION_MIX, -1.0).unwrap();
+        assert_eq!(synth.get_parameter(PARAM_SATURATION_MIX), Some(0.0));
+        synth.set_parameter(PARAM_OVERSAMPLE, 100.0).unwrap();
+        assert_eq!(synth.get_parameter(PARAM_OVERSAMPLE), Some(8.0));
+        synth.set_parameter(PARAM_ZDF_ENABLED, 0.7).unwrap();
+        assert_eq!(synth.get_parameter(PARAM_ZDF_ENABLED), Some(1.0));
+
+        for id in [
+            PARAM_MELODY_STYLE,
+            PARAM_MELODY_GENERATE,
+            PARAM_MELODY_LENGTH,
+            -1,
+        ] {
+            assert_eq!(
+                synth.set_parameter(id, 0.5),
+                Err(ParameterError::UnknownParameter(id))
+            );
+            assert_eq!(synth.get_parameter(id), None);
+            assert!(Synth::parameter_descriptor(id).is_none());
+        }
+    }
+
+    #[test]
+    fn test_parameter_descriptors_cover_every_param() {
+        let synth = Synth::new(48000.0);
+        let descriptors = Synth::parameter_descriptors();
+        let all_params = [
+            PARAM_ZDF_ENABLED,
+            PARAM_ZDF_CUTOFF,
+            PARAM_ZDF_RES,
+            PARAM_ZDF_DRIVE,
+            PARAM_SATURATION_DRIVE,
+            PARAM_SATURATION_MIX,
+            PARAM_OVERSAMPLE,
+            PARAM_ZDF_KEYTRACK,
+        ];
+
+        assert_eq!(descriptors.len(), all_params.len());
+        for id in all_params {
+            let descriptor = Synth::parameter_descriptor(id).unwrap();
+            assert!(!descriptor.name.is_empty());
+            assert!(descriptor.min < descriptor.max, "{}", descriptor.name);
+            assert!(
+                (descriptor.min..=descriptor.max).contains(&descriptor.default),
+                "{} default out of range",
+                descriptor.name
+            );
+            // Defaults describe a new synth
+            assert_eq!(
+                synth.get_parameter(id),
+                Some(descriptor.default),
+                "{}",
+                descriptor.name
+            );
+        }
+
+        let cutoff = Synth::parameter_descriptor(PARAM_ZDF_CUTOFF).unwrap();
+        assert!(cutoff.logarithmic);
+        assert_eq!(cutoff.unit, "Hz");
+        assert!(
+            Synth::parameter_descriptor(PARAM_ZDF_ENABLED)
+                .unwrap()
+                .stepped
+        );
+    }
+
+    // --- Ring modulator carrier tracks the played note ---
+    #[test]
+    fn test_ring_mod_carrier_tracks_note() {
+        let mut synth = Synth::new(48000.0);
+        synth.set_ring_mod_enabled(true);
+        synth.set_ring_mod_frequency(300.0);
+
+        // Tracking off: the fixed carrier ignores notes
+        synth.note_on(60, 100);
+        assert_eq!(synth.ring_mod_carrier_frequency(), 300.0);
+
+        synth.set_ring_mod_carrier_tracking(1.5);
+        let expected = midi_to_frequency(60) * 1.5;
+        assert!((synth.ring_mod_carrier_frequency() - expected).abs() < 1e-3);
+
+        synth.note_on(72, 100);
+        let expected = midi_to_frequency(72) * 1.5;
+        assert!((synth.ring_mod_carrier_frequency() - expected).abs() < 1e-3);
+
+        // Legato glides re-key the carrier too
+        synth.note_off();
+        synth.set_mono_mode(true);
+        synth.note_on(48, 100);
+        synth.note_on(55, 100);
+        let expected = midi_to_frequency(55) * 1.5;
+        assert!((synth.ring_mod_carrier_frequency() - expected).abs() < 1e-3);
+
+        synth.set_ring_mod_carrier_tracking(0.0);
+        assert_eq!(synth.ring_mod_carrier_frequency(), 300.0);
+        assert!(synth.process_mono().is_finite());
+    }
 }
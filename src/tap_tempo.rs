@@ -0,0 +1,176 @@
+//! Tap Tempo Module
+//!
+//! Learns a tempo from taps on a button or pad. The averaged tempo can be
+//! handed to anything that takes a BPM: the rhythm generator, the
+//! arpeggiator, tempo-synced LFOs and delays.
+//!
+//! Taps are timestamped by the caller, so the helper works with any clock
+//! and can be driven deterministically in tests.
+
+use std::time::{Duration, Instant};
+
+/// Tap intervals needed before a tempo is reported
+const MIN_INTERVALS: usize = 2;
+
+/// Most recent tap intervals averaged into the tempo
+const MAX_INTERVALS: usize = 8;
+
+/// An interval this far off the average (as a ratio) is treated as a mistap
+const OUTLIER_RATIO: f32 = 1.5;
+
+/// Gap after which the next tap starts a new tempo
+pub const TAP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Averages tap intervals into a tempo.
+#[derive(Debug, Clone, Default)]
+pub struct TapTempo {
+    /// Time of the previous tap
+    last_tap: Option<Instant>,
+
+    /// Accepted intervals in seconds, oldest first
+    intervals: Vec<f32>,
+
+    /// Interval rejected as an outlier, kept to detect a real tempo change
+    rejected: Option<f32>,
+}
+
+impl TapTempo {
+    /// Creates a tap tempo with no taps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a tap.
+    ///
+    /// A tap more than `TAP_TIMEOUT` after the previous one starts over. An
+    /// interval far from the current average (an accidental long gap or a
+    /// double tap) is ignored; two such intervals in a row that agree with
+    /// each other are taken as a new tempo.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Time of the tap
+    pub fn tap(&mut self, now: Instant) {
+        let Some(last_tap) = self.last_tap.replace(now) else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(last_tap);
+        if elapsed > TAP_TIMEOUT {
+            self.intervals.clear();
+            self.rejected = None;
+            return;
+        }
+
+        let interval = elapsed.as_secs_f32();
+        if interval <= 0.0 {
+            return;
+        }
+        if let Some(average) = self.average_interval() {
+            if !Self::agrees(interval, average) {
+                match self.rejected.replace(interval) {
+                    Some(previous) if Self::agrees(interval, previous) => {
+                        self.intervals = vec![previous, interval];
+                        self.rejected = None;
+                    }
+                    _ => {}
+                }
+                return;
+            }
+        }
+
+        self.rejected = None;
+        if self.intervals.len() == MAX_INTERVALS {
+            self.intervals.remove(0);
+        }
+        self.intervals.push(interval);
+    }
+
+    /// Gets the tempo in beats per minute.
+    ///
+    /// # Returns
+    ///
+    /// The averaged tempo, or `None` until at least three taps have been
+    /// registered
+    pub fn bpm(&self) -> Option<f32> {
+        if self.intervals.len() < MIN_INTERVALS {
+            return None;
+        }
+        self.average_interval().map(|interval| 60.0 / interval)
+    }
+
+    /// Forgets all taps.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Gets the mean of the accepted intervals in seconds.
+    fn average_interval(&self) -> Option<f32> {
+        if self.intervals.is_empty() {
+            return None;
+        }
+        Some(self.intervals.iter().sum::<f32>() / self.intervals.len() as f32)
+    }
+
+    /// Checks if two intervals are within `OUTLIER_RATIO` of each other.
+    fn agrees(a: f32, b: f32) -> bool {
+        a.max(b) / a.min(b) < OUTLIER_RATIO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tap_at(tap_tempo: &mut TapTempo, start: Instant, offsets_ms: &[u64]) {
+        for &offset in offsets_ms {
+            tap_tempo.tap(start + Duration::from_millis(offset));
+        }
+    }
+
+    #[test]
+    fn test_four_taps_at_500ms_read_120_bpm() {
+        let mut tap_tempo = TapTempo::new();
+        let start = Instant::now();
+
+        tap_at(&mut tap_tempo, start, &[0, 500]);
+        assert_eq!(tap_tempo.bpm(), None);
+
+        tap_at(&mut tap_tempo, start, &[1000, 1500]);
+        let bpm = tap_tempo.bpm().unwrap();
+        assert!((bpm - 120.0).abs() < 0.1, "{bpm}");
+    }
+
+    #[test]
+    fn test_outlier_interval_is_ignored() {
+        let mut tap_tempo = TapTempo::new();
+        let start = Instant::now();
+
+        // A missed tap leaves one 1000ms gap
+        tap_at(&mut tap_tempo, start, &[0, 500, 1000, 2000, 2500]);
+        let bpm = tap_tempo.bpm().unwrap();
+        assert!((bpm - 120.0).abs() < 0.1, "{bpm}");
+
+        // Two agreeing intervals at a new tempo take over
+        tap_at(&mut tap_tempo, start, &[3250, 4000]);
+        let bpm = tap_tempo.bpm().unwrap();
+        assert!((bpm - 80.0).abs() < 0.1, "{bpm}");
+    }
+
+    #[test]
+    fn test_timeout_starts_over() {
+        let mut tap_tempo = TapTempo::new();
+        let start = Instant::now();
+
+        tap_at(&mut tap_tempo, start, &[0, 500, 1000]);
+        assert!(tap_tempo.bpm().is_some());
+
+        tap_at(&mut tap_tempo, start, &[5000, 5400]);
+        assert_eq!(tap_tempo.bpm(), None);
+        tap_at(&mut tap_tempo, start, &[5800]);
+        let bpm = tap_tempo.bpm().unwrap();
+        assert!((bpm - 150.0).abs() < 0.1, "{bpm}");
+
+        tap_tempo.reset();
+        assert_eq!(tap_tempo.bpm(), None);
+    }
+}
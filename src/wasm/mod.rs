@@ -7,9 +7,13 @@
 //! - Memory sharing between Rust and JavaScript
 
 use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_4};
 use std::sync::atomic::{AtomicBool, Ordering};
 use wasm_bindgen::prelude::*;
 
+use crate::analysis::{magnitude_spectrum, WindowFn};
+use crate::filter::{BiquadFilter, FilterConfig, FilterType};
+
 #[cfg(feature = "wee_alloc")]
 use wee_alloc::WeeAlloc;
 
@@ -17,8 +21,8 @@ use wee_alloc::WeeAlloc;
 #[global_allocator]
 static ALLOC: WeeAlloc = WeeAlloc::INIT;
 
-/// Audio processing state
-static mut AUDIO_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Audio processing state, shared so the worklet can check it cheaply
+static AUDIO_RUNNING: AtomicBool = AtomicBool::new(false);
 
 /// Sample rate for audio processing
 const DEFAULT_SAMPLE_RATE: f64 = 48000.0;
@@ -26,6 +30,33 @@ const DEFAULT_SAMPLE_RATE: f64 = 48000.0;
 /// Buffer size for audio processing
 const DEFAULT_BUFFER_SIZE: usize = 128;
 
+/// Frames the host mixes per pass through its scratch buffers
+const SCRATCH_FRAMES: usize = DEFAULT_BUFFER_SIZE;
+
+/// Sequencer steps per beat (4 = 16th notes)
+const DEFAULT_STEPS_PER_BEAT: u32 = 4;
+
+/// Number of steps before the sequencer wraps
+const SEQUENCER_LENGTH: u16 = 256;
+
+/// Voice release time after note-off
+const DEFAULT_RELEASE_MS: f32 = 20.0;
+
+/// Longest voice attack time (ms)
+const MAX_ATTACK_MS: f32 = 2000.0;
+
+/// Longest voice release time (ms)
+const MAX_RELEASE_MS: f32 = 5000.0;
+
+/// FFT size for the track spectrum analyzer
+const DEFAULT_FFT_SIZE: usize = 256;
+
+/// Number of spectrum bins sent to the UI
+const DEFAULT_SPECTRUM_BINS: usize = 64;
+
+/// Floor for log-magnitude spectrum values (dB)
+const SPECTRUM_FLOOR_DB: f32 = -100.0;
+
 /// Message types for postMessage communication
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
@@ -50,6 +81,8 @@ pub enum AudioMessage {
     SetMasterVolume { volume: f32 },
     /// Set tempo (BPM)
     SetTempo { tempo: f32 },
+    /// Set sequencer resolution (steps per beat)
+    SetStepsPerBeat { steps_per_beat: u32 },
     /// Start playback
     Play,
     /// Stop playback
@@ -66,6 +99,12 @@ pub enum AudioMessage {
     },
     /// Clear sample
     ClearSample { track: u8 },
+    /// Configure the track spectrum analyzer
+    SetSpectrumSize {
+        track: u8,
+        fft_size: usize,
+        bins: usize,
+    },
     /// Get current state
     GetState,
 }
@@ -114,7 +153,7 @@ pub struct TrackState {
 }
 
 /// Parameter smoothing configuration
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SmoothingConfig {
     pub attack_ms: f32,
     pub release_ms: f32,
@@ -141,15 +180,13 @@ impl SmoothingConfig {
         }
     }
 
-    /// Calculate smoothing coefficient from time constant
-    pub fn coeff(&self, time_ms: f32, rising: bool) -> f32 {
-        let tau = time_ms / 1000.0;
-        let alpha = 1.0 - (-1.0 / (tau * self.sample_rate)).exp();
-        if rising {
-            alpha
-        } else {
-            alpha
-        }
+    /// Calculate smoothing coefficient from time constant.
+    ///
+    /// Rising and falling use the same one-pole curve; they differ only
+    /// through the time constant passed in (`attack_ms` vs `release_ms`).
+    pub fn coeff(&self, time_ms: f32, _rising: bool) -> f32 {
+        let tau = time_ms as f64 / 1000.0;
+        (1.0 - (-1.0 / (tau * self.sample_rate)).exp()) as f32
     }
 }
 
@@ -176,8 +213,19 @@ impl ParameterSmoother {
         self.target = target.clamp(0.0, 1.0);
     }
 
+    /// Whether the smoother has reached its target.
+    /// Callers can skip per-sample `process` calls while this is true.
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.current == self.target
+    }
+
     #[inline]
     pub fn process(&mut self) -> f32 {
+        if self.is_settled() {
+            return self.current;
+        }
+
         let diff = self.target - self.current;
         let coeff = if diff > 0.0 {
             self.coeff_rise
@@ -199,6 +247,12 @@ impl ParameterSmoother {
     }
 }
 
+impl Default for ParameterSmoother {
+    fn default() -> Self {
+        Self::new(SmoothingConfig::default())
+    }
+}
+
 /// Initialize the WASM module
 #[wasm_bindgen(start)]
 pub fn init() {
@@ -226,6 +280,12 @@ pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Whether the transport is running (audio is being mixed)
+#[wasm_bindgen]
+pub fn is_audio_running() -> bool {
+    AUDIO_RUNNING.load(Ordering::Acquire)
+}
+
 /// WasmAudioHost - Main interface for Web Audio integration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WasmAudioHost {
@@ -236,10 +296,27 @@ pub struct WasmAudioHost {
     smoother: ParameterSmoother,
     master_volume: f32,
     tempo: f32,
+    steps_per_beat: u32,
     playing: bool,
     recording: bool,
     current_step: u16,
+    #[serde(skip)]
+    step_accumulator: f64,
     tracks: Vec<WasmTrack>,
+    /// Scratch buffer each track renders into before mixing
+    #[serde(skip, default = "scratch_buffer")]
+    track_scratch: Vec<f32>,
+    /// Scratch stereo mix that `process` downmixes
+    #[serde(skip, default = "scratch_buffer")]
+    mix_left: Vec<f32>,
+    /// Right channel of the scratch mix
+    #[serde(skip, default = "scratch_buffer")]
+    mix_right: Vec<f32>,
+}
+
+/// One block of silence for the host's scratch buffers
+fn scratch_buffer() -> Vec<f32> {
+    vec![0.0; SCRATCH_FRAMES]
 }
 
 impl Default for WasmAudioHost {
@@ -257,42 +334,97 @@ impl WasmAudioHost {
             smoother: ParameterSmoother::new(SmoothingConfig::new(sample_rate)),
             master_volume: 0.8,
             tempo: 120.0,
+            steps_per_beat: DEFAULT_STEPS_PER_BEAT,
             playing: false,
             recording: false,
             current_step: 0,
+            step_accumulator: 0.0,
             tracks: (0..16).map(|i| WasmTrack::new(i, sample_rate)).collect(),
+            track_scratch: scratch_buffer(),
+            mix_left: scratch_buffer(),
+            mix_right: scratch_buffer(),
         }
     }
 
-    /// Process audio buffer (called from AudioWorklet)
+    /// Process audio buffer as a mono downmix of [`process_stereo`](Self::process_stereo).
+    /// A centred track keeps its level; hard-panned tracks are 3 dB down.
     pub fn process(&mut self, output: &mut [f32]) {
+        let mut mix_left = std::mem::take(&mut self.mix_left);
+        let mut mix_right = std::mem::take(&mut self.mix_right);
+
+        for block in output.chunks_mut(SCRATCH_FRAMES) {
+            let left = &mut mix_left[..block.len()];
+            let right = &mut mix_right[..block.len()];
+            self.process_stereo(left, right);
+
+            for (i, sample) in block.iter_mut().enumerate() {
+                *sample = (left[i] + right[i]) * FRAC_1_SQRT_2;
+            }
+        }
+
+        self.mix_left = mix_left;
+        self.mix_right = mix_right;
+    }
+
+    /// Process stereo audio buffers (called from AudioWorklet).
+    /// Each track is placed with an equal-power pan law.
+    pub fn process_stereo(&mut self, out_l: &mut [f32], out_r: &mut [f32]) {
+        let frames = out_l.len().min(out_r.len());
+        out_l.fill(0.0);
+        out_r.fill(0.0);
+
         if !self.playing {
             // Silence output when not playing
-            for sample in output.iter_mut() {
-                *sample = 0.0;
-            }
             return;
         }
 
-        // Mix all tracks
-        let mut mix = vec![0.0; output.len()];
+        // When any track is soloed only soloed tracks are heard (solo beats mute)
+        let any_solo = self.tracks.iter().any(|t| t.solo);
+
+        // Mix all tracks, one scratch block at a time
         for track in &mut self.tracks {
-            track.process(&mut mix);
+            let audible = if any_solo { track.solo } else { !track.muted };
+            let (gain_l, gain_r) = track.pan_gains();
+            for start in (0..frames).step_by(SCRATCH_FRAMES) {
+                let end = (start + SCRATCH_FRAMES).min(frames);
+                let rendered = &mut self.track_scratch[..end - start];
+                track.render(rendered);
+                if audible {
+                    for (i, &sample) in rendered.iter().enumerate() {
+                        out_l[start + i] += sample * gain_l;
+                        out_r[start + i] += sample * gain_r;
+                    }
+                }
+            }
         }
 
         // Apply master volume with smoothing
         let smoothed_vol = self.smoother.process();
-        for (i, sample) in output.iter_mut().enumerate() {
-            *sample = mix[i] * smoothed_vol;
+        for i in 0..frames {
+            out_l[i] *= smoothed_vol;
+            out_r[i] *= smoothed_vol;
         }
 
         // Advance step sequencer if needed
-        self.advance_sequencer(output.len());
+        self.advance_sequencer(frames);
+    }
+
+    /// Number of samples in one sequencer step at the current tempo
+    fn samples_per_step(&self) -> f64 {
+        let steps_per_second = self.tempo as f64 / 60.0 * self.steps_per_beat as f64;
+        self.sample_rate / steps_per_second
     }
 
-    fn advance_sequencer(&mut self, _frames: usize) {
-        // TODO: Implement step advancement based on tempo
-        self.current_step = (self.current_step + 1) % 256;
+    /// Advance the step counter by `frames` samples.
+    /// A single buffer may cross zero, one or several step boundaries.
+    fn advance_sequencer(&mut self, frames: usize) {
+        let samples_per_step = self.samples_per_step();
+        self.step_accumulator += frames as f64;
+
+        while self.step_accumulator >= samples_per_step {
+            self.step_accumulator -= samples_per_step;
+            self.current_step = (self.current_step + 1) % SEQUENCER_LENGTH;
+        }
     }
 
     /// Handle incoming message from JavaScript
@@ -352,12 +484,16 @@ impl WasmAudioHost {
             AudioMessage::SetTempo { tempo } => {
                 self.tempo = tempo.clamp(20.0, 300.0);
             }
+            AudioMessage::SetStepsPerBeat { steps_per_beat } => {
+                self.steps_per_beat = steps_per_beat.clamp(1, 16);
+            }
             AudioMessage::Play => {
-                self.playing = true;
+                self.set_playing(true);
             }
             AudioMessage::Stop => {
-                self.playing = false;
+                self.set_playing(false);
                 self.current_step = 0;
+                self.step_accumulator = 0.0;
             }
             AudioMessage::Record => {
                 self.recording = true;
@@ -375,10 +511,25 @@ impl WasmAudioHost {
                     t.clear_sample();
                 }
             }
+            AudioMessage::SetSpectrumSize {
+                track,
+                fft_size,
+                bins,
+            } => {
+                if let Some(t) = self.tracks.get_mut(track as usize) {
+                    t.set_spectrum_size(fft_size, bins);
+                }
+            }
             AudioMessage::GetState => {}
         }
     }
 
+    /// Update the transport state and the shared running flag
+    fn set_playing(&mut self, playing: bool) {
+        self.playing = playing;
+        AUDIO_RUNNING.store(playing, Ordering::Release);
+    }
+
     fn get_state(&self) -> Result<String, String> {
         let state = StateUpdate {
             playing: self.playing,
@@ -420,7 +571,20 @@ pub struct WasmTrack {
     sample_data: Option<Vec<f32>>,
     #[serde(skip)]
     active_voices: Vec<ActiveVoice>,
+    /// Normalized (0.0–1.0) track params, indexed by the TRACK_PARAM_* constants
+    params: [f32; PARAMS_PER_TRACK],
+    attack_ms: f32,
+    release_ms: f32,
+    #[serde(skip)]
+    filter: BiquadFilter,
     sample_rate: f64,
+    fft_size: usize,
+    spectrum_bins: usize,
+    /// Rolling buffer of recent track output for spectrum analysis
+    #[serde(skip)]
+    analysis_buffer: Vec<f32>,
+    #[serde(skip)]
+    analysis_pos: usize,
 }
 
 impl WasmTrack {
@@ -435,36 +599,92 @@ impl WasmTrack {
             has_sample: false,
             sample_data: None,
             active_voices: Vec::new(),
+            params: [
+                1.0,
+                0.0,
+                0.0,
+                release_ms_to_param(DEFAULT_RELEASE_MS),
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            ],
+            attack_ms: 0.0,
+            release_ms: DEFAULT_RELEASE_MS,
+            filter: BiquadFilter::with_config(FilterConfig {
+                filter_type: FilterType::LowPass,
+                cutoff_frequency: param_to_cutoff(1.0),
+                resonance: param_to_resonance(0.0),
+                gain: 0.0,
+                sample_rate: sample_rate as f32,
+            }),
             sample_rate,
+            fft_size: DEFAULT_FFT_SIZE,
+            spectrum_bins: DEFAULT_SPECTRUM_BINS,
+            analysis_buffer: vec![0.0; DEFAULT_FFT_SIZE],
+            analysis_pos: 0,
         }
     }
 
-    fn process(&mut self, output: &mut [f32]) {
-        if self.muted && self.solo {
-            // Only muted if nothing is soloed
-        }
-
+    /// Render the track's voices into `output` (mono, before pan).
+    /// Tracks render even when inaudible so their voices keep running.
+    fn render(&mut self, output: &mut [f32]) {
+        output.fill(0.0);
         for voice in &mut self.active_voices {
             voice.process(output);
         }
 
         // Clean up finished voices
         self.active_voices.retain(|v| !v.finished());
+
+        self.filter.process_buffer(output);
+
+        // Capture for the spectrum analyzer
+        for &sample in output.iter() {
+            self.analysis_buffer[self.analysis_pos] = sample;
+            self.analysis_pos = (self.analysis_pos + 1) % self.fft_size;
+        }
+    }
+
+    /// Equal-power (left, right) gains for the track's pan position
+    fn pan_gains(&self) -> (f32, f32) {
+        let angle = (self.pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_4;
+        (angle.cos(), angle.sin())
     }
 
     fn note_on(&mut self, note: u8, velocity: f32) {
         if let Some(ref sample) = self.sample_data {
-            let voice = ActiveVoice::new(note, velocity, sample, self.volume, self.sample_rate);
+            let mut voice = ActiveVoice::new(note, velocity, sample, self.volume, self.sample_rate);
+            voice.set_attack_ms(self.attack_ms);
+            voice.set_release_ms(self.release_ms);
             self.active_voices.push(voice);
         }
     }
 
-    fn note_off(&mut self, _note: u8) {
-        // Release voices - simplified for now
+    fn note_off(&mut self, note: u8) {
+        for voice in self.active_voices.iter_mut().filter(|v| v.note == note) {
+            voice.release();
+        }
     }
 
-    fn set_param(&mut self, _param: &str, _value: f32) {
-        // TODO: Connect to DSP parameters
+    /// Set a track parameter by name from a normalized (0.0–1.0) value.
+    /// Unknown names are ignored.
+    fn set_param(&mut self, param: &str, value: f32) {
+        let Some(index) = TRACK_PARAM_NAMES.iter().position(|&name| name == param) else {
+            return;
+        };
+        let value = value.clamp(0.0, 1.0);
+        self.params[index] = value;
+
+        match index {
+            TRACK_PARAM_CUTOFF => self.filter.set_cutoff(param_to_cutoff(value)),
+            TRACK_PARAM_RESONANCE => self.filter.set_resonance(param_to_resonance(value)),
+            TRACK_PARAM_ATTACK => self.attack_ms = value * value * MAX_ATTACK_MS,
+            TRACK_PARAM_RELEASE => {
+                self.release_ms = 1.0 + value * value * (MAX_RELEASE_MS - 1.0);
+            }
+            _ => {}
+        }
     }
 
     fn set_mute(&mut self, muted: bool) {
@@ -509,9 +729,41 @@ impl WasmTrack {
             .unwrap_or_default()
     }
 
+    /// Configure the spectrum analyzer.
+    /// `fft_size` must be a power of two and `bins` must divide `fft_size / 2`.
+    fn set_spectrum_size(&mut self, fft_size: usize, bins: usize) {
+        if !fft_size.is_power_of_two() || bins == 0 || !(fft_size / 2).is_multiple_of(bins) {
+            return;
+        }
+        self.fft_size = fft_size;
+        self.spectrum_bins = bins;
+        self.analysis_buffer = vec![0.0; fft_size];
+        self.analysis_pos = 0;
+    }
+
+    /// Log-magnitude spectrum (dB) of the track's recent output.
+    /// Adjacent FFT bins are averaged down to `spectrum_bins` values.
     fn get_spectrum(&self) -> Vec<f32> {
-        // Placeholder - would need FFT implementation
-        vec![0.0; 64]
+        let n = self.fft_size;
+
+        // Oldest sample first; a Hann-windowed full-scale sine reads 0 dB
+        let ordered: Vec<f32> = (0..n)
+            .map(|i| self.analysis_buffer[(self.analysis_pos + i) % n])
+            .collect();
+        let spectrum = magnitude_spectrum(&ordered, WindowFn::Hann);
+
+        let group = n / 2 / self.spectrum_bins;
+        (0..self.spectrum_bins)
+            .map(|bin| {
+                let start = bin * group;
+                let magnitude = spectrum[start..start + group].iter().sum::<f32>() / group as f32;
+                if magnitude > 0.0 {
+                    (20.0 * magnitude.log10()).max(SPECTRUM_FLOOR_DB)
+                } else {
+                    SPECTRUM_FLOOR_DB
+                }
+            })
+            .collect()
     }
 }
 
@@ -520,11 +772,24 @@ impl WasmTrack {
 struct ActiveVoice {
     note: u8,
     velocity: f32,
-    position: usize,
+    /// Fractional read position in sample frames
+    position: f64,
     playing: bool,
     sample: Vec<f32>,
     volume: f32,
-    pitch_ratio: f32,
+    /// Playback rate relative to the root note
+    pitch_ratio: f64,
+    sample_rate: f64,
+    /// Current attack gain (rises linearly from 0.0 to 1.0 after note-on)
+    attack_gain: f32,
+    /// Gain increment per sample during the attack
+    attack_step: f32,
+    /// Whether note-off has been received
+    releasing: bool,
+    /// Current release gain (1.0 until released, falls linearly to 0.0)
+    release_gain: f32,
+    /// Gain decrement per sample while releasing
+    release_step: f32,
 }
 
 impl ActiveVoice {
@@ -535,28 +800,76 @@ impl ActiveVoice {
         Self {
             note,
             velocity,
-            position: 0,
+            position: 0.0,
             playing: true,
             sample: sample.to_vec(),
             volume: velocity * volume,
-            pitch_ratio: pitch_ratio as f32,
+            pitch_ratio,
+            sample_rate,
+            attack_gain: 1.0,
+            attack_step: 1.0,
+            releasing: false,
+            release_gain: 1.0,
+            release_step: Self::release_step(DEFAULT_RELEASE_MS, sample_rate),
+        }
+    }
+
+    fn release_step(release_ms: f32, sample_rate: f64) -> f32 {
+        let release_samples = (release_ms as f64 / 1000.0 * sample_rate).max(1.0);
+        (1.0 / release_samples) as f32
+    }
+
+    /// Set the attack time; zero starts the voice at full gain
+    fn set_attack_ms(&mut self, attack_ms: f32) {
+        if attack_ms > 0.0 {
+            self.attack_gain = 0.0;
+            self.attack_step = Self::release_step(attack_ms, self.sample_rate);
+        } else {
+            self.attack_gain = 1.0;
         }
     }
 
+    /// Set the release time used after note-off
+    fn set_release_ms(&mut self, release_ms: f32) {
+        self.release_step = Self::release_step(release_ms, self.sample_rate);
+    }
+
+    /// Start the release phase; the voice finishes once the gain reaches zero
+    fn release(&mut self) {
+        self.releasing = true;
+    }
+
     fn process(&mut self, output: &mut [f32]) {
         if !self.playing {
             return;
         }
 
-        for (i, out_sample) in output.iter_mut().enumerate() {
-            if self.position < self.sample.len() {
-                let sample = self.sample[self.position];
-                *out_sample += sample * self.volume;
-                self.position = (self.position as f32 * self.pitch_ratio) as usize;
-            } else {
+        let len = self.sample.len();
+        for out_sample in output.iter_mut() {
+            if self.position >= len as f64 {
                 self.playing = false;
                 break;
             }
+
+            // Linear interpolation between adjacent frames
+            let index = self.position as usize;
+            let frac = (self.position - index as f64) as f32;
+            let current = self.sample[index];
+            let next = self.sample.get(index + 1).copied().unwrap_or(0.0);
+            let sample = current + (next - current) * frac;
+
+            *out_sample += sample * self.volume * self.attack_gain * self.release_gain;
+            self.position += self.pitch_ratio;
+            self.attack_gain = (self.attack_gain + self.attack_step).min(1.0);
+
+            if self.releasing {
+                self.release_gain -= self.release_step;
+                if self.release_gain <= 0.0 {
+                    self.release_gain = 0.0;
+                    self.playing = false;
+                    break;
+                }
+            }
         }
     }
 
@@ -577,7 +890,7 @@ impl ActiveVoice {
 //   [20..35]  track_pans       (16 × f32)
 //   [36..51]  track_mutes      (16 × f32, 0.0 or 1.0)
 //   [52..67]  track_solos      (16 × f32, 0.0 or 1.0)
-//   [68..195] track_params     (16 tracks × 8 params = 128 × f32)
+//   [68..195] track_params     (16 tracks × 8 params = 128 × f32, see TRACK_PARAM_*)
 //   [196..259] waveform_out    (64 × f32, oscilloscope ring for active track)
 //   [260]     active_track     (0–15)
 //   [261]     peak_l           (0.0–1.0)
@@ -607,6 +920,33 @@ pub const SAB_BYTE_LENGTH: usize = SAB_TOTAL_FLOATS * 4;
 /// Params-per-track in the SharedArrayBuffer block
 pub const PARAMS_PER_TRACK: usize = 8;
 
+// Per-track param indices within SAB_TRACK_PARAMS. Values are normalized 0.0–1.0.
+/// Low-pass cutoff, 20 Hz–20 kHz (exponential)
+pub const TRACK_PARAM_CUTOFF: usize = 0;
+/// Low-pass resonance, Q 0.707–20
+pub const TRACK_PARAM_RESONANCE: usize = 1;
+/// Voice attack, 0–2000 ms (quadratic)
+pub const TRACK_PARAM_ATTACK: usize = 2;
+/// Voice release, 1–5000 ms (quadratic)
+pub const TRACK_PARAM_RELEASE: usize = 3;
+// Indices 4..7 are reserved.
+
+/// Names accepted by `SetParam`, in SAB index order (empty = reserved)
+const TRACK_PARAM_NAMES: [&str; PARAMS_PER_TRACK] =
+    ["cutoff", "resonance", "attack", "release", "", "", "", ""];
+
+fn param_to_cutoff(value: f32) -> f32 {
+    20.0 * 1000.0f32.powf(value)
+}
+
+fn param_to_resonance(value: f32) -> f32 {
+    0.707 + value * (20.0 - 0.707)
+}
+
+fn release_ms_to_param(release_ms: f32) -> f32 {
+    ((release_ms - 1.0) / (MAX_RELEASE_MS - 1.0)).sqrt()
+}
+
 /// Read parameters from a SharedArrayBuffer-backed f32 slice.
 /// Called once per process() quantum to pull UI-written values into the engine.
 impl WasmAudioHost {
@@ -617,7 +957,7 @@ impl WasmAudioHost {
 
         // Transport
         let flags = sab[SAB_TRANSPORT_FLAGS] as u32;
-        self.playing = (flags & 1) != 0;
+        self.set_playing((flags & 1) != 0);
         self.recording = (flags & 2) != 0;
 
         // Tempo
@@ -640,6 +980,15 @@ impl WasmAudioHost {
                 track.pan = sab[SAB_TRACK_PANS + i].clamp(-1.0, 1.0);
                 track.muted = sab[SAB_TRACK_MUTES + i] > 0.5;
                 track.solo = sab[SAB_TRACK_SOLOS + i] > 0.5;
+
+                // Only dispatch params that changed since the last quantum
+                let base = SAB_TRACK_PARAMS + i * PARAMS_PER_TRACK;
+                for (p, name) in TRACK_PARAM_NAMES.iter().enumerate() {
+                    let value = sab[base + p];
+                    if !name.is_empty() && (value - track.params[p]).abs() > 0.0001 {
+                        track.set_param(name, value);
+                    }
+                }
             }
         }
     }
@@ -707,3 +1056,368 @@ pub fn dealloc(ptr: *mut u8, size: usize) {
         Vec::from_raw_parts(ptr, 0, size);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+    use std::sync::Mutex;
+
+    /// Serialises tests that drive the global transport flag
+    static TRANSPORT_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_smoother_settles() {
+        let mut smoother = ParameterSmoother::new(SmoothingConfig::new(48000.0));
+        assert!(smoother.is_settled());
+
+        smoother.set_target(1.0);
+        assert!(!smoother.is_settled());
+        // 1 ms attack: well converged after 20 ms
+        for _ in 0..960 {
+            smoother.process();
+        }
+        assert!(smoother.is_settled());
+        assert_eq!(smoother.process(), 1.0);
+
+        smoother.set_value(0.3);
+        assert!(smoother.is_settled());
+    }
+
+    #[test]
+    fn test_sequencer_advances_with_tempo() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        // 120 BPM, 16th notes at 48 kHz = 6000 samples per step
+        let mut host = WasmAudioHost::new(48000.0);
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+
+        let mut buffer = vec![0.0; 128];
+        for _ in 0..46 {
+            host.process(&mut buffer);
+        }
+        assert_eq!(host.current_step, 0); // 5888 samples, no boundary yet
+
+        host.process(&mut buffer);
+        assert_eq!(host.current_step, 1); // 6016 samples
+    }
+
+    #[test]
+    fn test_sequencer_multiple_steps_per_buffer() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut host = WasmAudioHost::new(48000.0);
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+
+        // One 15000-sample buffer crosses two boundaries
+        let mut buffer = vec![0.0; 15000];
+        host.process(&mut buffer);
+        assert_eq!(host.current_step, 2);
+
+        // The remainder carries over into the next buffer
+        let mut buffer = vec![0.0; 3000];
+        host.process(&mut buffer);
+        assert_eq!(host.current_step, 3);
+    }
+
+    #[test]
+    fn test_sequencer_steps_per_beat_message() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut host = WasmAudioHost::new(48000.0);
+        host.handle_message(r#"{"type":"SetStepsPerBeat","steps_per_beat":2}"#)
+            .unwrap();
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+
+        // 8th notes: 12000 samples per step
+        let mut buffer = vec![0.0; 11999];
+        host.process(&mut buffer);
+        assert_eq!(host.current_step, 0);
+
+        let mut buffer = vec![0.0; 1];
+        host.process(&mut buffer);
+        assert_eq!(host.current_step, 1);
+
+        host.handle_message(r#"{"type":"Stop"}"#).unwrap();
+        assert_eq!(host.current_step, 0);
+    }
+
+    #[test]
+    fn test_play_stop_gates_processing() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut host = WasmAudioHost::new(48000.0);
+        host.tracks[0].load_sample(&vec![0.5; 4800]);
+        host.handle_message(r#"{"type":"SetMasterVolume","volume":1.0}"#)
+            .unwrap();
+
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+        assert!(is_audio_running());
+        host.handle_message(r#"{"type":"NoteOn","note":60,"velocity":1.0,"track":0}"#)
+            .unwrap();
+        let mut buffer = vec![0.0; 128];
+        host.process(&mut buffer);
+        assert!(buffer.iter().any(|&s| s != 0.0));
+
+        let state = host.handle_message(r#"{"type":"Stop"}"#).unwrap();
+        assert!(state.contains(r#""playing":false"#));
+        assert!(!is_audio_running());
+        let mut buffer = vec![1.0; 128];
+        host.process(&mut buffer);
+        assert!(buffer.iter().all(|&s| s == 0.0));
+        assert_eq!(host.current_step, 0);
+    }
+
+    #[test]
+    fn test_long_buffers_mix_through_scratch_blocks() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let start = |host: &mut WasmAudioHost| {
+            host.smoother.set_value(1.0);
+            host.tracks[0].load_sample(&vec![0.5; 4800]);
+            host.tracks[0].pan = -0.5;
+            host.handle_message(r#"{"type":"Play"}"#).unwrap();
+            host.tracks[0].note_on(60, 1.0);
+        };
+        let mut mono = WasmAudioHost::new(48000.0);
+        let mut stereo = WasmAudioHost::new(48000.0);
+        start(&mut mono);
+        start(&mut stereo);
+
+        let mut output = vec![0.0; 1000];
+        mono.process(&mut output);
+        let mut left = vec![0.0; 1000];
+        let mut right = vec![0.0; 1000];
+        stereo.process_stereo(&mut left, &mut right);
+
+        for ((&sample, &l), &r) in output.iter().zip(&left).zip(&right) {
+            assert_eq!(sample, (l + r) * FRAC_1_SQRT_2);
+        }
+        assert!(output.iter().all(|&s| s > 0.0));
+        // The scratch buffers never grow to the callback size
+        assert_eq!(mono.track_scratch.len(), SCRATCH_FRAMES);
+        assert_eq!(mono.mix_left.len(), SCRATCH_FRAMES);
+        mono.handle_message(r#"{"type":"Stop"}"#).unwrap();
+    }
+
+    #[test]
+    fn test_stopped_host_silent_while_another_plays() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut playing = WasmAudioHost::new(48000.0);
+        let mut stopped = WasmAudioHost::new(48000.0);
+        stopped.tracks[0].load_sample(&vec![0.5; 4800]);
+        stopped.tracks[0].note_on(60, 1.0);
+
+        playing.handle_message(r#"{"type":"Play"}"#).unwrap();
+        assert!(is_audio_running());
+        let mut buffer = vec![1.0; 128];
+        stopped.process(&mut buffer);
+        assert!(buffer.iter().all(|&s| s == 0.0));
+        assert_eq!(stopped.current_step, 0);
+
+        playing.handle_message(r#"{"type":"Stop"}"#).unwrap();
+    }
+
+    /// SAB block with every track's params at their current values
+    fn shared_params(host: &WasmAudioHost) -> Vec<f32> {
+        let mut sab = vec![0.0; SAB_TOTAL_FLOATS];
+        sab[SAB_TEMPO] = host.tempo;
+        sab[SAB_MASTER_VOLUME] = host.master_volume;
+        for (i, track) in host.tracks.iter().enumerate() {
+            sab[SAB_TRACK_VOLUMES + i] = track.volume;
+            let base = SAB_TRACK_PARAMS + i * PARAMS_PER_TRACK;
+            sab[base..base + PARAMS_PER_TRACK].copy_from_slice(&track.params);
+        }
+        sab
+    }
+
+    #[test]
+    fn test_shared_params_reach_track() {
+        let mut host = WasmAudioHost::new(48000.0);
+        let mut sab = shared_params(&host);
+
+        let base = SAB_TRACK_PARAMS + 3 * PARAMS_PER_TRACK;
+        sab[base + TRACK_PARAM_CUTOFF] = 0.5;
+        sab[base + TRACK_PARAM_ATTACK] = 0.5;
+        sab[base + TRACK_PARAM_RELEASE] = 1.0;
+        host.read_shared_params(&sab);
+
+        let track = &host.tracks[3];
+        assert_eq!(track.params[TRACK_PARAM_CUTOFF], 0.5);
+        assert_eq!(track.attack_ms, 500.0);
+        assert_eq!(track.release_ms, MAX_RELEASE_MS);
+        // Other tracks keep their defaults
+        assert_eq!(host.tracks[2].params, WasmTrack::new(2, 48000.0).params);
+    }
+
+    #[test]
+    fn test_cutoff_param_filters_track() {
+        let sample_rate = 48000.0;
+        let tone: Vec<f32> = (0..48000)
+            .map(|i| (2.0 * PI * 8000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+        let energy = |track: &mut WasmTrack| {
+            track.load_sample(&tone);
+            track.note_on(60, 1.0);
+            let mut output = vec![0.0; 4800];
+            track.render(&mut output);
+            output[2400..].iter().map(|s| s * s).sum::<f32>()
+        };
+
+        let mut open = WasmTrack::new(0, sample_rate);
+        let mut closed = WasmTrack::new(1, sample_rate);
+        closed.set_param("cutoff", 0.2); // ~80 Hz
+        assert!(energy(&mut closed) < energy(&mut open) * 0.01);
+    }
+
+    #[test]
+    fn test_solo_mixes_only_soloed_tracks() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut host = WasmAudioHost::new(48000.0);
+        host.smoother.set_value(1.0);
+        host.tracks[1].load_sample(&vec![0.25; 4800]);
+        host.tracks[2].load_sample(&vec![0.5; 4800]);
+        // Solo wins over mute on the same track
+        host.handle_message(r#"{"type":"SetSolo","track":2,"solo":true}"#)
+            .unwrap();
+        host.handle_message(r#"{"type":"SetMute","track":2,"muted":true}"#)
+            .unwrap();
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+        for track in 1..=2 {
+            host.tracks[track].note_on(60, 1.0);
+        }
+
+        let mut buffer = vec![0.0; 128];
+        host.process(&mut buffer);
+        // Skip the track filters' settling time
+        let track_2_only = 0.5 * host.tracks[2].volume;
+        assert!(buffer[64..]
+            .iter()
+            .all(|&s| (s - track_2_only).abs() < 1e-3));
+
+        host.handle_message(r#"{"type":"SetSolo","track":2,"solo":false}"#)
+            .unwrap();
+        host.handle_message(r#"{"type":"SetMute","track":2,"muted":false}"#)
+            .unwrap();
+        host.process(&mut buffer);
+        let both = (0.25 + 0.5) * host.tracks[1].volume;
+        assert!(buffer[64..].iter().all(|&s| (s - both).abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_pan_hard_left_silences_right() {
+        let _transport = TRANSPORT_LOCK.lock().unwrap();
+        let mut host = WasmAudioHost::new(48000.0);
+        host.smoother.set_value(1.0);
+        host.tracks[0].load_sample(&vec![0.5; 4800]);
+        host.tracks[0].pan = -1.0;
+        host.handle_message(r#"{"type":"Play"}"#).unwrap();
+        host.tracks[0].note_on(60, 1.0);
+
+        let mut left = vec![0.0; 128];
+        let mut right = vec![0.0; 128];
+        host.process_stereo(&mut left, &mut right);
+        assert!(left.iter().map(|s| s * s).sum::<f32>() > 0.0);
+        assert!(right.iter().all(|&s| s.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_pan_centre_equal_power() {
+        let mut track = WasmTrack::new(0, 48000.0);
+        let (gain_l, gain_r) = track.pan_gains();
+        assert!((gain_l - FRAC_1_SQRT_2).abs() < 1e-6);
+        assert!((gain_l * gain_l + gain_r * gain_r - 1.0).abs() < 1e-6);
+
+        track.pan = 1.0;
+        let (gain_l, gain_r) = track.pan_gains();
+        assert!(gain_l.abs() < 1e-6 && (gain_r - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_voice_octave_up_reads_at_double_speed() {
+        let ramp: Vec<f32> = (0..100).map(|i| i as f32).collect();
+        // One octave above the C4 root
+        let mut voice = ActiveVoice::new(72, 1.0, &ramp, 1.0, 48000.0);
+        assert!((voice.pitch_ratio - 2.0).abs() < 1e-9);
+
+        let mut output = vec![0.0; 40];
+        voice.process(&mut output);
+        for (i, &sample) in output.iter().enumerate() {
+            assert!((sample - (i * 2) as f32).abs() < 1e-3);
+        }
+        assert!(!voice.finished());
+
+        // The remaining 10 frames finish the sample without overrunning
+        let mut output = vec![0.0; 40];
+        voice.process(&mut output);
+        assert_eq!(output[9], 98.0);
+        assert_eq!(output[10], 0.0);
+        assert!(voice.finished());
+    }
+
+    #[test]
+    fn test_voice_interpolates_between_frames() {
+        let ramp: Vec<f32> = (0..100).map(|i| i as f32).collect();
+        let mut voice = ActiveVoice::new(60, 1.0, &ramp, 1.0, 48000.0);
+        voice.pitch_ratio = 0.5;
+
+        let mut output = vec![0.0; 4];
+        voice.process(&mut output);
+        assert_eq!(output, vec![0.0, 0.5, 1.0, 1.5]);
+    }
+
+    #[test]
+    fn test_note_off_releases_voice() {
+        let mut track = WasmTrack::new(0, 48000.0);
+        track.load_sample(&vec![1.0; 48000]);
+        track.note_on(60, 1.0);
+        track.note_on(64, 1.0);
+
+        track.render(&mut [0.0; 128]);
+        assert_eq!(track.active_voices.len(), 2);
+
+        // 20 ms at 48 kHz = 960 samples of release
+        track.note_off(60);
+        let mut output = vec![0.0; 480];
+        track.render(&mut output);
+        assert_eq!(track.active_voices.len(), 2);
+        // Released voice fades while the held voice keeps sounding
+        assert!(output[479] > 0.8 * 1.0 && output[479] < 0.8 * 2.0);
+        assert!(output.windows(2).all(|w| w[1] <= w[0]));
+
+        track.render(&mut [0.0; 500]);
+        assert_eq!(track.active_voices.len(), 1);
+        assert_eq!(track.active_voices[0].note, 64);
+    }
+
+    #[test]
+    fn test_spectrum_peak_for_1khz_tone() {
+        let sample_rate = 48000.0;
+        let tone: Vec<f32> = (0..48000)
+            .map(|i| (2.0 * PI * 1000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+
+        let mut track = WasmTrack::new(0, sample_rate);
+        track.load_sample(&tone);
+        track.note_on(60, 1.0);
+        track.render(&mut [0.0; 512]);
+
+        let spectrum = track.get_spectrum();
+        assert_eq!(spectrum.len(), DEFAULT_SPECTRUM_BINS);
+
+        // 256-point FFT at 48 kHz: 187.5 Hz per FFT bin, two FFT bins per output bin
+        let expected = (1000.0 / 187.5 / 2.0) as usize;
+        let peak = spectrum
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(i, _)| i)
+            .unwrap();
+        assert_eq!(peak, expected);
+        assert!(spectrum[peak] > -20.0);
+        assert!(spectrum[20] < spectrum[peak] - 40.0);
+    }
+
+    #[test]
+    fn test_spectrum_silent_track() {
+        let mut track = WasmTrack::new(0, 48000.0);
+        track.set_spectrum_size(512, 32);
+        let spectrum = track.get_spectrum();
+        assert_eq!(spectrum, vec![SPECTRUM_FLOOR_DB; 32]);
+    }
+}